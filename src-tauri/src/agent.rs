@@ -9,9 +9,15 @@
 //! - 멱등. event id 는 메시지 ID 기반 결정적 값(`msg-<id>`)이며, 이미 있으면 skip → 사용자 편집 보존.
 //!   재전송·정정처럼 메시지 ID 가 달라도 내용이 사실상 같은 경우는 (날짜, 제목/원문 유사도)
 //!   기준의 내용 중복 판정으로 이중 등록을 막는다.
+//! - 날짜·시각을 바꾸는 정정 메시지는 새 일정 대신 기존 AI 일정을 옮긴다. 사용자가 고친
+//!   일정은 건드리지 않고 검토 대기로 남기며, 두 메시지의 연결은 `tbl_schedule_revisions` 에 기록한다.
 //! - CLI 미설치/미인증 시 기능 자동 비활성화, 앱 크래시 없음.
+//! - 추출 주체는 `briefing_backend::ExtractionBackend` 로 교체 가능하다(claude CLI 기본,
+//!   OpenAI 호환 HTTP, 규칙 기반). 어느 백엔드든 파싱·검증·등록 경로는 같다.
+//! - 날짜 계산은 `date_expr` 가 결정적으로 돕는다. 프롬프트에 날짜 표현 힌트를 넣고,
+//!   LLM 이 고른 날짜가 원문 발췌와 어긋나면 등록 전에 보정한다.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
@@ -20,7 +26,15 @@ use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::bell_schedule;
+use crate::briefing_backend::{
+    self, BackendKind, BackendOutput, ExtractionBackend, ExtractionRequest, SourceMessage,
+};
+use crate::briefing_rules::{self, Gate, RuleSet, RuleSubject};
+use crate::briefing_runs::{ItemOutcome, RunEnd, RunItem, RunRecorder};
+use crate::briefing_usage::{self, BudgetStatus, RunUsage};
 use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::date_expr;
 
 // ─── 상수 ─────────────────────────────────────────────────────────────────────
 
@@ -32,6 +46,8 @@ const DEBOUNCE_SECS: u64 = 45;
 const ALLOWED_TOOLS: &str = "mcp__hypercool__get_messages,mcp__hypercool__get_message_by_id,mcp__hypercool__read_attachment,mcp__hypercool__search_messages,mcp__hypercool__list_attachments";
 /// AI 자동 생성 일정을 시각적으로 구분하기 위한 색상(달력 위젯이 color 를 렌더에 사용).
 const AI_COLOR: &str = "#8B5CF6";
+/// AI 일정 판별 SQL 조건(?1 = AI_COLOR). 색상 규칙으로 색이 바뀐 일정은 스냅샷으로 알아본다.
+const AI_SCHEDULE_SQL: &str = "(COALESCE(color, '') = ?1 OR id IN (SELECT schedule_id FROM tbl_ai_schedule_snapshots))";
 /// content 에 남기는 AI 생성 마커.
 const AI_MARKER: &str = "AI 자동 생성";
 /// 구버전 이모지 마커(마이그레이션에서 제거 대상).
@@ -46,11 +62,18 @@ const TITLE_SIM_THRESHOLD: f64 = 0.85;
 const BODY_SIM_THRESHOLD: f64 = 0.90;
 /// 원문 유사도 비교를 적용할 최소 길이(정규화 후 문자 수) — 짧은 본문의 우연 일치 방지.
 const MIN_BODY_CHARS: usize = 20;
+/// 도구 없는 백엔드에 한 번에 넘기는 최대 신규 메시지 수.
+const INLINE_MESSAGE_LIMIT: i64 = 100;
+/// 프롬프트 날짜 힌트: 메시지당 / 전체 최대 줄 수.
+const DATE_HINTS_PER_MESSAGE: usize = 6;
+const DATE_HINTS_LIMIT: usize = 80;
 
 const REG_ENABLED: &str = "BriefingAgentEnabled";
 const REG_LAST_SEEN: &str = "BriefingLastSeenId";
 const REG_CLAUDE_PATH: &str = "ClaudeCliPath";
 
+const CLAUDE_NOT_FOUND: &str = "Claude Code CLI(claude)를 찾을 수 없습니다. 설치 후 다시 시도하세요.";
+
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
@@ -69,7 +92,7 @@ fn status_slot() -> &'static Mutex<BriefingStatusSnapshot> {
 // ─── 직렬화 타입 ──────────────────────────────────────────────────────────────
 
 /// claude 가 반환하는 일정 항목(프롬프트 스키마). 필드는 모두 관대하게 Option 처리.
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Serialize, Default)]
 #[serde(default)]
 struct ExtractedItem {
     id: Option<String>,
@@ -77,7 +100,6 @@ struct ExtractedItem {
     received_at: Option<String>,
     sender: Option<String>,
     #[serde(rename = "type")]
-    #[allow(dead_code)]
     item_type: Option<String>,
     title: Option<String>,
     detail: Option<String>,
@@ -85,14 +107,14 @@ struct ExtractedItem {
     time: Option<String>,
     all_day: Option<bool>,
     period: Option<String>,
-    #[allow(dead_code)]
     has_attachment: Option<bool>,
-    #[allow(dead_code)]
     urgency: Option<String>,
     active_from: Option<String>,
-    #[allow(dead_code)]
     active_until: Option<String>,
     source_text: Option<String>,
+    /// 앱이 강제 추출 규칙으로 만든 항목이면 그 규칙 이름(백엔드 출력에는 없음).
+    #[serde(skip)]
+    forced_by: Option<String>,
 }
 
 #[derive(Clone, Serialize, Default)]
@@ -106,6 +128,10 @@ pub struct BriefingStatusSnapshot {
 #[derive(Serialize)]
 pub struct BriefingStatus {
     pub enabled: bool,
+    /// 설정된 추출 백엔드("claude_cli" | "openai_compat" | "rules").
+    pub backend: String,
+    /// 백엔드를 지금 쓸 수 없는 사유(있을 때만).
+    pub backend_error: Option<String>,
     pub claude_installed: bool,
     pub claude_path: Option<String>,
     pub authed: bool,
@@ -114,6 +140,8 @@ pub struct BriefingStatus {
     pub last_new_count: i64,
     pub last_error: Option<String>,
     pub last_seen_id: i64,
+    /// 오늘·이번 달 사용량과 예산. `paused` 가 있으면 예산 초과로 실행을 멈춘 상태.
+    pub budget: Option<BudgetStatus>,
 }
 
 #[derive(Serialize)]
@@ -169,11 +197,14 @@ pub fn set_briefing_agent_enabled(app: AppHandle, enabled: bool) -> Result<(), S
 }
 
 #[tauri::command]
-pub fn get_briefing_agent_status() -> BriefingStatus {
+pub fn get_briefing_agent_status(app: AppHandle) -> BriefingStatus {
     let claude = find_claude();
+    let backend = select_backend();
     let snapshot = status_slot().lock().unwrap().clone();
     BriefingStatus {
         enabled: is_enabled(),
+        backend: backend.name().to_string(),
+        backend_error: backend.check().err(),
         claude_installed: claude.is_some(),
         claude_path: claude.map(|p| p.to_string_lossy().to_string()),
         authed: is_authed(),
@@ -182,14 +213,15 @@ pub fn get_briefing_agent_status() -> BriefingStatus {
         last_new_count: snapshot.last_new_count,
         last_error: snapshot.last_error,
         last_seen_id: read_last_seen_id(),
+        budget: briefing_usage::current_status(&app),
     }
 }
 
-/// 수동 실행(설정 페이지 "지금 실행"). ENABLED 여부와 무관하게 즉시 1회(단, CLI 필요).
+/// 수동 실행(설정 페이지 "지금 실행"). ENABLED 여부와 무관하게 즉시 1회(단, 백엔드 사용 가능해야 함).
 #[tauri::command]
 pub async fn run_briefing_agent_now(app: AppHandle) -> Result<BriefingRunResult, String> {
     let app2 = app.clone();
-    tauri::async_runtime::spawn_blocking(move || run_briefing_locked(&app2))
+    tauri::async_runtime::spawn_blocking(move || run_briefing_locked(&app2, "manual"))
         .await
         .map_err(|e| format!("실행 작업 실패: {}", e))
 }
@@ -212,9 +244,9 @@ pub async fn run_briefing_agent_debug(
 
 // ─── 트리거(watcher 연결) ─────────────────────────────────────────────────────
 
-/// 새 메시지 sync 성공 후 워처에서 호출한다. ENABLED + CLI 존재 시 debounce 실행.
+/// 새 메시지 sync 성공 후 워처에서 호출한다. ENABLED + 백엔드 사용 가능 시 debounce 실행.
 pub fn on_new_messages(app: &AppHandle) {
-    if !is_enabled() || find_claude().is_none() {
+    if !is_enabled() || select_backend().check().is_err() {
         return;
     }
 
@@ -235,13 +267,25 @@ pub fn on_new_messages(app: &AppHandle) {
             return;
         }
 
-        let _ = run_briefing_locked(&app);
+        let _ = run_briefing_locked(&app, "auto");
     });
 }
 
 // ─── 실행 (single-flight) ─────────────────────────────────────────────────────
 
-fn run_briefing_locked(app: &AppHandle) -> BriefingRunResult {
+/// `trigger`: 실행 기록에 남길 계기("auto" | "manual").
+/// 예산을 넘었으면 자동·수동 모두 실행하지 않는다.
+fn run_briefing_locked(app: &AppHandle, trigger: &'static str) -> BriefingRunResult {
+    if let Some(reason) = briefing_usage::paused_reason(app) {
+        return BriefingRunResult {
+            ran: false,
+            new_count: 0,
+            skipped: 0,
+            error: None,
+            reason: Some(reason),
+        };
+    }
+
     // single-flight: 이미 실행 중이면 PENDING 만 세우고 반환.
     if RUNNING
         .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
@@ -263,7 +307,7 @@ fn run_briefing_locked(app: &AppHandle) -> BriefingRunResult {
 
     loop {
         PENDING.store(false, Ordering::SeqCst);
-        match run_pass(app, &PassOpts { since_override: None, advance: true }) {
+        match run_pass(app, &PassOpts { since_override: None, advance: true, trigger }) {
             Ok((n, s)) => {
                 total_new += n;
                 total_skipped += s;
@@ -274,8 +318,12 @@ fn run_briefing_locked(app: &AppHandle) -> BriefingRunResult {
         }
         update_and_emit_status(app, total_new, last_err.clone());
 
-        // 실행 중 새 메시지가 또 들어왔으면(PENDING) 한 번 더 처리. 단, 오류 시엔 다음 틱에 맡긴다.
-        if !PENDING.load(Ordering::SeqCst) || last_err.is_some() {
+        // 실행 중 새 메시지가 또 들어왔으면(PENDING) 한 번 더 처리. 단, 오류 시엔 다음 틱에 맡기고,
+        // 이번 실행으로 예산을 넘었으면 멈춘다.
+        if !PENDING.load(Ordering::SeqCst)
+            || last_err.is_some()
+            || briefing_usage::paused_reason(app).is_some()
+        {
             break;
         }
     }
@@ -294,6 +342,8 @@ fn run_briefing_locked(app: &AppHandle) -> BriefingRunResult {
 /// 디버그 실행의 상세 진단 리포트. "신규 0건" 원인 파악용 정보를 모두 노출한다.
 #[derive(Serialize, Default)]
 pub struct BriefingDebugReport {
+    /// 이번 실행에 쓴 추출 백엔드 이름.
+    pub backend: String,
     pub claude_installed: bool,
     pub claude_path: Option<String>,
     pub authed: bool,
@@ -325,6 +375,8 @@ pub struct BriefingDebugReport {
     pub registered_new: i64,
     pub skipped_dedup: i64,
     pub skipped_invalid: i64,
+    /// 브리핑 규칙으로 제외된 항목 수.
+    pub skipped_rule: i64,
     pub error: Option<String>,
     pub notes: Vec<String>,
 }
@@ -334,6 +386,8 @@ pub struct BriefingDebugReport {
 fn run_debug_report(app: &AppHandle, count: i64) -> BriefingDebugReport {
     let mut rep = BriefingDebugReport::default();
     let claude = find_claude();
+    let backend = select_backend();
+    rep.backend = backend.name().to_string();
     rep.claude_installed = claude.is_some();
     rep.claude_path = claude.as_ref().map(|p| p.to_string_lossy().to_string());
     rep.authed = is_authed();
@@ -394,33 +448,36 @@ fn run_debug_report(app: &AppHandle, count: i64) -> BriefingDebugReport {
         rep.notes.push(format!("id > {} 인 대상 메시지가 0건입니다 (최근 {}개 범위에 신규 없음).", since, count));
     }
 
-    // 4) claude 실행(획득했고 CLI 있고 대상 있으면).
+    // 4) 추출 백엔드 실행(획득했고 백엔드 사용 가능하고 대상 있으면).
     if acquired {
-        if let Some(claude_path) = claude {
-            if rep.target_messages > 0 {
-                let started = Instant::now();
-                match run_claude_debug(app, &claude_path, since) {
-                    Ok(dbg) => {
-                        rep.ran_claude = true;
-                        rep.claude_is_error = dbg.is_error;
-                        rep.raw_result = Some(dbg.result_text);
-                        rep.raw_stderr_tail = dbg.stderr_tail;
-                        rep.extracted_count = dbg.extracted;
-                        rep.registered_new = dbg.registered;
-                        rep.skipped_dedup = dbg.skipped_dedup;
-                        rep.skipped_invalid = dbg.skipped_invalid;
-                        if let Some(e) = dbg.parse_error {
-                            rep.error = Some(e);
-                        }
+        if let Err(e) = backend.check() {
+            rep.notes.push(format!("추출 백엔드({})를 쓸 수 없어 실행하지 않았습니다: {}", backend.name(), e));
+        } else if let Some(reason) = briefing_usage::paused_reason(app) {
+            rep.notes.push(reason);
+        } else if rep.target_messages > 0 {
+            let started = Instant::now();
+            match run_backend_debug(app, backend.as_ref(), since) {
+                Ok(dbg) => {
+                    rep.ran_claude = true;
+                    rep.raw_result = Some(dbg.result_text);
+                    rep.raw_stderr_tail = dbg.stderr_tail;
+                    rep.extracted_count = dbg.extracted;
+                    rep.registered_new = dbg.registered;
+                    rep.skipped_dedup = dbg.skipped_dedup;
+                    rep.skipped_invalid = dbg.skipped_invalid;
+                    rep.skipped_rule = dbg.skipped_rule;
+                    if let Some(e) = dbg.parse_error {
+                        rep.error = Some(e);
                     }
-                    Err(e) => rep.error = Some(e),
                 }
-                rep.duration_ms = started.elapsed().as_millis() as u64;
-            } else {
-                rep.notes.push("대상 메시지가 없어 claude 를 실행하지 않았습니다.".to_string());
+                Err(e) => {
+                    rep.claude_is_error = true;
+                    rep.error = Some(e);
+                }
             }
+            rep.duration_ms = started.elapsed().as_millis() as u64;
         } else {
-            rep.notes.push("Claude Code CLI를 찾지 못해 실행하지 않았습니다.".to_string());
+            rep.notes.push("대상 메시지가 없어 추출을 실행하지 않았습니다.".to_string());
         }
         RUNNING.store(false, Ordering::SeqCst);
     }
@@ -436,90 +493,103 @@ fn run_debug_report(app: &AppHandle, count: i64) -> BriefingDebugReport {
             if rep.skipped_invalid > 0 {
                 rep.notes.push(format!("{}건이 과거 날짜/날짜 없음으로 제외되었습니다.", rep.skipped_invalid));
             }
+            if rep.skipped_rule > 0 {
+                rep.notes.push(format!("{}건이 브리핑 규칙으로 제외되었습니다.", rep.skipped_rule));
+            }
         }
     }
 
     rep
 }
 
-/// claude 디버그 실행 결과(내부).
+/// 백엔드 디버그 실행 결과(내부).
 struct ClaudeDebugRun {
-    is_error: bool,
     result_text: String,
     stderr_tail: Option<String>,
     extracted: i64,
     registered: i64,
     skipped_dedup: i64,
     skipped_invalid: i64,
+    skipped_rule: i64,
     parse_error: Option<String>,
 }
 
-/// claude 를 실행하고 원문/추출/등록 breakdown 을 채운다(디버그 전용).
-fn run_claude_debug(app: &AppHandle, claude: &PathBuf, since: i64) -> Result<ClaudeDebugRun, String> {
-    let mcp_path = ensure_mcp_config(app)?;
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+/// 백엔드를 실행하고 원문/추출/등록 breakdown 을 채운다(디버그 전용).
+fn run_backend_debug(
+    app: &AppHandle,
+    backend: &dyn ExtractionBackend,
+    since: i64,
+) -> Result<ClaudeDebugRun, String> {
     let today = seoul_today();
-    let prompt = build_prompt(app, &today, since);
-
-    let (stdout, stderr) = spawn_claude(claude, &prompt, &mcp_path, &app_data_dir)?;
-    let stderr_tail = {
-        let t = tail_chars(&stderr, 400);
-        if t.is_empty() { None } else { Some(t) }
+    let _pii_scope = crate::pii_redact::begin_run();
+    let recorder = RunRecorder::start(app, "debug", backend.name(), since);
+    let rules = briefing_rules::load_rules(app);
+    let (output, covered, gated) = match run_backend(app, backend, &today, since, &rules) {
+        Ok(v) => v,
+        Err(e) => {
+            recorder.finish(&RunEnd { error: Some(e.clone()), ..Default::default() });
+            return Err(e);
+        }
+    };
+    if let Some(e) = output.failure {
+        recorder.finish(&RunEnd { error: Some(e.clone()), usage: output.usage, ..Default::default() });
+        return Err(e);
+    }
+    let result_text = output.text;
+    let mut end = RunEnd {
+        until_id: Some(covered.unwrap_or_else(|| current_max_message_id(app))),
+        raw_output: Some(result_text.clone()),
+        usage: output.usage,
+        ..Default::default()
     };
-
-    // 봉투 파싱 → result 텍스트.
-    let envelope: serde_json::Value = serde_json::from_str(stdout.trim())
-        .map_err(|e| format!("claude 출력(JSON 봉투) 파싱 실패: {} / 원문: {}", e, head_chars(&stdout, 400)))?;
-    let is_error = envelope.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
-    let result_text = envelope
-        .get("result")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
 
     let mut run = ClaudeDebugRun {
-        is_error,
         result_text: head_chars(&result_text, 4000),
-        stderr_tail,
+        stderr_tail: output.log_tail,
         extracted: 0,
         registered: 0,
         skipped_dedup: 0,
         skipped_invalid: 0,
+        skipped_rule: 0,
         parse_error: None,
     };
 
-    if is_error {
-        run.parse_error = Some("claude가 오류로 종료했습니다(is_error). raw_result/stderr 확인.".to_string());
-        return Ok(run);
-    }
-
     // 배열 추출 + 등록(멱등, breakdown 포함).
-    match extract_json_array(&result_text) {
-        Some(arr_text) => match serde_json::from_str::<Vec<ExtractedItem>>(&arr_text) {
-            Ok(items) => {
-                run.extracted = items.len() as i64;
-                let (new_c, dedup_c, invalid_c) = register_items_breakdown(app, items, &today);
-                run.registered = new_c;
-                run.skipped_dedup = dedup_c;
-                run.skipped_invalid = invalid_c;
-                if run.registered > 0 {
-                    let _ = app.emit("calendar-update", ());
+    match parse_items_from_output(&result_text) {
+        Ok(mut items) => {
+            apply_forced_rules(app, &rules, since, covered, &mut items, &today);
+            run.extracted = items.len() as i64;
+            match register_items_core(app, items, &today, &rules) {
+                Ok(mut report) => {
+                    report.extend_filtered(gated);
+                    run.registered = report.new_count;
+                    run.skipped_dedup = report.dedup_count;
+                    run.skipped_invalid = report.invalid_count;
+                    run.skipped_rule = report.filtered_count;
+                    end.new_count = report.new_count;
+                    end.skipped_count = report.skipped();
+                    end.items = report.items;
+                }
+                Err(e) => {
+                    run.skipped_invalid = run.extracted;
+                    end.error = Some(e);
                 }
             }
-            Err(e) => run.parse_error = Some(format!("일정 JSON 배열 파싱 실패: {}", e)),
-        },
-        None => run.parse_error = Some("claude 결과에서 JSON 배열([...])을 찾지 못했습니다.".to_string()),
+            if run.registered > 0 {
+                let _ = app.emit("calendar-update", ());
+            }
+        }
+        Err(e) => {
+            end.until_id = None;
+            end.error = Some(e.clone());
+            run.parse_error = Some(e);
+        }
     }
+    recorder.finish(&end);
 
     Ok(run)
 }
 
-/// register_items 와 동일하되 (신규, 중복skip, 검증skip) breakdown 을 반환.
-fn register_items_breakdown(app: &AppHandle, items: Vec<ExtractedItem>, today: &str) -> (i64, i64, i64) {
-    let total = items.len() as i64;
-    register_items_core(app, items, today).unwrap_or((0, 0, total))
-}
-
 /// 검색 DB 통계: (존재, 메시지 수, 최신 id).
 fn search_db_stats(app: &AppHandle) -> (bool, i64, i64) {
     let dir = match app.path().app_data_dir() {
@@ -544,6 +614,36 @@ fn search_db_stats(app: &AppHandle) -> (bool, i64, i64) {
     }
 }
 
+/// 검색 DB 에서 메시지의 첨부 파일 경로 목록을 읽는다(없거나 실패하면 빈 목록).
+fn fetch_message_files(app: &AppHandle, id: i64) -> Vec<String> {
+    let Some(db) = app.path().app_data_dir().ok().map(|d| d.join("hypercool_search.db")) else {
+        return Vec::new();
+    };
+    if !db.exists() {
+        return Vec::new();
+    }
+    Connection::open(&db)
+        .and_then(|conn| {
+            conn.query_row("SELECT COALESCE(file_paths, '') FROM messages WHERE id = ?1", [id], |r| {
+                r.get::<_, String>(0)
+            })
+        })
+        .ok()
+        .and_then(|files| serde_json::from_str(&files).ok())
+        .unwrap_or_default()
+}
+
+/// 검색 DB 에서 메시지의 발신자를 읽는다(없거나 실패하면 None).
+fn fetch_message_sender(app: &AppHandle, id: i64) -> Option<String> {
+    let db = app.path().app_data_dir().ok()?.join("hypercool_search.db");
+    if !db.exists() {
+        return None;
+    }
+    Connection::open(&db)
+        .and_then(|conn| conn.query_row("SELECT sender FROM messages WHERE id = ?1", [id], |r| r.get(0)))
+        .ok()
+}
+
 /// 검색 DB에서 메시지 원문 HTML 조회. 정제/절단 없이 원문 전체를 그대로 반환한다.
 /// 달력 위젯은 이 HTML 을 메시지 뷰어와 동일하게 렌더링하므로, 원문이 그대로 보여야 한다.
 fn fetch_message_html(app: &AppHandle, id: i64) -> Option<String> {
@@ -589,7 +689,7 @@ fn rebuild_ai_content(old_content: &str, fresh_original_html: Option<&str>) -> S
     }
 }
 
-/// 기등록 AI 일정(color = AI_COLOR)의 content 를 최신 형식으로 다시 만든다.
+/// 기등록 AI 일정(AI 색이거나 스냅샷이 있는 일정)의 content 를 최신 형식으로 다시 만든다.
 /// reference_id(원본 메시지 id)로 원문 HTML 을 정제 없이 재조회해 [원문] 섹션을 갱신하고,
 /// 구버전 이모지 마커를 제거한다. 멱등 — 반복 실행해도 결과가 같다. 반환: 갱신한 항목 수.
 #[tauri::command]
@@ -598,10 +698,11 @@ pub fn migrate_ai_schedules_content(app: AppHandle) -> Result<u32, String> {
     let conn = Connection::open(dir.join("hypercool.db")).map_err(|e| e.to_string())?;
     // 시작 시점 동시 쓰기(동기화 등)와 겹칠 수 있어 잠금 대기 여유를 둔다.
     let _ = conn.busy_timeout(std::time::Duration::from_secs(5));
+    ensure_revision_tables(&conn)?;
 
     let rows: Vec<(String, Option<String>, Option<String>)> = {
         let mut stmt = conn
-            .prepare("SELECT id, content, reference_id FROM tbl_schedules WHERE color = ?1")
+            .prepare(&format!("SELECT id, content, reference_id FROM tbl_schedules WHERE {}", AI_SCHEDULE_SQL))
             .map_err(|e| e.to_string())?;
         let mapped = stmt
             .query_map([AI_COLOR], |r| {
@@ -691,33 +792,41 @@ struct PassOpts {
     since_override: Option<i64>,
     /// 성공 시 저장된 last_seen_id 를 current_max 로 전진시킬지. 디버그 실행은 false.
     advance: bool,
+    /// 실행 기록에 남길 계기.
+    trigger: &'static str,
 }
 
-/// 실제 1회 실행: claude 호출 → 파싱 → 검증 → 등록 → (옵션) last_seen 전진.
+/// 실제 1회 실행: 백엔드 호출 → 파싱 → 검증 → 등록 → (옵션) last_seen 전진.
+/// 백엔드를 실제로 부른 실행은 결과와 무관하게 `briefing_runs` 에 기록한다.
 /// 반환: (신규 등록 수, skip 수). 실패 시 Err(사유) — last_seen 은 전진하지 않음.
 fn run_pass(app: &AppHandle, opts: &PassOpts) -> Result<(i64, i64), String> {
-    let claude = find_claude()
-        .ok_or_else(|| "Claude Code CLI(claude)를 찾을 수 없습니다. 설치 후 다시 시도하세요.".to_string())?;
-    let mcp_path = ensure_mcp_config(app)?;
-    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let backend = select_backend();
+    backend.check()?;
 
     let last_seen = opts.since_override.unwrap_or_else(read_last_seen_id);
     let current_max = current_max_message_id(app);
-    // 신규 메시지가 없으면 claude 실행 없이 즉시 종료(증분).
+    // 신규 메시지가 없으면 백엔드 실행 없이 즉시 종료(증분).
     if current_max <= last_seen {
         return Ok((0, 0));
     }
 
     let today = seoul_today();
-    let prompt = build_prompt(app, &today, last_seen);
-
-    let (stdout, _stderr) = spawn_claude(&claude, &prompt, &mcp_path, &app_data_dir)?;
-    let items = parse_items_from_output(&stdout)?;
-    let (new_count, skipped) = register_items(app, items, &today)?;
-
-    // 성공(프로세스 정상 종료 + 파싱 성공)했으므로 last_seen 을 전진시킨다(추출 0건이어도).
+    // 이번 실행에서 발급한 개인정보 토큰은 등록까지 마치면 버린다.
+    let _pii_scope = crate::pii_redact::begin_run();
+    let recorder = RunRecorder::start(app, opts.trigger, backend.name(), last_seen);
+    let mut end = RunEnd::default();
+    // 메시지를 직접 넘긴 백엔드는 실제로 넘긴 마지막 id 까지만 처리한 것이다(개수 상한 초과분은 다음 실행).
+    let result = extract_and_register(app, backend.as_ref(), &today, last_seen, &mut end)
+        .map(|covered| covered.unwrap_or(current_max).min(current_max));
+    end.until_id = result.as_ref().ok().copied();
+    end.error = result.as_ref().err().cloned();
+    recorder.finish(&end);
+    let covered = result?;
+    let (new_count, skipped) = (end.new_count, end.skipped_count);
+
+    // 성공(백엔드 정상 종료 + 파싱 성공)했으므로 last_seen 을 전진시킨다(추출 0건이어도).
     if opts.advance {
-        write_last_seen_id(current_max);
+        write_last_seen_id(covered);
     }
 
     if new_count > 0 {
@@ -732,12 +841,211 @@ fn run_pass(app: &AppHandle, opts: &PassOpts) -> Result<(i64, i64), String> {
     Ok((new_count, skipped))
 }
 
+/// 백엔드 실행 → 파싱 → 등록. 원문 출력·항목별 결과·건수를 `end` 에 채운다.
+/// 반환: 직접 넘긴 마지막 메시지 id(`run_backend` 와 같음).
+fn extract_and_register(
+    app: &AppHandle,
+    backend: &dyn ExtractionBackend,
+    today: &str,
+    since: i64,
+    end: &mut RunEnd,
+) -> Result<Option<i64>, String> {
+    let rules = briefing_rules::load_rules(app);
+    let (output, covered, gated) = run_backend(app, backend, today, since, &rules)?;
+    end.usage = output.usage.clone();
+    if let Some(e) = output.failure {
+        return Err(e);
+    }
+    end.raw_output = Some(output.text.clone());
+    let mut items = parse_items_from_output(&output.text)?;
+    apply_forced_rules(app, &rules, since, covered, &mut items, today);
+    let mut report = register_items_core(app, items, today, &rules)?;
+    report.extend_filtered(gated);
+    end.new_count = report.new_count;
+    end.skipped_count = report.skipped();
+    end.items = report.items;
+    Ok(covered)
+}
+
+/// 프롬프트를 만들고(도구 없는 백엔드면 신규 메시지도 함께) 백엔드를 1회 실행한다.
+/// 반환: (출력, 직접 넘긴 마지막 메시지 id — 도구로 읽는 백엔드는 None, 규칙 제외 기록).
+/// 출력의 `failure` 는 호출 측이 확인한다(사용량을 먼저 기록하기 위해).
+/// 제외 규칙에 걸린 메시지는 백엔드에 넘기지 않는다(처리한 범위에는 포함).
+fn run_backend(
+    app: &AppHandle,
+    backend: &dyn ExtractionBackend,
+    today: &str,
+    since: i64,
+    rules: &RuleSet,
+) -> Result<(BackendOutput, Option<i64>, Vec<RunItem>), String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let (mcp_path, mut messages) = if backend.needs_messages() {
+        (None, load_source_messages(app, since)?)
+    } else {
+        (Some(ensure_mcp_config(app)?), Vec::new())
+    };
+    let covered = messages.last().map(|m| m.id);
+    let gated = drop_skipped_messages(&mut messages, rules);
+    let prompt = build_prompt(app, today, since, rules);
+    let output = backend.extract(&ExtractionRequest {
+        prompt: &prompt,
+        today,
+        messages: &messages,
+        mcp_config: mcp_path.as_deref(),
+        cwd: &app_data_dir,
+    })?;
+    Ok((output, covered, gated))
+}
+
+/// 제외 규칙에 걸린 메시지를 빼고, 뺀 메시지마다 규칙 제외 기록을 남긴다.
+fn drop_skipped_messages(messages: &mut Vec<SourceMessage>, rules: &RuleSet) -> Vec<RunItem> {
+    let mut gated = Vec::new();
+    messages.retain(|m| {
+        let Gate::Skip(name) = rules.gate(&message_subject(m)) else {
+            return true;
+        };
+        gated.push(RunItem {
+            source_message_id: Some(m.id),
+            title: m.text.lines().map(str::trim).find(|l| !l.is_empty()).map(|l| head_chars(l, 40)),
+            date: None,
+            outcome: ItemOutcome::Filtered,
+            schedule_id: None,
+            reason: Some(format!("규칙 「{}」로 제외", name)),
+            item_json: serde_json::json!({ "source_message_id": m.id }).to_string(),
+        });
+        false
+    });
+    gated
+}
+
+fn message_subject(m: &SourceMessage) -> RuleSubject<'_> {
+    RuleSubject { sender: &m.sender, text: &m.text, attachments: &m.attachments }
+}
+
+/// 강제 추출 규칙이 있으면 이번 범위의 메시지를 다시 읽어 빠진 항목을 채운다.
+fn apply_forced_rules(
+    app: &AppHandle,
+    rules: &RuleSet,
+    since: i64,
+    covered: Option<i64>,
+    items: &mut Vec<ExtractedItem>,
+    today: &str,
+) {
+    if !rules.has_force() {
+        return;
+    }
+    let messages: Vec<SourceMessage> = load_source_messages(app, since)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|m| covered.is_none_or(|c| m.id <= c))
+        .collect();
+    add_forced_items(rules, &messages, items, today);
+}
+
+/// 강제 추출 규칙에 걸렸는데 백엔드가 항목을 내지 않은 메시지에 항목을 만든다.
+/// 날짜 표현이 있으면 규칙 기반 추출과 같은 방식으로, 없으면 오늘 할 일로 만든다.
+fn add_forced_items(rules: &RuleSet, messages: &[SourceMessage], items: &mut Vec<ExtractedItem>, today: &str) {
+    let Ok(today_date) = chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d") else {
+        return;
+    };
+    for m in messages {
+        if items.iter().any(|i| i.source_message_id == Some(m.id)) {
+            continue;
+        }
+        let Gate::Force(name) = rules.gate(&message_subject(m)) else {
+            continue;
+        };
+        let mut item = briefing_backend::rule_item(m, today_date)
+            .and_then(|v| serde_json::from_value::<ExtractedItem>(v).ok())
+            .unwrap_or_else(|| ExtractedItem {
+                source_message_id: Some(m.id),
+                received_at: m.receive_date.clone(),
+                sender: Some(m.sender.clone()),
+                item_type: Some("task".to_string()),
+                title: m.text.lines().map(str::trim).find(|l| !l.is_empty()).map(|l| head_chars(l, 40)),
+                detail: Some(head_chars(&m.text, 200)),
+                date: Some(today.to_string()),
+                has_attachment: Some(!m.attachments.is_empty()),
+                ..Default::default()
+            });
+        item.forced_by = Some(name);
+        items.push(item);
+    }
+}
+
+/// 설정에 따라 추출 백엔드를 고른다.
+fn select_backend() -> Box<dyn ExtractionBackend> {
+    let settings = briefing_backend::load_settings();
+    match settings.kind {
+        BackendKind::ClaudeCli => Box::new(ClaudeCliBackend),
+        BackendKind::OpenaiCompat => {
+            Box::new(briefing_backend::OpenAiCompatBackend::from_settings(&settings))
+        }
+        BackendKind::Rules => Box::new(briefing_backend::RuleBackend),
+    }
+}
+
+/// Claude Code CLI 백엔드: hypercool MCP 읽기 도구로 메시지를 직접 조회한다.
+struct ClaudeCliBackend;
+
+impl ExtractionBackend for ClaudeCliBackend {
+    fn name(&self) -> &'static str {
+        "claude_cli"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        find_claude().map(|_| ()).ok_or_else(|| CLAUDE_NOT_FOUND.to_string())
+    }
+
+    fn needs_messages(&self) -> bool {
+        false
+    }
+
+    fn extract(&self, req: &ExtractionRequest<'_>) -> Result<BackendOutput, String> {
+        let claude = find_claude().ok_or_else(|| CLAUDE_NOT_FOUND.to_string())?;
+        let mcp_path = req
+            .mcp_config
+            .ok_or_else(|| "claude 용 MCP 설정 경로가 없습니다.".to_string())?;
+        let (stdout, stderr) = spawn_claude(&claude, req.prompt, mcp_path, req.cwd)?;
+        let tail = tail_chars(&stderr, 400);
+        let mut output = parse_claude_envelope(&stdout)?;
+        output.log_tail = if tail.is_empty() { None } else { Some(tail) };
+        Ok(output)
+    }
+}
+
+/// 검색 DB에서 id > since 인 메시지를 오래된 순으로 읽는다(도구 없는 백엔드 입력).
+fn load_source_messages(app: &AppHandle, since: i64) -> Result<Vec<SourceMessage>, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let conn = Connection::open(dir.join("hypercool_search.db"))
+        .map_err(|e| format!("검색 DB 연결 실패: {}", e))?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, sender, receive_date, content_text, COALESCE(file_paths, '')
+             FROM messages WHERE id > ?1 ORDER BY id LIMIT ?2",
+        )
+        .map_err(|e| format!("메시지 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params![since, INLINE_MESSAGE_LIMIT], |r| {
+            let files: String = r.get(4)?;
+            Ok(SourceMessage {
+                id: r.get(0)?,
+                sender: r.get(1)?,
+                receive_date: r.get(2)?,
+                text: r.get(3)?,
+                attachments: serde_json::from_str(&files).unwrap_or_default(),
+            })
+        })
+        .map_err(|e| format!("메시지 조회 실패: {}", e))?;
+    Ok(rows.flatten().collect())
+}
+
 /// claude headless 프로세스를 실행하고 (stdout, stderr)을 반환한다. 타임아웃 시 kill.
 fn spawn_claude(
-    claude: &PathBuf,
+    claude: &Path,
     prompt: &str,
-    mcp_path: &PathBuf,
-    cwd: &PathBuf,
+    mcp_path: &Path,
+    cwd: &Path,
 ) -> Result<(String, String), String> {
     use std::io::Read;
 
@@ -825,29 +1133,44 @@ fn spawn_claude(
 
 // ─── 파싱 ─────────────────────────────────────────────────────────────────────
 
-fn parse_items_from_output(stdout: &str) -> Result<Vec<ExtractedItem>, String> {
+/// claude `--output-format json` 봉투에서 최종 응답(result)과 사용량을 꺼낸다.
+/// `is_error` 봉투도 비용은 청구되므로 `Err` 대신 `failure` 에 사유를 담아 사용량을 남긴다.
+fn parse_claude_envelope(stdout: &str) -> Result<BackendOutput, String> {
     let envelope: serde_json::Value = serde_json::from_str(stdout.trim())
-        .map_err(|e| format!("claude 출력(JSON 봉투) 파싱 실패: {}", e))?;
+        .map_err(|e| format!("claude 출력(JSON 봉투) 파싱 실패: {} / 원문: {}", e, head_chars(stdout, 400)))?;
+    let usage = RunUsage::from_claude_envelope(&envelope);
+    let result = envelope.get("result").and_then(|v| v.as_str());
 
     if envelope
         .get("is_error")
         .and_then(|v| v.as_bool())
         .unwrap_or(false)
     {
-        let msg = envelope
-            .get("result")
-            .and_then(|v| v.as_str())
-            .unwrap_or("알 수 없는 오류");
-        return Err(format!("claude 오류: {}", tail_chars(msg, 300)));
+        let msg = result.unwrap_or("알 수 없는 오류");
+        return Ok(BackendOutput {
+            usage,
+            failure: Some(format!("claude 오류: {}", tail_chars(msg, 300))),
+            ..Default::default()
+        });
     }
 
-    let result_str = envelope
-        .get("result")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| "claude 출력에 result 필드가 없습니다.".to_string())?;
+    match result {
+        Some(text) => Ok(BackendOutput { text: text.to_string(), usage, ..Default::default() }),
+        None => Ok(BackendOutput {
+            usage,
+            failure: Some("claude 출력에 result 필드가 없습니다.".to_string()),
+            ..Default::default()
+        }),
+    }
+}
 
-    let arr_text = extract_json_array(result_str)
-        .ok_or_else(|| "claude 결과에서 JSON 배열을 찾지 못했습니다.".to_string())?;
+/// 백엔드 최종 응답 텍스트에서 일정 항목 배열을 파싱한다(모든 백엔드 공통).
+fn parse_items_from_output(result_str: &str) -> Result<Vec<ExtractedItem>, String> {
+    // MCP 결과의 개인정보 토큰([PHONE_1] 등)을 원문으로 되돌린 뒤 파싱한다.
+    let result_str = crate::pii_redact::restore(result_str);
+
+    let arr_text = extract_json_array(&result_str)
+        .ok_or_else(|| "응답에서 JSON 배열을 찾지 못했습니다.".to_string())?;
 
     serde_json::from_str(&arr_text).map_err(|e| format!("일정 JSON 배열 파싱 실패: {}", e))
 }
@@ -864,41 +1187,142 @@ fn extract_json_array(s: &str) -> Option<String> {
 
 // ─── 등록 ─────────────────────────────────────────────────────────────────────
 
-fn register_items(
+/// 등록 결과: 건수와 항목별 처리 기록(실행 기록용).
+#[derive(Default)]
+pub(crate) struct RegisterReport {
+    pub(crate) new_count: i64,
+    pub(crate) dedup_count: i64,
+    pub(crate) invalid_count: i64,
+    pub(crate) filtered_count: i64,
+    pub(crate) items: Vec<RunItem>,
+}
+
+impl RegisterReport {
+    fn skipped(&self) -> i64 {
+        self.dedup_count + self.invalid_count + self.filtered_count
+    }
+
+    fn push(
+        &mut self,
+        item_json: String,
+        item: &ExtractedItem,
+        outcome: ItemOutcome,
+        schedule_id: Option<String>,
+        reason: Option<String>,
+    ) {
+        match outcome {
+            ItemOutcome::Created | ItemOutcome::Superseded => self.new_count += 1,
+            ItemOutcome::Flagged | ItemOutcome::Duplicate => self.dedup_count += 1,
+            ItemOutcome::Invalid => self.invalid_count += 1,
+            ItemOutcome::Filtered => self.filtered_count += 1,
+        }
+        self.items.push(RunItem {
+            source_message_id: item.source_message_id,
+            title: item.title.clone(),
+            date: item.date.clone(),
+            outcome,
+            schedule_id,
+            reason,
+            item_json,
+        });
+    }
+
+    /// 백엔드에 넘기기 전에 규칙으로 뺀 메시지 기록을 더한다.
+    fn extend_filtered(&mut self, gated: Vec<RunItem>) {
+        self.filtered_count += gated.len() as i64;
+        self.items.extend(gated);
+    }
+}
+
+/// 등록 공통 경로: 일정 DB 를 열어 `register_items_with` 로 등록한다.
+fn register_items_core(
     app: &AppHandle,
     items: Vec<ExtractedItem>,
     today: &str,
-) -> Result<(i64, i64), String> {
-    register_items_core(app, items, today).map(|(new_c, dedup_c, invalid_c)| (new_c, dedup_c + invalid_c))
+    rules: &RuleSet,
+) -> Result<RegisterReport, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let conn = Connection::open(dir.join("hypercool.db"))
+        .map_err(|e| format!("일정 DB 연결 실패: {}", e))?;
+    register_items_with(&conn, items, today, rules, |mid| SourceDetail {
+        sender: fetch_message_sender(app, mid),
+        html: fetch_message_html(app, mid),
+        attachments: fetch_message_files(app, mid),
+    })
+}
+
+/// 등록 시 규칙 판정과 원문 보존에 쓰는 출처 메시지 정보.
+#[derive(Default)]
+pub(crate) struct SourceDetail {
+    /// 검색 DB 에 저장된 발신자(규칙 판정은 백엔드가 적은 발신자 대신 이것을 쓴다).
+    pub(crate) sender: Option<String>,
+    pub(crate) html: Option<String>,
+    pub(crate) attachments: Vec<String>,
 }
 
-/// 등록 공통 경로: (신규, 중복 skip, 검증/실패 skip)을 반환.
+/// 항목마다 (신규, 중복 skip, 검증/실패 skip, 규칙 제외) 중 하나로 판정해 등록한다.
+/// 출처 메시지 조회(`fetch_source`)를 주입받아 앱 없이도 돈다.
+/// 브리핑 규칙: 제외 규칙에 걸린 항목은 등록하지 않고, 제목 접두어·기본 시각·색상을 입힌다.
+/// 정정 메시지로 기존 일정을 갱신한 건은 신규에, 검토 대기로 남긴 건은 중복 skip 에 센다.
 /// 중복 방지는 2단계다.
 /// ① 결정적 id(msg-<id>) 존재 여부 — 같은 메시지의 재처리를 막는다.
 /// ② 내용 기반 — 재전송·정정으로 메시지 ID 가 달라져도 같은 날짜에 사실상 같은
 ///    일정이 이미 있으면 skip. 프롬프트에 기등록 일정을 주입해 claude 가 1차로
 ///    거르지만, LLM 출력은 비결정적이므로 여기서 결정적으로 한 번 더 막는다.
-fn register_items_core(
-    app: &AppHandle,
+fn register_items_with(
+    conn: &Connection,
     items: Vec<ExtractedItem>,
     today: &str,
-) -> Result<(i64, i64, i64), String> {
-    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let conn = Connection::open(dir.join("hypercool.db"))
-        .map_err(|e| format!("일정 DB 연결 실패: {}", e))?;
-
+    rules: &RuleSet,
+    fetch_source: impl Fn(i64) -> SourceDetail,
+) -> Result<RegisterReport, String> {
+    ensure_revision_tables(conn)?;
     let today_date = chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d").ok();
 
-    let (mut new_c, mut dedup_c, mut invalid_c) = (0i64, 0i64, 0i64);
-    // 이번 배치에서 등록한 항목 키 — 한 실행 안에서 온 재전송 메시지끼리도 중복 방지.
-    let mut batch_keys: Vec<DedupKey> = Vec::new();
-
-    for item in items {
-        let full = item.source_message_id.and_then(|mid| fetch_message_html(app, mid));
+    let mut report = RegisterReport::default();
+    // 이번 배치에서 등록한 항목 키와 일정 id — 한 실행 안에서 온 재전송 메시지끼리도 중복 방지.
+    let mut batch_keys: Vec<(DedupKey, String)> = Vec::new();
+
+    for mut item in items {
+        let item_json = serde_json::to_string(&item).unwrap_or_default();
+        let original_date = item.date.clone();
+        reconcile_item_date(&mut item, today_date);
+        let date_note = (item.date != original_date).then(|| {
+            format!(
+                "원문 날짜 표현으로 보정: {} → {}",
+                original_date.as_deref().unwrap_or("없음"),
+                item.date.as_deref().unwrap_or("없음")
+            )
+        });
+        let source = item.source_message_id.map(&fetch_source).unwrap_or_default();
+        let full = source.html;
+        let forced_by = item.forced_by.clone();
+        let mut color = None;
+        if !rules.is_empty() {
+            let sender = source.sender.clone().or_else(|| item.sender.clone()).unwrap_or_default();
+            let text = rule_text(&item, full.as_deref());
+            let subject = RuleSubject { sender: &sender, text: &text, attachments: &source.attachments };
+            if forced_by.is_none() {
+                if let Gate::Skip(name) = rules.gate(&subject) {
+                    let reason = format!("규칙 「{}」로 제외", name);
+                    report.push(item_json, &item, ItemOutcome::Filtered, None, Some(reason));
+                    continue;
+                }
+            }
+            let adj = rules.adjustments(&subject);
+            apply_adjustments(&mut item, &adj);
+            color = adj.color;
+        }
         let sched = match to_schedule_item(&item, today_date, full.as_deref()) {
-            Some(s) => s,
+            Some(mut s) => {
+                if let Some(c) = color {
+                    s.color = Some(c);
+                }
+                s
+            }
             None => {
-                invalid_c += 1;
+                let reason = invalid_reason(&item, today_date);
+                report.push(item_json, &item, ItemOutcome::Invalid, None, Some(reason));
                 continue;
             }
         };
@@ -914,33 +1338,118 @@ fn register_items_core(
             .unwrap_or(None)
             .unwrap_or(false);
         if exists {
-            dedup_c += 1;
+            let reason = "같은 id 의 일정이 이미 있음(재처리)".to_string();
+            report.push(item_json, &item, ItemOutcome::Duplicate, Some(sched.id), Some(reason));
             continue;
         }
 
+        // 정정 메시지: 같은 건의 기존 AI 일정 날짜·시각이 바뀐 경우 새로 만들지 않고 기존 일정을
+        // 갱신(손대지 않은 일정)하거나 검토 대기로 남긴다(사용자가 고친 일정).
+        if is_correction(&item, full.as_deref()) {
+            if let Some(target) = find_superseded(conn, &sched, item.sender.as_deref()) {
+                let (outcome, reason) = match apply_supersession(conn, &target, &sched, item.source_message_id) {
+                    Ok(Supersession::Applied) => (
+                        ItemOutcome::Superseded,
+                        format!(
+                            "정정 메시지로 기존 일정 변경: {} → {}",
+                            describe_start(target.start_date.as_deref()),
+                            describe_start(sched.start_date.as_deref())
+                        ),
+                    ),
+                    Ok(Supersession::Flagged) => {
+                        (ItemOutcome::Flagged, "사용자가 고친 일정이라 정정을 검토 대기로 남김".to_string())
+                    }
+                    Ok(Supersession::Ignored) => (
+                        ItemOutcome::Duplicate,
+                        "삭제된 일정이거나 같은 정정이 이미 검토 대기 중".to_string(),
+                    ),
+                    Err(e) => (ItemOutcome::Invalid, e),
+                };
+                report.push(item_json, &item, outcome, Some(target.id), Some(reason));
+                continue;
+            }
+        }
+
         // ② 내용 기반: 같은 날짜의 기존 일정(삭제·완료 포함 — 사용자 결정 존중)과
         //    제목/원문이 사실상 같으면 skip.
         let key = dedup_key_for(&sched);
         if let Some(k) = &key {
-            let dup_in_batch = batch_keys.iter().any(|b| keys_similar(k, b, true));
-            if dup_in_batch || find_semantic_duplicate(&conn, k).is_some() {
-                dedup_c += 1;
+            if let Some((_, id)) = batch_keys.iter().find(|(b, _)| keys_similar(k, b, true)) {
+                let reason = "이번 실행에서 같은 일정을 이미 등록".to_string();
+                report.push(item_json, &item, ItemOutcome::Duplicate, Some(id.clone()), Some(reason));
+                continue;
+            }
+            if let Some(id) = find_semantic_duplicate(conn, k) {
+                let reason = "같은 날짜에 사실상 같은 일정이 있음".to_string();
+                report.push(item_json, &item, ItemOutcome::Duplicate, Some(id), Some(reason));
                 continue;
             }
         }
 
-        match crate::db::create_schedule_impl(&conn, sched) {
-            Ok(_) => {
-                new_c += 1;
+        match crate::db::create_schedule_impl(conn, sched) {
+            Ok(created) => {
+                let _ = record_ai_snapshot(conn, &created);
                 if let Some(k) = key {
-                    batch_keys.push(k);
+                    batch_keys.push((k, created.id.clone()));
                 }
+                let note = match (forced_by, date_note) {
+                    (Some(name), Some(d)) => Some(format!("규칙 「{}」으로 강제 추출, {}", name, d)),
+                    (Some(name), None) => Some(format!("규칙 「{}」으로 강제 추출", name)),
+                    (None, d) => d,
+                };
+                report.push(item_json, &item, ItemOutcome::Created, Some(created.id), note);
             }
-            Err(_) => invalid_c += 1,
+            Err(e) => report.push(item_json, &item, ItemOutcome::Invalid, None, Some(e)),
+        }
+    }
+
+    Ok(report)
+}
+
+/// 규칙 판정에 쓸 본문: 추출 항목의 제목·요점과 원문(HTML 제거).
+fn rule_text(item: &ExtractedItem, html: Option<&str>) -> String {
+    let original = html.map(strip_html_tags);
+    [item.title.as_deref(), item.detail.as_deref(), original.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 규칙의 제목 접두어·기본 시각을 항목에 입힌다(색상은 일정으로 바꾼 뒤 입힌다).
+/// 기본 시각은 시각·교시가 모두 없을 때만, 접두어는 이미 붙어 있으면 다시 붙이지 않는다.
+fn apply_adjustments(item: &mut ExtractedItem, adj: &briefing_rules::Adjustments) {
+    if let Some(prefix) = adj.title_prefix.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        let title = item.title.as_deref().map(str::trim).unwrap_or("할 일");
+        if !title.starts_with(prefix) {
+            item.title = Some(format!("{} {}", prefix, title));
+        }
+    }
+    let no_time = item.time.as_deref().is_none_or(|t| t.trim().is_empty())
+        && item.period.as_deref().is_none_or(|p| p.trim().is_empty());
+    if no_time {
+        if let Some(t) = &adj.default_time {
+            item.time = Some(t.clone());
+            item.all_day = Some(false);
         }
     }
+}
 
-    Ok((new_c, dedup_c, invalid_c))
+/// `to_schedule_item` 이 None 을 돌려준 이유(실행 기록용). 같은 순서로 검사한다.
+fn invalid_reason(item: &ExtractedItem, today: Option<chrono::NaiveDate>) -> String {
+    let has_id = item.id.as_deref().is_some_and(|s| !s.trim().is_empty()) || item.source_message_id.is_some();
+    if !has_id {
+        return "id 와 출처 메시지 id 가 모두 없음".to_string();
+    }
+    let date = [item.date.as_deref(), item.active_from.as_deref(), item.received_at.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(parse_date_loose);
+    match (date, today) {
+        (None, _) => "날짜를 알 수 없음".to_string(),
+        (Some(d), Some(t)) if d < t => format!("과거 날짜({})", d.format("%Y-%m-%d")),
+        _ => "일정으로 변환할 수 없음".to_string(),
+    }
 }
 
 // ─── 내용 기반 중복 판정 ──────────────────────────────────────────────────────
@@ -998,32 +1507,32 @@ fn keys_similar(a: &DedupKey, b: &DedupKey, fuzzy: bool) -> bool {
 /// 제목 완전 일치만 비교해 오탐을 줄인다. 반환: 기존 일정 id.
 fn find_semantic_duplicate(conn: &Connection, key: &DedupKey) -> Option<String> {
     let mut stmt = conn
-        .prepare(
-            "SELECT id, title, content, color, reference_id FROM tbl_schedules
-             WHERE substr(COALESCE(start_date, ''), 1, 10) = ?1",
-        )
+        .prepare(&format!(
+            "SELECT id, title, content, {}, reference_id FROM tbl_schedules
+             WHERE substr(COALESCE(start_date, ''), 1, 10) = ?2",
+            AI_SCHEDULE_SQL
+        ))
         .ok()?;
     let rows = stmt
-        .query_map([&key.date], |r| {
+        .query_map(rusqlite::params![AI_COLOR, key.date], |r| {
             Ok((
                 r.get::<_, String>(0)?,
                 r.get::<_, String>(1)?,
                 r.get::<_, Option<String>>(2)?,
-                r.get::<_, Option<String>>(3)?,
+                r.get::<_, bool>(3)?,
                 r.get::<_, Option<String>>(4)?,
             ))
         })
         .ok()?;
     for row in rows.flatten() {
-        let (id, title, content, color, ref_id) = row;
+        let (id, title, content, is_ai, ref_id) = row;
         let existing = DedupKey {
             date: key.date.clone(),
             norm_title: normalize_for_match(&title),
             norm_body: normalized_original_body(content.as_deref().unwrap_or("")),
             source_ref: ref_id,
         };
-        let fuzzy = color.as_deref() == Some(AI_COLOR);
-        if keys_similar(key, &existing, fuzzy) {
+        if keys_similar(key, &existing, is_ai) {
             return Some(id);
         }
     }
@@ -1108,6 +1617,521 @@ fn bigram_dice(a: &str, b: &str) -> f64 {
     2.0 * inter as f64 / (xa.len() + xb.len()) as f64
 }
 
+// ─── 정정 메시지 반영 ─────────────────────────────────────────────────────────
+
+/// 정정·변경 안내를 나타내는 표현. 제목·요점·원문 어디든 날짜 표현 가까이에 있으면 정정 후보로 본다.
+/// "수정"·"조정"·"→" 는 평범한 안내문("수정 사항 반영", "시간 조정")에도 흔해 넣지 않는다.
+const CORRECTION_KEYWORDS: &[&str] = &["변경", "정정", "연기", "앞당", "재공지"];
+/// 정정 표현과 날짜 표현 사이의 최대 거리(글자).
+const CORRECTION_DATE_WINDOW_CHARS: usize = 20;
+/// 주제 비교 전에 제목에서 지우는 꼬리표(정정 표현 + 흔한 안내 문구).
+const TOPIC_NOISE: &[&str] = &["변경", "정정", "수정", "연기", "조정", "재공지", "재안내", "안내", "알림", "일정"];
+/// 정정 대상 탐색 범위: 새 날짜 기준 ±일.
+const SUPERSEDE_WINDOW_DAYS: i64 = 60;
+/// 정정 판정: 주제(날짜·꼬리표를 뺀 제목) 유사도 기준. 정정 표현·발신자·기간 조건이 함께
+/// 걸리므로 내용 중복 판정보다 느슨하게 둔다.
+const SUPERSEDE_TITLE_THRESHOLD: f64 = 0.7;
+/// 정정 판정: 원문 유사도가 이 값 이상이면 같은 건(날짜만 바뀐 재공지)으로 본다.
+const SUPERSEDE_BODY_THRESHOLD: f64 = 0.75;
+
+/// 정정 반영 기록과 "에이전트가 마지막으로 쓴 값" 스냅샷 테이블을 보장한다.
+/// 스냅샷과 현재 값이 다르면 사용자가 손댄 일정으로 보고 자동 갱신하지 않는다.
+fn ensure_revision_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_ai_schedule_snapshots (
+            schedule_id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            start_date TEXT,
+            end_date TEXT,
+            is_all_day BOOLEAN NOT NULL DEFAULT 0,
+            written_at TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS tbl_schedule_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            schedule_id TEXT NOT NULL,
+            old_message_id INTEGER,
+            new_message_id INTEGER,
+            old_start TEXT,
+            new_start TEXT,
+            new_end TEXT,
+            new_all_day BOOLEAN NOT NULL DEFAULT 0,
+            title TEXT NOT NULL,
+            status TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            resolved_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_schedule_revisions_schedule ON tbl_schedule_revisions(schedule_id);",
+    )
+    .map_err(|e| format!("정정 기록 테이블 생성 실패: {}", e))
+}
+
+fn record_ai_snapshot(conn: &Connection, sched: &crate::db::ScheduleItem) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO tbl_ai_schedule_snapshots (schedule_id, title, start_date, end_date, is_all_day, written_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![sched.id, sched.title, sched.start_date, sched.end_date, sched.is_all_day, sched.updated_at],
+    )
+    .map_err(|e| format!("일정 스냅샷 기록 실패: {}", e))?;
+    Ok(())
+}
+
+/// 정정 메시지인지: 정정 표현이 같은 텍스트의 날짜 표현 가까이에 있어야 한다
+/// ("회신 기한 변경: 15일 → 17일", "7월 16일로 연기되었습니다").
+fn is_correction(item: &ExtractedItem, full_body: Option<&str>) -> bool {
+    let body = full_body.map(strip_html_tags).unwrap_or_default();
+    let texts = [
+        item.title.as_deref(),
+        item.detail.as_deref(),
+        item.source_text.as_deref(),
+        Some(body.as_str()),
+    ];
+    texts.iter().flatten().any(|t| correction_near_date(t))
+}
+
+fn correction_near_date(text: &str) -> bool {
+    let reference = chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    let dates: Vec<(usize, usize)> = date_expr::extract(text, reference)
+        .into_iter()
+        .filter(|e| e.date.is_some())
+        .map(|e| (e.start, e.end))
+        .collect();
+    if dates.is_empty() {
+        return false;
+    }
+    let gap = |a: usize, b: usize| text[a.min(b)..a.max(b)].chars().count();
+    CORRECTION_KEYWORDS.iter().any(|k| {
+        text.match_indices(k).any(|(start, m)| {
+            let end = start + m.len();
+            dates.iter().any(|&(ds, de)| {
+                let distance = if de <= start { gap(de, start) } else if ds >= end { gap(end, ds) } else { 0 };
+                distance <= CORRECTION_DATE_WINDOW_CHARS
+            })
+        })
+    })
+}
+
+/// 주제 비교용 제목: 날짜·시각·교시 표현과 정정·안내 꼬리표를 지우고 정규화한다.
+/// 학년·반 같은 나머지 숫자는 남겨 "1학년 협의회"와 "2학년 협의회"를 구분한다.
+fn topic_key(title: &str) -> String {
+    let reference = chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    let mut stripped = String::with_capacity(title.len());
+    let mut pos = 0;
+    for e in date_expr::extract(title, reference) {
+        stripped.push_str(&title[pos..e.start]);
+        pos = e.end;
+    }
+    stripped.push_str(&title[pos..]);
+    let mut t = normalize_for_match(&stripped);
+    for w in TOPIC_NOISE {
+        t = t.replace(w, "");
+    }
+    t
+}
+
+/// content 의 "발신: …" 줄에서 발신자를 꺼낸다(to_schedule_item 형식).
+fn sender_of_content(content: &str) -> Option<String> {
+    let start = content.find("<div>발신: ")? + "<div>발신: ".len();
+    let end = content[start..].find("</div>")? + start;
+    Some(content[start..end].to_string())
+}
+
+/// 정정 메시지가 대체할 기존 AI 일정.
+struct SupersedeTarget {
+    id: String,
+    title: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    is_all_day: bool,
+    is_completed: bool,
+    is_deleted: bool,
+    created_at: String,
+    updated_at: String,
+    reference_id: Option<String>,
+}
+
+/// 새 일정과 주제·발신자가 같고 시작 시각만 다른 기존 AI 일정을 찾는다(다른 메시지 출처만).
+/// 삭제된 일정도 후보에 넣어, 사용자가 지운 일정을 정정 메시지로 되살리지 않는다.
+/// 여럿이면 주제가 가장 비슷한 것, 같으면 최근 생성분.
+fn find_superseded(
+    conn: &Connection,
+    sched: &crate::db::ScheduleItem,
+    sender: Option<&str>,
+) -> Option<SupersedeTarget> {
+    let new_start = sched.start_date.as_deref()?;
+    let new_date = parse_date_loose(new_start)?;
+    let lo = (new_date - chrono::Duration::days(SUPERSEDE_WINDOW_DAYS)).format("%Y-%m-%d").to_string();
+    let hi = (new_date + chrono::Duration::days(SUPERSEDE_WINDOW_DAYS)).format("%Y-%m-%d").to_string();
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id, s.title, s.content, s.start_date, s.end_date, s.is_all_day, s.is_completed,
+                    s.is_deleted, s.created_at, s.updated_at, s.reference_id, a.title
+             FROM tbl_schedules s
+             LEFT JOIN tbl_ai_schedule_snapshots a ON a.schedule_id = s.id
+             WHERE (s.color = ?1 OR a.schedule_id IS NOT NULL)
+               AND substr(COALESCE(s.start_date, ''), 1, 10) BETWEEN ?2 AND ?3
+             ORDER BY s.created_at DESC",
+        )
+        .ok()?;
+    let rows = stmt
+        .query_map(rusqlite::params![AI_COLOR, lo, hi], |r| {
+            Ok((
+                SupersedeTarget {
+                    id: r.get(0)?,
+                    title: r.get(1)?,
+                    start_date: r.get(3)?,
+                    end_date: r.get(4)?,
+                    is_all_day: r.get(5)?,
+                    is_completed: r.get(6)?,
+                    is_deleted: r.get(7)?,
+                    created_at: r.get(8)?,
+                    updated_at: r.get(9)?,
+                    reference_id: r.get(10)?,
+                },
+                r.get::<_, Option<String>>(2)?,
+                r.get::<_, Option<String>>(11)?,
+            ))
+        })
+        .ok()?;
+
+    let new_topic = topic_key(&sched.title);
+    let new_body = normalized_original_body(sched.content.as_deref().unwrap_or(""));
+    let new_sender = sender.map(|s| html_escape(s.trim()));
+    let mut best: Option<(f64, SupersedeTarget)> = None;
+    for (target, content, ai_title) in rows.flatten() {
+        // 같은 메시지에서 나온 일정은 서로를 정정하지 않는다(출처를 모르는 일정끼리는 비교하지 않음).
+        let same_source = target.reference_id.is_some() && target.reference_id == sched.reference_id;
+        if target.start_date.as_deref() == Some(new_start) || same_source {
+            continue;
+        }
+        let content = content.unwrap_or_default();
+        if let (Some(a), Some(b)) = (new_sender.as_deref(), sender_of_content(&content)) {
+            if a != b {
+                continue;
+            }
+        }
+        // 사용자가 제목을 고쳤을 수 있으므로 에이전트가 처음 쓴 제목과도 비교한다.
+        let topic_sim = |title: &str| {
+            let old_topic = topic_key(title);
+            if digits_of(&old_topic) != digits_of(&new_topic) {
+                0.0
+            } else {
+                bigram_dice(&new_topic, &old_topic)
+            }
+        };
+        let title_sim = topic_sim(&target.title).max(ai_title.as_deref().map(topic_sim).unwrap_or(0.0));
+        let old_body = normalized_original_body(&content);
+        let body_sim = if new_body.chars().count() >= MIN_BODY_CHARS && old_body.chars().count() >= MIN_BODY_CHARS {
+            bigram_dice(&new_body, &old_body)
+        } else {
+            0.0
+        };
+        if title_sim < SUPERSEDE_TITLE_THRESHOLD && body_sim < SUPERSEDE_BODY_THRESHOLD {
+            continue;
+        }
+        let score = title_sim.max(body_sim);
+        if best.as_ref().is_none_or(|(b, _)| score > *b) {
+            best = Some((score, target));
+        }
+    }
+    best.map(|(_, t)| t)
+}
+
+/// 사용자가 손댄 일정인지: 완료 처리했거나, 에이전트가 마지막으로 쓴 값과 제목·시작·종료·종일이
+/// 다르다. 스냅샷이 없는(기능 도입 전) 일정은 생성 후 한 번도 수정되지 않았을 때만 손대지 않은 것으로 본다.
+fn user_edited(conn: &Connection, t: &SupersedeTarget) -> bool {
+    if t.is_completed {
+        return true;
+    }
+    let snapshot = conn
+        .query_row(
+            "SELECT title, start_date, end_date, is_all_day FROM tbl_ai_schedule_snapshots WHERE schedule_id = ?1",
+            [&t.id],
+            |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    r.get::<_, Option<String>>(1)?,
+                    r.get::<_, Option<String>>(2)?,
+                    r.get::<_, bool>(3)?,
+                ))
+            },
+        )
+        .optional()
+        .unwrap_or(None);
+    match snapshot {
+        Some((title, start, end, all_day)) => {
+            title != t.title || start != t.start_date || end != t.end_date || all_day != t.is_all_day
+        }
+        None => t.updated_at != t.created_at,
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Supersession {
+    /// 손대지 않은 AI 일정을 새 날짜·시각으로 갱신했다.
+    Applied,
+    /// 사용자가 고친 일정이라 검토 대기로 남겼다.
+    Flagged,
+    /// 사용자가 지운 일정이거나 이미 같은 정정이 대기 중이다.
+    Ignored,
+}
+
+/// 정정 반영: 기존 일정 id 는 유지하고 시작·종료·종일·원문을 새 메시지 기준으로 바꾼다.
+/// 두 메시지의 연결은 tbl_schedule_revisions 에 남는다.
+fn apply_supersession(
+    conn: &Connection,
+    target: &SupersedeTarget,
+    sched: &crate::db::ScheduleItem,
+    new_message_id: Option<i64>,
+) -> Result<Supersession, String> {
+    if target.is_deleted {
+        return Ok(Supersession::Ignored);
+    }
+    let old_message_id = target.reference_id.as_deref().and_then(|r| r.trim().parse::<i64>().ok());
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+
+    if user_edited(conn, target) {
+        let already = conn
+            .query_row(
+                "SELECT 1 FROM tbl_schedule_revisions
+                 WHERE schedule_id = ?1 AND new_message_id IS ?2 AND status = 'pending' LIMIT 1",
+                rusqlite::params![target.id, new_message_id],
+                |_| Ok(true),
+            )
+            .optional()
+            .unwrap_or(None)
+            .unwrap_or(false);
+        if already {
+            return Ok(Supersession::Ignored);
+        }
+        insert_revision(conn, target, sched, old_message_id, new_message_id, "pending", &now, None)?;
+        return Ok(Supersession::Flagged);
+    }
+
+    let note = format!(
+        "<div>일정 변경: {} → {}</div>",
+        describe_start(target.start_date.as_deref()),
+        describe_start(sched.start_date.as_deref())
+    );
+    let marker = format!("<div>{}</div>", AI_MARKER);
+    let content = sched
+        .content
+        .as_deref()
+        .unwrap_or("")
+        .replacen(&marker, &format!("{}{}", note, marker), 1);
+    conn.execute(
+        "UPDATE tbl_schedules SET start_date = ?1, end_date = ?2, is_all_day = ?3, content = ?4,
+                reference_id = ?5, updated_at = ?6
+         WHERE id = ?7",
+        rusqlite::params![
+            sched.start_date,
+            sched.end_date,
+            sched.is_all_day,
+            content,
+            sched.reference_id,
+            now,
+            target.id
+        ],
+    )
+    .map_err(|e| format!("일정 정정 반영 실패: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO tbl_ai_schedule_snapshots (schedule_id, title, start_date, end_date, is_all_day, written_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![target.id, target.title, sched.start_date, sched.end_date, sched.is_all_day, now],
+    )
+    .map_err(|e| format!("일정 스냅샷 기록 실패: {}", e))?;
+    insert_revision(conn, target, sched, old_message_id, new_message_id, "applied", &now, Some(&now))?;
+    Ok(Supersession::Applied)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn insert_revision(
+    conn: &Connection,
+    target: &SupersedeTarget,
+    sched: &crate::db::ScheduleItem,
+    old_message_id: Option<i64>,
+    new_message_id: Option<i64>,
+    status: &str,
+    now: &str,
+    resolved_at: Option<&str>,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO tbl_schedule_revisions
+            (schedule_id, old_message_id, new_message_id, old_start, new_start, new_end, new_all_day,
+             title, status, created_at, resolved_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        rusqlite::params![
+            target.id,
+            old_message_id,
+            new_message_id,
+            target.start_date,
+            sched.start_date,
+            sched.end_date,
+            sched.is_all_day,
+            target.title,
+            status,
+            now,
+            resolved_at
+        ],
+    )
+    .map_err(|e| format!("정정 기록 실패: {}", e))?;
+    Ok(())
+}
+
+/// "2026-07-15T14:20:00+09:00" → "07/15 14:20", 종일이면 "07/15".
+fn describe_start(start: Option<&str>) -> String {
+    let Some(s) = start else { return "?".to_string() };
+    let date = s.get(5..10).map(|md| md.replace('-', "/")).unwrap_or_else(|| s.to_string());
+    match s.get(11..16) {
+        Some(t) => format!("{} {}", date, t),
+        None => date,
+    }
+}
+
+/// 정정 기록 한 건(AI 연동 탭 "일정 변경 검토" 목록).
+#[derive(Serialize)]
+pub struct ScheduleRevision {
+    pub id: i64,
+    pub schedule_id: String,
+    pub title: String,
+    pub old_message_id: Option<i64>,
+    pub new_message_id: Option<i64>,
+    pub old_start: Option<String>,
+    pub new_start: Option<String>,
+    /// "applied" | "pending" | "dismissed"
+    pub status: String,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+fn open_schedule_db(app: &AppHandle) -> Result<Connection, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let conn = Connection::open(dir.join("hypercool.db"))
+        .map_err(|e| format!("일정 DB 연결 실패: {}", e))?;
+    ensure_revision_tables(&conn)?;
+    Ok(conn)
+}
+
+/// 정정 기록 목록(최근순). `pending_only` 면 검토 대기 건만.
+#[tauri::command]
+pub fn get_schedule_revisions(app: AppHandle, pending_only: Option<bool>) -> Result<Vec<ScheduleRevision>, String> {
+    let conn = open_schedule_db(&app)?;
+    list_revisions(&conn, pending_only.unwrap_or(false))
+}
+
+fn list_revisions(conn: &Connection, pending_only: bool) -> Result<Vec<ScheduleRevision>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, schedule_id, title, old_message_id, new_message_id, old_start, new_start,
+                    status, created_at, resolved_at
+             FROM tbl_schedule_revisions
+             WHERE (?1 = 0 OR status = 'pending')
+             ORDER BY id DESC LIMIT 200",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([pending_only], |r| {
+            Ok(ScheduleRevision {
+                id: r.get(0)?,
+                schedule_id: r.get(1)?,
+                title: r.get(2)?,
+                old_message_id: r.get(3)?,
+                new_message_id: r.get(4)?,
+                old_start: r.get(5)?,
+                new_start: r.get(6)?,
+                status: r.get(7)?,
+                created_at: r.get(8)?,
+                resolved_at: r.get(9)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(rows.filter_map(Result::ok).collect())
+}
+
+/// 검토 대기 정정을 처리한다. `apply` 면 일정의 시작·종료·종일만 새 값으로 바꾸고
+/// (사용자가 고친 제목·내용은 유지), 아니면 무시로 닫는다.
+#[tauri::command]
+pub fn resolve_schedule_revision(app: AppHandle, id: i64, apply: bool) -> Result<(), String> {
+    let conn = open_schedule_db(&app)?;
+    resolve_revision(&conn, id, apply)?;
+    if apply {
+        // 열려 있는 달력·탁상달력도 바뀐 날짜를 반영하게 한다(run_pass 와 같은 경로).
+        if let Ok(Some(path)) = crate::db::detect_desktopcal() {
+            let _ = crate::db::sync_to_desktopcal(app.clone(), path);
+        }
+        let _ = app.emit("calendar-update", ());
+    }
+    Ok(())
+}
+
+fn resolve_revision(conn: &Connection, id: i64, apply: bool) -> Result<(), String> {
+    let (schedule_id, new_start, new_end, new_all_day, status) = conn
+        .query_row(
+            "SELECT schedule_id, new_start, new_end, new_all_day, status FROM tbl_schedule_revisions WHERE id = ?1",
+            [id],
+            |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    r.get::<_, Option<String>>(1)?,
+                    r.get::<_, Option<String>>(2)?,
+                    r.get::<_, bool>(3)?,
+                    r.get::<_, String>(4)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("정정 기록을 찾을 수 없습니다: {}", id))?;
+    if status != "pending" {
+        return Err(format!("이미 처리된 정정입니다: {}", status));
+    }
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    if apply {
+        conn.execute(
+            "UPDATE tbl_schedules SET start_date = ?1, end_date = ?2, is_all_day = ?3, updated_at = ?4 WHERE id = ?5",
+            rusqlite::params![new_start, new_end, new_all_day, now, schedule_id],
+        )
+        .map_err(|e| format!("일정 정정 반영 실패: {}", e))?;
+    }
+    conn.execute(
+        "UPDATE tbl_schedule_revisions SET status = ?1, resolved_at = ?2 WHERE id = ?3",
+        rusqlite::params![if apply { "applied" } else { "dismissed" }, now, id],
+    )
+    .map_err(|e| format!("정정 기록 갱신 실패: {}", e))?;
+    Ok(())
+}
+
+/// LLM 이 고른 날짜를 항목 원문 발췌(source_text)의 결정적 날짜 해석과 대조해 보정한다.
+/// - 원문에 확실한 날짜 표현이 있는데 어느 것과도 맞지 않고, 하나로 좁혀지면 그 날짜로 교체.
+/// - 날짜가 비었거나 읽을 수 없으면 같은 기준으로 채운다.
+///
+/// 근거가 없거나 여러 날짜 중 하나로 좁혀지지 않으면 LLM 판단을 그대로 둔다.
+fn reconcile_item_date(item: &mut ExtractedItem, today: Option<chrono::NaiveDate>) {
+    let Some(src) = item.source_text.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return;
+    };
+    // 상대 표현은 메시지 수신일 기준이다.
+    let Some(reference) = item.received_at.as_deref().and_then(parse_date_loose).or(today) else {
+        return;
+    };
+    let suggestion = match item.date.as_deref().and_then(parse_date_loose) {
+        Some(d) => match date_expr::check_date(d, src, reference) {
+            date_expr::DateCheck::Contradicted { suggestion } => suggestion,
+            _ => None,
+        },
+        None => {
+            let exprs = date_expr::extract(src, reference);
+            let confident: Vec<&date_expr::DateExpr> = exprs
+                .iter()
+                .filter(|e| e.date.is_some() && e.confidence >= date_expr::CONFIDENT)
+                .collect();
+            date_expr::suggest_date(&confident)
+        }
+    };
+    if let Some(s) = suggestion.filter(|s| today.is_none_or(|t| *s >= t)) {
+        item.date = Some(s.format("%Y-%m-%d").to_string());
+    }
+}
+
 /// ExtractedItem → db::ScheduleItem 변환 및 검증. 등록 불가(날짜 없음/과거)면 None.
 /// `full_body`: 검색 DB에서 가져온 원문 메시지 전체(있으면 content 에 [원문]으로 첨부).
 fn to_schedule_item(
@@ -1154,14 +2178,20 @@ fn to_schedule_item(
         }
     }
     let date_str = date.format("%Y-%m-%d").to_string();
+    // 노출 기간 끝: active_until(없으면 없음). 일정 날짜보다 이르면 일정 날짜까지는 보이게 한다.
+    let visible_until = item
+        .active_until
+        .as_deref()
+        .and_then(parse_date_loose)
+        .map(|d| d.max(date).format("%Y-%m-%d").to_string());
 
-    // 시각 결정: 명시 time 우선, 없으면 교시(period)를 학교 시간표로 환산한다.
-    // (예: "6교시" → 14:20) 교시만 있는 메시지도 종일이 아니라 해당 슬롯 시각에 배치된다.
+    // 시각 결정: 명시 time 우선, 없으면 교시(period)를 그날 종 시간표로 환산한다.
+    // (예: 평소 "6교시" → 14:20) 교시만 있는 메시지도 종일이 아니라 해당 슬롯 시각에 배치된다.
     let resolved_time = item
         .time
         .as_deref()
         .and_then(parse_start_time)
-        .or_else(|| item.period.as_deref().and_then(period_to_start_time));
+        .or_else(|| item.period.as_deref().and_then(|p| period_to_start_time(p, date)));
     // 시각(명시 또는 교시 환산)이 잡히면 시간 지정 일정. 그 외에는 all_day(LLM 값 우선, 기본 종일).
     let all_day = match &resolved_time {
         Some(_) => false,
@@ -1232,9 +2262,102 @@ fn to_schedule_item(
         created_at: now.clone(),
         updated_at: now,
         is_deleted: false,
+        priority: normalize_choice(item.urgency.as_deref(), &["high", "medium", "low"]),
+        item_kind: normalize_choice(item.item_type.as_deref(), &["event", "deadline", "task", "notice"]),
+        visible_until,
+        has_attachment: Some(item.has_attachment.unwrap_or(false)),
     })
 }
 
+/// LLM 이 준 열거형 문자열을 허용 값 중 하나로 정규화(대소문자·공백 무시). 그 외는 None.
+fn normalize_choice(value: Option<&str>, allowed: &[&str]) -> Option<String> {
+    let v = value?.trim().to_ascii_lowercase();
+    allowed.iter().find(|a| **a == v).map(|a| a.to_string())
+}
+
+// ─── 오프라인 평가 지원(briefing_eval) ─────────────────────────────────────────
+// 앱 없이 실제 경로(프롬프트 치환 → 파싱 → 날짜 보정 → 변환 → 중복 판정 → 등록)를
+// 그대로 돌리기 위한 진입점. 일정 DB 는 호출 측이 준비한 (보통 메모리) 연결을 쓴다.
+
+/// 평가용 프롬프트: 템플릿에 기등록 일정(`conn`)·메시지의 날짜 힌트·규칙을 채운다.
+pub(crate) fn eval_prompt(
+    template: &str,
+    conn: &Connection,
+    today: &str,
+    since: i64,
+    messages: &[SourceMessage],
+    rules: &RuleSet,
+) -> String {
+    let lines = date_hint_lines(messages, chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d").ok());
+    let hints = if lines.is_empty() { "(없음)".to_string() } else { lines.join("\n") };
+    fill_prompt(template, today, since, &existing_schedules_text(conn, today), &hints, &rules.prompt_section())
+}
+
+/// 평가용 메시지 선별: 제외 규칙에 걸린 메시지는 백엔드에 넘기지 않는다(실제 경로와 같게).
+pub(crate) fn eval_backend_messages(messages: &[SourceMessage], rules: &RuleSet) -> Vec<SourceMessage> {
+    messages
+        .iter()
+        .filter(|m| !matches!(rules.gate(&message_subject(m)), Gate::Skip(_)))
+        .cloned()
+        .collect()
+}
+
+/// 평가용 기등록 일정을 만든다. `item` 은 백엔드 출력과 같은 스키마의 항목 JSON.
+/// `manual` 이면 수동 할 일로, 아니면 AI 일정(원문 `body` 포함)으로 넣는다. 반환: 일정 id.
+pub(crate) fn eval_seed(
+    conn: &Connection,
+    item: &serde_json::Value,
+    body: Option<&str>,
+    manual: bool,
+    deleted: bool,
+) -> Result<String, String> {
+    ensure_revision_tables(conn)?;
+    let item: ExtractedItem =
+        serde_json::from_value(item.clone()).map_err(|e| format!("기등록 일정 항목 파싱 실패: {}", e))?;
+    let mut sched = to_schedule_item(&item, None, body)
+        .ok_or_else(|| "기등록 일정 항목에 id·날짜가 없습니다.".to_string())?;
+    if manual {
+        sched.content = body.map(str::to_string);
+        sched.color = None;
+        sched.reference_id = None;
+    }
+    sched.is_deleted = deleted;
+    let created = crate::db::create_schedule_impl(conn, sched)?;
+    if !manual {
+        record_ai_snapshot(conn, &created)?;
+    }
+    Ok(created.id)
+}
+
+/// 평가용 등록: 백엔드 출력 텍스트를 파싱해 강제 추출 규칙을 적용한 뒤 `conn` 에 등록한다.
+/// 원문·첨부는 `messages` 에서 찾는다.
+pub(crate) fn eval_register(
+    conn: &Connection,
+    output: &str,
+    today: &str,
+    messages: &[SourceMessage],
+    rules: &RuleSet,
+) -> Result<RegisterReport, String> {
+    let mut items = parse_items_from_output(output)?;
+    add_forced_items(rules, messages, &mut items, today);
+    register_items_with(conn, items, today, rules, |mid| {
+        messages
+            .iter()
+            .find(|m| m.id == mid)
+            .map(|m| SourceDetail {
+                sender: Some(m.sender.clone()),
+                html: Some(m.text.clone()),
+                attachments: m.attachments.clone(),
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// 평가용 제목 유사도(중복 판정과 같은 정규화·bigram Dice).
+pub(crate) fn title_similarity(a: &str, b: &str) -> f64 {
+    bigram_dice(&normalize_for_match(a), &normalize_for_match(b))
+}
+
 // ─── 보조 ─────────────────────────────────────────────────────────────────────
 
 fn read_last_seen_id() -> i64 {
@@ -1281,18 +2404,79 @@ fn ensure_mcp_config(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(path)
 }
 
-/// 프롬프트 템플릿 치환: 오늘 날짜 + 기준 id + 기등록 일정 목록.
+/// 프롬프트 템플릿 치환: 오늘 날짜 + 기준 id + 기등록 일정 목록 + 날짜 표현 힌트.
 /// 기등록 일정을 주입해 claude 가 재전송·정정·표현만 다른 동일 업무를 의미 수준에서
 /// 거를 수 있게 한다(등록 측의 내용 중복 백스톱과 이중 방어).
-fn build_prompt(app: &AppHandle, today: &str, since: i64) -> String {
-    load_prompt_template(app)
+fn build_prompt(app: &AppHandle, today: &str, since: i64, rules: &RuleSet) -> String {
+    fill_prompt(
+        &load_prompt_template(app),
+        today,
+        since,
+        &existing_schedules_snippet(app, today),
+        &date_hints_snippet(app, today, since),
+        &rules.prompt_section(),
+    )
+}
+
+fn fill_prompt(
+    template: &str,
+    today: &str,
+    since: i64,
+    existing: &str,
+    date_hints: &str,
+    rules: &str,
+) -> String {
+    template
         .replace("{{TODAY}}", today)
         .replace("{{LAST_SEEN_ID}}", &since.to_string())
-        .replace("{{EXISTING_SCHEDULES}}", &existing_schedules_snippet(app, today))
+        .replace("{{EXISTING_SCHEDULES}}", existing)
+        .replace("{{DATE_HINTS}}", date_hints)
+        .replace("{{BRIEFING_RULES}}", rules)
+}
+
+/// 프롬프트에 주입할 "날짜 표현 힌트": 신규 메시지 본문에서 결정적으로 찾은 날짜 표현과
+/// 수신일 기준 해석 결과. 상대 표현 계산을 LLM 에 맡기지 않기 위한 사전 패스다.
+fn date_hints_snippet(app: &AppHandle, today: &str, since: i64) -> String {
+    let messages = match load_source_messages(app, since) {
+        Ok(m) => m,
+        Err(_) => return "(조회 실패)".to_string(),
+    };
+    let today = chrono::NaiveDate::parse_from_str(today, "%Y-%m-%d").ok();
+    let lines = date_hint_lines(&messages, today);
+    if lines.is_empty() {
+        "(없음)".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+fn date_hint_lines(messages: &[SourceMessage], today: Option<chrono::NaiveDate>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for m in messages {
+        let reference = m
+            .receive_date
+            .as_deref()
+            .and_then(parse_date_loose)
+            .or(today);
+        let Some(reference) = reference else { continue };
+        let exprs = date_expr::extract(&m.text, reference);
+        for e in exprs.iter().filter(|e| e.date.is_some()).take(DATE_HINTS_PER_MESSAGE) {
+            let mut line = format!("- 메시지 {}: {}", m.id, e.describe());
+            if e.confidence < date_expr::CONFIDENT {
+                line.push_str(" (불확실)");
+            }
+            lines.push(line);
+        }
+        if lines.len() >= DATE_HINTS_LIMIT {
+            lines.truncate(DATE_HINTS_LIMIT);
+            break;
+        }
+    }
+    lines
 }
 
 /// 프롬프트에 주입할 "이미 등록된 일정" 목록. 오늘 이후 일정만:
-/// - AI 생성분(color = AI_COLOR)은 삭제·완료된 것도 포함 — 사용자가 지운/끝낸 일정을
+/// - AI 생성분은 삭제·완료된 것도 포함 — 사용자가 지운/끝낸 일정을
 ///   재전송 메시지로 되살리지 않도록 상태를 함께 보여준다.
 /// - 수동 일정(manual_todo/desktopcal_memo)은 살아있는 것만.
 fn existing_schedules_snippet(app: &AppHandle, today: &str) -> String {
@@ -1300,26 +2484,34 @@ fn existing_schedules_snippet(app: &AppHandle, today: &str) -> String {
         Ok(d) => d,
         Err(_) => return "(조회 실패)".to_string(),
     };
-    let conn = match Connection::open(dir.join("hypercool.db")) {
-        Ok(c) => c,
-        Err(_) => return "(조회 실패)".to_string(),
-    };
-    let mut stmt = match conn.prepare(
-        "SELECT substr(COALESCE(start_date, ''), 1, 10), title, color, reference_id, is_completed, is_deleted
+    match Connection::open(dir.join("hypercool.db")) {
+        Ok(conn) => existing_schedules_text(&conn, today),
+        Err(_) => "(조회 실패)".to_string(),
+    }
+}
+
+/// AI 일정은 색상 규칙으로 색이 바뀌었어도 스냅샷으로 알아본다.
+fn existing_schedules_text(conn: &Connection, today: &str) -> String {
+    if ensure_revision_tables(conn).is_err() {
+        return "(조회 실패)".to_string();
+    }
+    let mut stmt = match conn.prepare(&format!(
+        "SELECT substr(COALESCE(start_date, ''), 1, 10), title, {ai} AS is_ai, reference_id, is_completed, is_deleted
          FROM tbl_schedules
-         WHERE substr(COALESCE(start_date, ''), 1, 10) >= ?1
-           AND (color = ?2 OR (is_deleted = 0 AND type IN ('manual_todo', 'desktopcal_memo')))
+         WHERE substr(COALESCE(start_date, ''), 1, 10) >= ?2
+           AND ({ai} OR (is_deleted = 0 AND type IN ('manual_todo', 'desktopcal_memo')))
          ORDER BY 1
          LIMIT 150",
-    ) {
+        ai = AI_SCHEDULE_SQL
+    )) {
         Ok(s) => s,
         Err(_) => return "(조회 실패)".to_string(),
     };
-    let rows = stmt.query_map(rusqlite::params![today, AI_COLOR], |r| {
+    let rows = stmt.query_map(rusqlite::params![AI_COLOR, today], |r| {
         Ok((
             r.get::<_, String>(0)?,
             r.get::<_, String>(1)?,
-            r.get::<_, Option<String>>(2)?,
+            r.get::<_, bool>(2)?,
             r.get::<_, Option<String>>(3)?,
             r.get::<_, bool>(4)?,
             r.get::<_, bool>(5)?,
@@ -1327,8 +2519,8 @@ fn existing_schedules_snippet(app: &AppHandle, today: &str) -> String {
     });
     let mut lines = Vec::new();
     if let Ok(rows) = rows {
-        for (date, title, color, ref_id, done, deleted) in rows.flatten() {
-            let source = if color.as_deref() == Some(AI_COLOR) {
+        for (date, title, is_ai, ref_id, done, deleted) in rows.flatten() {
+            let source = if is_ai {
                 match ref_id {
                     Some(r) => format!("AI(msg-{})", r),
                     None => "AI".to_string(),
@@ -1348,10 +2540,18 @@ fn existing_schedules_snippet(app: &AppHandle, today: &str) -> String {
     if lines.is_empty() {
         "(없음)".to_string()
     } else {
-        lines.join("\n")
+        // 일정 제목에도 이름·연락처가 들어갈 수 있어 MCP 결과와 같은 토큰으로 가린다.
+        let text = lines.join("\n");
+        match crate::pii_redact::active_config() {
+            Some(cfg) => crate::pii_redact::redact_text(&cfg, &text),
+            None => text,
+        }
     }
 }
 
+/// 컴파일 임베드된 프롬프트 템플릿.
+pub(crate) const BUNDLED_PROMPT: &str = include_str!("../resources/briefing_prompt.md");
+
 /// 런타임 프롬프트 템플릿: 번들 resource 우선, 실패 시 컴파일 임베드본 폴백.
 fn load_prompt_template(app: &AppHandle) -> String {
     if let Ok(res_dir) = app.path().resource_dir() {
@@ -1362,7 +2562,7 @@ fn load_prompt_template(app: &AppHandle) -> String {
             }
         }
     }
-    include_str!("../resources/briefing_prompt.md").to_string()
+    BUNDLED_PROMPT.to_string()
 }
 
 /// search DB(hypercool_search.db)의 최대 메시지 id(=UDB MessageKey).
@@ -1496,33 +2696,21 @@ fn parse_date_loose(s: &str) -> Option<chrono::NaiveDate> {
     None
 }
 
-/// "N교시" → 시작 시각 "HH:MM"(학교 위젯 types.ts PERIOD_TIMES / main.rs get_current_period 과 동일).
-/// 점심(인덱스 4)은 교시가 아니므로 5~7교시는 한 칸 밀어 인덱싱한다. 1~7교시 외에는 None.
-fn period_to_start_time(period: &str) -> Option<String> {
-    // (시작시, 분) — types.ts PERIOD_TIMES 시작값과 동일. 점심(index 4) 포함 8칸.
-    const PERIOD_START: [(u32, u32); 8] = [
-        (8, 30),  // 1교시
-        (9, 30),  // 2교시
-        (10, 30), // 3교시
-        (11, 30), // 4교시
-        (12, 20), // 점심(교시 아님)
-        (13, 20), // 5교시
-        (14, 20), // 6교시
-        (15, 20), // 7교시
-    ];
+/// "N교시" → `date` 의 종 시간표(`bell_schedule`)에서 그 교시 시작 시각 "HH:MM".
+/// 그날 프로필에 없는 교시이거나 숫자가 없으면 None.
+pub(crate) fn period_to_start_time(period: &str, date: chrono::NaiveDate) -> Option<String> {
+    period_start_time_in(&bell_schedule::load(), period, date)
+}
+
+fn period_start_time_in(schedule: &bell_schedule::BellSchedule, period: &str, date: chrono::NaiveDate) -> Option<String> {
     let n: u32 = period
         .chars()
         .filter(|c| c.is_ascii_digit())
         .collect::<String>()
         .parse()
         .ok()?;
-    if !(1..=7).contains(&n) {
-        return None;
-    }
-    // 점심(4) 슬롯을 건너뛰어 5교시부터 한 칸 민다.
-    let idx = if n <= 4 { (n - 1) as usize } else { n as usize };
-    let (h, m) = PERIOD_START[idx];
-    Some(format!("{:02}:{:02}", h, m))
+    let start = schedule.profile_for(date).period_start(n)?;
+    Some(start.format("%H:%M").to_string())
 }
 
 /// "HH:MM" 또는 "HH:MM~HH:MM"/"HH:MM-HH:MM"에서 시작 시각 "HH:MM"을 정규화.
@@ -1576,6 +2764,116 @@ mod tests {
         assert_eq!(extract_json_array("no array here"), None);
     }
 
+    #[test]
+    fn claude_envelope_and_backend_text_share_item_parsing() {
+        let ok = r#"{"is_error":false,"result":"```json\n[{\"id\":\"msg-1\",\"title\":\"회신\"}]\n```"}"#;
+        let out = parse_claude_envelope(ok).unwrap();
+        assert!(out.failure.is_none());
+        let items = parse_items_from_output(&out.text).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id.as_deref(), Some("msg-1"));
+
+        // 실패한 실행도 청구된 사용량은 남긴다.
+        let err = r#"{"is_error":true,"result":"rate limited","total_cost_usd":0.03,"num_turns":30}"#;
+        let out = parse_claude_envelope(err).unwrap();
+        assert!(out.failure.unwrap().contains("rate limited"));
+        assert_eq!(out.usage.map(|u| u.num_turns), Some(30));
+        assert!(parse_claude_envelope("not json").is_err());
+
+        // HTTP·규칙 백엔드는 봉투 없이 배열 텍스트를 그대로 넘긴다.
+        let items = parse_items_from_output(r#"[{"source_message_id":5}]"#).unwrap();
+        assert_eq!(items[0].source_message_id, Some(5));
+    }
+
+    #[test]
+    fn to_schedule_item_carries_extraction_metadata() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 7, 1);
+        let item = ExtractedItem {
+            source_message_id: Some(500),
+            item_type: Some("Deadline".to_string()),
+            urgency: Some(" high ".to_string()),
+            has_attachment: Some(true),
+            date: Some("2026-07-10".to_string()),
+            active_until: Some("2026-07-03".to_string()),
+            ..Default::default()
+        };
+        let s = to_schedule_item(&item, today, None).unwrap();
+        assert_eq!(s.item_kind.as_deref(), Some("deadline"));
+        assert_eq!(s.priority.as_deref(), Some("high"));
+        assert_eq!(s.has_attachment, Some(true));
+        // active_until 이 일정 날짜보다 이르면 일정 날짜까지 보이게.
+        assert_eq!(s.visible_until.as_deref(), Some("2026-07-10"));
+
+        let notice = ExtractedItem {
+            source_message_id: Some(501),
+            item_type: Some("공지".to_string()),
+            urgency: None,
+            date: Some("2026-07-02".to_string()),
+            active_until: Some("2026-07-09".to_string()),
+            ..Default::default()
+        };
+        let s = to_schedule_item(&notice, today, None).unwrap();
+        assert_eq!(s.item_kind, None);
+        assert_eq!(s.priority, None);
+        assert_eq!(s.has_attachment, Some(false));
+        assert_eq!(s.visible_until.as_deref(), Some("2026-07-09"));
+    }
+
+    #[test]
+    fn reconcile_item_date_corrects_against_source_text() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 4);
+        let mut item = ExtractedItem {
+            received_at: Some("2026-03-04T10:00:00+09:00".to_string()),
+            date: Some("2026-03-11".to_string()),
+            source_text: Some("다음 주 화요일 6교시까지 제출".to_string()),
+            ..Default::default()
+        };
+        reconcile_item_date(&mut item, today);
+        assert_eq!(item.date.as_deref(), Some("2026-03-10"));
+
+        // 원문에 같은 날짜가 있으면 LLM 판단 유지.
+        let mut item = ExtractedItem {
+            date: Some("2026-03-16".to_string()),
+            source_text: Some("상담은 3월 16일(월)부터, 신청은 3월 6일까지".to_string()),
+            ..Default::default()
+        };
+        reconcile_item_date(&mut item, today);
+        assert_eq!(item.date.as_deref(), Some("2026-03-16"));
+
+        // 날짜가 비었으면 원문 기한으로 채운다.
+        let mut item = ExtractedItem {
+            source_text: Some("이번 달 말까지 회신".to_string()),
+            ..Default::default()
+        };
+        reconcile_item_date(&mut item, today);
+        assert_eq!(item.date.as_deref(), Some("2026-03-31"));
+
+        // 근거가 없으면 손대지 않는다.
+        let mut item = ExtractedItem {
+            date: Some("2026-03-20".to_string()),
+            source_text: Some("추후 안내 예정".to_string()),
+            ..Default::default()
+        };
+        reconcile_item_date(&mut item, today);
+        assert_eq!(item.date.as_deref(), Some("2026-03-20"));
+    }
+
+    #[test]
+    fn date_hint_lines_use_receive_date_as_reference() {
+        let messages = [SourceMessage {
+            id: 42,
+            sender: "교무부".to_string(),
+            receive_date: Some("2026-03-06T16:00:00+09:00".to_string()),
+            text: "내일 오전 10시 협의회, 자료는 3/15(금) 까지".to_string(),
+            attachments: Vec::new(),
+        }];
+        let lines = date_hint_lines(&messages, chrono::NaiveDate::from_ymd_opt(2026, 3, 9));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "- 메시지 42: 「내일 오전 10시」 → 2026-03-07(토) 10:00");
+        assert!(lines[1].starts_with("- 메시지 42: 「3/15(금)」 → 2026-03-15(일) 기한"));
+        assert!(lines[1].ends_with("(불확실)"));
+    }
+
     #[test]
     fn parse_start_time_handles_ranges() {
         assert_eq!(parse_start_time("13:20~14:10").as_deref(), Some("13:20"));
@@ -1586,16 +2884,19 @@ mod tests {
 
     #[test]
     fn period_to_start_time_matches_school_timetable() {
+        let bells = bell_schedule::BellSchedule::default();
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let start = |p: &str| period_start_time_in(&bells, p, day);
         // 1~4교시는 그대로, 5~7교시는 점심 슬롯을 건너뛴 시각.
-        assert_eq!(period_to_start_time("1교시").as_deref(), Some("08:30"));
-        assert_eq!(period_to_start_time("4교시").as_deref(), Some("11:30"));
-        assert_eq!(period_to_start_time("5교시").as_deref(), Some("13:20"));
-        assert_eq!(period_to_start_time("6교시").as_deref(), Some("14:20"));
-        assert_eq!(period_to_start_time("7교시").as_deref(), Some("15:20"));
+        assert_eq!(start("1교시").as_deref(), Some("08:30"));
+        assert_eq!(start("4교시").as_deref(), Some("11:30"));
+        assert_eq!(start("5교시").as_deref(), Some("13:20"));
+        assert_eq!(start("6교시").as_deref(), Some("14:20"));
+        assert_eq!(start("7교시").as_deref(), Some("15:20"));
         // 범위 밖/무효.
-        assert_eq!(period_to_start_time("8교시"), None);
-        assert_eq!(period_to_start_time("교시"), None);
-        assert_eq!(period_to_start_time("점심"), None);
+        assert_eq!(start("8교시"), None);
+        assert_eq!(start("교시"), None);
+        assert_eq!(start("점심"), None);
     }
 
     #[test]
@@ -1739,9 +3040,343 @@ mod tests {
             [],
         )
         .unwrap();
+        crate::db::migrate_schedule_columns(&conn).unwrap();
+        ensure_revision_tables(&conn).unwrap();
         conn
     }
 
+    fn insert_ai(conn: &Connection, item: &ExtractedItem, today: Option<chrono::NaiveDate>, body: &str) -> crate::db::ScheduleItem {
+        let s = to_schedule_item(item, today, Some(body)).unwrap();
+        let created = crate::db::create_schedule_impl(conn, s).unwrap();
+        record_ai_snapshot(conn, &created).unwrap();
+        created
+    }
+
+    fn start_of(conn: &Connection, id: &str) -> String {
+        conn.query_row("SELECT start_date FROM tbl_schedules WHERE id = ?1", [id], |r| r.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn register_items_records_outcome_per_item() {
+        let conn = mem_conn();
+        let item = |mid: i64, title: &str, date: Option<&str>| ExtractedItem {
+            source_message_id: Some(mid),
+            title: Some(title.to_string()),
+            date: date.map(str::to_string),
+            ..Default::default()
+        };
+        let items = vec![
+            item(500, "체험학습 신청서 제출", Some("2026-07-10")),
+            item(500, "체험학습 신청서 제출", Some("2026-07-10")),
+            item(501, "지난 회의", Some("2026-06-20")),
+            item(502, "날짜 없는 안내", None),
+        ];
+        let report =
+            register_items_with(&conn, items, "2026-07-01", &RuleSet::default(), |_| SourceDetail::default()).unwrap();
+        assert_eq!((report.new_count, report.dedup_count, report.invalid_count), (1, 1, 2));
+
+        let outcomes: Vec<_> = report.items.iter().map(|i| (i.outcome, i.schedule_id.as_deref())).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (ItemOutcome::Created, Some("msg-500")),
+                (ItemOutcome::Duplicate, Some("msg-500")),
+                (ItemOutcome::Invalid, None),
+                (ItemOutcome::Invalid, None),
+            ]
+        );
+        assert_eq!(report.items[2].reason.as_deref(), Some("과거 날짜(2026-06-20)"));
+        assert_eq!(report.items[3].reason.as_deref(), Some("날짜를 알 수 없음"));
+        // 원 항목 JSON 이 남아 나중에 재검토할 수 있다.
+        assert!(report.items[0].item_json.contains("\"source_message_id\":500"));
+    }
+
+    fn rule(name: &str, action: briefing_rules::RuleAction, sender: Option<&str>, keywords: Option<&str>, value: Option<&str>) -> briefing_rules::BriefingRule {
+        briefing_rules::BriefingRule {
+            id: 0,
+            name: name.to_string(),
+            enabled: true,
+            position: 0,
+            sender: sender.map(str::to_string),
+            keywords: keywords.map(str::to_string),
+            pattern: None,
+            attachment_ext: None,
+            action,
+            value: value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn register_items_applies_briefing_rules() {
+        use briefing_rules::RuleAction;
+        let conn = mem_conn();
+        let rules = RuleSet::new(vec![
+            rule("행정실 제외", RuleAction::Skip, Some("행정실"), None, None),
+            rule("연구부 접두어", RuleAction::TitlePrefix, Some("연구부"), None, Some("[연구]")),
+            rule("협의회 아침", RuleAction::DefaultTime, None, Some("협의회"), Some("08:30")),
+            rule("공개수업 색", RuleAction::Color, None, Some("공개수업"), Some("#ff8800")),
+        ]);
+        let item = |mid: i64, sender: &str, title: &str| ExtractedItem {
+            source_message_id: Some(mid),
+            sender: Some(sender.to_string()),
+            title: Some(title.to_string()),
+            date: Some("2026-07-10".to_string()),
+            ..Default::default()
+        };
+        let items = vec![
+            item(600, "행정실", "급식 설문 제출"),
+            item(601, "연구부", "공개수업 협의회"),
+            item(602, "연구부", "[연구] 수업 나눔 보고서 제출"),
+        ];
+        let report = register_items_with(&conn, items, "2026-07-01", &rules, |mid| SourceDetail {
+            html: Some(format!("<p>메시지 {} 원문</p>", mid)),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!((report.new_count, report.filtered_count), (2, 1));
+        assert_eq!(report.skipped(), 1);
+        assert_eq!(report.items[0].outcome, ItemOutcome::Filtered);
+        assert_eq!(report.items[0].reason.as_deref(), Some("규칙 「행정실 제외」로 제외"));
+
+        let (title, start, all_day, color): (String, String, bool, String) = conn
+            .query_row(
+                "SELECT title, start_date, is_all_day, color FROM tbl_schedules WHERE id = 'msg-601'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(title, "[연구] 공개수업 협의회");
+        assert_eq!(start, "2026-07-10T08:30:00+09:00");
+        assert!(!all_day);
+        assert_eq!(color, "#ff8800");
+        // 접두어가 이미 있으면 다시 붙이지 않는다.
+        let title: String = conn
+            .query_row("SELECT title FROM tbl_schedules WHERE id = 'msg-602'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(title, "[연구] 수업 나눔 보고서 제출");
+    }
+
+    #[test]
+    fn skip_rules_use_stored_sender_and_record_gated_messages() {
+        use briefing_rules::RuleAction;
+        let conn = mem_conn();
+        let rules = RuleSet::new(vec![rule("행정실 제외", RuleAction::Skip, Some("행정실"), None, None)]);
+        // 백엔드가 발신자를 잘못 적어도 저장된 발신자로 판정한다.
+        let items = vec![ExtractedItem {
+            source_message_id: Some(610),
+            sender: Some("연구부".to_string()),
+            title: Some("급식 설문 제출".to_string()),
+            date: Some("2026-07-10".to_string()),
+            ..Default::default()
+        }];
+        let report = register_items_with(&conn, items, "2026-07-01", &rules, |_| SourceDetail {
+            sender: Some("행정실".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!((report.new_count, report.filtered_count), (0, 1));
+
+        // 백엔드에 넘기기 전에 뺀 메시지도 규칙 이름과 함께 기록된다.
+        let message = |id: i64, sender: &str| SourceMessage {
+            id,
+            sender: sender.to_string(),
+            receive_date: None,
+            text: "\n  7월 급식 설문 안내\n".to_string(),
+            attachments: Vec::new(),
+        };
+        let mut messages = vec![message(611, "행정실"), message(612, "연구부")];
+        let gated = drop_skipped_messages(&mut messages, &rules);
+        assert_eq!(messages.iter().map(|m| m.id).collect::<Vec<_>>(), vec![612]);
+        assert_eq!(gated.len(), 1);
+        assert_eq!(gated[0].source_message_id, Some(611));
+        assert_eq!(gated[0].title.as_deref(), Some("7월 급식 설문 안내"));
+        assert_eq!(gated[0].outcome, ItemOutcome::Filtered);
+        assert_eq!(gated[0].reason.as_deref(), Some("규칙 「행정실 제외」로 제외"));
+
+        let mut report = RegisterReport::default();
+        report.extend_filtered(gated);
+        assert_eq!(report.skipped(), 1);
+    }
+
+    #[test]
+    fn forced_rule_creates_item_for_message_without_date() {
+        use briefing_rules::RuleAction;
+        let conn = mem_conn();
+        let rules = RuleSet::new(vec![rule("교장 필독", RuleAction::Force, Some("교장"), Some("필독"), None)]);
+        let messages = [
+            SourceMessage {
+                id: 700,
+                sender: "교장".to_string(),
+                receive_date: Some("2026-07-01 08:00:00".to_string()),
+                text: "[필독] 학교 안전 점검 결과를 꼭 확인해 주세요.".to_string(),
+                attachments: Vec::new(),
+            },
+            SourceMessage {
+                id: 701,
+                sender: "교장".to_string(),
+                receive_date: Some("2026-07-01 09:00:00".to_string()),
+                text: "좋은 하루 보내세요.".to_string(),
+                attachments: Vec::new(),
+            },
+        ];
+        let mut items = Vec::new();
+        add_forced_items(&rules, &messages, &mut items, "2026-07-01");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].date.as_deref(), Some("2026-07-01"));
+        assert_eq!(items[0].forced_by.as_deref(), Some("교장 필독"));
+
+        // 백엔드가 이미 낸 메시지에는 덧붙이지 않는다.
+        add_forced_items(&rules, &messages, &mut items, "2026-07-01");
+        assert_eq!(items.len(), 1);
+
+        let report = register_items_with(&conn, items, "2026-07-01", &rules, |_| SourceDetail::default()).unwrap();
+        assert_eq!(report.new_count, 1);
+        assert_eq!(report.items[0].reason.as_deref(), Some("규칙 「교장 필독」으로 강제 추출"));
+    }
+
+    #[test]
+    fn correction_message_moves_untouched_ai_schedule() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 7, 1);
+        let conn = mem_conn();
+        ensure_revision_tables(&conn).unwrap();
+        let original = ExtractedItem {
+            source_message_id: Some(100),
+            sender: Some("교무부".to_string()),
+            date: Some("2026-07-15".to_string()),
+            title: Some("학부모 상담 주간 희망 시간 회신".to_string()),
+            ..Default::default()
+        };
+        insert_ai(&conn, &original, today, "<p>7월 15일까지 학부모 상담 희망 시간을 회신해 주세요.</p>");
+
+        let correction = ExtractedItem {
+            source_message_id: Some(110),
+            sender: Some("교무부".to_string()),
+            date: Some("2026-07-17".to_string()),
+            time: Some("16:00".to_string()),
+            title: Some("[변경] 학부모 상담 희망 시간 회신".to_string()),
+            source_text: Some("회신 기한 변경: 15일 → 17일 16시".to_string()),
+            ..Default::default()
+        };
+        let body = "<p>회신 기한이 7월 17일 16시로 변경되었습니다.</p>";
+        assert!(is_correction(&correction, Some(body)));
+        let sched = to_schedule_item(&correction, today, Some(body)).unwrap();
+        let target = find_superseded(&conn, &sched, correction.sender.as_deref()).unwrap();
+        assert_eq!(target.id, "msg-100");
+        assert_eq!(apply_supersession(&conn, &target, &sched, Some(110)).unwrap(), Supersession::Applied);
+
+        assert_eq!(start_of(&conn, "msg-100"), "2026-07-17T16:00:00+09:00");
+        let content: String = conn
+            .query_row("SELECT content FROM tbl_schedules WHERE id = 'msg-100'", [], |r| r.get(0))
+            .unwrap();
+        assert!(content.contains("일정 변경: 07/15 → 07/17 16:00"));
+        let revs = list_revisions(&conn, false).unwrap();
+        assert_eq!(revs.len(), 1);
+        assert_eq!(revs[0].status, "applied");
+        assert_eq!((revs[0].old_message_id, revs[0].new_message_id), (Some(100), Some(110)));
+
+        // 같은 정정을 다시 처리해도 이미 옮겨졌으므로 대상이 없다(내용 중복 판정으로 넘어감).
+        assert!(find_superseded(&conn, &sched, correction.sender.as_deref()).is_none());
+    }
+
+    #[test]
+    fn correction_on_user_edited_schedule_is_flagged_for_review() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 7, 1);
+        let conn = mem_conn();
+        ensure_revision_tables(&conn).unwrap();
+        let original = ExtractedItem {
+            source_message_id: Some(100),
+            date: Some("2026-07-15".to_string()),
+            title: Some("교직원 연수".to_string()),
+            ..Default::default()
+        };
+        insert_ai(&conn, &original, today, "<p>7월 15일 교직원 연수가 있습니다.</p>");
+        // 사용자가 제목을 고쳤다.
+        conn.execute("UPDATE tbl_schedules SET title = '연수(시청각실)' WHERE id = 'msg-100'", [])
+            .unwrap();
+
+        let correction = ExtractedItem {
+            source_message_id: Some(110),
+            date: Some("2026-07-16".to_string()),
+            title: Some("교직원 연수 일정 변경".to_string()),
+            ..Default::default()
+        };
+        let sched = to_schedule_item(&correction, today, Some("<p>연수가 7월 16일로 연기되었습니다.</p>")).unwrap();
+        let target = find_superseded(&conn, &sched, None).unwrap();
+        assert_eq!(apply_supersession(&conn, &target, &sched, Some(110)).unwrap(), Supersession::Flagged);
+        assert_eq!(start_of(&conn, "msg-100"), "2026-07-15");
+        // 재처리 시 대기 건을 중복으로 만들지 않는다.
+        assert_eq!(apply_supersession(&conn, &target, &sched, Some(110)).unwrap(), Supersession::Ignored);
+
+        let pending = list_revisions(&conn, true).unwrap();
+        assert_eq!(pending.len(), 1);
+        resolve_revision(&conn, pending[0].id, true).unwrap();
+        assert_eq!(start_of(&conn, "msg-100"), "2026-07-16");
+        assert!(list_revisions(&conn, true).unwrap().is_empty());
+        assert!(resolve_revision(&conn, pending[0].id, false).is_err());
+    }
+
+    #[test]
+    fn correction_without_source_message_still_finds_target() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 7, 1);
+        let conn = mem_conn();
+        ensure_revision_tables(&conn).unwrap();
+        let original = ExtractedItem {
+            id: Some("ai-a".to_string()),
+            date: Some("2026-07-15".to_string()),
+            title: Some("교직원 연수".to_string()),
+            ..Default::default()
+        };
+        let created = insert_ai(&conn, &original, today, "<p>7월 15일 교직원 연수가 있습니다.</p>");
+        let correction = ExtractedItem {
+            id: Some("ai-b".to_string()),
+            date: Some("2026-07-16".to_string()),
+            title: Some("교직원 연수 일정 변경".to_string()),
+            ..Default::default()
+        };
+        let sched = to_schedule_item(&correction, today, Some("<p>연수가 7월 16일로 연기되었습니다.</p>")).unwrap();
+        assert_eq!(sched.reference_id, None);
+        assert_eq!(find_superseded(&conn, &sched, None).map(|t| t.id), Some(created.id));
+    }
+
+    #[test]
+    fn recurring_topic_without_correction_wording_is_not_superseded() {
+        let plain = ExtractedItem {
+            title: Some("학년 협의회".to_string()),
+            source_text: Some("7월 22일 학년 협의회".to_string()),
+            ..Default::default()
+        };
+        assert!(!is_correction(&plain, Some("<p>이번 주 학년 협의회 안내</p>")));
+        // 흔한 안내 표현이나 날짜와 동떨어진 정정 표현만으로는 정정으로 보지 않는다.
+        let notice = ExtractedItem {
+            title: Some("생활기록부 수정 사항 반영 안내".to_string()),
+            source_text: Some("7월 22일까지 수정 → 제출, 시간 조정 가능".to_string()),
+            ..Default::default()
+        };
+        assert!(!is_correction(&notice, Some("<p>7월 22일 마감입니다. 양식이 지난해와 비교해 조금 달라졌으니 변경된 부분을 꼭 확인하세요.</p>")));
+        // 발신자가 다르면 같은 주제여도 대상이 아니다.
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 7, 1);
+        let conn = mem_conn();
+        ensure_revision_tables(&conn).unwrap();
+        let a = ExtractedItem {
+            source_message_id: Some(100),
+            sender: Some("1학년부".to_string()),
+            date: Some("2026-07-15".to_string()),
+            title: Some("학년 협의회".to_string()),
+            ..Default::default()
+        };
+        insert_ai(&conn, &a, today, "<p>협의회</p>");
+        let b = ExtractedItem {
+            source_message_id: Some(110),
+            sender: Some("2학년부".to_string()),
+            date: Some("2026-07-16".to_string()),
+            title: Some("학년 협의회 변경".to_string()),
+            ..Default::default()
+        };
+        let sched = to_schedule_item(&b, today, None).unwrap();
+        assert!(find_superseded(&conn, &sched, b.sender.as_deref()).is_none());
+    }
+
     #[test]
     fn normalize_and_dice_basics() {
         assert_eq!(normalize_for_match("[필독] 창체 소감문, 제출!"), "필독창체소감문제출");
@@ -1881,6 +3516,10 @@ mod tests {
                 created_at: now.clone(),
                 updated_at: now,
                 is_deleted: false,
+                priority: None,
+                item_kind: None,
+                visible_until: None,
+                has_attachment: None,
             },
         )
         .unwrap();
@@ -1,12 +1,32 @@
+//! 압핀시간표(amc42.dat) 파서.
+//!
+//! 레코드 0~7 은 머리(행사·과목·교사·반 목록 등), 8 번부터는 하루 한 레코드다. 레코드 8 이
+//! 학년도 첫날(보통 3월 1일)이고, 학년도 첫날은 설정(레지스트리 AppinStartDate) → 파일 머리의
+//! "N학년도" 표기 → 파일 수정 시각의 학년도 순으로 정한다. 파일 위치도 설정(AppinDataPath)이
+//! 없으면 Program Files 의 압핀시간표 폴더에서 찾는다.
+
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use chrono::{NaiveDate, Datelike, Duration as ChronoDuration};
 use encoding_rs::EUC_KR;
+use regex::Regex;
+
+use crate::commands::system::{get_registry_value, set_registry_value};
 
 const XOR_KEY: &[u8] = b"7n1bmu";
 
+const REG_PATH: &str = "AppinDataPath";
+const REG_START: &str = "AppinStartDate";
+const APPIN_DIR: &str = "압핀시간표";
+
+/// 일별 레코드가 시작하는 위치. 이 레코드가 학년도 첫날이다.
+const FIRST_DAY_RECORD: usize = 8;
+/// 수업 자료가 이 일수보다 길게 비면 방학으로 본다.
+const BREAK_MIN_DAYS: i64 = 14;
+
 fn decrypt_bytes(raw: &[u8]) -> Vec<u8> {
     let mut result = Vec::with_capacity(raw.len());
     for (i, &byte) in raw.iter().enumerate() {
@@ -259,6 +279,14 @@ fn parse_header_grade_events(body_text: &str) -> [Option<usize>; 3] {
     out
 }
 
+/// 수업이 이어지는 기간(학기). 학기 사이는 방학.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppinTerm {
+    /// YYYY-MM-DD
+    pub start: String,
+    pub end: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AppinTimetableData {
@@ -271,23 +299,167 @@ pub struct AppinTimetableData {
     pub events_by_date_class: HashMap<String, HashMap<String, String>>,
     // Date -> [학년1, 학년2, 학년3] 행사 라벨 (헤더 기반 fallback)
     pub events_by_date_grade: HashMap<String, Vec<Option<String>>>,
+    /// 학년도 첫날(레코드 8 의 날짜, YYYY-MM-DD)
+    pub start_date: String,
+    pub terms: Vec<AppinTerm>,
 }
 
-pub fn parse_appin_timetable() -> Result<AppinTimetableData, String> {
-    let fp = Path::new(r"C:\Program Files (x86)\압핀시간표\amc42.dat");
-    if !fp.exists() {
-        return Err("amc42.dat file not found".to_string());
+fn ymd(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+/// 학년도 첫날의 1년 뒤(이 날부터는 다음 학년도).
+fn year_end(start: NaiveDate) -> NaiveDate {
+    start.with_year(start.year() + 1).unwrap_or(start + ChronoDuration::days(365))
+}
+
+impl AppinTimetableData {
+    /// 학년도 밖이거나 학기 사이(방학)인 날. 마지막 학기 뒤는 아직 입력하지 않은 날로 본다.
+    pub fn is_break(&self, date: NaiveDate) -> bool {
+        let Some(start) = ymd(&self.start_date) else { return false };
+        if date < start || date >= year_end(start) {
+            return true;
+        }
+        let terms: Vec<(NaiveDate, NaiveDate)> = self
+            .terms
+            .iter()
+            .filter_map(|t| Some((ymd(&t.start)?, ymd(&t.end)?)))
+            .collect();
+        if terms.first().is_some_and(|(first, _)| date < *first) {
+            return true;
+        }
+        terms.windows(2).any(|w| date > w[0].1 && date < w[1].0)
+    }
+}
+
+/// 수업 자료가 있는 날(오름차순)을 `BREAK_MIN_DAYS` 보다 긴 공백에서 끊어 학기로 나눈다.
+fn terms_of(days: &[NaiveDate]) -> Vec<AppinTerm> {
+    let fmt = |d: NaiveDate| d.format("%Y-%m-%d").to_string();
+    let mut out: Vec<AppinTerm> = Vec::new();
+    let mut run: Option<(NaiveDate, NaiveDate)> = None;
+    for &d in days {
+        run = match run {
+            Some((s, e)) if (d - e).num_days() <= BREAK_MIN_DAYS => Some((s, d)),
+            Some((s, e)) => {
+                out.push(AppinTerm { start: fmt(s), end: fmt(e) });
+                Some((d, d))
+            }
+            None => Some((d, d)),
+        };
+    }
+    if let Some((s, e)) = run {
+        out.push(AppinTerm { start: fmt(s), end: fmt(e) });
+    }
+    out
+}
+
+// ─── 파일 위치·학년도 ─────────────────────────────────────────────────────────
+
+/// 폴더에서 가장 최근에 바뀐 amc*.dat.
+fn newest_dat_in(dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("").to_ascii_lowercase();
+            name.starts_with("amc") && name.ends_with(".dat")
+        })
+        .filter_map(|p| Some((fs::metadata(&p).ok()?.modified().ok()?, p)))
+        .max_by_key(|(t, _)| *t)
+        .map(|(_, p)| p)
+}
+
+/// 압핀시간표가 보통 설치되는 폴더들.
+fn candidate_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["ProgramFiles(x86)", "ProgramFiles", "ProgramW6432"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .map(|root| Path::new(&root).join(APPIN_DIR))
+        .collect();
+    dirs.push(PathBuf::from(r"C:\Program Files (x86)").join(APPIN_DIR));
+    dirs.dedup();
+    dirs
+}
+
+fn configured_path() -> Option<PathBuf> {
+    get_registry_value(REG_PATH.to_string())
+        .ok()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 설정한 학년도 첫날.
+pub fn configured_start() -> Option<NaiveDate> {
+    get_registry_value(REG_START.to_string()).ok().flatten().and_then(|s| ymd(&s))
+}
+
+/// 자동으로 찾은 시간표 파일.
+fn discover_appin_file() -> Option<PathBuf> {
+    candidate_dirs().iter().find_map(|d| newest_dat_in(d))
+}
+
+/// 읽을 시간표 파일: 설정한 경로(파일 또는 폴더), 없으면 자동으로 찾은 파일.
+pub fn find_appin_file() -> Option<PathBuf> {
+    match configured_path() {
+        Some(p) if p.is_dir() => newest_dat_in(&p),
+        Some(p) => p.exists().then_some(p),
+        None => discover_appin_file(),
     }
+}
+
+/// 학년도 표기("2027학년도").
+static SCHOOL_YEAR_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// 사용자가 이름을 적는 머리 레코드(행사·과목·교사·반 목록). 행사 이름 같은 데 든 연도를
+/// 학년도로 잘못 읽지 않게 건너뛴다.
+const NAME_LIST_RECORDS: [usize; 4] = [1, 3, 4, 5];
+
+/// 머리 레코드(0~7, 이름 목록 제외)의 "2027학년도" 표기로 학년도 첫날(그해 3월 1일)을 정한다.
+/// 머리의 다른 숫자는 뜻이 확인되지 않아 날짜로 쓰지 않는다. 표기가 없으면 None.
+fn detect_start_date(records: &[Vec<u8>]) -> Option<NaiveDate> {
+    let year_re = SCHOOL_YEAR_REGEX.get_or_init(|| Regex::new(r"(20\d{2})\s*학년도").unwrap());
+    records
+        .iter()
+        .take(FIRST_DAY_RECORD)
+        .enumerate()
+        .filter(|(i, _)| !NAME_LIST_RECORDS.contains(i))
+        .map(|(_, r)| decode_euc_kr(&decrypt_bytes(r)))
+        .find_map(|h| year_re.captures(&h).and_then(|c| c[1].parse().ok()))
+        .and_then(|y| NaiveDate::from_ymd_opt(y, 3, 1))
+}
+
+/// 날짜가 속한 학년도의 3월 1일. 새 학년도 시간표는 2월에 만드므로 2월부터 그해로 본다.
+fn school_year_start(date: NaiveDate) -> NaiveDate {
+    let year = if date.month() >= 2 { date.year() } else { date.year() - 1 };
+    NaiveDate::from_ymd_opt(year, 3, 1).unwrap()
+}
+
+fn modified_date(path: &Path) -> Option<NaiveDate> {
+    let t = fs::metadata(path).ok()?.modified().ok()?;
+    Some(chrono::DateTime::<chrono::Local>::from(t).date_naive())
+}
+
+/// 학년도 첫날: 설정값 → 파일 머리 → 파일 수정 시각(없으면 오늘)의 학년도.
+fn resolve_start_date(records: &[Vec<u8>], path: &Path) -> NaiveDate {
+    configured_start()
+        .or_else(|| detect_start_date(records))
+        .unwrap_or_else(|| school_year_start(modified_date(path).unwrap_or_else(|| chrono::Local::now().date_naive())))
+}
 
-    let records = load_dat(fp)?;
+// ─── 파싱 ─────────────────────────────────────────────────────────────────────
+
+fn parse_records(records: &[Vec<u8>], start: NaiveDate) -> Result<AppinTimetableData, String> {
     if records.len() < 10 {
         return Err("Invalid amc42.dat".to_string());
     }
 
-    let subjects = parse_subjects(&records);
-    let teachers = parse_teachers(&records);
-    let classes = parse_classes(&records);
-    let events = parse_events(&records);
+    let subjects = parse_subjects(records);
+    let teachers = parse_teachers(records);
+    let classes = parse_classes(records);
+    let events = parse_events(records);
 
     let event_label = |idx_1based: usize| -> Option<String> {
         if idx_1based == 0 || idx_1based > events.len() { return None; }
@@ -295,16 +467,19 @@ pub fn parse_appin_timetable() -> Result<AppinTimetableData, String> {
         if name.is_empty() { None } else { Some(name.to_string()) }
     };
 
-    let base = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    let end = year_end(start);
     let mut days_map: HashMap<String, HashMap<String, HashMap<String, AppinSlot>>> = HashMap::new();
     let mut events_by_date_class: HashMap<String, HashMap<String, String>> = HashMap::new();
     let mut events_by_date_grade: HashMap<String, Vec<Option<String>>> = HashMap::new();
+    let mut lesson_days: Vec<NaiveDate> = Vec::new();
 
-    for ri in 8..records.len() {
-        let d = base + ChronoDuration::try_days((ri as i64) - 9).unwrap();
+    for (ri, record) in records.iter().enumerate().skip(FIRST_DAY_RECORD) {
+        let d = start + ChronoDuration::days((ri - FIRST_DAY_RECORD) as i64);
+        // 학년도가 끝난 뒤의 레코드는 다음 학년도 자료가 아니므로 읽지 않는다.
+        if d >= end { break; }
         if d.weekday().number_from_monday() >= 6 { continue; } // Exclude Sat/Sun
 
-        let body = decrypt_text(&records[ri]);
+        let body = decrypt_text(record);
         let header_events = parse_header_grade_events(&body);
         let parsed = parse_daily(&body);
 
@@ -343,6 +518,7 @@ pub fn parse_appin_timetable() -> Result<AppinTimetableData, String> {
                 day_classes.insert(classes[ci - 1].clone(), str_periods);
             }
             days_map.insert(date_str.clone(), day_classes);
+            lesson_days.push(d);
         }
     }
 
@@ -354,10 +530,226 @@ pub fn parse_appin_timetable() -> Result<AppinTimetableData, String> {
         days: days_map,
         events_by_date_class,
         events_by_date_grade,
+        start_date: start.format("%Y-%m-%d").to_string(),
+        terms: terms_of(&lesson_days),
     })
 }
 
+/// 시간표 파일 하나를 읽는다. `start` 가 없으면 파일에서 학년도 첫날을 찾는다.
+pub fn parse_appin_file(path: &Path, start: Option<NaiveDate>) -> Result<AppinTimetableData, String> {
+    let records = load_dat(path)?;
+    let start = start.unwrap_or_else(|| resolve_start_date(&records, path));
+    parse_records(&records, start)
+}
+
+pub fn parse_appin_timetable() -> Result<AppinTimetableData, String> {
+    let fp = find_appin_file().ok_or_else(|| "amc42.dat file not found".to_string())?;
+    parse_appin_file(&fp, configured_start())
+}
+
 #[tauri::command]
 pub fn get_appin_timetable_data() -> Result<AppinTimetableData, String> {
     parse_appin_timetable()
 }
+
+/// 압핀 설정과 자동으로 찾은 값.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppinSettings {
+    /// 설정한 파일·폴더 경로
+    pub path: Option<String>,
+    /// 설정한 학년도 첫날(YYYY-MM-DD)
+    pub start_date: Option<String>,
+    /// 실제로 읽을 파일
+    pub resolved_path: Option<String>,
+    /// 실제로 쓸 학년도 첫날
+    pub resolved_start_date: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_appin_settings() -> Result<AppinSettings, String> {
+    let resolved = find_appin_file();
+    let resolved_start = resolved
+        .as_deref()
+        .and_then(|p| Some(resolve_start_date(&load_dat(p).ok()?, p)));
+    Ok(AppinSettings {
+        path: configured_path().map(|p| p.to_string_lossy().into_owned()),
+        start_date: configured_start().map(|d| d.format("%Y-%m-%d").to_string()),
+        resolved_path: resolved.map(|p| p.to_string_lossy().into_owned()),
+        resolved_start_date: resolved_start.map(|d| d.format("%Y-%m-%d").to_string()),
+    })
+}
+
+/// 압핀 파일 경로와 학년도 첫날을 저장한다. 비워 두면 자동으로 찾는다.
+#[tauri::command]
+pub fn set_appin_settings(path: Option<String>, start_date: Option<String>) -> Result<(), String> {
+    let path = path.map(|p| p.trim().to_string()).unwrap_or_default();
+    if !path.is_empty() && !Path::new(&path).exists() {
+        return Err(format!("시간표 파일을 찾을 수 없습니다: {}", path));
+    }
+    let start_date = start_date.map(|s| s.trim().to_string()).unwrap_or_default();
+    if !start_date.is_empty() && ymd(&start_date).is_none() {
+        return Err(format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", start_date));
+    }
+    set_registry_value(REG_PATH.to_string(), path)?;
+    set_registry_value(REG_START.to_string(), start_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        ymd(s).unwrap()
+    }
+
+    /// fixtures/appin/daily_records.txt 에서 이름이 `name` 인 일별 레코드 본문.
+    fn daily_record(name: &str) -> String {
+        let text = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/appin/daily_records.txt")).unwrap();
+        text.lines()
+            .filter(|l| !l.starts_with('#'))
+            .find_map(|l| l.split_once('\t').filter(|(n, _)| *n == name).map(|(_, body)| body.to_string()))
+            .unwrap_or_else(|| panic!("fixture record {} missing", name))
+    }
+
+    fn encrypt(plain: &[u8]) -> Vec<u8> {
+        plain
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                let e = b ^ XOR_KEY[i % 6];
+                if b > 0x20 && e > 0x20 { e } else { b }
+            })
+            .collect()
+    }
+
+    fn slot(tt: &HashMap<usize, HashMap<usize, AppinSlot>>, class: usize, period: usize) -> (Option<usize>, Option<usize>, Option<usize>) {
+        let s = &tt[&class][&period];
+        (s.subject, s.teacher, s.room)
+    }
+
+    #[test]
+    fn parse_daily_slots() {
+        let parsed = parse_daily(&daily_record("normal"));
+        let tt = &parsed.timetable;
+        assert_eq!(slot(tt, 1, 1), (Some(2), Some(1), Some(3)));
+        assert_eq!(slot(tt, 1, 2), (Some(3), Some(0), None));
+        assert!(!tt[&1].contains_key(&3));
+        // "|..." 꼬리는 버린다.
+        assert_eq!(slot(tt, 1, 4), (Some(11), Some(9), Some(1)));
+        // "원래>바뀐" 은 바뀐 쪽.
+        assert_eq!(slot(tt, 1, 5), (Some(5), Some(2), None));
+        assert_eq!(slot(tt, 2, 1), (Some(4), Some(6), None));
+        // 교시는 9 개까지.
+        assert_eq!(tt[&4].len(), 9);
+        assert!(!tt[&4].contains_key(&10));
+        assert_eq!(tt.len(), 3);
+        assert!(parsed.class_events.is_empty());
+    }
+
+    #[test]
+    fn parse_daily_events() {
+        let parsed = parse_daily(&daily_record("events"));
+        // "~4*", "4*1", "4*" 는 행사 칸이고 수업으로 넣지 않는다.
+        assert_eq!(parsed.class_events.get(&1), Some(&4));
+        assert_eq!(parsed.timetable[&1].keys().copied().collect::<Vec<_>>(), vec![4]);
+        // 가장 많이 나온 행사.
+        assert_eq!(parsed.class_events.get(&2), Some(&5));
+        assert!(!parsed.timetable.contains_key(&2));
+        // 과목·교사 번호가 0 이거나 숫자가 아닌 칸은 버린다.
+        assert!(!parsed.timetable.contains_key(&3));
+        assert!(!parsed.class_events.contains_key(&3));
+    }
+
+    #[test]
+    fn parse_header_grade_events_by_grade() {
+        assert_eq!(parse_header_grade_events(&daily_record("events")), [Some(3), None, Some(7)]);
+        assert_eq!(parse_header_grade_events(&daily_record("normal")), [None, None, None]);
+        assert_eq!(parse_header_grade_events("0,x"), [None, None, None]);
+        assert_eq!(parse_header_grade_events(""), [None, None, None]);
+    }
+
+    /// 2027학년도 파일: 3/2~3/31 수업, 4월 공백(방학), 5/3~5/7 수업, 학년도 뒤 레코드 하나.
+    fn sample_records(header: &str) -> Vec<Vec<u8>> {
+        let euc = |s: &str| encrypt(&EUC_KR.encode(s).0);
+        let mut records = vec![
+            euc(header),
+            euc("#hensa 1^개학식,2^체육대회"),
+            euc("#etc 0"),
+            euc("#kwamok 국어^1,수학^2"),
+            euc("#teacher 김교사^1,박교사^2"),
+            euc("#class 1^2-1@a,2^2-2@b"),
+            euc("#etc 0"),
+            euc("#etc 0"),
+        ];
+        let start = d("2027-03-01");
+        for i in 0..380 {
+            let date = start + ChronoDuration::days(i);
+            let in_term = (date >= d("2027-03-02") && date <= d("2027-03-31"))
+                || (date >= d("2027-05-03") && date <= d("2027-05-07"))
+                || date >= d("2028-03-01");
+            let body = match (in_term, date == d("2027-03-03")) {
+                (true, true) => "0,0,2,0{1,0,1(1),2(2){2,0,2(2)",
+                (true, false) => "0,0,0,0{1,0,1(1),2(2){2,0,2(2)",
+                _ => "0,0,0,0",
+            };
+            records.push(encrypt(body.as_bytes()));
+        }
+        records
+    }
+
+    #[test]
+    fn start_date_from_header() {
+        assert_eq!(detect_start_date(&sample_records("#amc 2027학년도 압핀")), Some(d("2027-03-01")));
+        // 학년도 표기가 없으면 머리의 숫자를 날짜로 짐작하지 않는다.
+        assert_eq!(detect_start_date(&sample_records("#amc 20270301")), None);
+        assert_eq!(detect_start_date(&sample_records("#amc 42")), None);
+        // 행사 이름 속 연도는 학년도 표기로 보지 않는다.
+        let mut records = sample_records("#amc 42");
+        records[1] = encrypt(&EUC_KR.encode("#hensa 1^2026학년도 졸업식").0);
+        assert_eq!(detect_start_date(&records), None);
+        assert_eq!(school_year_start(d("2028-01-15")), d("2027-03-01"));
+        assert_eq!(school_year_start(d("2028-02-20")), d("2028-03-01"));
+    }
+
+    #[test]
+    fn parses_records_from_start_date_with_terms() {
+        let records = sample_records("#amc 2027학년도");
+        let t = parse_records(&records, detect_start_date(&records).unwrap()).unwrap();
+        assert_eq!(t.classes, vec!["2-1", "2-2"]);
+        assert_eq!(t.teachers, vec!["김교사", "박교사"]);
+        assert_eq!(t.days["2027-03-02"]["2-1"]["1"].teacher, Some(0));
+        assert!(!t.days.contains_key("2027-03-06")); // 토요일
+        assert_eq!(t.events_by_date_grade["2027-03-03"], vec![None, Some("체육대회".to_string()), None]);
+        // 다음 학년도 레코드는 읽지 않는다.
+        assert!(!t.days.keys().any(|k| k.as_str() >= "2028-03-01"));
+        assert_eq!(
+            t.terms,
+            vec![
+                AppinTerm { start: "2027-03-02".to_string(), end: "2027-03-31".to_string() },
+                AppinTerm { start: "2027-05-03".to_string(), end: "2027-05-07".to_string() },
+            ]
+        );
+        assert!(t.is_break(d("2027-04-15")));
+        assert!(t.is_break(d("2027-03-01")));
+        assert!(!t.is_break(d("2027-03-15")));
+        // 마지막 학기 뒤는 아직 입력하지 않은 날.
+        assert!(!t.is_break(d("2027-06-01")));
+        assert!(t.is_break(d("2028-03-02")));
+    }
+
+    #[test]
+    fn parses_dat_file() {
+        let dir = std::env::temp_dir().join(format!("hc_appin_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("amc42.dat");
+        fs::write(&path, sample_records("#amc 2027학년도").join(&b"\r\n"[..])).unwrap();
+        assert_eq!(newest_dat_in(&dir), Some(path.clone()));
+
+        let t = parse_appin_file(&path, None).unwrap();
+        assert_eq!(t.start_date, "2027-03-01");
+        let t = parse_appin_file(&path, Some(d("2027-02-28"))).unwrap();
+        assert!(t.days.contains_key("2027-03-03"));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,492 @@
+//! 첨부 파일 샌드박스: MCP `read_attachment`/`view_image` 가 받은 파일명을 쿨메신저
+//! 수신 폴더 안의 실제 파일로만 해석하고, 파일 크기·형식별 추출 예산을 강제한다.
+//!
+//! - 파일명은 상대 경로 구성요소만 허용한다(`..`, 절대 경로, 드라이브/ADS `:`, 장치명 거부).
+//! - 정규화(canonicalize)한 결과가 수신 폴더의 정규화 경로 아래에 있어야 한다.
+//! - 심볼릭 링크/정션은 기본 거부. 레지스트리 `AttachmentSymlinkPolicy=follow_within_root`
+//!   이면 따라가되 최종 대상이 수신 폴더 안일 때만 허용한다.
+//! - 추출은 별도 스레드에서 형식별 시간 예산 안에 끝나야 한다(거대한 PDF/xlsx 로 서버가 멈추지 않게).
+//!   예산을 넘기면 `CancelToken` 으로 작업 스레드에 취소를 알리고, 아직 끝나지 않은 작업
+//!   스레드가 `MAX_LIVE_WORKERS` 개면 새 추출을 받지 않는다.
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use crate::commands::system::get_registry_value;
+
+const REG_SYMLINK_POLICY: &str = "AttachmentSymlinkPolicy";
+
+/// Windows 예약 장치명 — 열면 콘솔/포트가 열리거나 읽기가 멈춘다.
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+const MB: u64 = 1024 * 1024;
+
+/// 동시에 살아 있을 수 있는 추출 작업 스레드 수(시간 초과 후 아직 정리 중인 것 포함).
+const MAX_LIVE_WORKERS: usize = 4;
+
+static LIVE_WORKERS: AtomicUsize = AtomicUsize::new(0);
+
+// ─── 오류 ─────────────────────────────────────────────────────────────────────
+
+/// 샌드박스 거부 사유. `code()` 는 JSON-RPC 서버 정의 오류 범위(-32000~-32099)를 쓴다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SandboxError {
+    /// 수신 폴더를 찾을 수 없음(레지스트리 미설정·폴더 없음)
+    RootUnavailable,
+    /// 파일명 형식 자체가 허용되지 않음(`..`, 절대 경로 등)
+    InvalidName(String),
+    /// 정규화 결과가 수신 폴더 밖
+    OutsideRoot,
+    /// 정책상 허용되지 않는 심볼릭 링크/정션
+    SymlinkRejected,
+    NotFound,
+    /// 파일 크기가 형식별 한도 초과 (실제 크기, 한도)
+    TooLarge(u64, u64),
+    UnsupportedType(String),
+    /// 추출 시간 예산 초과(초)
+    BudgetExceeded(u64),
+    /// 끝나지 않은 추출 작업이 너무 많음
+    Busy,
+}
+
+impl SandboxError {
+    pub fn code(&self) -> i32 {
+        match self {
+            SandboxError::RootUnavailable => -32001,
+            SandboxError::InvalidName(_) => -32002,
+            SandboxError::OutsideRoot => -32003,
+            SandboxError::SymlinkRejected => -32004,
+            SandboxError::NotFound => -32005,
+            SandboxError::TooLarge(..) => -32006,
+            SandboxError::UnsupportedType(_) => -32007,
+            SandboxError::BudgetExceeded(_) => -32008,
+            SandboxError::Busy => -32009,
+        }
+    }
+
+    /// 클라이언트가 분기할 수 있는 기계용 사유 문자열(오류 `data.reason`).
+    pub fn reason(&self) -> &'static str {
+        match self {
+            SandboxError::RootUnavailable => "attachment_root_unavailable",
+            SandboxError::InvalidName(_) => "invalid_file_name",
+            SandboxError::OutsideRoot => "path_outside_root",
+            SandboxError::SymlinkRejected => "symlink_rejected",
+            SandboxError::NotFound => "file_not_found",
+            SandboxError::TooLarge(..) => "file_too_large",
+            SandboxError::UnsupportedType(_) => "unsupported_file_type",
+            SandboxError::BudgetExceeded(_) => "extraction_budget_exceeded",
+            SandboxError::Busy => "extraction_busy",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            SandboxError::RootUnavailable => "쿨메신저 수신 파일 경로를 찾을 수 없습니다.".to_string(),
+            SandboxError::InvalidName(why) => format!("허용되지 않는 파일명입니다: {}", why),
+            SandboxError::OutsideRoot => "수신 파일 폴더 밖의 경로는 읽을 수 없습니다.".to_string(),
+            SandboxError::SymlinkRejected => {
+                "심볼릭 링크/정션을 통한 접근은 허용되지 않습니다.".to_string()
+            }
+            SandboxError::NotFound => "파일을 찾을 수 없습니다.".to_string(),
+            SandboxError::TooLarge(size, limit) => format!(
+                "파일이 너무 큽니다 ({:.1}MB, 한도 {:.0}MB).",
+                *size as f64 / MB as f64,
+                *limit as f64 / MB as f64
+            ),
+            SandboxError::UnsupportedType(ext) => format!("지원하지 않는 파일 형식입니다: .{}", ext),
+            SandboxError::BudgetExceeded(secs) => {
+                format!("파일 추출이 제한 시간({}초)을 넘었습니다.", secs)
+            }
+            SandboxError::Busy => {
+                "이전 파일 추출이 아직 정리 중입니다. 잠시 후 다시 시도하세요.".to_string()
+            }
+        }
+    }
+}
+
+// ─── 심볼릭 링크 정책 ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// 경로 중 하나라도 링크면 거부(기본)
+    Deny,
+    /// 링크를 따라가되 최종 대상이 수신 폴더 안일 때만 허용
+    FollowWithinRoot,
+}
+
+pub fn load_symlink_policy() -> SymlinkPolicy {
+    match get_registry_value(REG_SYMLINK_POLICY.to_string()) {
+        Ok(Some(v)) if v == "follow_within_root" => SymlinkPolicy::FollowWithinRoot,
+        _ => SymlinkPolicy::Deny,
+    }
+}
+
+// ─── 경로 해석 ─────────────────────────────────────────────────────────────────
+
+/// 파일명을 검증해 정상 구성요소 목록으로 쪼갠다. 플랫폼과 무관하게 `/`, `\` 모두 구분자로 본다.
+pub fn validate_file_name(name: &str) -> Result<Vec<String>, SandboxError> {
+    let invalid = |why: &str| Err(SandboxError::InvalidName(why.to_string()));
+    if name.trim().is_empty() {
+        return invalid("빈 파일명");
+    }
+    if name.contains('\0') {
+        return invalid("NUL 문자 포함");
+    }
+    // 드라이브 문자(C:), NTFS 대체 데이터 스트림(file:stream) 차단
+    if name.contains(':') {
+        return invalid("':' 포함");
+    }
+    if name.starts_with('/') || name.starts_with('\\') || Path::new(name).has_root() {
+        return invalid("절대 경로");
+    }
+
+    let mut parts = Vec::new();
+    for part in name.split(['/', '\\']) {
+        if part.is_empty() {
+            continue;
+        }
+        if part.chars().all(|c| c == '.' || c == ' ') {
+            return invalid("'.'/'..' 경로 구성요소");
+        }
+        // Windows 는 끝의 점·공백을 버리므로 다른 파일로 해석될 수 있다.
+        if part.ends_with('.') || part.ends_with(' ') {
+            return invalid("점/공백으로 끝나는 이름");
+        }
+        let stem = part.split('.').next().unwrap_or("").trim().to_ascii_uppercase();
+        if RESERVED_NAMES.contains(&stem.as_str()) {
+            return invalid("예약된 장치 이름");
+        }
+        let mut comps = Path::new(part).components();
+        match (comps.next(), comps.next()) {
+            (Some(Component::Normal(_)), None) => parts.push(part.to_string()),
+            _ => return invalid("허용되지 않는 경로 구성요소"),
+        }
+    }
+    if parts.is_empty() {
+        return invalid("빈 파일명");
+    }
+    Ok(parts)
+}
+
+/// `root` 아래의 `name` 을 정책에 맞게 해석해 정규화된 실제 파일 경로를 돌려준다.
+pub fn resolve_in_root(root: &Path, name: &str, policy: SymlinkPolicy) -> Result<PathBuf, SandboxError> {
+    let parts = validate_file_name(name)?;
+    let root_canon = root.canonicalize().map_err(|_| SandboxError::RootUnavailable)?;
+
+    // 구성요소별로 내려가며 링크 여부 확인(중간 디렉토리 링크도 포함).
+    let mut cur = root_canon.clone();
+    for part in &parts {
+        cur.push(part);
+        let meta = std::fs::symlink_metadata(&cur).map_err(|_| SandboxError::NotFound)?;
+        if meta.file_type().is_symlink() && policy == SymlinkPolicy::Deny {
+            return Err(SandboxError::SymlinkRejected);
+        }
+    }
+
+    let canon = cur.canonicalize().map_err(|_| SandboxError::NotFound)?;
+    if !canon.starts_with(&root_canon) {
+        return Err(SandboxError::OutsideRoot);
+    }
+    let meta = std::fs::metadata(&canon).map_err(|_| SandboxError::NotFound)?;
+    if !meta.is_file() {
+        return Err(SandboxError::NotFound);
+    }
+    Ok(canon)
+}
+
+// ─── 추출 예산 ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionBudget {
+    pub max_bytes: u64,
+    pub timeout: Duration,
+}
+
+/// 형식별 예산. None 이면 지원하지 않는 형식.
+pub fn budget_for_ext(ext: &str) -> Option<ExtractionBudget> {
+    let (mb, secs) = match ext.to_ascii_lowercase().as_str() {
+        "hwp" | "hwpx" => (30, 20),
+        "pdf" => (30, 20),
+        "xlsx" | "xls" | "xlsm" | "xlsb" => (20, 15),
+        "odt" | "pptx" => (30, 15),
+        "md" | "txt" | "csv" | "html" | "htm" => (5, 5),
+        "jpg" | "jpeg" | "png" | "gif" | "webp" | "bmp" | "tif" | "tiff" | "heic" | "heif"
+        | "avif" => (25, 15),
+        _ => return None,
+    };
+    Some(ExtractionBudget {
+        max_bytes: mb * MB,
+        timeout: Duration::from_secs(secs),
+    })
+}
+
+/// 파일 크기가 예산 안인지 확인한다.
+pub fn check_size(path: &Path, budget: &ExtractionBudget) -> Result<(), SandboxError> {
+    let len = std::fs::metadata(path).map_err(|_| SandboxError::NotFound)?.len();
+    if len > budget.max_bytes {
+        return Err(SandboxError::TooLarge(len, budget.max_bytes));
+    }
+    Ok(())
+}
+
+/// 추출 작업에 넘기는 취소 신호. 작업은 섹션·시트·페이지 사이에서 `check` 를 불러
+/// 시간 예산을 넘겼으면 중단한다.
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    /// 취소되지 않는 토큰(예산 없이 실행하는 Edufine 수집 등).
+    pub fn none() -> CancelToken {
+        CancelToken { cancelled: Arc::new(AtomicBool::new(false)), deadline: None }
+    }
+
+    fn with_deadline(timeout: Duration) -> CancelToken {
+        CancelToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self.deadline.map(|d| Instant::now() >= d).unwrap_or(false)
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err("추출 작업이 취소되었습니다.".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 작업 스레드가 끝날 때(패닉 포함) 살아 있는 작업 수를 되돌린다.
+struct WorkerSlot;
+
+impl Drop for WorkerSlot {
+    fn drop(&mut self) {
+        LIVE_WORKERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// 추출 작업을 별도 스레드에서 실행하고 시간 예산을 넘기면 포기한다.
+/// 초과 시 응답은 즉시 돌아가고, 작업 스레드는 취소 신호를 받아 다음 `check` 에서 끝난다.
+pub fn run_with_budget<T, F>(budget: &ExtractionBudget, f: F) -> Result<Result<T, String>, SandboxError>
+where
+    T: Send + 'static,
+    F: FnOnce(&CancelToken) -> Result<T, String> + Send + 'static,
+{
+    if LIVE_WORKERS.fetch_add(1, Ordering::SeqCst) >= MAX_LIVE_WORKERS {
+        LIVE_WORKERS.fetch_sub(1, Ordering::SeqCst);
+        return Err(SandboxError::Busy);
+    }
+    let slot = WorkerSlot;
+    let token = CancelToken::with_deadline(budget.timeout);
+    let worker_token = token.clone();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _slot = slot;
+        let _ = tx.send(f(&worker_token));
+    });
+    match rx.recv_timeout(budget.timeout) {
+        Ok(r) => Ok(r),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(SandboxError::BudgetExceeded(budget.timeout.as_secs()))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Ok(Err("추출 작업이 비정상 종료되었습니다.".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SEQ: AtomicUsize = AtomicUsize::new(0);
+
+    /// 테스트마다 고유한 임시 폴더: <tmp>/hc_sandbox_<pid>_<n>/{root, outside}. drop 시 삭제.
+    struct Fixture {
+        base: PathBuf,
+        root: PathBuf,
+        outside: PathBuf,
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.base);
+        }
+    }
+
+    fn fixture() -> Fixture {
+        let base = std::env::temp_dir().join(format!(
+            "hc_sandbox_{}_{}",
+            std::process::id(),
+            SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        let root = base.join("root");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(root.join("공문.hwp"), b"data").unwrap();
+        std::fs::write(root.join("sub").join("a.txt"), b"data").unwrap();
+        std::fs::write(outside.join("secret.txt"), b"secret").unwrap();
+        Fixture { base, root, outside }
+    }
+
+    #[test]
+    fn resolves_plain_and_nested_names() {
+        let fx = fixture();
+        let root = fx.root.clone();
+        let p = resolve_in_root(&root, "공문.hwp", SymlinkPolicy::Deny).unwrap();
+        assert!(p.ends_with("공문.hwp"));
+        let p = resolve_in_root(&root, "sub\\a.txt", SymlinkPolicy::Deny).unwrap();
+        assert!(p.ends_with("a.txt"));
+    }
+
+    #[test]
+    fn rejects_traversal_and_absolute_paths() {
+        let fx = fixture();
+        let root = fx.root.clone();
+        for name in [
+            "..\\outside\\secret.txt",
+            "../outside/secret.txt",
+            "sub/../../outside/secret.txt",
+            "/etc/passwd",
+            "\\\\server\\share\\x.txt",
+            "C:\\Windows\\win.ini",
+            "공문.hwp:Zone.Identifier",
+            "..",
+            ". ",
+        ] {
+            let err = resolve_in_root(&root, name, SymlinkPolicy::Deny).unwrap_err();
+            assert_eq!(err.code(), -32002, "{} → {:?}", name, err);
+        }
+    }
+
+    #[test]
+    fn rejects_reserved_device_names_and_trailing_dots() {
+        assert!(validate_file_name("CON").is_err());
+        assert!(validate_file_name("nul.txt").is_err());
+        assert!(validate_file_name("report.pdf.").is_err());
+        assert!(validate_file_name("console.txt").is_ok());
+    }
+
+    #[test]
+    fn missing_file_and_directory_are_not_found() {
+        let fx = fixture();
+        let root = fx.root.clone();
+        assert_eq!(
+            resolve_in_root(&root, "없음.pdf", SymlinkPolicy::Deny),
+            Err(SandboxError::NotFound)
+        );
+        assert_eq!(
+            resolve_in_root(&root, "sub", SymlinkPolicy::Deny),
+            Err(SandboxError::NotFound)
+        );
+    }
+
+    #[test]
+    fn missing_root_is_unavailable() {
+        let fx = fixture();
+        let root = fx.root.clone();
+        let gone = root.join("no_such_dir");
+        assert_eq!(
+            resolve_in_root(&gone, "a.txt", SymlinkPolicy::Deny),
+            Err(SandboxError::RootUnavailable)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_policy_is_enforced() {
+        let fx = fixture();
+        let (root, outside) = (fx.root.clone(), fx.outside.clone());
+        std::os::unix::fs::symlink(outside.join("secret.txt"), root.join("escape.txt")).unwrap();
+        std::os::unix::fs::symlink(root.join("공문.hwp"), root.join("inner.hwp")).unwrap();
+
+        assert_eq!(
+            resolve_in_root(&root, "inner.hwp", SymlinkPolicy::Deny),
+            Err(SandboxError::SymlinkRejected)
+        );
+        assert!(resolve_in_root(&root, "inner.hwp", SymlinkPolicy::FollowWithinRoot).is_ok());
+        assert_eq!(
+            resolve_in_root(&root, "escape.txt", SymlinkPolicy::FollowWithinRoot),
+            Err(SandboxError::OutsideRoot)
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn symlink_policy_is_enforced() {
+        let fx = fixture();
+        let (root, outside) = (fx.root.clone(), fx.outside.clone());
+        // 개발자 모드/관리자 권한이 없으면 링크 생성 자체가 실패하므로 건너뜀.
+        if std::os::windows::fs::symlink_file(outside.join("secret.txt"), root.join("escape.txt")).is_err() {
+            return;
+        }
+        assert_eq!(
+            resolve_in_root(&root, "escape.txt", SymlinkPolicy::Deny),
+            Err(SandboxError::SymlinkRejected)
+        );
+        assert_eq!(
+            resolve_in_root(&root, "escape.txt", SymlinkPolicy::FollowWithinRoot),
+            Err(SandboxError::OutsideRoot)
+        );
+    }
+
+    #[test]
+    fn budget_by_type_and_size_check() {
+        assert!(budget_for_ext("exe").is_none());
+        let pdf = budget_for_ext("PDF").unwrap();
+        assert_eq!(pdf.max_bytes, 30 * MB);
+
+        let fx = fixture();
+        let root = fx.root.clone();
+        let tiny = ExtractionBudget { max_bytes: 2, timeout: Duration::from_secs(1) };
+        assert_eq!(
+            check_size(&root.join("공문.hwp"), &tiny),
+            Err(SandboxError::TooLarge(4, 2))
+        );
+        assert!(check_size(&root.join("공문.hwp"), &pdf).is_ok());
+    }
+
+    #[test]
+    fn run_with_budget_times_out_and_cancels_worker() {
+        let budget = ExtractionBudget { max_bytes: MB, timeout: Duration::from_millis(50) };
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = stopped.clone();
+        // 작업이 제 마감을 보고 먼저 끝나 버리지 않도록 명시적 취소만 기다린다.
+        let slow = run_with_budget(&budget, move |cancel| {
+            while !cancel.cancelled.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            flag.store(true, Ordering::SeqCst);
+            Ok::<_, String>(1)
+        });
+        assert_eq!(slow.unwrap_err().code(), -32008);
+        for _ in 0..100 {
+            if stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(stopped.load(Ordering::SeqCst), "취소 후에도 작업 스레드가 남아 있음");
+
+        // 병렬 테스트로 스레드 시작이 늦어져도 시간 초과로 보지 않게 넉넉한 예산을 쓴다.
+        let roomy = ExtractionBudget { max_bytes: MB, timeout: Duration::from_secs(10) };
+        let fast = run_with_budget(&roomy, |_| Ok::<_, String>(7));
+        assert_eq!(fast.unwrap(), Ok(7));
+    }
+}
@@ -0,0 +1,463 @@
+//! 학교 종 시간표(교시별 시작·끝 시각).
+//!
+//! 평소·단축수업·시험처럼 이름 붙은 프로필(`BellProfile`)을 여러 개 두고, 기간·요일 규칙
+//! (`BellRule`)과 날짜별 지정(`BellOverride`)으로 그날 쓸 프로필을 고른다.
+//! 현재 교시(`main::get_current_period`), "N교시" 시작 시각(`agent::period_to_start_time`),
+//! 수업 중 판정(`utils::is_class_time`)이 모두 여기서 시각을 읽는다.
+//!
+//! 설정은 레지스트리 `BellSchedule` 에 JSON 으로 둔다. 아직 저장한 적이 없으면 예전 설정 화면에서
+//! 저장한 `ClassTimes`(`HHMM-HHMM` 목록)를 평소 프로필로 옮겨 쓴다. 둘 다 없으면 수업 중 판정은
+//! "수업 아님"으로 보고, 현재 교시와 교시 시작 시각은 기본 프로필로 계산한다.
+
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+
+const REG_BELL_SCHEDULE: &str = "BellSchedule";
+const REG_LEGACY_CLASS_TIMES: &str = "ClassTimes";
+
+/// 기본 프로필 id.
+pub const PROFILE_NORMAL: &str = "normal";
+pub const PROFILE_SHORT: &str = "short";
+pub const PROFILE_EXAM: &str = "exam";
+
+/// 종 시간표의 한 칸. `period` 가 없으면 점심·조회처럼 교시가 아닌 칸이다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BellSlot {
+    pub label: String,
+    /// "HH:MM"
+    pub start: String,
+    /// "HH:MM"
+    pub end: String,
+    #[serde(default)]
+    pub period: Option<u32>,
+}
+
+impl BellSlot {
+    fn new(label: &str, start: &str, end: &str, period: Option<u32>) -> Self {
+        Self { label: label.to_string(), start: start.to_string(), end: end.to_string(), period }
+    }
+
+    fn lesson(period: u32, start: &str, end: &str) -> Self {
+        Self::new(&format!("{}교시", period), start, end, Some(period))
+    }
+
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        parse_hm(&self.start)
+    }
+
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        parse_hm(&self.end)
+    }
+
+    /// `time` 이 이 칸 안인지(양 끝 포함).
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match (self.start_time(), self.end_time()) {
+            (Some(s), Some(e)) => s <= time && time <= e,
+            _ => false,
+        }
+    }
+}
+
+/// 이름 붙은 종 시간표 하나(평소, 단축수업, 시험 …).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BellProfile {
+    pub id: String,
+    pub name: String,
+    /// 시각 순서대로.
+    pub slots: Vec<BellSlot>,
+}
+
+impl BellProfile {
+    /// `time` 이 걸린 칸. 경계 시각이 두 칸에 겹치면 앞 칸.
+    pub fn slot_at(&self, time: NaiveTime) -> Option<&BellSlot> {
+        self.slots.iter().find(|s| s.contains(time))
+    }
+
+    /// `time` 의 교시(1부터). 쉬는 시간·점심이면 None.
+    pub fn period_at(&self, time: NaiveTime) -> Option<u32> {
+        self.slot_at(time).and_then(|s| s.period)
+    }
+
+    /// `time` 이 어느 교시 안인지.
+    pub fn is_class_time(&self, time: NaiveTime) -> bool {
+        self.period_at(time).is_some()
+    }
+
+    pub fn period_start(&self, period: u32) -> Option<NaiveTime> {
+        self.slots.iter().find(|s| s.period == Some(period)).and_then(BellSlot::start_time)
+    }
+}
+
+/// 기간·요일로 프로필을 고르는 규칙. 날짜는 "YYYY-MM-DD", 양 끝 포함.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BellRule {
+    pub profile: String,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    /// 1(월)~7(일). 비어 있으면 모든 요일.
+    #[serde(default)]
+    pub weekdays: Vec<u32>,
+    #[serde(default)]
+    pub note: String,
+}
+
+impl BellRule {
+    fn matches(&self, date: NaiveDate) -> bool {
+        let after_from = self.from.as_deref().and_then(parse_date).is_none_or(|f| f <= date);
+        let before_to = self.to.as_deref().and_then(parse_date).is_none_or(|t| date <= t);
+        let weekday = self.weekdays.is_empty() || self.weekdays.contains(&date.weekday().number_from_monday());
+        after_from && before_to && weekday
+    }
+}
+
+/// 특정 날짜 하나에 쓸 프로필(규칙보다 우선).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BellOverride {
+    pub date: String,
+    pub profile: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BellSchedule {
+    pub profiles: Vec<BellProfile>,
+    /// 규칙·지정이 없는 날 쓸 프로필.
+    pub default_profile: String,
+    /// 위에 있는 규칙이 우선.
+    #[serde(default)]
+    pub calendar: Vec<BellRule>,
+    #[serde(default)]
+    pub overrides: Vec<BellOverride>,
+}
+
+impl Default for BellSchedule {
+    fn default() -> Self {
+        let normal = BellProfile {
+            id: PROFILE_NORMAL.to_string(),
+            name: "평소".to_string(),
+            slots: vec![
+                BellSlot::lesson(1, "08:30", "09:20"),
+                BellSlot::lesson(2, "09:30", "10:20"),
+                BellSlot::lesson(3, "10:30", "11:20"),
+                BellSlot::lesson(4, "11:30", "12:20"),
+                BellSlot::new("점심", "12:20", "13:20", None),
+                BellSlot::lesson(5, "13:20", "14:10"),
+                BellSlot::lesson(6, "14:20", "15:10"),
+                BellSlot::lesson(7, "15:20", "16:10"),
+            ],
+        };
+        let short = BellProfile {
+            id: PROFILE_SHORT.to_string(),
+            name: "단축수업".to_string(),
+            slots: vec![
+                BellSlot::lesson(1, "08:30", "09:10"),
+                BellSlot::lesson(2, "09:20", "10:00"),
+                BellSlot::lesson(3, "10:10", "10:50"),
+                BellSlot::lesson(4, "11:00", "11:40"),
+                BellSlot::new("점심", "11:40", "12:30", None),
+                BellSlot::lesson(5, "12:30", "13:10"),
+                BellSlot::lesson(6, "13:20", "14:00"),
+                BellSlot::lesson(7, "14:10", "14:50"),
+            ],
+        };
+        let exam = BellProfile {
+            id: PROFILE_EXAM.to_string(),
+            name: "시험".to_string(),
+            slots: vec![
+                BellSlot::lesson(1, "09:00", "09:50"),
+                BellSlot::lesson(2, "10:10", "11:00"),
+                BellSlot::lesson(3, "11:20", "12:10"),
+            ],
+        };
+        Self {
+            profiles: vec![normal, short, exam],
+            default_profile: PROFILE_NORMAL.to_string(),
+            calendar: Vec::new(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl BellSchedule {
+    pub fn profile(&self, id: &str) -> Option<&BellProfile> {
+        self.profiles.iter().find(|p| p.id == id)
+    }
+
+    /// `date` 에 쓸 프로필 id: 날짜 지정 → 규칙(위에서부터) → 기본.
+    pub fn profile_id_for(&self, date: NaiveDate) -> &str {
+        let known = |id: &str| self.profile(id).is_some();
+        if let Some(o) = self
+            .overrides
+            .iter()
+            .find(|o| parse_date(&o.date) == Some(date) && known(&o.profile))
+        {
+            return &o.profile;
+        }
+        if let Some(r) = self.calendar.iter().find(|r| r.matches(date) && known(&r.profile)) {
+            return &r.profile;
+        }
+        &self.default_profile
+    }
+
+    /// `date` 에 쓸 프로필. 설정이 깨져 있어도 첫 프로필이나 기본 평소 시간표로 물러선다.
+    pub fn profile_for(&self, date: NaiveDate) -> BellProfile {
+        self.profile(self.profile_id_for(date))
+            .or_else(|| self.profiles.first())
+            .cloned()
+            .unwrap_or_else(|| BellSchedule::default().profiles.remove(0))
+    }
+
+    /// `now` 의 교시(1부터). 그날 프로필에서 교시가 아닌 칸이면 None.
+    pub fn period_on(&self, now: NaiveDateTime) -> Option<u32> {
+        self.profile_for(now.date()).period_at(now.time())
+    }
+
+    /// 저장 전 검사. 오류 메시지는 설정 화면에 그대로 보인다.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.profiles.is_empty() {
+            return Err("종 시간표 프로필이 하나 이상 있어야 합니다".to_string());
+        }
+        let mut ids = HashSet::new();
+        for p in &self.profiles {
+            if p.id.trim().is_empty() {
+                return Err("프로필 id 가 비어 있습니다".to_string());
+            }
+            if !ids.insert(p.id.as_str()) {
+                return Err(format!("프로필 id 가 중복됩니다: {}", p.id));
+            }
+            let mut periods = HashSet::new();
+            let mut prev_start: Option<NaiveTime> = None;
+            for s in &p.slots {
+                let (Some(start), Some(end)) = (s.start_time(), s.end_time()) else {
+                    return Err(format!("{} · {}: 시각 형식이 올바르지 않습니다(HH:MM)", p.name, s.label));
+                };
+                if start >= end {
+                    return Err(format!("{} · {}: 끝 시각이 시작 시각보다 늦어야 합니다", p.name, s.label));
+                }
+                if prev_start.is_some_and(|prev| start < prev) {
+                    return Err(format!("{} · {}: 칸은 시각 순서대로 두어야 합니다", p.name, s.label));
+                }
+                prev_start = Some(start);
+                if let Some(n) = s.period {
+                    if n == 0 || !periods.insert(n) {
+                        return Err(format!("{} · {}: 교시 번호가 잘못되었거나 중복됩니다", p.name, s.label));
+                    }
+                }
+            }
+        }
+        if !ids.contains(self.default_profile.as_str()) {
+            return Err(format!("기본 프로필을 찾을 수 없습니다: {}", self.default_profile));
+        }
+        for r in &self.calendar {
+            if !ids.contains(r.profile.as_str()) {
+                return Err(format!("규칙의 프로필을 찾을 수 없습니다: {}", r.profile));
+            }
+            for d in [&r.from, &r.to].into_iter().flatten() {
+                if parse_date(d).is_none() {
+                    return Err(format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", d));
+                }
+            }
+            if let (Some(f), Some(t)) = (r.from.as_deref().and_then(parse_date), r.to.as_deref().and_then(parse_date)) {
+                if f > t {
+                    return Err(format!("규칙 기간이 거꾸로입니다: {} ~ {}", f, t));
+                }
+            }
+            if r.weekdays.iter().any(|w| !(1..=7).contains(w)) {
+                return Err("요일은 1(월)~7(일)이어야 합니다".to_string());
+            }
+        }
+        for o in &self.overrides {
+            if parse_date(&o.date).is_none() {
+                return Err(format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", o.date));
+            }
+            if !ids.contains(o.profile.as_str()) {
+                return Err(format!("{} 의 프로필을 찾을 수 없습니다: {}", o.date, o.profile));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_hm(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+/// 예전 `ClassTimes`(["0830-0920", …])를 교시 칸 목록으로. 순서대로 1교시부터 번호를 매긴다.
+fn slots_from_class_times(times: &[String]) -> Vec<BellSlot> {
+    let hm = |s: &str| crate::utils::parse_hhmm(s.trim()).map(|t| t.format("%H:%M").to_string());
+    times
+        .iter()
+        .filter_map(|r| {
+            let (s, e) = r.split_once('-')?;
+            Some((hm(s)?, hm(e)?))
+        })
+        .enumerate()
+        .map(|(i, (s, e))| BellSlot::lesson(i as u32 + 1, &s, &e))
+        .collect()
+}
+
+fn read_reg(key: &str) -> Option<String> {
+    get_registry_value(key.to_string())
+        .ok()
+        .flatten()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// 사용자가 설정한 종 시간표(없으면 예전 `ClassTimes` 를 옮긴 것). 둘 다 없으면 None.
+pub fn load_configured() -> Option<BellSchedule> {
+    if let Some(s) = read_reg(REG_BELL_SCHEDULE)
+        .and_then(|v| serde_json::from_str::<BellSchedule>(&v).ok())
+        .filter(|s| s.validate().is_ok())
+    {
+        return Some(s);
+    }
+    let legacy = read_reg(REG_LEGACY_CLASS_TIMES)
+        .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
+        .map(|t| slots_from_class_times(&t))
+        .filter(|slots| !slots.is_empty())?;
+    let mut schedule = BellSchedule::default();
+    schedule.profiles[0].slots = legacy;
+    Some(schedule)
+}
+
+/// 저장된 종 시간표. 설정하지 않았거나 깨져 있으면 기본값.
+pub fn load() -> BellSchedule {
+    load_configured().unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_bell_schedule() -> BellSchedule {
+    load()
+}
+
+#[tauri::command]
+pub fn set_bell_schedule(schedule: BellSchedule) -> Result<(), String> {
+    schedule.validate()?;
+    let json = serde_json::to_string(&schedule).map_err(|e| e.to_string())?;
+    set_registry_value(REG_BELL_SCHEDULE.to_string(), json)
+}
+
+/// `date`(YYYY-MM-DD, 없으면 오늘)에 쓸 프로필.
+#[tauri::command]
+pub fn get_bell_profile_for(date: Option<String>) -> Result<BellProfile, String> {
+    let date = match date.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(s) => parse_date(s).ok_or_else(|| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", s))?,
+        None => chrono::Local::now().date_naive(),
+    };
+    Ok(load().profile_for(date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    fn t(s: &str) -> NaiveTime {
+        parse_hm(s).unwrap()
+    }
+
+    #[test]
+    fn default_profile_matches_previous_hardcoded_times() {
+        let s = BellSchedule::default();
+        assert!(s.validate().is_ok());
+        let p = s.profile_for(d("2026-03-04"));
+        assert_eq!(p.id, PROFILE_NORMAL);
+        assert_eq!(p.period_at(t("08:30")), Some(1));
+        assert_eq!(p.period_at(t("09:25")), None); // 쉬는 시간
+        assert_eq!(p.period_at(t("12:20")), Some(4)); // 경계는 앞 칸
+        assert_eq!(p.period_at(t("12:40")), None); // 점심
+        assert_eq!(p.period_at(t("13:20")), None); // 점심 끝 경계도 앞 칸(점심)
+        assert_eq!(p.period_at(t("13:21")), Some(5));
+        assert_eq!(p.period_at(t("16:10")), Some(7));
+        assert_eq!(p.period_at(t("16:11")), None);
+        assert_eq!(p.period_start(6), Some(t("14:20")));
+        assert_eq!(p.period_start(8), None);
+    }
+
+    #[test]
+    fn unconfigured_schedule_still_reports_current_period() {
+        // 설정이 없으면 `load()` 가 기본 프로필을 돌려준다.
+        let s = BellSchedule::default();
+        let at = |hm: &str| d("2026-03-04").and_time(t(hm));
+        assert_eq!(s.period_on(at("08:30")), Some(1));
+        assert_eq!(s.period_on(at("12:40")), None);
+        assert_eq!(s.period_on(at("13:21")), Some(5));
+    }
+
+    #[test]
+    fn override_beats_calendar_beats_default() {
+        let mut s = BellSchedule::default();
+        s.calendar.push(BellRule {
+            profile: PROFILE_EXAM.to_string(),
+            from: Some("2026-04-27".to_string()),
+            to: Some("2026-04-30".to_string()),
+            weekdays: Vec::new(),
+            note: "중간고사".to_string(),
+        });
+        s.calendar.push(BellRule {
+            profile: PROFILE_SHORT.to_string(),
+            from: None,
+            to: None,
+            weekdays: vec![3],
+            note: "매주 수요일 단축".to_string(),
+        });
+        s.overrides.push(BellOverride {
+            date: "2026-04-28".to_string(),
+            profile: PROFILE_NORMAL.to_string(),
+            note: String::new(),
+        });
+        assert!(s.validate().is_ok());
+        assert_eq!(s.profile_id_for(d("2026-04-27")), PROFILE_EXAM);
+        assert_eq!(s.profile_id_for(d("2026-04-28")), PROFILE_NORMAL); // 날짜 지정
+        assert_eq!(s.profile_id_for(d("2026-04-29")), PROFILE_EXAM); // 수요일이지만 위 규칙이 우선
+        assert_eq!(s.profile_id_for(d("2026-05-06")), PROFILE_SHORT); // 수요일
+        assert_eq!(s.profile_id_for(d("2026-05-07")), PROFILE_NORMAL);
+        assert_eq!(s.profile_for(d("2026-04-27")).period_start(2), Some(t("10:10")));
+    }
+
+    #[test]
+    fn validate_rejects_broken_settings() {
+        let mut s = BellSchedule::default();
+        s.default_profile = "없음".to_string();
+        assert!(s.validate().is_err());
+
+        let mut s = BellSchedule::default();
+        s.profiles[0].slots[1].period = Some(1);
+        assert!(s.validate().is_err());
+
+        let mut s = BellSchedule::default();
+        s.profiles[0].slots[0].end = "08:00".to_string();
+        assert!(s.validate().is_err());
+
+        let mut s = BellSchedule::default();
+        s.overrides.push(BellOverride { date: "2026-13-01".to_string(), profile: PROFILE_NORMAL.to_string(), note: String::new() });
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn legacy_class_times_become_numbered_slots() {
+        let slots = slots_from_class_times(&["0830-0920".to_string(), "잘못됨".to_string(), "1320-1410".to_string()]);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0], BellSlot::lesson(1, "08:30", "09:20"));
+        assert_eq!(slots[1], BellSlot::lesson(2, "13:20", "14:10"));
+    }
+}
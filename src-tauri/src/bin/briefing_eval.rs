@@ -0,0 +1,6 @@
+//! 브리핑 에이전트 오프라인 평가 도구. 자세한 내용은 `hypercool::briefing_eval` 참고.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(hypercool::briefing_eval::run_cli(&args));
+}
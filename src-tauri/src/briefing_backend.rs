@@ -0,0 +1,643 @@
+//! 브리핑 에이전트의 추출 백엔드.
+//!
+//! 메시지에서 일정 JSON 배열을 뽑아내는 주체를 교체할 수 있게 한다.
+//! - `claude_cli`: Claude Code CLI 가 MCP 도구로 직접 메시지를 읽는다(구현은 `agent`).
+//! - `openai_compat`: OpenAI 호환 `/chat/completions` 엔드포인트(llama.cpp·Ollama 등 로컬 서버 포함).
+//!   도구를 쓸 수 없으므로 앱이 신규 메시지를 프롬프트에 직접 넣는다.
+//! - `rules`: 네트워크 없이 날짜·기한 표현만으로 항목을 만드는 결정적 추출기.
+//!
+//! 모든 백엔드는 "최종 응답 텍스트"를 돌려주고, 이후 JSON 배열 추출·검증·등록은
+//! `agent::parse_items_from_output` → `register_items` 로 같은 경로를 탄다.
+
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::briefing_usage::RunUsage;
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::date_expr::{self, DateExpr};
+use crate::secret_store;
+
+const REG_BACKEND: &str = "BriefingBackend";
+const REG_LLM_ENDPOINT: &str = "BriefingLlmEndpoint";
+const REG_LLM_MODEL: &str = "BriefingLlmModel";
+const REG_LLM_API_KEY: &str = "BriefingLlmApiKey";
+
+/// HTTP 백엔드 요청 제한 시간(초). 로컬 모델은 느릴 수 있어 CLI 와 같은 수준으로 둔다.
+const HTTP_TIMEOUT_SECS: u64 = 240;
+/// 프롬프트에 넣는 메시지 한 건의 최대 글자 수.
+const INLINE_MESSAGE_CHARS: usize = 2000;
+/// 규칙 기반 추출의 제목 최대 글자 수.
+const RULE_TITLE_CHARS: usize = 30;
+/// 규칙 기반 추출의 원문 발췌 최대 글자 수.
+const RULE_EXCERPT_CHARS: usize = 80;
+
+/// 기한(내가 해야 할 행동)을 나타내는 표현.
+const DEADLINE_KEYWORDS: &[&str] = &["까지", "마감", "제출", "회신", "신청"];
+/// 참석형 일정을 나타내는 표현.
+const EVENT_KEYWORDS: &[&str] = &["회의", "연수", "협의회", "참석", "행사", "설명회"];
+
+// ─── 설정 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    ClaudeCli,
+    OpenaiCompat,
+    Rules,
+}
+
+impl BackendKind {
+    fn key(self) -> &'static str {
+        match self {
+            BackendKind::ClaudeCli => "claude_cli",
+            BackendKind::OpenaiCompat => "openai_compat",
+            BackendKind::Rules => "rules",
+        }
+    }
+
+    fn from_key(s: &str) -> Option<BackendKind> {
+        [BackendKind::ClaudeCli, BackendKind::OpenaiCompat, BackendKind::Rules]
+            .into_iter()
+            .find(|k| k.key() == s.trim())
+    }
+}
+
+/// 추출 백엔드 설정. API 키가 들어 있으므로 화면에는 `BackendSettingsView` 로 내보낸다.
+#[derive(Debug, Clone)]
+pub struct BackendSettings {
+    pub kind: BackendKind,
+    /// OpenAI 호환 엔드포인트 기준 URL(예: `http://localhost:11434/v1`)
+    pub endpoint: String,
+    pub model: String,
+    /// 로컬 서버는 보통 비워 둔다.
+    pub api_key: String,
+}
+
+impl Default for BackendSettings {
+    fn default() -> Self {
+        BackendSettings {
+            kind: BackendKind::ClaudeCli,
+            endpoint: String::new(),
+            model: String::new(),
+            api_key: String::new(),
+        }
+    }
+}
+
+/// 레지스트리에서 설정을 읽는다(값이 없으면 기존 동작인 claude CLI).
+pub fn load_settings() -> BackendSettings {
+    let read = |key: &str| {
+        get_registry_value(key.to_string())
+            .ok()
+            .flatten()
+            .map(|v| v.trim().to_string())
+            .unwrap_or_default()
+    };
+    BackendSettings {
+        kind: BackendKind::from_key(&read(REG_BACKEND)).unwrap_or(BackendKind::ClaudeCli),
+        endpoint: read(REG_LLM_ENDPOINT),
+        model: read(REG_LLM_MODEL),
+        api_key: load_api_key(&read(REG_LLM_API_KEY)),
+    }
+}
+
+/// 저장된 API 키를 푼다. 암호화 도입 전의 평문 값은 읽은 김에 암호화해 다시 저장한다.
+fn load_api_key(stored: &str) -> String {
+    if stored.is_empty() {
+        return String::new();
+    }
+    if !secret_store::is_sealed(stored) {
+        let _ = save_api_key(stored);
+        return stored.to_string();
+    }
+    match secret_store::open(stored) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) => {
+            eprintln!("[briefing] LLM API 키를 풀 수 없습니다: {}", e);
+            String::new()
+        }
+    }
+}
+
+fn save_api_key(key: &str) -> Result<(), String> {
+    let key = key.trim();
+    let value = if key.is_empty() {
+        String::new()
+    } else {
+        secret_store::seal(key.as_bytes()).map_err(|e| format!("API 키 {}", e))?
+    };
+    set_registry_value(REG_LLM_API_KEY.to_string(), value)
+}
+
+/// 설정 화면에 보여 줄 값. API 키는 돌려주지 않는다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendSettingsView {
+    pub kind: BackendKind,
+    pub endpoint: String,
+    pub model: String,
+    pub has_api_key: bool,
+}
+
+#[tauri::command]
+pub fn get_briefing_backend_settings() -> BackendSettingsView {
+    let s = load_settings();
+    BackendSettingsView {
+        kind: s.kind,
+        endpoint: s.endpoint,
+        model: s.model,
+        has_api_key: !s.api_key.is_empty(),
+    }
+}
+
+/// `api_key` 를 생략하면 저장된 키를 그대로 쓰고, 빈 문자열이면 지운다.
+#[tauri::command]
+pub fn set_briefing_backend_settings(
+    kind: BackendKind,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+) -> Result<(), String> {
+    if kind == BackendKind::OpenaiCompat && endpoint.trim().is_empty() {
+        return Err("OpenAI 호환 백엔드는 엔드포인트 URL 이 필요합니다.".to_string());
+    }
+    set_registry_value(REG_BACKEND.to_string(), kind.key().to_string())?;
+    set_registry_value(REG_LLM_ENDPOINT.to_string(), endpoint.trim().to_string())?;
+    set_registry_value(REG_LLM_MODEL.to_string(), model.trim().to_string())?;
+    match api_key {
+        Some(key) => save_api_key(&key),
+        None => Ok(()),
+    }
+}
+
+// ─── 백엔드 트레이트 ──────────────────────────────────────────────────────────
+
+/// 도구 없이 동작하는 백엔드에 넘기는 신규 메시지(검색 DB 의 한 행).
+#[derive(Debug, Clone)]
+pub struct SourceMessage {
+    pub id: i64,
+    pub sender: String,
+    pub receive_date: Option<String>,
+    pub text: String,
+    /// 첨부 파일 경로(규칙의 확장자 조건용). 비어 있으면 첨부 없음.
+    pub attachments: Vec<String>,
+}
+
+/// 1회 추출 요청.
+pub struct ExtractionRequest<'a> {
+    /// 템플릿 치환까지 끝난 브리핑 프롬프트
+    pub prompt: &'a str,
+    /// 오늘 날짜(YYYY-MM-DD, Asia/Seoul)
+    pub today: &'a str,
+    /// `needs_messages()` 인 백엔드에만 채워지는 신규 메시지
+    pub messages: &'a [SourceMessage],
+    /// claude CLI 용 mcp.json 경로
+    pub mcp_config: Option<&'a Path>,
+    /// 프로세스 작업 디렉터리(app_data_dir)
+    pub cwd: &'a Path,
+}
+
+/// 백엔드 실행 결과.
+#[derive(Debug, Default)]
+pub struct BackendOutput {
+    /// JSON 배열이 담긴 최종 응답 텍스트
+    pub text: String,
+    /// 진단용 부가 로그 꼬리(claude stderr 등)
+    pub log_tail: Option<String>,
+    /// 토큰·비용 사용량(알 수 있는 백엔드만).
+    pub usage: Option<RunUsage>,
+    /// 백엔드가 응답은 했지만 실패를 알린 경우의 사유(claude `is_error` 등).
+    /// 실패해도 사용량은 남기도록 `Err` 대신 여기에 담는다 — 호출 측이 확인해야 한다.
+    pub failure: Option<String>,
+}
+
+pub trait ExtractionBackend: Send + Sync {
+    /// 상태·진단 표시에 쓰는 이름
+    fn name(&self) -> &'static str;
+
+    /// 실행 가능 여부. 불가하면 사용자에게 보여줄 사유.
+    fn check(&self) -> Result<(), String>;
+
+    /// MCP 도구 없이 동작해 앱이 메시지를 직접 넘겨야 하는지
+    fn needs_messages(&self) -> bool;
+
+    /// 추출을 실행하고 JSON 배열이 담긴 최종 응답 텍스트를 돌려준다.
+    fn extract(&self, req: &ExtractionRequest<'_>) -> Result<BackendOutput, String>;
+}
+
+// ─── OpenAI 호환 HTTP ─────────────────────────────────────────────────────────
+
+pub struct OpenAiCompatBackend {
+    pub endpoint: String,
+    pub model: String,
+    pub api_key: String,
+}
+
+impl OpenAiCompatBackend {
+    pub fn from_settings(s: &BackendSettings) -> Self {
+        OpenAiCompatBackend {
+            endpoint: s.endpoint.clone(),
+            model: s.model.clone(),
+            api_key: s.api_key.clone(),
+        }
+    }
+
+    /// 기준 URL 뒤에 `/chat/completions` 를 붙인다(이미 붙어 있으면 그대로).
+    fn completions_url(&self) -> String {
+        let base = self.endpoint.trim().trim_end_matches('/');
+        if base.ends_with("/chat/completions") {
+            base.to_string()
+        } else {
+            format!("{}/chat/completions", base)
+        }
+    }
+}
+
+impl ExtractionBackend for OpenAiCompatBackend {
+    fn name(&self) -> &'static str {
+        "openai_compat"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.endpoint.trim().is_empty() {
+            return Err("OpenAI 호환 엔드포인트 URL 이 설정되지 않았습니다.".to_string());
+        }
+        Ok(())
+    }
+
+    fn needs_messages(&self) -> bool {
+        true
+    }
+
+    fn extract(&self, req: &ExtractionRequest<'_>) -> Result<BackendOutput, String> {
+        let prompt = compose_inline_prompt(req.prompt, req.messages);
+        let (text, usage) = self.complete(&prompt)?;
+        Ok(BackendOutput { text, usage, ..Default::default() })
+    }
+}
+
+impl OpenAiCompatBackend {
+    /// 프롬프트 하나를 보내고 응답 본문과 서버가 알려 준 사용량을 받는다(다이제스트 문장 생성에도 쓴다).
+    pub fn complete(&self, prompt: &str) -> Result<(String, Option<RunUsage>), String> {
+        self.check()?;
+        let mut prompt = prompt.to_string();
+        // 외부(또는 로컬) 서버로 본문이 그대로 나가므로 MCP 결과와 같은 규칙으로 가린다.
+        if let Some(cfg) = crate::pii_redact::active_config() {
+            prompt = crate::pii_redact::redact_text(&cfg, &prompt);
+        }
+
+        let mut body = json!({
+            "messages": [{ "role": "user", "content": prompt }],
+            "temperature": 0,
+            "stream": false,
+        });
+        if !self.model.trim().is_empty() {
+            body["model"] = json!(self.model.trim());
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| format!("HTTP 클라이언트 생성 실패: {}", e))?;
+        let mut request = client.post(self.completions_url()).json(&body);
+        if !self.api_key.trim().is_empty() {
+            request = request.bearer_auth(self.api_key.trim());
+        }
+        let resp = request
+            .send()
+            .map_err(|e| format!("LLM 엔드포인트 호출 실패: {}", e))?;
+        let status = resp.status();
+        let text = resp
+            .text()
+            .map_err(|e| format!("LLM 응답 읽기 실패: {}", e))?;
+        if !status.is_success() {
+            return Err(format!(
+                "LLM 엔드포인트 오류(HTTP {}): {}",
+                status.as_u16(),
+                text.chars().take(300).collect::<String>()
+            ));
+        }
+        parse_chat_completion(&text)
+    }
+}
+
+/// `/chat/completions` 응답 본문에서 첫 선택지의 메시지 내용과 `usage` 를 꺼낸다.
+fn parse_chat_completion(body: &str) -> Result<(String, Option<RunUsage>), String> {
+    let v: Value = serde_json::from_str(body)
+        .map_err(|e| format!("LLM 응답 JSON 파싱 실패: {}", e))?;
+    let content = v
+        .pointer("/choices/0/message/content")
+        .and_then(|c| c.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "LLM 응답에 choices[0].message.content 가 없습니다.".to_string())?;
+    Ok((content, RunUsage::from_chat_completion(&v)))
+}
+
+/// 도구가 없는 백엔드용 프롬프트: 브리핑 프롬프트 뒤에 신규 메시지 원문을 붙인다.
+pub fn compose_inline_prompt(prompt: &str, messages: &[SourceMessage]) -> String {
+    let mut out = String::from(prompt);
+    out.push_str("\n\n## 대상 메시지 (앱이 직접 제공)\n");
+    out.push_str(
+        "이 실행에서는 MCP 도구를 쓸 수 없다. 도구 호출 절차는 건너뛰고, 아래 목록을 \
+         last_seen_id 이후 신규 메시지 전부로 보고 판단한다. 첨부 내용은 확인할 수 없으므로 \
+         본문에 드러난 정보만 사용한다.\n",
+    );
+    if messages.is_empty() {
+        out.push_str("\n(신규 메시지 없음)\n");
+    }
+    for m in messages {
+        out.push_str(&format!(
+            "\n### 메시지 {}\n- 발신: {}\n- 수신: {}\n- 첨부: {}\n",
+            m.id,
+            m.sender,
+            m.receive_date.as_deref().unwrap_or("알 수 없음"),
+            if m.attachments.is_empty() { "없음" } else { "있음" },
+        ));
+        let body: String = m.text.trim().chars().take(INLINE_MESSAGE_CHARS).collect();
+        out.push_str(&body);
+        out.push('\n');
+    }
+    out
+}
+
+// ─── 규칙 기반 ────────────────────────────────────────────────────────────────
+
+/// 날짜와 기한·참석 표현이 함께 있는 메시지만 항목으로 만드는 결정적 추출기.
+/// LLM 없이도 "~까지 제출" 류 공지는 놓치지 않는 것이 목표이며, 판단이 애매한
+/// 메시지는 만들지 않는다.
+pub struct RuleBackend;
+
+/// 기한·참석 표현이 있어 할 일이 될 만한 메시지인지(다이제스트의 미처리 메시지 선별용).
+pub fn looks_actionable(text: &str) -> bool {
+    DEADLINE_KEYWORDS.iter().chain(EVENT_KEYWORDS).any(|k| text.contains(k))
+}
+
+impl ExtractionBackend for RuleBackend {
+    fn name(&self) -> &'static str {
+        "rules"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn needs_messages(&self) -> bool {
+        true
+    }
+
+    fn extract(&self, req: &ExtractionRequest<'_>) -> Result<BackendOutput, String> {
+        let today = chrono::NaiveDate::parse_from_str(req.today, "%Y-%m-%d")
+            .map_err(|e| format!("오늘 날짜 형식 오류: {}", e))?;
+        let items: Vec<Value> = req
+            .messages
+            .iter()
+            .filter_map(|m| rule_item(m, today))
+            .collect();
+        Ok(BackendOutput {
+            text: serde_json::to_string(&items).map_err(|e| e.to_string())?,
+            ..Default::default()
+        })
+    }
+}
+
+/// 메시지 하나를 규칙 기반 항목(JSON)으로 만든다. 날짜 표현이 없으면 None.
+/// 브리핑 규칙의 강제 추출에도 쓴다.
+pub fn rule_item(m: &SourceMessage, today: chrono::NaiveDate) -> Option<Value> {
+    let text = m.text.as_str();
+    let deadline = DEADLINE_KEYWORDS.iter().any(|k| text.contains(k));
+    let event = EVENT_KEYWORDS.iter().any(|k| text.contains(k));
+    if !deadline && !event {
+        return None;
+    }
+
+    // "내일", "다음 주 화요일" 같은 상대 표현은 메시지를 받은 날 기준으로 푼다.
+    let reference = m
+        .receive_date
+        .as_deref()
+        .and_then(|d| d.get(..10))
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .unwrap_or(today);
+    let exprs: Vec<DateExpr> = date_expr::extract(text, reference)
+        .into_iter()
+        .filter(|e| e.date.is_some_and(|d| d >= today))
+        .collect();
+    // 기한형은 "까지"가 붙은 날짜 → 기한 표현 앞의 마지막 날짜 순, 그 외에는 오늘 이후 첫 날짜.
+    let anchor = DEADLINE_KEYWORDS
+        .iter()
+        .filter_map(|k| text.find(k))
+        .min();
+    let hit = if deadline {
+        exprs
+            .iter()
+            .find(|e| e.deadline)
+            .or_else(|| anchor.and_then(|pos| exprs.iter().rev().find(|e| e.start < pos)))
+            .or_else(|| exprs.first())
+    } else {
+        exprs.first()
+    }?;
+
+    let line = line_at(text, hit.start);
+    let title: String = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("할 일")
+        .chars()
+        .take(RULE_TITLE_CHARS)
+        .collect();
+    let time = hit.time.map(|t| t.format("%H:%M").to_string());
+    let period = hit.period.map(|p| format!("{}교시", p));
+    let date = hit.date?.format("%Y-%m-%d").to_string();
+
+    Some(json!({
+        "id": format!("msg-{}", m.id),
+        "source_message_id": m.id,
+        "received_at": m.receive_date,
+        "sender": m.sender,
+        "type": if deadline { "deadline" } else { "event" },
+        "title": title,
+        "detail": Value::Null,
+        "date": date,
+        "time": time,
+        "all_day": time.is_none() && period.is_none(),
+        "period": period,
+        "has_attachment": !m.attachments.is_empty(),
+        "urgency": "medium",
+        "active_from": m.receive_date.as_deref().and_then(|d| d.get(..10)),
+        "active_until": date,
+        "source_text": line.trim().chars().take(RULE_EXCERPT_CHARS).collect::<String>(),
+    }))
+}
+
+fn line_at(text: &str, byte_pos: usize) -> &str {
+    let start = text[..byte_pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = text[byte_pos..].find('\n').map(|i| byte_pos + i).unwrap_or(text.len());
+    &text[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    fn msg(id: i64, text: &str) -> SourceMessage {
+        SourceMessage {
+            id,
+            sender: "김교사".to_string(),
+            receive_date: Some("2026-03-02T09:00:00+09:00".to_string()),
+            text: text.to_string(),
+            attachments: Vec::new(),
+        }
+    }
+
+    fn req<'a>(messages: &'a [SourceMessage], prompt: &'a str) -> ExtractionRequest<'a> {
+        ExtractionRequest {
+            prompt,
+            today: "2026-03-02",
+            messages,
+            mcp_config: None,
+            cwd: Path::new("."),
+        }
+    }
+
+    #[test]
+    fn rules_pick_deadline_date_before_keyword() {
+        let messages = [
+            msg(10, "학부모 상담 주간 안내\n상담은 3월 16일(월)부터 진행합니다.\n희망 시간을 3월 6일 오후 4시까지 회신 바랍니다."),
+            msg(11, "오늘 점심 맛있게 드세요"),
+            msg(12, "교직원 연수 안내\n2026-03-11 6교시 시청각실"),
+        ];
+        let out = RuleBackend.extract(&req(&messages, "")).unwrap().text;
+        let items: Vec<Value> = serde_json::from_str(&out).unwrap();
+        assert_eq!(items.len(), 2);
+
+        assert_eq!(items[0]["source_message_id"], 10);
+        assert_eq!(items[0]["type"], "deadline");
+        assert_eq!(items[0]["date"], "2026-03-06");
+        assert_eq!(items[0]["time"], "16:00");
+        assert_eq!(items[0]["title"], "학부모 상담 주간 안내");
+
+        assert_eq!(items[1]["type"], "event");
+        assert_eq!(items[1]["date"], "2026-03-11");
+        assert_eq!(items[1]["period"], "6교시");
+        assert_eq!(items[1]["all_day"], false);
+    }
+
+    #[test]
+    fn rules_skip_phone_numbers_and_roll_year_forward() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 12, 20).unwrap();
+        let mut m = msg(20, "방과후 신청서\n문의 010-1234-5678, 제출 1.8.(금) 오전 9시 반까지");
+        m.receive_date = None;
+        let item = rule_item(&m, today).unwrap();
+        assert_eq!(item["date"], "2027-01-08");
+        assert_eq!(item["time"], "09:30");
+
+        let m = msg(21, "협의회 안내\n다음 주 화요일 14:05 시작");
+        let item = rule_item(&m, chrono::NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()).unwrap();
+        assert_eq!(item["date"], "2026-03-10");
+        assert_eq!(item["time"], "14:05");
+    }
+
+    #[test]
+    fn inline_prompt_lists_messages() {
+        let messages = [msg(7, "회의 안내")];
+        let p = compose_inline_prompt("PROMPT", &messages);
+        assert!(p.starts_with("PROMPT\n\n## 대상 메시지"));
+        assert!(p.contains("### 메시지 7\n- 발신: 김교사"));
+        assert!(p.contains("회의 안내"));
+        assert!(compose_inline_prompt("P", &[]).contains("(신규 메시지 없음)"));
+    }
+
+    #[test]
+    fn completions_url_is_normalized() {
+        let mut b = OpenAiCompatBackend {
+            endpoint: "http://localhost:11434/v1/".to_string(),
+            model: String::new(),
+            api_key: String::new(),
+        };
+        assert_eq!(b.completions_url(), "http://localhost:11434/v1/chat/completions");
+        b.endpoint = "http://h/v1/chat/completions".to_string();
+        assert_eq!(b.completions_url(), "http://h/v1/chat/completions");
+        b.endpoint = " ".to_string();
+        assert!(b.check().is_err());
+    }
+
+    /// 요청 한 건을 받아 본문을 돌려보내고 고정 응답을 주는 로컬 스텁 서버.
+    fn stub_server(status: &'static str, response: String) -> (String, std::thread::JoinHandle<String>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut content_length = 0usize;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = v.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+            format!("{}\n{}", head, String::from_utf8_lossy(&body))
+        });
+        (format!("http://{}/v1", addr), handle)
+    }
+
+    #[test]
+    fn openai_backend_posts_prompt_and_returns_content() {
+        let reply = json!({
+            "choices": [{ "message": { "role": "assistant", "content": "[{\"id\":\"msg-7\"}]" } }],
+            "usage": { "prompt_tokens": 812, "completion_tokens": 34, "total_tokens": 846 }
+        })
+        .to_string();
+        let (endpoint, server) = stub_server("200 OK", reply);
+        let backend = OpenAiCompatBackend {
+            endpoint,
+            model: "llama3".to_string(),
+            api_key: "secret".to_string(),
+        };
+        let messages = [msg(7, "3월 5일까지 제출")];
+        let out = backend.extract(&req(&messages, "PROMPT")).unwrap();
+        assert_eq!(out.text, "[{\"id\":\"msg-7\"}]");
+        let usage = out.usage.expect("usage 를 읽어야 한다");
+        assert_eq!((usage.input_tokens, usage.output_tokens, usage.num_turns), (812, 34, 1));
+
+        let seen = server.join().unwrap();
+        assert!(seen.starts_with("POST /v1/chat/completions"));
+        assert!(seen.to_ascii_lowercase().contains("authorization: bearer secret"));
+        let body: Value = serde_json::from_str(seen.split_once("\r\n\n").map(|x| x.1).unwrap_or("")).unwrap();
+        assert_eq!(body["model"], "llama3");
+        assert!(body["messages"][0]["content"].as_str().unwrap().contains("3월 5일까지 제출"));
+    }
+
+    #[test]
+    fn openai_backend_reports_http_errors() {
+        let (endpoint, server) = stub_server("500 Internal Server Error", "{\"error\":\"boom\"}".to_string());
+        let backend = OpenAiCompatBackend {
+            endpoint,
+            model: String::new(),
+            api_key: String::new(),
+        };
+        let err = backend.extract(&req(&[], "P")).unwrap_err();
+        assert!(err.contains("HTTP 500"), "{}", err);
+        server.join().unwrap();
+    }
+}
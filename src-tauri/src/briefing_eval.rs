@@ -0,0 +1,656 @@
+//! 브리핑 에이전트 오프라인 평가.
+//!
+//! 라벨링된 메시지 묶음(JSON 픽스처)을 추출 백엔드 또는 기록된 모델 출력으로 재생하고,
+//! 실제 등록 경로(날짜 보정 → `to_schedule_item` → 중복 판정 → 등록)를 메모리 DB 에서 돌려
+//! 날짜·제목·중복 판정의 정밀도/재현율을 잰다. 프롬프트나 중복 임계값을 바꾼 뒤
+//! 같은 픽스처로 다시 돌려 비교하는 용도다. 기록 출력 재생(`recorded`)은 네트워크 없이 돈다.
+//!
+//! 실행: `cargo run --bin briefing_eval -- eval/briefing [--backend rules] [--json]`
+//!
+//! 픽스처 한 파일 = 사례 하나:
+//! - `today`, `since`: 실행 시점과 기준 id(메시지는 id > since 만 대상).
+//! - `messages`: 검색 DB 한 행과 같은 모양(id, sender, receive_date, text, attachments).
+//! - `existing`: 미리 등록돼 있던 일정. `item` 은 백엔드 출력과 같은 스키마, `body` 는 원문.
+//! - `rules`: (선택) 이 사례에 적용할 브리핑 규칙. 앱의 규칙 편집 화면과 같은 모양.
+//! - `expected`: 정답. 메시지별 (title, date), 중복으로 걸러져야 하면 `duplicate: true`.
+//! - `recorded_output`: 모델 최종 응답 원문. `--record` 로 실제 백엔드 출력을 채울 수 있다.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent;
+use crate::briefing_backend::{
+    BackendKind, BackendSettings, ExtractionBackend, ExtractionRequest, OpenAiCompatBackend, RuleBackend,
+    SourceMessage,
+};
+use crate::briefing_rules::{BriefingRule, RuleSet};
+use crate::briefing_runs::ItemOutcome;
+
+/// 제목을 "맞았다"고 볼 최소 유사도(정규화 bigram Dice).
+const TITLE_MATCH: f64 = 0.6;
+
+#[derive(Deserialize, Debug)]
+pub struct EvalCase {
+    #[serde(default)]
+    pub name: String,
+    pub today: String,
+    #[serde(default)]
+    pub since: i64,
+    pub messages: Vec<FixtureMessage>,
+    #[serde(default)]
+    pub existing: Vec<FixtureExisting>,
+    #[serde(default)]
+    pub rules: Vec<BriefingRule>,
+    #[serde(default)]
+    pub expected: Vec<ExpectedItem>,
+    #[serde(default)]
+    pub recorded_output: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FixtureMessage {
+    pub id: i64,
+    #[serde(default)]
+    pub sender: String,
+    #[serde(default)]
+    pub receive_date: Option<String>,
+    pub text: String,
+    /// 첨부 파일 이름(확장자 규칙 판정용).
+    #[serde(default)]
+    pub attachments: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FixtureExisting {
+    pub item: Value,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub manual: bool,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExpectedItem {
+    pub source_message_id: i64,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub duplicate: bool,
+}
+
+/// 정밀도/재현율 계산용 건수.
+#[derive(Serialize, Default, Clone, Copy, Debug, PartialEq)]
+pub struct Score {
+    pub matched: usize,
+    pub predicted: usize,
+    pub expected: usize,
+}
+
+impl Score {
+    /// 예측이 없으면 틀린 예측도 없으므로 1.
+    pub fn precision(&self) -> f64 {
+        if self.predicted == 0 {
+            1.0
+        } else {
+            self.matched as f64 / self.predicted as f64
+        }
+    }
+
+    /// 정답이 없으면 놓친 것도 없으므로 1.
+    pub fn recall(&self) -> f64 {
+        if self.expected == 0 {
+            1.0
+        } else {
+            self.matched as f64 / self.expected as f64
+        }
+    }
+
+    fn add(&mut self, other: &Score) {
+        self.matched += other.matched;
+        self.predicted += other.predicted;
+        self.expected += other.expected;
+    }
+
+    fn cell(&self) -> String {
+        format!("{:.2}/{:.2}", self.precision(), self.recall())
+    }
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct CaseReport {
+    pub name: String,
+    /// 평가하지 못한 사유(기록 출력 없음, 백엔드 오류 등).
+    pub error: Option<String>,
+    pub dates: Score,
+    pub titles: Score,
+    pub duplicates: Score,
+    /// 틀린 항목 설명(누락·오탐·불일치).
+    pub misses: Vec<String>,
+}
+
+/// 모델 출력을 얻는 방법.
+pub enum OutputSource {
+    /// 픽스처의 `recorded_output` 을 재생한다.
+    Recorded,
+    /// 백엔드를 실제로 실행한다(메시지를 직접 넘기는 백엔드만).
+    Backend(Box<dyn ExtractionBackend>),
+}
+
+/// 사례 하나를 평가한다. 반환: (보고서, 백엔드를 실행했다면 그 원문 출력).
+pub fn evaluate_case(case: &EvalCase, source: &OutputSource, template: &str) -> (CaseReport, Option<String>) {
+    let mut report = CaseReport { name: case.name.clone(), ..Default::default() };
+    match run_case(case, source, template, &mut report) {
+        Ok(raw) => (report, raw),
+        Err(e) => {
+            report.error = Some(e);
+            (report, None)
+        }
+    }
+}
+
+fn run_case(
+    case: &EvalCase,
+    source: &OutputSource,
+    template: &str,
+    report: &mut CaseReport,
+) -> Result<Option<String>, String> {
+    let conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+    crate::db::ensure_schedule_table(&conn)?;
+    for ex in &case.existing {
+        agent::eval_seed(&conn, &ex.item, ex.body.as_deref(), ex.manual, ex.deleted)?;
+    }
+
+    let mut messages: Vec<SourceMessage> = case
+        .messages
+        .iter()
+        .filter(|m| m.id > case.since)
+        .map(|m| SourceMessage {
+            id: m.id,
+            sender: m.sender.clone(),
+            receive_date: m.receive_date.clone(),
+            text: m.text.clone(),
+            attachments: m.attachments.clone(),
+        })
+        .collect();
+    messages.sort_by_key(|m| m.id);
+    let rules = RuleSet::new(case.rules.clone());
+
+    let (output, raw) = match source {
+        OutputSource::Recorded => (
+            case.recorded_output
+                .clone()
+                .ok_or_else(|| "기록된 모델 출력(recorded_output)이 없습니다.".to_string())?,
+            None,
+        ),
+        OutputSource::Backend(backend) => {
+            backend.check()?;
+            let prompt = agent::eval_prompt(template, &conn, &case.today, case.since, &messages, &rules);
+            let inline = agent::eval_backend_messages(&messages, &rules);
+            let cwd = std::env::temp_dir();
+            let out = backend.extract(&ExtractionRequest {
+                prompt: &prompt,
+                today: &case.today,
+                messages: &inline,
+                mcp_config: None,
+                cwd: &cwd,
+            })?;
+            if let Some(e) = out.failure {
+                return Err(e);
+            }
+            (out.text.clone(), Some(out.text))
+        }
+    };
+
+    let registered = agent::eval_register(&conn, &output, &case.today, &messages, &rules)?;
+    score_case(case, &conn, &registered.items, report);
+    Ok(raw)
+}
+
+struct Predicted {
+    message_id: Option<i64>,
+    title: String,
+    date: String,
+}
+
+fn score_case(case: &EvalCase, conn: &Connection, items: &[crate::briefing_runs::RunItem], report: &mut CaseReport) {
+    // 등록(또는 정정 반영)된 일정은 DB 에 실제로 들어간 제목·날짜로 채점한다.
+    let predicted: Vec<Predicted> = items
+        .iter()
+        .filter(|i| matches!(i.outcome, ItemOutcome::Created | ItemOutcome::Superseded))
+        .filter_map(|i| {
+            let id = i.schedule_id.as_deref()?;
+            let (title, start): (String, Option<String>) = conn
+                .query_row("SELECT title, start_date FROM tbl_schedules WHERE id = ?1", [id], |r| {
+                    Ok((r.get(0)?, r.get(1)?))
+                })
+                .ok()?;
+            Some(Predicted {
+                message_id: i.source_message_id,
+                title,
+                date: start.unwrap_or_default().chars().take(10).collect(),
+            })
+        })
+        .collect();
+
+    let expected: Vec<&ExpectedItem> = case.expected.iter().filter(|e| !e.duplicate).collect();
+    report.dates = Score { predicted: predicted.len(), expected: expected.len(), ..Default::default() };
+    report.titles = report.dates;
+
+    // 같은 메시지 안에서 제목이 가장 비슷한 예측과 짝짓는다.
+    let mut used = vec![false; predicted.len()];
+    for e in &expected {
+        let want_title = e.title.as_deref().unwrap_or("");
+        let best = predicted
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| !used[*i] && p.message_id == Some(e.source_message_id))
+            .map(|(i, p)| (i, agent::title_similarity(want_title, &p.title)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((i, sim)) = best else {
+            report.misses.push(format!(
+                "누락: 메시지 {} 「{}」 {}",
+                e.source_message_id,
+                want_title,
+                e.date.as_deref().unwrap_or("?")
+            ));
+            continue;
+        };
+        used[i] = true;
+        let p = &predicted[i];
+        if e.date.as_deref().is_none_or(|d| d == p.date) {
+            report.dates.matched += 1;
+        } else {
+            report.misses.push(format!(
+                "날짜 불일치: 메시지 {} 기대 {} / 결과 {}",
+                e.source_message_id,
+                e.date.as_deref().unwrap_or("?"),
+                p.date
+            ));
+        }
+        if e.title.is_none() || sim >= TITLE_MATCH {
+            report.titles.matched += 1;
+        } else {
+            report.misses.push(format!(
+                "제목 불일치: 메시지 {} 기대 「{}」 / 결과 「{}」",
+                e.source_message_id, want_title, p.title
+            ));
+        }
+    }
+    for (p, _) in predicted.iter().zip(&used).filter(|(_, u)| !**u) {
+        report.misses.push(format!(
+            "오탐: 메시지 {} 「{}」 {}",
+            p.message_id.map(|m| m.to_string()).unwrap_or_else(|| "?".to_string()),
+            p.title,
+            p.date
+        ));
+    }
+
+    // 중복: 정답이 "중복"인 메시지에서 중복 판정이 나왔는지.
+    let mut dup_pred: Vec<Option<i64>> = items
+        .iter()
+        .filter(|i| i.outcome == ItemOutcome::Duplicate)
+        .map(|i| i.source_message_id)
+        .collect();
+    report.duplicates = Score {
+        predicted: dup_pred.len(),
+        expected: case.expected.iter().filter(|e| e.duplicate).count(),
+        ..Default::default()
+    };
+    for e in case.expected.iter().filter(|e| e.duplicate) {
+        match dup_pred.iter().position(|m| *m == Some(e.source_message_id)) {
+            Some(pos) => {
+                dup_pred.remove(pos);
+                report.duplicates.matched += 1;
+            }
+            None => report.misses.push(format!("중복 미탐지: 메시지 {}", e.source_message_id)),
+        }
+    }
+    for m in dup_pred {
+        report.misses.push(format!(
+            "중복 오판: 메시지 {}",
+            m.map(|m| m.to_string()).unwrap_or_else(|| "?".to_string())
+        ));
+    }
+}
+
+// ─── 픽스처 입출력 ────────────────────────────────────────────────────────────
+
+/// 경로 목록(파일 또는 디렉터리)에서 `.json` 픽스처를 이름순으로 모은다.
+pub fn collect_fixtures(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for p in paths {
+        if p.is_dir() {
+            let entries = std::fs::read_dir(p).map_err(|e| format!("{} 읽기 실패: {}", p.display(), e))?;
+            let mut found: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|f| f.extension().is_some_and(|x| x == "json"))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else if p.is_file() {
+            files.push(p.clone());
+        } else {
+            return Err(format!("픽스처 경로를 찾을 수 없습니다: {}", p.display()));
+        }
+    }
+    Ok(files)
+}
+
+pub fn load_case(path: &Path) -> Result<EvalCase, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{} 읽기 실패: {}", path.display(), e))?;
+    let mut case: EvalCase =
+        serde_json::from_str(&text).map_err(|e| format!("{} 파싱 실패: {}", path.display(), e))?;
+    if case.name.is_empty() {
+        case.name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    }
+    Ok(case)
+}
+
+/// 픽스처 파일의 `recorded_output` 만 바꿔 다시 쓴다(다른 필드는 그대로).
+fn record_output(path: &Path, output: &str) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut value: Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+    let obj = value.as_object_mut().ok_or_else(|| "픽스처 최상위가 객체가 아닙니다.".to_string())?;
+    obj.insert("recorded_output".to_string(), Value::String(output.to_string()));
+    let pretty = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    std::fs::write(path, pretty + "\n").map_err(|e| format!("{} 쓰기 실패: {}", path.display(), e))
+}
+
+// ─── CLI ─────────────────────────────────────────────────────────────────────
+
+const USAGE: &str = "사용법: briefing_eval <픽스처 파일|디렉터리>... [옵션]
+  --backend recorded|rules|openai_compat   출력 공급원(기본 recorded: 기록 출력 재생)
+  --endpoint URL --model NAME --api-key KEY openai_compat 설정
+  --prompt FILE                             프롬프트 템플릿(기본: 번들 briefing_prompt.md)
+  --record                                  실행한 백엔드 출력을 픽스처 recorded_output 에 저장
+  --json                                    결과를 JSON 으로 출력";
+
+struct CliOptions {
+    paths: Vec<PathBuf>,
+    backend: String,
+    settings: BackendSettings,
+    prompt: Option<PathBuf>,
+    record: bool,
+    json: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut opts = CliOptions {
+        paths: Vec::new(),
+        backend: "recorded".to_string(),
+        settings: BackendSettings { kind: BackendKind::OpenaiCompat, ..Default::default() },
+        prompt: None,
+        record: false,
+        json: false,
+    };
+    let mut it = args.iter();
+    while let Some(a) = it.next() {
+        let mut value = |name: &str| it.next().cloned().ok_or_else(|| format!("{} 뒤에 값이 필요합니다.", name));
+        match a.as_str() {
+            "--backend" => opts.backend = value(a)?,
+            "--endpoint" => opts.settings.endpoint = value(a)?,
+            "--model" => opts.settings.model = value(a)?,
+            "--api-key" => opts.settings.api_key = value(a)?,
+            "--prompt" => opts.prompt = Some(PathBuf::from(value(a)?)),
+            "--record" => opts.record = true,
+            "--json" => opts.json = true,
+            "-h" | "--help" => return Err(String::new()),
+            s if s.starts_with("--") => return Err(format!("알 수 없는 옵션: {}", s)),
+            s => opts.paths.push(PathBuf::from(s)),
+        }
+    }
+    if opts.paths.is_empty() {
+        return Err("픽스처 경로가 필요합니다.".to_string());
+    }
+    Ok(opts)
+}
+
+fn output_source(opts: &CliOptions) -> Result<OutputSource, String> {
+    match opts.backend.as_str() {
+        "recorded" => Ok(OutputSource::Recorded),
+        "rules" => Ok(OutputSource::Backend(Box::new(RuleBackend))),
+        "openai_compat" => Ok(OutputSource::Backend(Box::new(OpenAiCompatBackend::from_settings(&opts.settings)))),
+        "claude_cli" => Err("claude_cli 는 MCP 도구로 앱 DB 를 읽어 오프라인 재생이 불가합니다. 기록 출력(recorded)을 쓰세요.".to_string()),
+        other => Err(format!("알 수 없는 백엔드: {}", other)),
+    }
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    backend: &'a str,
+    cases: &'a [CaseReport],
+    dates: Score,
+    titles: Score,
+    duplicates: Score,
+}
+
+/// `briefing_eval` 바이너리 진입점. 반환: 종료 코드(0 정상, 1 사용법 오류, 2 평가 실패 사례 있음).
+pub fn run_cli(args: &[String]) -> i32 {
+    let opts = match parse_args(args) {
+        Ok(o) => o,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}", e);
+            }
+            eprintln!("{}", USAGE);
+            return 1;
+        }
+    };
+    let source = match output_source(&opts) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+    let template = match &opts.prompt {
+        Some(p) => match std::fs::read_to_string(p) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("프롬프트 읽기 실패({}): {}", p.display(), e);
+                return 1;
+            }
+        },
+        None => agent::BUNDLED_PROMPT.to_string(),
+    };
+    let files = match collect_fixtures(&opts.paths) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    let mut reports = Vec::new();
+    for file in &files {
+        let case = match load_case(file) {
+            Ok(c) => c,
+            Err(e) => {
+                reports.push(CaseReport { name: file.display().to_string(), error: Some(e), ..Default::default() });
+                continue;
+            }
+        };
+        let (report, raw) = evaluate_case(&case, &source, &template);
+        if let (true, Some(raw)) = (opts.record, raw) {
+            if let Err(e) = record_output(file, &raw) {
+                eprintln!("출력 기록 실패({}): {}", file.display(), e);
+            }
+        }
+        reports.push(report);
+    }
+
+    let mut summary = Summary {
+        backend: &opts.backend,
+        cases: &reports,
+        dates: Score::default(),
+        titles: Score::default(),
+        duplicates: Score::default(),
+    };
+    for r in reports.iter().filter(|r| r.error.is_none()) {
+        summary.dates.add(&r.dates);
+        summary.titles.add(&r.titles);
+        summary.duplicates.add(&r.duplicates);
+    }
+
+    if opts.json {
+        match serde_json::to_string_pretty(&summary) {
+            Ok(s) => println!("{}", s),
+            Err(e) => eprintln!("{}", e),
+        }
+    } else {
+        print!("{}", format_table(&summary));
+    }
+
+    if reports.iter().any(|r| r.error.is_some()) {
+        2
+    } else {
+        0
+    }
+}
+
+fn format_table(summary: &Summary) -> String {
+    let mut out = format!(
+        "백엔드: {}\n{:<28} {:>11} {:>11} {:>11}\n",
+        summary.backend, "사례", "날짜 P/R", "제목 P/R", "중복 P/R"
+    );
+    for r in summary.cases {
+        match &r.error {
+            Some(e) => out.push_str(&format!("{:<28} 평가 실패: {}\n", r.name, e)),
+            None => {
+                out.push_str(&format!(
+                    "{:<28} {:>11} {:>11} {:>11}\n",
+                    r.name,
+                    r.dates.cell(),
+                    r.titles.cell(),
+                    r.duplicates.cell()
+                ));
+                for m in &r.misses {
+                    out.push_str(&format!("    {}\n", m));
+                }
+            }
+        }
+    }
+    out.push_str(&format!(
+        "{:<28} {:>11} {:>11} {:>11}\n",
+        "합계",
+        summary.dates.cell(),
+        summary.titles.cell(),
+        summary.duplicates.cell()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("eval").join("briefing")
+    }
+
+    fn case_json(recorded: &str) -> EvalCase {
+        serde_json::from_value(serde_json::json!({
+            "name": "inline",
+            "today": "2026-07-01",
+            "messages": [
+                {"id": 10, "sender": "교무부", "receive_date": "2026-07-01", "text": "7월 10일까지 체험학습 신청서 제출"},
+                {"id": 11, "sender": "교무부", "receive_date": "2026-07-01", "text": "[재공지] 7월 10일까지 체험학습 신청서 제출"}
+            ],
+            "expected": [
+                {"source_message_id": 10, "title": "체험학습 신청서 제출", "date": "2026-07-10"},
+                {"source_message_id": 11, "duplicate": true}
+            ],
+            "recorded_output": recorded
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn recorded_output_is_scored_against_expectations() {
+        let case = case_json(
+            r#"[{"source_message_id":10,"title":"체험학습 신청서 제출","date":"2026-07-10"},
+                {"source_message_id":11,"title":"체험학습 신청서 제출","date":"2026-07-10"}]"#,
+        );
+        let (r, raw) = evaluate_case(&case, &OutputSource::Recorded, agent::BUNDLED_PROMPT);
+        assert!(r.error.is_none(), "{:?}", r.error);
+        assert!(raw.is_none());
+        assert_eq!(r.dates, Score { matched: 1, predicted: 1, expected: 1 });
+        assert_eq!(r.titles.matched, 1);
+        assert_eq!(r.duplicates, Score { matched: 1, predicted: 1, expected: 1 });
+        assert!(r.misses.is_empty(), "{:?}", r.misses);
+    }
+
+    #[test]
+    fn wrong_date_and_missed_duplicate_are_reported() {
+        // 재공지(11)에 다른 날짜를 붙이면 내용 중복으로 걸러지지 않고 오탐이 된다.
+        let case = case_json(
+            r#"[{"source_message_id":10,"title":"체험학습 신청서 제출","date":"2026-07-09"},
+                {"source_message_id":11,"title":"체험학습 신청서 제출","date":"2026-07-20"}]"#,
+        );
+        let (r, _) = evaluate_case(&case, &OutputSource::Recorded, agent::BUNDLED_PROMPT);
+        assert_eq!(r.dates, Score { matched: 0, predicted: 2, expected: 1 });
+        assert_eq!(r.duplicates, Score { matched: 0, predicted: 0, expected: 1 });
+        assert!(r.misses.iter().any(|m| m.starts_with("날짜 불일치: 메시지 10")));
+        assert!(r.misses.iter().any(|m| m.starts_with("오탐: 메시지 11")));
+        assert!(r.misses.iter().any(|m| m == "중복 미탐지: 메시지 11"));
+    }
+
+    #[test]
+    fn missing_recording_is_an_error_not_a_zero_score() {
+        let mut case = case_json("[]");
+        case.recorded_output = None;
+        let (r, _) = evaluate_case(&case, &OutputSource::Recorded, agent::BUNDLED_PROMPT);
+        assert!(r.error.is_some());
+    }
+
+    #[test]
+    fn bundled_fixtures_replay_offline() {
+        let s = |matched, predicted, expected| Score { matched, predicted, expected };
+        // (사례, 날짜, 제목, 중복, 틀린 항목 수). 등록·규칙 엔진이 바뀌어 점수가 달라지면 여기서 걸린다.
+        // 픽스처를 추가하면 기대값도 함께 적는다.
+        let expected = [
+            ("correction_move", s(1, 1, 1), s(1, 1, 1), s(0, 0, 0), 0),
+            // 기록 출력의 제목 하나(「학부모 총회 가정통신문 배부」)가 기대보다 길어 불일치로 남는다.
+            ("deadlines_basic", s(3, 3, 3), s(2, 3, 3), s(0, 0, 0), 1),
+            ("resend_dedup", s(1, 1, 1), s(1, 1, 1), s(1, 1, 1), 0),
+            ("rules_filter", s(2, 2, 2), s(2, 2, 2), s(0, 0, 0), 0),
+        ];
+
+        let files = collect_fixtures(&[fixture_dir()]).unwrap();
+        assert_eq!(files.len(), expected.len());
+        let (mut dates, mut titles, mut duplicates) = (Score::default(), Score::default(), Score::default());
+        for f in files {
+            let case = load_case(&f).unwrap();
+            let (r, _) = evaluate_case(&case, &OutputSource::Recorded, agent::BUNDLED_PROMPT);
+            assert!(r.error.is_none(), "{}: {:?}", r.name, r.error);
+            let &(_, d, t, dup, misses) = expected
+                .iter()
+                .find(|e| e.0 == r.name)
+                .unwrap_or_else(|| panic!("기대값이 없는 픽스처: {}", r.name));
+            assert_eq!((r.dates, r.titles, r.duplicates), (d, t, dup), "{}", r.name);
+            assert_eq!(r.misses.len(), misses, "{}: {:?}", r.name, r.misses);
+            dates.add(&r.dates);
+            titles.add(&r.titles);
+            duplicates.add(&r.duplicates);
+        }
+        assert_eq!((dates.precision(), dates.recall()), (1.0, 1.0));
+        assert_eq!((titles.precision(), titles.recall()), (6.0 / 7.0, 6.0 / 7.0));
+        assert_eq!((duplicates.precision(), duplicates.recall()), (1.0, 1.0));
+    }
+
+    #[test]
+    fn score_ratios_handle_empty_sides() {
+        let s = Score { matched: 0, predicted: 0, expected: 0 };
+        assert_eq!((s.precision(), s.recall()), (1.0, 1.0));
+        let s = Score { matched: 1, predicted: 2, expected: 4 };
+        assert_eq!((s.precision(), s.recall()), (0.5, 0.25));
+    }
+}
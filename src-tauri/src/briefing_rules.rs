@@ -0,0 +1,591 @@
+//! 브리핑 에이전트 사용자 규칙.
+//!
+//! 발신자·키워드·정규식·첨부 확장자 조건으로 메시지/추출 항목을 골라
+//! 제외(skip)·강제 추출(force)·색상·기본 시각·제목 접두어를 적용한다.
+//! 규칙은 `hypercool.db` 의 `tbl_briefing_rules` 에 두고 명령으로 편집한다.
+//!
+//! 적용 시점:
+//! - 추출 전: 메시지를 직접 넘기는 백엔드에는 제외 규칙에 걸린 메시지를 넘기지 않는다.
+//!   프롬프트에도 규칙 요약을 넣어 도구로 읽는 백엔드(claude CLI)도 따르게 한다.
+//! - 추출 후: 항목마다 다시 판정해 제외하거나 색상·시각·접두어를 입힌다.
+//!   강제 추출 규칙에 걸렸는데 항목이 나오지 않은 메시지는 앱이 항목을 만든다.
+//!
+//! 조건은 모두 AND 이며, 한 조건 안의 쉼표 구분 값은 OR 이다. 규칙은 `position` 순으로
+//! 보고, 종류(제외/강제, 색상, 시각, 접두어)마다 처음 걸린 규칙 하나만 적용한다.
+
+use regex::Regex;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::db::open_app_db;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    /// 할 일로 만들지 않는다.
+    Skip,
+    /// 항상 할 일로 만든다.
+    Force,
+    /// 일정 색상(`value` = `#RRGGBB`).
+    Color,
+    /// 시각이 없을 때 쓸 기본 시각(`value` = `HH:MM`).
+    DefaultTime,
+    /// 제목 앞에 붙일 접두어(`value`).
+    TitlePrefix,
+}
+
+impl RuleAction {
+    fn key(self) -> &'static str {
+        match self {
+            RuleAction::Skip => "skip",
+            RuleAction::Force => "force",
+            RuleAction::Color => "color",
+            RuleAction::DefaultTime => "default_time",
+            RuleAction::TitlePrefix => "title_prefix",
+        }
+    }
+
+    fn from_key(s: &str) -> Option<Self> {
+        [
+            RuleAction::Skip,
+            RuleAction::Force,
+            RuleAction::Color,
+            RuleAction::DefaultTime,
+            RuleAction::TitlePrefix,
+        ]
+        .into_iter()
+        .find(|a| a.key() == s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BriefingRule {
+    /// 0 이면 새 규칙.
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 평가 순서(작을수록 먼저).
+    #[serde(default)]
+    pub position: i64,
+    /// 발신자 부분 일치(쉼표로 여러 개).
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// 본문 키워드(쉼표로 여러 개, 하나라도 있으면).
+    #[serde(default)]
+    pub keywords: Option<String>,
+    /// 본문 정규식.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// 첨부 확장자(점 없이, 쉼표로 여러 개).
+    #[serde(default)]
+    pub attachment_ext: Option<String>,
+    pub action: RuleAction,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 규칙을 판정할 대상(메시지 또는 추출 항목).
+pub struct RuleSubject<'a> {
+    pub sender: &'a str,
+    pub text: &'a str,
+    pub attachments: &'a [String],
+}
+
+/// 제외/강제 판정 결과.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gate {
+    /// 규칙 없음 — 평소대로.
+    Default,
+    /// 제외(규칙 이름).
+    Skip(String),
+    /// 강제 추출(규칙 이름).
+    Force(String),
+}
+
+/// 항목에 입힐 값.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Adjustments {
+    pub color: Option<String>,
+    pub default_time: Option<String>,
+    pub title_prefix: Option<String>,
+}
+
+struct Compiled {
+    rule: BriefingRule,
+    regex: Option<Regex>,
+}
+
+/// 활성 규칙 묶음(평가 순서대로).
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<Compiled>,
+}
+
+fn split_list(s: Option<&str>) -> Vec<String> {
+    s.unwrap_or("")
+        .split(',')
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+impl Compiled {
+    fn matches(&self, s: &RuleSubject) -> bool {
+        let r = &self.rule;
+        let senders = split_list(r.sender.as_deref());
+        if !senders.is_empty() {
+            let sender = s.sender.to_lowercase();
+            if !senders.iter().any(|p| sender.contains(p)) {
+                return false;
+            }
+        }
+        let keywords = split_list(r.keywords.as_deref());
+        if !keywords.is_empty() {
+            let text = s.text.to_lowercase();
+            if !keywords.iter().any(|k| text.contains(k)) {
+                return false;
+            }
+        }
+        if let Some(re) = &self.regex {
+            if !re.is_match(s.text) {
+                return false;
+            }
+        }
+        let exts = split_list(r.attachment_ext.as_deref());
+        if !exts.is_empty() {
+            let hit = s.attachments.iter().any(|f| {
+                let f = f.to_lowercase();
+                exts.iter().any(|e| f.ends_with(&format!(".{}", e.trim_start_matches('.'))))
+            });
+            if !hit {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl RuleSet {
+    /// 활성 규칙만 `position` 순으로 담는다. 정규식이 잘못된 규칙은 건너뛴다.
+    pub fn new(mut rules: Vec<BriefingRule>) -> Self {
+        rules.sort_by_key(|r| (r.position, r.id));
+        let rules = rules
+            .into_iter()
+            .filter(|r| r.enabled && has_condition(r))
+            .filter_map(|rule| {
+                let regex = match rule.pattern.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+                    Some(p) => Some(Regex::new(p).ok()?),
+                    None => None,
+                };
+                Some(Compiled { rule, regex })
+            })
+            .collect();
+        RuleSet { rules }
+    }
+
+    /// DB 의 규칙을 읽는다. 읽을 수 없으면 빈 규칙(기존 동작).
+    pub fn load(conn: &Connection) -> Self {
+        list_rules(conn).map(RuleSet::new).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn has_force(&self) -> bool {
+        self.rules.iter().any(|c| c.rule.action == RuleAction::Force)
+    }
+
+    /// 처음 걸린 제외/강제 규칙.
+    pub fn gate(&self, s: &RuleSubject) -> Gate {
+        for c in &self.rules {
+            match c.rule.action {
+                RuleAction::Skip if c.matches(s) => return Gate::Skip(c.rule.name.clone()),
+                RuleAction::Force if c.matches(s) => return Gate::Force(c.rule.name.clone()),
+                _ => {}
+            }
+        }
+        Gate::Default
+    }
+
+    /// 종류별로 처음 걸린 규칙의 값.
+    pub fn adjustments(&self, s: &RuleSubject) -> Adjustments {
+        let mut adj = Adjustments::default();
+        for c in &self.rules {
+            let slot = match c.rule.action {
+                RuleAction::Color => &mut adj.color,
+                RuleAction::DefaultTime => &mut adj.default_time,
+                RuleAction::TitlePrefix => &mut adj.title_prefix,
+                RuleAction::Skip | RuleAction::Force => continue,
+            };
+            if slot.is_none() && c.matches(s) {
+                *slot = c.rule.value.clone().filter(|v| !v.trim().is_empty());
+            }
+        }
+        adj
+    }
+
+    /// 프롬프트에 넣을 규칙 요약(제외·강제만 — 나머지는 앱이 등록 시 적용).
+    pub fn prompt_section(&self) -> String {
+        let lines: Vec<String> = self
+            .rules
+            .iter()
+            .filter_map(|c| {
+                let verb = match c.rule.action {
+                    RuleAction::Skip => "할 일로 만들지 않는다",
+                    RuleAction::Force => "날짜가 없더라도 반드시 할 일로 만든다",
+                    _ => return None,
+                };
+                Some(format!("- {} → {}", describe_conditions(&c.rule), verb))
+            })
+            .collect();
+        if lines.is_empty() {
+            "(없음)".to_string()
+        } else {
+            lines.join("\n")
+        }
+    }
+}
+
+fn has_condition(r: &BriefingRule) -> bool {
+    [&r.sender, &r.keywords, &r.pattern, &r.attachment_ext]
+        .iter()
+        .any(|c| c.as_deref().is_some_and(|v| !v.trim().is_empty()))
+}
+
+fn describe_conditions(r: &BriefingRule) -> String {
+    let mut parts = Vec::new();
+    if let Some(v) = r.sender.as_deref().filter(|v| !v.trim().is_empty()) {
+        parts.push(format!("발신자에 「{}」 포함", v.trim()));
+    }
+    if let Some(v) = r.keywords.as_deref().filter(|v| !v.trim().is_empty()) {
+        parts.push(format!("본문에 「{}」 중 하나 포함", v.trim()));
+    }
+    if let Some(v) = r.pattern.as_deref().filter(|v| !v.trim().is_empty()) {
+        parts.push(format!("본문이 정규식 /{}/ 와 일치", v.trim()));
+    }
+    if let Some(v) = r.attachment_ext.as_deref().filter(|v| !v.trim().is_empty()) {
+        parts.push(format!("첨부 확장자 {}", v.trim()));
+    }
+    parts.join(" 그리고 ")
+}
+
+/// 저장 전 검증.
+pub fn validate_rule(r: &BriefingRule) -> Result<(), String> {
+    if r.name.trim().is_empty() {
+        return Err("규칙 이름이 필요합니다.".to_string());
+    }
+    if !has_condition(r) {
+        return Err("발신자·키워드·정규식·첨부 확장자 중 하나 이상의 조건이 필요합니다.".to_string());
+    }
+    if let Some(p) = r.pattern.as_deref().filter(|p| !p.trim().is_empty()) {
+        Regex::new(p.trim()).map_err(|e| format!("정규식 오류: {}", e))?;
+    }
+    let value = r.value.as_deref().map(str::trim).unwrap_or("");
+    match r.action {
+        RuleAction::Skip | RuleAction::Force => Ok(()),
+        RuleAction::Color => {
+            let ok = value.len() == 7
+                && value.starts_with('#')
+                && value[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if ok {
+                Ok(())
+            } else {
+                Err("색상은 #RRGGBB 형식이어야 합니다.".to_string())
+            }
+        }
+        RuleAction::DefaultTime => chrono::NaiveTime::parse_from_str(value, "%H:%M")
+            .map(|_| ())
+            .map_err(|_| "기본 시각은 HH:MM 형식이어야 합니다.".to_string()),
+        RuleAction::TitlePrefix => {
+            if value.is_empty() {
+                Err("제목 접두어가 비어 있습니다.".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+// ─── 저장소 ───────────────────────────────────────────────────────────────────
+
+pub fn ensure_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_briefing_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            enabled BOOLEAN NOT NULL DEFAULT 1,
+            position INTEGER NOT NULL DEFAULT 0,
+            sender TEXT,
+            keywords TEXT,
+            pattern TEXT,
+            attachment_ext TEXT,
+            action TEXT NOT NULL,
+            value TEXT,
+            updated_at TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("브리핑 규칙 테이블 생성 실패: {}", e))
+}
+
+fn none_if_blank(v: &Option<String>) -> Option<String> {
+    v.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+pub fn list_rules(conn: &Connection) -> Result<Vec<BriefingRule>, String> {
+    ensure_table(conn)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, enabled, position, sender, keywords, pattern, attachment_ext, action, value
+             FROM tbl_briefing_rules ORDER BY position, id",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |r| {
+            let action: String = r.get(8)?;
+            Ok((
+                BriefingRule {
+                    id: r.get(0)?,
+                    name: r.get(1)?,
+                    enabled: r.get(2)?,
+                    position: r.get(3)?,
+                    sender: r.get(4)?,
+                    keywords: r.get(5)?,
+                    pattern: r.get(6)?,
+                    attachment_ext: r.get(7)?,
+                    action: RuleAction::Skip,
+                    value: r.get(9)?,
+                },
+                action,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    // 알 수 없는 동작(새 버전에서 만든 규칙 등)은 목록에서 뺀다.
+    Ok(rows
+        .flatten()
+        .filter_map(|(mut rule, action)| {
+            rule.action = RuleAction::from_key(&action)?;
+            Some(rule)
+        })
+        .collect())
+}
+
+/// 규칙을 저장한다(id 0 이면 추가). 반환: 저장된 규칙.
+pub fn save_rule(conn: &Connection, rule: &BriefingRule) -> Result<BriefingRule, String> {
+    validate_rule(rule)?;
+    ensure_table(conn)?;
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let mut saved = BriefingRule {
+        name: rule.name.trim().to_string(),
+        sender: none_if_blank(&rule.sender),
+        keywords: none_if_blank(&rule.keywords),
+        pattern: none_if_blank(&rule.pattern),
+        attachment_ext: none_if_blank(&rule.attachment_ext),
+        value: none_if_blank(&rule.value),
+        ..rule.clone()
+    };
+    let sql = if saved.id > 0 {
+        "UPDATE tbl_briefing_rules
+         SET name = ?1, enabled = ?2, position = ?3, sender = ?4, keywords = ?5, pattern = ?6,
+             attachment_ext = ?7, action = ?8, value = ?9, updated_at = ?10
+         WHERE id = ?11"
+    } else {
+        // 새 규칙은 id 가 0 이라 NULLIF 로 자동 증가 값을 받는다.
+        "INSERT INTO tbl_briefing_rules
+            (id, name, enabled, position, sender, keywords, pattern, attachment_ext, action, value, updated_at)
+         VALUES (NULLIF(?11, 0), ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+    };
+    let n = conn
+        .execute(
+            sql,
+            rusqlite::params![
+                saved.name,
+                saved.enabled,
+                saved.position,
+                saved.sender,
+                saved.keywords,
+                saved.pattern,
+                saved.attachment_ext,
+                saved.action.key(),
+                saved.value,
+                now,
+                saved.id.max(0),
+            ],
+        )
+        .map_err(|e| format!("브리핑 규칙 저장 실패: {}", e))?;
+    if n == 0 {
+        return Err(format!("브리핑 규칙을 찾을 수 없습니다: {}", saved.id));
+    }
+    if saved.id <= 0 {
+        saved.id = conn.last_insert_rowid();
+    }
+    Ok(saved)
+}
+
+pub fn delete_rule(conn: &Connection, id: i64) -> Result<(), String> {
+    ensure_table(conn)?;
+    let exists = conn
+        .query_row("SELECT 1 FROM tbl_briefing_rules WHERE id = ?1", [id], |_| Ok(()))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if exists.is_none() {
+        return Err(format!("브리핑 규칙을 찾을 수 없습니다: {}", id));
+    }
+    conn.execute("DELETE FROM tbl_briefing_rules WHERE id = ?1", [id])
+        .map_err(|e| format!("브리핑 규칙 삭제 실패: {}", e))?;
+    Ok(())
+}
+
+/// 에이전트 실행용: 앱 DB 의 규칙을 읽는다(실패 시 빈 규칙).
+pub fn load_rules(app: &AppHandle) -> RuleSet {
+    open_app_db(app).map(|c| RuleSet::load(&c)).unwrap_or_default()
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_briefing_rules(app: AppHandle) -> Result<Vec<BriefingRule>, String> {
+    list_rules(&open_app_db(&app)?)
+}
+
+#[tauri::command]
+pub fn save_briefing_rule(app: AppHandle, rule: BriefingRule) -> Result<BriefingRule, String> {
+    save_rule(&open_app_db(&app)?, &rule)
+}
+
+#[tauri::command]
+pub fn delete_briefing_rule(app: AppHandle, id: i64) -> Result<(), String> {
+    delete_rule(&open_app_db(&app)?, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, action: RuleAction) -> BriefingRule {
+        BriefingRule {
+            id: 0,
+            name: name.to_string(),
+            enabled: true,
+            position: 0,
+            sender: None,
+            keywords: None,
+            pattern: None,
+            attachment_ext: None,
+            action,
+            value: None,
+        }
+    }
+
+    fn subject<'a>(sender: &'a str, text: &'a str, attachments: &'a [String]) -> RuleSubject<'a> {
+        RuleSubject { sender, text, attachments }
+    }
+
+    #[test]
+    fn gate_follows_position_order() {
+        let skip = BriefingRule { sender: Some("행정실, 급식".into()), position: 2, ..rule("대량 공지", RuleAction::Skip) };
+        let force = BriefingRule { keywords: Some("제출".into()), position: 1, ..rule("제출 건", RuleAction::Force) };
+        let set = RuleSet::new(vec![skip, force]);
+
+        assert_eq!(set.gate(&subject("급식실", "오늘 메뉴 안내", &[])), Gate::Skip("대량 공지".into()));
+        // 두 규칙에 모두 걸리면 position 이 앞선 규칙이 이긴다.
+        assert_eq!(set.gate(&subject("행정실", "계약서 제출 바랍니다", &[])), Gate::Force("제출 건".into()));
+        assert_eq!(set.gate(&subject("교무부", "회의 안내", &[])), Gate::Default);
+    }
+
+    #[test]
+    fn conditions_are_anded_and_values_ored() {
+        let r = BriefingRule {
+            sender: Some("교무부장".into()),
+            pattern: Some(r"\d+학년".into()),
+            attachment_ext: Some("hwp,HWPX".into()),
+            ..rule("교무부장 학년 공문", RuleAction::Force)
+        };
+        let set = RuleSet::new(vec![r]);
+        let hwpx = vec!["C:\\files\\계획.HWPX".to_string()];
+        let pdf = vec!["계획.pdf".to_string()];
+        assert!(matches!(set.gate(&subject("교무부장 김OO", "2학년 계획 검토", &hwpx)), Gate::Force(_)));
+        assert_eq!(set.gate(&subject("교무부장 김OO", "2학년 계획 검토", &pdf)), Gate::Default);
+        assert_eq!(set.gate(&subject("교무부장 김OO", "계획 검토", &hwpx)), Gate::Default);
+    }
+
+    #[test]
+    fn adjustments_take_first_match_per_kind() {
+        let rules = vec![
+            BriefingRule { sender: Some("연구부".into()), value: Some("#10B981".into()), ..rule("연구부 색", RuleAction::Color) },
+            BriefingRule { keywords: Some("회의".into()), value: Some("15:30".into()), ..rule("회의 시각", RuleAction::DefaultTime) },
+            BriefingRule { sender: Some("연구부".into()), value: Some("[연구] ".into()), ..rule("접두어", RuleAction::TitlePrefix) },
+            BriefingRule { keywords: Some("회의".into()), value: Some("#000000".into()), position: 5, ..rule("늦은 색", RuleAction::Color) },
+        ];
+        let set = RuleSet::new(rules);
+        let adj = set.adjustments(&subject("연구부", "협의회 회의", &[]));
+        assert_eq!(adj.color.as_deref(), Some("#10B981"));
+        assert_eq!(adj.default_time.as_deref(), Some("15:30"));
+        assert_eq!(adj.title_prefix.as_deref(), Some("[연구] "));
+        assert_eq!(set.adjustments(&subject("교무부", "공지", &[])), Adjustments::default());
+    }
+
+    #[test]
+    fn disabled_and_conditionless_rules_are_ignored() {
+        let off = BriefingRule { enabled: false, sender: Some("행정실".into()), ..rule("꺼짐", RuleAction::Skip) };
+        let empty = rule("조건 없음", RuleAction::Skip);
+        let set = RuleSet::new(vec![off, empty]);
+        assert!(set.is_empty());
+        assert_eq!(set.prompt_section(), "(없음)");
+    }
+
+    #[test]
+    fn validate_rejects_bad_values() {
+        let base = BriefingRule { sender: Some("행정실".into()), ..rule("r", RuleAction::Color) };
+        assert!(validate_rule(&BriefingRule { value: Some("purple".into()), ..base.clone() }).is_err());
+        assert!(validate_rule(&BriefingRule { value: Some("#8b5cf6".into()), ..base.clone() }).is_ok());
+        let time = BriefingRule { action: RuleAction::DefaultTime, value: Some("25:00".into()), ..base.clone() };
+        assert!(validate_rule(&time).is_err());
+        let re = BriefingRule { action: RuleAction::Skip, pattern: Some("(".into()), ..base.clone() };
+        assert!(validate_rule(&re).is_err());
+        assert!(validate_rule(&rule("조건 없음", RuleAction::Skip)).is_err());
+    }
+
+    #[test]
+    fn rules_roundtrip_through_db() {
+        let conn = Connection::open_in_memory().unwrap();
+        let saved = save_rule(
+            &conn,
+            &BriefingRule { sender: Some(" 급식 ".into()), keywords: Some("  ".into()), ..rule("급식 제외", RuleAction::Skip) },
+        )
+        .unwrap();
+        assert!(saved.id > 0);
+        assert_eq!(saved.sender.as_deref(), Some("급식"));
+        assert_eq!(saved.keywords, None);
+
+        let updated = save_rule(&conn, &BriefingRule { enabled: false, ..saved.clone() }).unwrap();
+        assert_eq!(list_rules(&conn).unwrap(), vec![updated]);
+        assert!(RuleSet::load(&conn).is_empty());
+
+        delete_rule(&conn, saved.id).unwrap();
+        assert!(list_rules(&conn).unwrap().is_empty());
+        assert!(delete_rule(&conn, saved.id).is_err());
+        assert!(save_rule(&conn, &BriefingRule { id: 99, ..saved }).is_err());
+    }
+
+    #[test]
+    fn prompt_section_describes_gate_rules_only() {
+        let set = RuleSet::new(vec![
+            BriefingRule { sender: Some("급식".into()), ..rule("급식", RuleAction::Skip) },
+            BriefingRule { keywords: Some("제출".into()), ..rule("제출", RuleAction::Force) },
+            BriefingRule { sender: Some("연구부".into()), value: Some("#10B981".into()), ..rule("색", RuleAction::Color) },
+        ]);
+        let text = set.prompt_section();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.contains("발신자에 「급식」 포함 → 할 일로 만들지 않는다"));
+        assert!(text.contains("본문에 「제출」 중 하나 포함 → 날짜가 없더라도 반드시 할 일로 만든다"));
+    }
+}
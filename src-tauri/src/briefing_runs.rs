@@ -0,0 +1,526 @@
+//! 브리핑 에이전트 실행 기록.
+//!
+//! 실행마다 시작·종료 시각, 대상 메시지 id 범위, 백엔드, 모델 원문 출력, 파싱된 항목과
+//! 항목별 결과(등록·중복·제외와 그 근거)를 `hypercool.db` 에 남긴다.
+//! "메시지 12345 는 왜 할 일이 되지 않았나?"를 실행 기록으로 거슬러 확인하기 위한 것이다.
+//! 기록은 best-effort 다 — 기록 실패가 실행 자체를 막지 않는다.
+
+use std::time::Instant;
+
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::briefing_usage::{self, RunUsage};
+use crate::db::open_app_db;
+
+/// 보관할 최근 실행 수. 오래된 실행은 항목과 함께 지운다.
+const KEEP_RUNS: i64 = 300;
+/// 저장하는 모델 원문 출력의 최대 바이트 수.
+const RAW_OUTPUT_MAX_BYTES: usize = 64 * 1024;
+
+/// 추출 항목 하나의 처리 결과.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemOutcome {
+    /// 새 일정으로 등록했다.
+    Created,
+    /// 정정 메시지로 기존 AI 일정의 날짜·시각을 바꿨다.
+    Superseded,
+    /// 사용자가 고친 일정의 정정이라 검토 대기로 남겼다.
+    Flagged,
+    /// 이미 있는 일정과 같아 건너뛰었다.
+    Duplicate,
+    /// 검증에 실패해 건너뛰었다(날짜 없음·과거 날짜·저장 실패 등).
+    Invalid,
+    /// 브리핑 규칙에 걸려 제외했다.
+    Filtered,
+}
+
+impl ItemOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemOutcome::Created => "created",
+            ItemOutcome::Superseded => "superseded",
+            ItemOutcome::Flagged => "flagged",
+            ItemOutcome::Duplicate => "duplicate",
+            ItemOutcome::Invalid => "invalid",
+            ItemOutcome::Filtered => "filtered",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "created" => ItemOutcome::Created,
+            "superseded" => ItemOutcome::Superseded,
+            "flagged" => ItemOutcome::Flagged,
+            "duplicate" => ItemOutcome::Duplicate,
+            "filtered" => ItemOutcome::Filtered,
+            _ => ItemOutcome::Invalid,
+        }
+    }
+}
+
+/// 실행 기록에 남기는 항목 한 건.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RunItem {
+    pub source_message_id: Option<i64>,
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub outcome: ItemOutcome,
+    /// 등록·갱신한 일정 id, 또는 중복으로 판정된 기존 일정 id.
+    pub schedule_id: Option<String>,
+    /// 판정 근거(제외 사유, 날짜 보정 등).
+    pub reason: Option<String>,
+    /// 백엔드가 돌려준 항목 JSON(보정 전).
+    pub item_json: String,
+}
+
+/// 실행 목록의 한 줄.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct BriefingRunSummary {
+    pub id: i64,
+    /// "auto" | "manual" | "debug" | "digest"(다이제스트 문장 요약)
+    pub trigger: String,
+    pub backend: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub duration_ms: Option<i64>,
+    /// 대상 메시지는 id > since_id.
+    pub since_id: i64,
+    /// 실제로 처리한 마지막 메시지 id(실패 시 없음).
+    pub until_id: Option<i64>,
+    pub item_count: i64,
+    pub new_count: i64,
+    pub skipped_count: i64,
+    pub error: Option<String>,
+}
+
+/// 실행 한 건의 상세.
+#[derive(Serialize, Clone, Debug)]
+pub struct BriefingRunDetail {
+    #[serde(flatten)]
+    pub summary: BriefingRunSummary,
+    pub raw_output: Option<String>,
+    pub items: Vec<RunItem>,
+    /// 토큰·비용 사용량(백엔드가 알려 준 경우만).
+    pub usage: Option<RunUsage>,
+}
+
+/// 특정 메시지에 대한 실행별 처리 흔적.
+#[derive(Serialize, Clone, Debug)]
+pub struct MessageTrace {
+    pub run: BriefingRunSummary,
+    /// 이 실행이 만든 해당 메시지의 항목. 비어 있으면 범위에는 들었지만 추출되지 않은 것.
+    pub items: Vec<RunItem>,
+}
+
+/// 실행 종료 시 기록할 내용.
+#[derive(Default)]
+pub struct RunEnd {
+    pub until_id: Option<i64>,
+    pub raw_output: Option<String>,
+    pub items: Vec<RunItem>,
+    pub new_count: i64,
+    pub skipped_count: i64,
+    pub error: Option<String>,
+    /// 백엔드가 알려 준 사용량. 실패한 실행이라도 있으면 기록한다.
+    pub usage: Option<RunUsage>,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS briefing_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            trigger TEXT NOT NULL,
+            backend TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT,
+            duration_ms INTEGER,
+            since_id INTEGER NOT NULL,
+            until_id INTEGER,
+            raw_output TEXT,
+            new_count INTEGER NOT NULL DEFAULT 0,
+            skipped_count INTEGER NOT NULL DEFAULT 0,
+            error TEXT
+        );
+        CREATE TABLE IF NOT EXISTS briefing_run_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            run_id INTEGER NOT NULL,
+            seq INTEGER NOT NULL,
+            source_message_id INTEGER,
+            title TEXT,
+            date TEXT,
+            outcome TEXT NOT NULL,
+            schedule_id TEXT,
+            reason TEXT,
+            item_json TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_briefing_run_items_run ON briefing_run_items(run_id);
+        CREATE INDEX IF NOT EXISTS idx_briefing_run_items_message ON briefing_run_items(source_message_id);",
+    )
+    .map_err(|e| format!("브리핑 실행 기록 테이블 생성 실패: {}", e))
+}
+
+fn now_string() -> String {
+    chrono::Local::now().to_rfc3339()
+}
+
+/// 실행 시작을 기록하고 run id 를 돌려준다.
+pub fn begin_run(conn: &Connection, trigger: &str, backend: &str, since_id: i64) -> Result<i64, String> {
+    conn.execute(
+        "INSERT INTO briefing_runs (trigger, backend, started_at, since_id) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![trigger, backend, now_string(), since_id],
+    )
+    .map_err(|e| format!("브리핑 실행 기록 실패: {}", e))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// 실행 종료를 기록한다(항목·사용량 포함). 끝나면 오래된 실행을 정리한다.
+pub fn finish_run(conn: &Connection, run_id: i64, duration_ms: i64, end: &RunEnd) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("브리핑 실행 기록 실패: {}", e))?;
+    tx.execute(
+        "UPDATE briefing_runs
+         SET finished_at = ?1, duration_ms = ?2, until_id = ?3, raw_output = ?4,
+             new_count = ?5, skipped_count = ?6, error = ?7
+         WHERE id = ?8",
+        rusqlite::params![
+            now_string(),
+            duration_ms,
+            end.until_id,
+            end.raw_output.as_deref().map(|s| truncate_bytes(s, RAW_OUTPUT_MAX_BYTES)),
+            end.new_count,
+            end.skipped_count,
+            end.error,
+            run_id
+        ],
+    )
+    .map_err(|e| format!("브리핑 실행 기록 실패: {}", e))?;
+    for (seq, item) in end.items.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO briefing_run_items
+                (run_id, seq, source_message_id, title, date, outcome, schedule_id, reason, item_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            rusqlite::params![
+                run_id,
+                seq as i64,
+                item.source_message_id,
+                item.title,
+                item.date,
+                item.outcome.as_str(),
+                item.schedule_id,
+                item.reason,
+                item.item_json
+            ],
+        )
+        .map_err(|e| format!("브리핑 항목 기록 실패: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("브리핑 실행 기록 실패: {}", e))?;
+    if let Some(usage) = &end.usage {
+        briefing_usage::record(conn, run_id, briefing_usage::kst_today(), usage)?;
+    }
+    prune_runs(conn, KEEP_RUNS)
+}
+
+/// 최근 `keep` 건만 남기고 오래된 실행과 그 항목을 지운다.
+pub fn prune_runs(conn: &Connection, keep: i64) -> Result<(), String> {
+    conn.execute_batch(&format!(
+        "DELETE FROM briefing_run_items WHERE run_id NOT IN
+            (SELECT id FROM briefing_runs ORDER BY id DESC LIMIT {keep});
+         DELETE FROM briefing_runs WHERE id NOT IN
+            (SELECT id FROM briefing_runs ORDER BY id DESC LIMIT {keep});"
+    ))
+    .map_err(|e| format!("브리핑 실행 기록 정리 실패: {}", e))
+}
+
+const SUMMARY_COLUMNS: &str = "r.id, r.trigger, r.backend, r.started_at, r.finished_at, r.duration_ms,
+     r.since_id, r.until_id,
+     (SELECT COUNT(*) FROM briefing_run_items i WHERE i.run_id = r.id),
+     r.new_count, r.skipped_count, r.error";
+
+fn map_summary(r: &rusqlite::Row) -> rusqlite::Result<BriefingRunSummary> {
+    Ok(BriefingRunSummary {
+        id: r.get(0)?,
+        trigger: r.get(1)?,
+        backend: r.get(2)?,
+        started_at: r.get(3)?,
+        finished_at: r.get(4)?,
+        duration_ms: r.get(5)?,
+        since_id: r.get(6)?,
+        until_id: r.get(7)?,
+        item_count: r.get(8)?,
+        new_count: r.get(9)?,
+        skipped_count: r.get(10)?,
+        error: r.get(11)?,
+    })
+}
+
+fn map_item(r: &rusqlite::Row) -> rusqlite::Result<RunItem> {
+    let outcome: String = r.get(3)?;
+    Ok(RunItem {
+        source_message_id: r.get(0)?,
+        title: r.get(1)?,
+        date: r.get(2)?,
+        outcome: ItemOutcome::parse(&outcome),
+        schedule_id: r.get(4)?,
+        reason: r.get(5)?,
+        item_json: r.get(6)?,
+    })
+}
+
+const ITEM_COLUMNS: &str = "source_message_id, title, date, outcome, schedule_id, reason, item_json";
+
+/// 최근 실행 목록(최신순).
+pub fn list_runs(conn: &Connection, limit: i64) -> Result<Vec<BriefingRunSummary>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {SUMMARY_COLUMNS} FROM briefing_runs r ORDER BY r.id DESC LIMIT ?1"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows = stmt.query_map([limit], map_summary).map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<_>>().map_err(|e| e.to_string())
+}
+
+/// 실행 한 건의 상세(원문 출력·항목 포함). 없으면 None.
+pub fn get_run(conn: &Connection, id: i64) -> Result<Option<BriefingRunDetail>, String> {
+    let head = conn
+        .query_row(
+            &format!("SELECT {SUMMARY_COLUMNS}, r.raw_output FROM briefing_runs r WHERE r.id = ?1"),
+            [id],
+            |r| Ok((map_summary(r)?, r.get::<_, Option<String>>(12)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((summary, raw_output)) = head else {
+        return Ok(None);
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {ITEM_COLUMNS} FROM briefing_run_items WHERE run_id = ?1 ORDER BY seq"
+        ))
+        .map_err(|e| e.to_string())?;
+    let items = stmt
+        .query_map([id], map_item)
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<_>>()
+        .map_err(|e| e.to_string())?;
+    let usage = briefing_usage::get_run_usage(conn, id)?;
+    Ok(Some(BriefingRunDetail { summary, raw_output, items, usage }))
+}
+
+/// 메시지 하나가 거쳐 간 실행들(최신순): 대상 범위에 들었거나 그 메시지로 항목이 나온 실행.
+pub fn trace_message(conn: &Connection, message_id: i64) -> Result<Vec<MessageTrace>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {SUMMARY_COLUMNS} FROM briefing_runs r
+             WHERE (r.since_id < ?1 AND r.until_id >= ?1)
+                OR EXISTS (SELECT 1 FROM briefing_run_items i WHERE i.run_id = r.id AND i.source_message_id = ?1)
+             ORDER BY r.id DESC LIMIT 50"
+        ))
+        .map_err(|e| e.to_string())?;
+    let runs: Vec<BriefingRunSummary> = stmt
+        .query_map([message_id], map_summary)
+        .map_err(|e| e.to_string())?
+        .collect::<rusqlite::Result<_>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut item_stmt = conn
+        .prepare(&format!(
+            "SELECT {ITEM_COLUMNS} FROM briefing_run_items
+             WHERE run_id = ?1 AND source_message_id = ?2 ORDER BY seq"
+        ))
+        .map_err(|e| e.to_string())?;
+    let mut traces = Vec::with_capacity(runs.len());
+    for run in runs {
+        let items = item_stmt
+            .query_map(rusqlite::params![run.id, message_id], map_item)
+            .map_err(|e| e.to_string())?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(|e| e.to_string())?;
+        traces.push(MessageTrace { run, items });
+    }
+    Ok(traces)
+}
+
+/// UTF-8 경계를 지키며 `max` 바이트 이하로 자른다.
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+// ─── 실행 중 기록기 ──────────────────────────────────────────────────────────
+
+/// 실행 한 건의 기록기. 시작 시 행을 만들고 `finish` 에서 결과를 채운다.
+/// DB 를 열 수 없으면 아무것도 기록하지 않는다.
+pub struct RunRecorder {
+    conn: Option<Connection>,
+    run_id: i64,
+    started: Instant,
+}
+
+impl RunRecorder {
+    pub fn start(app: &AppHandle, trigger: &str, backend: &str, since_id: i64) -> Self {
+        let started = Instant::now();
+        let conn = open_app_db(app).ok().filter(|c| ensure_tables(c).is_ok());
+        let run_id = conn
+            .as_ref()
+            .and_then(|c| begin_run(c, trigger, backend, since_id).ok())
+            .unwrap_or(0);
+        RunRecorder {
+            conn: conn.filter(|_| run_id > 0),
+            run_id,
+            started,
+        }
+    }
+
+    pub fn finish(self, end: &RunEnd) {
+        if let Some(conn) = &self.conn {
+            let ms = self.started.elapsed().as_millis() as i64;
+            if let Err(e) = finish_run(conn, self.run_id, ms, end) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+/// 최근 브리핑 실행 목록(기본 50건).
+#[tauri::command]
+pub fn list_briefing_runs(app: AppHandle, limit: Option<i64>) -> Result<Vec<BriefingRunSummary>, String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    list_runs(&conn, limit.unwrap_or(50).clamp(1, KEEP_RUNS))
+}
+
+/// 브리핑 실행 한 건의 상세.
+#[tauri::command]
+pub fn get_briefing_run(app: AppHandle, id: i64) -> Result<BriefingRunDetail, String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    get_run(&conn, id)?.ok_or_else(|| format!("브리핑 실행 기록을 찾을 수 없습니다: {}", id))
+}
+
+/// 메시지 하나가 어떤 실행에서 어떻게 처리됐는지.
+#[tauri::command]
+pub fn trace_briefing_message(app: AppHandle, message_id: i64) -> Result<Vec<MessageTrace>, String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    trace_message(&conn, message_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        ensure_tables(&conn).unwrap();
+        conn
+    }
+
+    fn item(mid: i64, outcome: ItemOutcome, reason: Option<&str>) -> RunItem {
+        RunItem {
+            source_message_id: Some(mid),
+            title: Some(format!("항목 {}", mid)),
+            date: Some("2026-07-10".to_string()),
+            outcome,
+            schedule_id: Some(format!("msg-{}", mid)),
+            reason: reason.map(str::to_string),
+            item_json: format!("{{\"source_message_id\":{}}}", mid),
+        }
+    }
+
+    #[test]
+    fn run_roundtrip_with_items() {
+        let conn = mem();
+        let id = begin_run(&conn, "manual", "rules", 100).unwrap();
+        let end = RunEnd {
+            until_id: Some(110),
+            raw_output: Some("[{...}]".to_string()),
+            items: vec![
+                item(103, ItemOutcome::Created, None),
+                item(105, ItemOutcome::Invalid, Some("과거 날짜(2026-01-02)")),
+            ],
+            new_count: 1,
+            skipped_count: 1,
+            error: None,
+            usage: Some(RunUsage { input_tokens: 900, output_tokens: 120, cost_usd: 0.05, ..Default::default() }),
+        };
+        finish_run(&conn, id, 1200, &end).unwrap();
+
+        let runs = list_runs(&conn, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].item_count, 2);
+        assert_eq!(runs[0].until_id, Some(110));
+        assert_eq!(runs[0].duration_ms, Some(1200));
+
+        let detail = get_run(&conn, id).unwrap().unwrap();
+        assert_eq!(detail.raw_output.as_deref(), Some("[{...}]"));
+        assert_eq!(detail.items, end.items);
+        assert_eq!(detail.usage, end.usage);
+        assert!(get_run(&conn, id + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn trace_distinguishes_not_extracted_from_skipped() {
+        let conn = mem();
+        let a = begin_run(&conn, "auto", "claude_cli", 100).unwrap();
+        finish_run(
+            &conn,
+            a,
+            10,
+            &RunEnd {
+                until_id: Some(110),
+                items: vec![item(105, ItemOutcome::Duplicate, Some("같은 날짜에 비슷한 일정이 있음"))],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // 실패한 실행은 범위(until_id)가 없어 메시지 추적에 잡히지 않는다.
+        let b = begin_run(&conn, "auto", "claude_cli", 110).unwrap();
+        finish_run(&conn, b, 10, &RunEnd { error: Some("시간 초과".to_string()), ..Default::default() }).unwrap();
+
+        let traced = trace_message(&conn, 105).unwrap();
+        assert_eq!(traced.len(), 1);
+        assert_eq!(traced[0].items[0].outcome, ItemOutcome::Duplicate);
+
+        // 범위에는 들었지만 항목이 없던 메시지.
+        let silent = trace_message(&conn, 107).unwrap();
+        assert_eq!(silent.len(), 1);
+        assert!(silent[0].items.is_empty());
+
+        assert!(trace_message(&conn, 111).unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_keeps_most_recent_runs() {
+        let conn = mem();
+        for i in 0..5 {
+            let id = begin_run(&conn, "auto", "rules", i * 10).unwrap();
+            finish_run(&conn, id, 1, &RunEnd { items: vec![item(i, ItemOutcome::Created, None)], ..Default::default() })
+                .unwrap();
+        }
+        prune_runs(&conn, 2).unwrap();
+        let runs = list_runs(&conn, 10).unwrap();
+        assert_eq!(runs.iter().map(|r| r.since_id).collect::<Vec<_>>(), vec![40, 30]);
+        let orphans: i64 = conn
+            .query_row("SELECT COUNT(*) FROM briefing_run_items WHERE run_id NOT IN (SELECT id FROM briefing_runs)", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(orphans, 0);
+    }
+
+    #[test]
+    fn truncate_bytes_respects_char_boundary() {
+        assert_eq!(truncate_bytes("가나다", 4), "가");
+        assert_eq!(truncate_bytes("abc", 10), "abc");
+    }
+}
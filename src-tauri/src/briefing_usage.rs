@@ -0,0 +1,443 @@
+//! 브리핑 에이전트 사용량·비용 집계와 예산.
+//!
+//! claude `--output-format json` 봉투의 토큰·비용·턴 수·소요 시간을 실행마다 `briefing_usage`
+//! 에 남긴다. 실행 기록(`briefing_runs`)은 최근 것만 보관하므로 사용량은 따로 두어
+//! 일별·월별 합계가 실행 기록 정리와 무관하게 유지되게 한다.
+//! 일·월 예산(USD)을 정해 두면 넘긴 동안 자동·수동 실행을 멈춘다.
+
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::db::open_app_db;
+
+const REG_BUDGET_DAILY: &str = "BriefingBudgetDailyUsd";
+const REG_BUDGET_MONTHLY: &str = "BriefingBudgetMonthlyUsd";
+
+/// 사용량 보관 기간(일). 월별 합계를 1년 넘게 볼 수 있도록 넉넉히 둔다.
+const KEEP_DAYS: i64 = 400;
+
+/// 실행 한 건의 사용량.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunUsage {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    /// 백엔드가 알려 준 비용(USD). 알 수 없으면 0.
+    pub cost_usd: f64,
+    pub num_turns: i64,
+    /// 백엔드가 보고한 전체 소요 시간(ms).
+    pub duration_ms: i64,
+    /// 그중 API 호출에 쓴 시간(ms).
+    pub api_duration_ms: i64,
+}
+
+impl RunUsage {
+    /// claude `--output-format json` 봉투에서 사용량을 읽는다. 관련 필드가 하나도 없으면 None.
+    /// 구버전 CLI 는 비용을 `cost_usd` 로 내보내므로 함께 본다.
+    pub fn from_claude_envelope(envelope: &Value) -> Option<RunUsage> {
+        let int = |v: Option<&Value>| v.and_then(Value::as_i64);
+        let usage = envelope.get("usage");
+        let token = |key: &str| int(usage.and_then(|u| u.get(key)));
+        let cost = envelope
+            .get("total_cost_usd")
+            .or_else(|| envelope.get("cost_usd"))
+            .and_then(Value::as_f64);
+        let turns = int(envelope.get("num_turns"));
+        let duration = int(envelope.get("duration_ms"));
+        if usage.is_none() && cost.is_none() && turns.is_none() && duration.is_none() {
+            return None;
+        }
+        Some(RunUsage {
+            input_tokens: token("input_tokens").unwrap_or(0),
+            output_tokens: token("output_tokens").unwrap_or(0),
+            cache_read_tokens: token("cache_read_input_tokens").unwrap_or(0),
+            cache_creation_tokens: token("cache_creation_input_tokens").unwrap_or(0),
+            cost_usd: cost.unwrap_or(0.0),
+            num_turns: turns.unwrap_or(0),
+            duration_ms: duration.unwrap_or(0),
+            api_duration_ms: int(envelope.get("duration_api_ms")).unwrap_or(0),
+        })
+    }
+
+    /// OpenAI 호환 `/chat/completions` 응답의 `usage` 에서 사용량을 읽는다. 없으면 None.
+    /// 비용은 서버가 `usage.cost` 로 알려 줄 때만(OpenRouter 등) 채운다.
+    pub fn from_chat_completion(response: &Value) -> Option<RunUsage> {
+        let usage = response.get("usage")?;
+        let int = |ptr: &str| usage.pointer(ptr).and_then(Value::as_i64).unwrap_or(0);
+        Some(RunUsage {
+            input_tokens: int("/prompt_tokens"),
+            output_tokens: int("/completion_tokens"),
+            cache_read_tokens: int("/prompt_tokens_details/cached_tokens"),
+            cost_usd: usage.get("cost").and_then(Value::as_f64).unwrap_or(0.0),
+            num_turns: 1,
+            ..Default::default()
+        })
+    }
+}
+
+/// 기간 합계.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub runs: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    pub cost_usd: f64,
+    pub num_turns: i64,
+    pub duration_ms: i64,
+}
+
+/// 일별("YYYY-MM-DD") 또는 월별("YYYY-MM") 합계 한 줄.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageBucket {
+    pub period: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+/// 예산(USD). None 이면 제한 없음.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Budget {
+    pub daily_usd: Option<f64>,
+    pub monthly_usd: Option<f64>,
+}
+
+/// 오늘·이번 달 사용량과 예산 상태.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub today: UsageTotals,
+    pub month: UsageTotals,
+    pub budget: Budget,
+    /// 예산을 넘어 실행을 멈춘 사유(넘지 않았으면 None).
+    pub paused: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageReport {
+    /// 최근 일별 합계(최신순).
+    pub daily: Vec<UsageBucket>,
+    /// 최근 월별 합계(최신순).
+    pub monthly: Vec<UsageBucket>,
+    pub status: BudgetStatus,
+}
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS briefing_usage (
+            run_id INTEGER PRIMARY KEY,
+            day TEXT NOT NULL,
+            backend TEXT,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+            cache_creation_tokens INTEGER NOT NULL DEFAULT 0,
+            cost_usd REAL NOT NULL DEFAULT 0,
+            num_turns INTEGER NOT NULL DEFAULT 0,
+            duration_ms INTEGER NOT NULL DEFAULT 0,
+            api_duration_ms INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE INDEX IF NOT EXISTS idx_briefing_usage_day ON briefing_usage(day);",
+    )
+    .map_err(|e| format!("브리핑 사용량 테이블 생성 실패: {}", e))
+}
+
+/// 오늘 날짜(KST). 일·월 경계는 한국 시간 기준이다.
+pub fn kst_today() -> NaiveDate {
+    let kst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+    chrono::Utc::now().with_timezone(&kst).date_naive()
+}
+
+/// 실행 한 건의 사용량을 `day` 에 기록한다(같은 실행이면 덮어쓴다). 백엔드 이름은 실행 기록에서 가져온다.
+pub fn record(conn: &Connection, run_id: i64, day: NaiveDate, usage: &RunUsage) -> Result<(), String> {
+    ensure_tables(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO briefing_usage
+            (run_id, day, backend, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+             cost_usd, num_turns, duration_ms, api_duration_ms)
+         VALUES (?1, ?2, (SELECT backend FROM briefing_runs WHERE id = ?1), ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            run_id,
+            day.format("%Y-%m-%d").to_string(),
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_creation_tokens,
+            usage.cost_usd,
+            usage.num_turns,
+            usage.duration_ms,
+            usage.api_duration_ms
+        ],
+    )
+    .map_err(|e| format!("브리핑 사용량 기록 실패: {}", e))?;
+    let cutoff = (day - chrono::Duration::days(KEEP_DAYS)).format("%Y-%m-%d").to_string();
+    conn.execute("DELETE FROM briefing_usage WHERE day < ?1", [cutoff])
+        .map_err(|e| format!("브리핑 사용량 정리 실패: {}", e))?;
+    Ok(())
+}
+
+/// 실행 한 건의 사용량(기록이 없으면 None).
+pub fn get_run_usage(conn: &Connection, run_id: i64) -> Result<Option<RunUsage>, String> {
+    use rusqlite::OptionalExtension;
+    ensure_tables(conn)?;
+    conn.query_row(
+        "SELECT input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                cost_usd, num_turns, duration_ms, api_duration_ms
+         FROM briefing_usage WHERE run_id = ?1",
+        [run_id],
+        |r| {
+            Ok(RunUsage {
+                input_tokens: r.get(0)?,
+                output_tokens: r.get(1)?,
+                cache_read_tokens: r.get(2)?,
+                cache_creation_tokens: r.get(3)?,
+                cost_usd: r.get(4)?,
+                num_turns: r.get(5)?,
+                duration_ms: r.get(6)?,
+                api_duration_ms: r.get(7)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+const TOTAL_COLUMNS: &str = "COUNT(*), COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0),
+     COALESCE(SUM(cache_read_tokens), 0), COALESCE(SUM(cache_creation_tokens), 0),
+     COALESCE(SUM(cost_usd), 0), COALESCE(SUM(num_turns), 0), COALESCE(SUM(duration_ms), 0)";
+
+fn map_totals(r: &rusqlite::Row, offset: usize) -> rusqlite::Result<UsageTotals> {
+    Ok(UsageTotals {
+        runs: r.get(offset)?,
+        input_tokens: r.get(offset + 1)?,
+        output_tokens: r.get(offset + 2)?,
+        cache_read_tokens: r.get(offset + 3)?,
+        cache_creation_tokens: r.get(offset + 4)?,
+        cost_usd: r.get(offset + 5)?,
+        num_turns: r.get(offset + 6)?,
+        duration_ms: r.get(offset + 7)?,
+    })
+}
+
+/// `day` 의 접두어(YYYY-MM-DD 또는 YYYY-MM)가 `prefix` 인 기록의 합계.
+fn totals_for(conn: &Connection, prefix: &str) -> Result<UsageTotals, String> {
+    conn.query_row(
+        &format!("SELECT {TOTAL_COLUMNS} FROM briefing_usage WHERE day LIKE ?1 || '%'"),
+        [prefix],
+        |r| map_totals(r, 0),
+    )
+    .map_err(|e| format!("브리핑 사용량 조회 실패: {}", e))
+}
+
+/// `day` 를 앞 `len` 글자(10 = 일, 7 = 월)로 묶은 합계(최신순, 최대 `limit` 줄).
+fn rollup(conn: &Connection, len: usize, limit: i64) -> Result<Vec<UsageBucket>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT substr(day, 1, {len}) AS period, {TOTAL_COLUMNS}
+             FROM briefing_usage GROUP BY period ORDER BY period DESC LIMIT ?1"
+        ))
+        .map_err(|e| format!("브리핑 사용량 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map([limit], |r| Ok(UsageBucket { period: r.get(0)?, totals: map_totals(r, 1)? }))
+        .map_err(|e| format!("브리핑 사용량 조회 실패: {}", e))?;
+    rows.collect::<rusqlite::Result<_>>().map_err(|e| e.to_string())
+}
+
+pub fn daily_rollup(conn: &Connection, days: i64) -> Result<Vec<UsageBucket>, String> {
+    ensure_tables(conn)?;
+    rollup(conn, 10, days)
+}
+
+pub fn monthly_rollup(conn: &Connection, months: i64) -> Result<Vec<UsageBucket>, String> {
+    ensure_tables(conn)?;
+    rollup(conn, 7, months)
+}
+
+/// 오늘·이번 달 합계를 예산과 비교한다. 예산과 같거나 넘으면 멈춘다.
+pub fn budget_status(conn: &Connection, today: NaiveDate, budget: &Budget) -> Result<BudgetStatus, String> {
+    ensure_tables(conn)?;
+    let today_totals = totals_for(conn, &today.format("%Y-%m-%d").to_string())?;
+    let month_totals = totals_for(conn, &format!("{:04}-{:02}", today.year(), today.month()))?;
+    let paused = match (budget.daily_usd, budget.monthly_usd) {
+        (_, Some(limit)) if month_totals.cost_usd >= limit => Some(format!(
+            "이번 달 사용액(${:.2})이 월 예산(${:.2})에 도달해 브리핑 에이전트를 멈췄습니다.",
+            month_totals.cost_usd, limit
+        )),
+        (Some(limit), _) if today_totals.cost_usd >= limit => Some(format!(
+            "오늘 사용액(${:.2})이 일 예산(${:.2})에 도달해 내일까지 브리핑 에이전트를 멈췄습니다.",
+            today_totals.cost_usd, limit
+        )),
+        _ => None,
+    };
+    Ok(BudgetStatus { today: today_totals, month: month_totals, budget: budget.clone(), paused })
+}
+
+// ─── 설정 ─────────────────────────────────────────────────────────────────────
+
+fn read_limit(key: &str) -> Option<f64> {
+    get_registry_value(key.to_string())
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| *v > 0.0)
+}
+
+pub fn load_budget() -> Budget {
+    Budget {
+        daily_usd: read_limit(REG_BUDGET_DAILY),
+        monthly_usd: read_limit(REG_BUDGET_MONTHLY),
+    }
+}
+
+/// 현재 예산 상태. DB 를 열 수 없으면 None(이때는 실행을 막지 않는다).
+pub fn current_status(app: &AppHandle) -> Option<BudgetStatus> {
+    let conn = open_app_db(app).ok()?;
+    budget_status(&conn, kst_today(), &load_budget()).ok()
+}
+
+/// 예산을 넘었으면 그 사유.
+pub fn paused_reason(app: &AppHandle) -> Option<String> {
+    current_status(app).and_then(|s| s.paused)
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+/// 최근 `days` 일(기본 30)·12개월 사용량과 예산 상태.
+#[tauri::command]
+pub fn get_briefing_usage(app: AppHandle, days: Option<i64>) -> Result<UsageReport, String> {
+    let conn = open_app_db(&app)?;
+    Ok(UsageReport {
+        daily: daily_rollup(&conn, days.unwrap_or(30).clamp(1, KEEP_DAYS))?,
+        monthly: monthly_rollup(&conn, 12)?,
+        status: budget_status(&conn, kst_today(), &load_budget())?,
+    })
+}
+
+#[tauri::command]
+pub fn get_briefing_budget() -> Budget {
+    load_budget()
+}
+
+/// 예산 저장. 0 이하·비어 있으면 제한 없음.
+#[tauri::command]
+pub fn set_briefing_budget(budget: Budget) -> Result<(), String> {
+    let value = |v: Option<f64>| v.filter(|x| *x > 0.0).map(|x| x.to_string()).unwrap_or_default();
+    set_registry_value(REG_BUDGET_DAILY.to_string(), value(budget.daily_usd))?;
+    set_registry_value(REG_BUDGET_MONTHLY.to_string(), value(budget.monthly_usd))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mem() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::briefing_runs::ensure_tables(&conn).unwrap();
+        ensure_tables(&conn).unwrap();
+        conn
+    }
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn usage(cost: f64, input: i64) -> RunUsage {
+        RunUsage { input_tokens: input, output_tokens: 10, cost_usd: cost, num_turns: 3, ..Default::default() }
+    }
+
+    #[test]
+    fn parses_claude_envelope_usage() {
+        let env: Value = serde_json::from_str(
+            r#"{"type":"result","subtype":"success","is_error":false,"duration_ms":51234,
+                "duration_api_ms":40120,"num_turns":7,"result":"[]","total_cost_usd":0.1834,
+                "usage":{"input_tokens":1200,"cache_creation_input_tokens":3000,
+                         "cache_read_input_tokens":45000,"output_tokens":860}}"#,
+        )
+        .unwrap();
+        let u = RunUsage::from_claude_envelope(&env).unwrap();
+        assert_eq!(u.input_tokens, 1200);
+        assert_eq!(u.output_tokens, 860);
+        assert_eq!(u.cache_read_tokens, 45000);
+        assert_eq!(u.cache_creation_tokens, 3000);
+        assert_eq!(u.num_turns, 7);
+        assert_eq!(u.duration_ms, 51234);
+        assert_eq!(u.api_duration_ms, 40120);
+        assert!((u.cost_usd - 0.1834).abs() < 1e-9);
+
+        let legacy: Value = serde_json::from_str(r#"{"result":"[]","cost_usd":0.02}"#).unwrap();
+        assert!((RunUsage::from_claude_envelope(&legacy).unwrap().cost_usd - 0.02).abs() < 1e-9);
+        let bare: Value = serde_json::from_str(r#"{"result":"[]"}"#).unwrap();
+        assert!(RunUsage::from_claude_envelope(&bare).is_none());
+    }
+
+    #[test]
+    fn parses_chat_completion_usage() {
+        let resp: Value = serde_json::from_str(
+            r#"{"choices":[{"message":{"content":"[]"}}],
+                "usage":{"prompt_tokens":900,"completion_tokens":40,"total_tokens":940,
+                         "prompt_tokens_details":{"cached_tokens":512},"cost":0.0031}}"#,
+        )
+        .unwrap();
+        let u = RunUsage::from_chat_completion(&resp).unwrap();
+        assert_eq!((u.input_tokens, u.output_tokens, u.cache_read_tokens, u.num_turns), (900, 40, 512, 1));
+        assert!((u.cost_usd - 0.0031).abs() < 1e-9);
+
+        let bare: Value = serde_json::from_str(r#"{"choices":[]}"#).unwrap();
+        assert!(RunUsage::from_chat_completion(&bare).is_none());
+    }
+
+    #[test]
+    fn rolls_up_by_day_and_month() {
+        let conn = mem();
+        record(&conn, 1, d("2026-09-30"), &usage(0.5, 100)).unwrap();
+        record(&conn, 2, d("2026-10-01"), &usage(0.25, 200)).unwrap();
+        record(&conn, 3, d("2026-10-01"), &usage(0.25, 300)).unwrap();
+        // 같은 실행을 다시 기록하면 덮어쓴다.
+        record(&conn, 3, d("2026-10-01"), &usage(0.25, 300)).unwrap();
+
+        let daily = daily_rollup(&conn, 30).unwrap();
+        assert_eq!(daily.iter().map(|b| b.period.as_str()).collect::<Vec<_>>(), vec!["2026-10-01", "2026-09-30"]);
+        assert_eq!(daily[0].totals.runs, 2);
+        assert_eq!(daily[0].totals.input_tokens, 500);
+        assert_eq!(daily[0].totals.num_turns, 6);
+
+        let monthly = monthly_rollup(&conn, 12).unwrap();
+        assert_eq!(monthly.iter().map(|b| b.period.as_str()).collect::<Vec<_>>(), vec!["2026-10", "2026-09"]);
+        assert!((monthly[0].totals.cost_usd - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn budget_pauses_when_reached() {
+        let conn = mem();
+        record(&conn, 1, d("2026-10-17"), &usage(4.0, 100)).unwrap();
+        record(&conn, 2, d("2026-10-18"), &usage(0.9, 100)).unwrap();
+        let today = d("2026-10-18");
+
+        let none = budget_status(&conn, today, &Budget::default()).unwrap();
+        assert!(none.paused.is_none());
+        assert!((none.month.cost_usd - 4.9).abs() < 1e-9);
+
+        let daily = Budget { daily_usd: Some(1.0), monthly_usd: None };
+        assert!(budget_status(&conn, today, &daily).unwrap().paused.is_none());
+        record(&conn, 3, today, &usage(0.1, 100)).unwrap();
+        let paused = budget_status(&conn, today, &daily).unwrap().paused.unwrap();
+        assert!(paused.contains("일 예산"));
+        // 다음 날이면 일 예산은 풀린다.
+        assert!(budget_status(&conn, d("2026-10-19"), &daily).unwrap().paused.is_none());
+
+        let monthly = Budget { daily_usd: Some(10.0), monthly_usd: Some(5.0) };
+        assert!(budget_status(&conn, today, &monthly).unwrap().paused.unwrap().contains("월 예산"));
+        assert!(budget_status(&conn, d("2026-11-01"), &monthly).unwrap().paused.is_none());
+    }
+}
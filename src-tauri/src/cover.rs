@@ -0,0 +1,410 @@
+//! 보강 교사 찾기.
+//!
+//! "오늘 4교시 2-3 반 들어갈 수 있는 분?"에 답한다. 그 시간에 수업이 없는 교사를 같은 과목,
+//! 그날 수업 수, 이번 달 보강 횟수 순으로 돌려준다. 맡긴 보강은 `hypercool.db` 의
+//! `tbl_cover_lessons` 에 적어 두고 순위와 빈 시간 판단에 함께 쓴다.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::db::open_app_db;
+use crate::timetable::{TimetableProvider, TimetableSource};
+
+/// 교사가 가르치는 과목을 볼 때 거슬러 올라가는 주 수(날짜별 출처).
+const SUBJECT_WEEKS: i64 = 4;
+
+/// 보강 후보 한 명.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverCandidate {
+    pub teacher: String,
+    /// 요청한 과목을 가르치는지.
+    pub same_subject: bool,
+    /// 그날 수업 수(맡은 보강 포함).
+    pub lessons_today: u32,
+    /// 이번 달 그날까지 맡은 보강 횟수.
+    pub covers_this_month: u32,
+}
+
+/// 기록된 보강 한 건.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverLesson {
+    pub id: i64,
+    /// YYYY-MM-DD
+    pub date: String,
+    pub period: u32,
+    pub class_name: Option<String>,
+    pub subject: Option<String>,
+    /// 빠진 교사.
+    pub absent_teacher: Option<String>,
+    pub cover_teacher: String,
+}
+
+// ─── 저장 ─────────────────────────────────────────────────────────────────────
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_cover_lessons (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            period INTEGER NOT NULL,
+            class_name TEXT,
+            subject TEXT,
+            absent_teacher TEXT,
+            cover_teacher TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_cover_lessons_date ON tbl_cover_lessons(date);",
+    )
+    .map_err(|e| format!("보강 테이블 생성 실패: {}", e))
+}
+
+fn ymd(d: NaiveDate) -> String {
+    d.format("%Y-%m-%d").to_string()
+}
+
+/// 보강을 기록한다. 같은 교사가 같은 시간에 이미 보강을 맡았으면 거절한다.
+pub fn record_cover(conn: &Connection, cover: &CoverLesson) -> Result<i64, String> {
+    ensure_tables(conn)?;
+    let taken: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tbl_cover_lessons WHERE date = ?1 AND period = ?2 AND cover_teacher = ?3",
+            params![cover.date, cover.period, cover.cover_teacher],
+            |r| r.get(0),
+        )
+        .map_err(|e| format!("보강 조회 실패: {}", e))?;
+    if taken > 0 {
+        return Err(format!("{} 선생님은 그 시간에 이미 보강이 있습니다.", cover.cover_teacher));
+    }
+    conn.execute(
+        "INSERT INTO tbl_cover_lessons (date, period, class_name, subject, absent_teacher, cover_teacher, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            cover.date,
+            cover.period,
+            cover.class_name,
+            cover.subject,
+            cover.absent_teacher,
+            cover.cover_teacher,
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()
+        ],
+    )
+    .map_err(|e| format!("보강 저장 실패: {}", e))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// `from`~`to`(포함) 사이의 보강.
+pub fn list_covers(conn: &Connection, from: NaiveDate, to: NaiveDate) -> Result<Vec<CoverLesson>, String> {
+    ensure_tables(conn)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, period, class_name, subject, absent_teacher, cover_teacher
+             FROM tbl_cover_lessons WHERE date >= ?1 AND date <= ?2 ORDER BY date, period, id",
+        )
+        .map_err(|e| format!("보강 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map(params![ymd(from), ymd(to)], |r| {
+            Ok(CoverLesson {
+                id: r.get(0)?,
+                date: r.get(1)?,
+                period: r.get(2)?,
+                class_name: r.get(3)?,
+                subject: r.get(4)?,
+                absent_teacher: r.get(5)?,
+                cover_teacher: r.get(6)?,
+            })
+        })
+        .map_err(|e| format!("보강 조회 실패: {}", e))?;
+    Ok(rows.flatten().collect())
+}
+
+fn month_range(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let first = date.with_day(1).unwrap();
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    }
+    .unwrap();
+    (first, next.pred_opt().unwrap())
+}
+
+// ─── 찾기 ─────────────────────────────────────────────────────────────────────
+
+/// 보강 요청: 몇 교시, (선택) 어느 반·무슨 과목.
+#[derive(Debug, Clone, Default)]
+pub struct CoverRequest<'a> {
+    pub period: u32,
+    pub subject: Option<&'a str>,
+    pub class_name: Option<&'a str>,
+}
+
+/// 교사별로 가르치는 과목. 날짜별 출처는 그 주까지 `SUBJECT_WEEKS` 주를 본다.
+fn teacher_subjects(provider: &dyn TimetableProvider, date: NaiveDate) -> HashMap<String, HashSet<String>> {
+    let monday = date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64);
+    let weeks = if provider.dated_days().is_empty() { 1 } else { SUBJECT_WEEKS };
+    let mut out: HashMap<String, HashSet<String>> = HashMap::new();
+    for w in 0..weeks {
+        for offset in 0..5 {
+            let day = monday - chrono::Duration::weeks(w) + chrono::Duration::days(offset);
+            for l in provider.lessons_on(day) {
+                out.entry(l.teacher).or_default().insert(l.subject);
+            }
+        }
+    }
+    out
+}
+
+/// 그 시간에 수업이 없는 교사를 순위대로 돌려준다. `covers` 는 그달의 보강 기록.
+/// 반을 주고 과목을 주지 않으면 그 반의 그 시간 수업 과목으로 찾고, 원래 교사는 뺀다.
+pub fn find_cover_teachers(
+    provider: &dyn TimetableProvider,
+    date: NaiveDate,
+    request: &CoverRequest,
+    covers: &[CoverLesson],
+) -> Result<Vec<CoverCandidate>, String> {
+    if request.period == 0 {
+        return Err("교시는 1 이상이어야 합니다.".to_string());
+    }
+    let lessons = provider.lessons_on(date);
+    if lessons.is_empty() {
+        return Err(format!("{} 에는 시간표 자료가 없습니다.", ymd(date)));
+    }
+
+    let class_lesson = request
+        .class_name
+        .and_then(|c| lessons.iter().find(|l| l.period == request.period && l.class_name.as_deref() == Some(c)));
+    let subject = request
+        .subject
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .or_else(|| class_lesson.map(|l| l.subject.clone()));
+    let absent = class_lesson.map(|l| l.teacher.as_str());
+
+    let today = ymd(date);
+    let mut lessons_today: HashMap<&str, u32> = HashMap::new();
+    let mut busy: HashSet<&str> = HashSet::new();
+    for l in &lessons {
+        *lessons_today.entry(l.teacher.as_str()).or_insert(0) += 1;
+        if l.period == request.period {
+            busy.insert(l.teacher.as_str());
+        }
+    }
+    let mut month_covers: HashMap<&str, u32> = HashMap::new();
+    for c in covers {
+        // 그날 뒤로 미리 잡힌 보강은 "지금까지" 맡은 횟수에 넣지 않는다.
+        if c.date.as_str() <= today.as_str() {
+            *month_covers.entry(c.cover_teacher.as_str()).or_insert(0) += 1;
+        }
+        if c.date == today {
+            *lessons_today.entry(c.cover_teacher.as_str()).or_insert(0) += 1;
+            if c.period == request.period {
+                busy.insert(c.cover_teacher.as_str());
+            }
+        }
+    }
+
+    let subjects = match &subject {
+        Some(_) => teacher_subjects(provider, date),
+        None => HashMap::new(),
+    };
+    let mut out: Vec<CoverCandidate> = provider
+        .teachers()
+        .into_iter()
+        .filter(|t| !t.trim().is_empty() && !busy.contains(t.as_str()) && Some(t.as_str()) != absent)
+        .map(|t| CoverCandidate {
+            same_subject: subject.as_ref().is_some_and(|s| subjects.get(&t).is_some_and(|set| set.contains(s))),
+            lessons_today: lessons_today.get(t.as_str()).copied().unwrap_or(0),
+            covers_this_month: month_covers.get(t.as_str()).copied().unwrap_or(0),
+            teacher: t,
+        })
+        .collect();
+    out.sort_by(|a, b| {
+        b.same_subject
+            .cmp(&a.same_subject)
+            .then(a.lessons_today.cmp(&b.lessons_today))
+            .then(a.covers_this_month.cmp(&b.covers_this_month))
+            .then_with(|| a.teacher.cmp(&b.teacher))
+    });
+    Ok(out)
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(|_| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", s))
+}
+
+/// 그 시간에 들어갈 수 있는 교사. 학교 위젯에 설정된 시간표를 쓴다.
+#[tauri::command]
+pub fn find_substitute_teachers(
+    app: AppHandle,
+    date: String,
+    period: u32,
+    subject: Option<String>,
+    class_name: Option<String>,
+) -> Result<Vec<CoverCandidate>, String> {
+    let date = parse_date(&date)?;
+    let provider = TimetableSource::from_registry().load()?;
+    let (first, last) = month_range(date);
+    let covers = list_covers(&open_app_db(&app)?, first, last)?;
+    let request = CoverRequest { period, subject: subject.as_deref(), class_name: class_name.as_deref() };
+    find_cover_teachers(provider.as_ref(), date, &request, &covers)
+}
+
+/// 보강을 맡긴다.
+#[tauri::command]
+pub fn assign_cover_lesson(
+    app: AppHandle,
+    date: String,
+    period: u32,
+    class_name: Option<String>,
+    subject: Option<String>,
+    absent_teacher: Option<String>,
+    cover_teacher: String,
+) -> Result<i64, String> {
+    let date = parse_date(&date)?;
+    if cover_teacher.trim().is_empty() {
+        return Err("보강 교사를 골라 주세요.".to_string());
+    }
+    let cover = CoverLesson {
+        id: 0,
+        date: ymd(date),
+        period,
+        class_name,
+        subject,
+        absent_teacher,
+        cover_teacher: cover_teacher.trim().to_string(),
+    };
+    record_cover(&open_app_db(&app)?, &cover)
+}
+
+/// `month`(YYYY-MM) 의 보강 기록.
+#[tauri::command]
+pub fn get_cover_lessons(app: AppHandle, month: String) -> Result<Vec<CoverLesson>, String> {
+    let date = parse_date(&format!("{}-01", month.trim()))?;
+    let (first, last) = month_range(date);
+    list_covers(&open_app_db(&app)?, first, last)
+}
+
+#[tauri::command]
+pub fn delete_cover_lesson(app: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    conn.execute("DELETE FROM tbl_cover_lessons WHERE id = ?1", params![id])
+        .map_err(|e| format!("보강 삭제 실패: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timetable_parser::TimetableData;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// 수요일: 김교사 국어 1·4교시(4교시 2-3), 이교사 국어 1교시, 박교사 수학 2교시, 최교사 수업 없음.
+    fn timetable() -> TimetableData {
+        let empty = || vec![vec![vec![String::new(), String::new()]; 5]; 8];
+        let cell = |s: &str, c: &str| vec![s.to_string(), c.to_string()];
+        let mut timetables = HashMap::new();
+        let mut kim = empty();
+        kim[0][2] = cell("국어", "2-1");
+        kim[3][2] = cell("국어", "2-3");
+        timetables.insert("김교사".to_string(), kim);
+        let mut lee = empty();
+        lee[0][2] = cell("국어", "2-2");
+        timetables.insert("이교사".to_string(), lee);
+        let mut park = empty();
+        park[1][2] = cell("수학", "2-3");
+        timetables.insert("박교사".to_string(), park);
+        timetables.insert("최교사".to_string(), empty());
+        TimetableData {
+            teachers: ["김교사", "이교사", "박교사", "최교사"].iter().map(|s| s.to_string()).collect(),
+            subjects: vec!["국어".to_string(), "수학".to_string()],
+            timetables,
+        }
+    }
+
+    fn cover(date: &str, period: u32, teacher: &str) -> CoverLesson {
+        CoverLesson {
+            id: 0,
+            date: date.to_string(),
+            period,
+            class_name: None,
+            subject: None,
+            absent_teacher: None,
+            cover_teacher: teacher.to_string(),
+        }
+    }
+
+    fn names(c: &[CoverCandidate]) -> Vec<&str> {
+        c.iter().map(|c| c.teacher.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_by_subject_then_load() {
+        let t = timetable();
+        let wed = d("2026-03-04");
+        let request = CoverRequest { period: 4, class_name: Some("2-3"), ..Default::default() };
+        let found = find_cover_teachers(&t, wed, &request, &[]).unwrap();
+        // 2-3 4교시는 김교사 국어: 원래 교사는 빠지고 국어 교사가 먼저.
+        assert_eq!(names(&found), vec!["이교사", "최교사", "박교사"]);
+        assert!(found[0].same_subject);
+        assert_eq!((found[1].lessons_today, found[2].lessons_today), (0, 1));
+
+        // 과목 없이 1교시: 그 시간 수업이 있는 교사는 빠진다.
+        let request = CoverRequest { period: 1, ..Default::default() };
+        assert_eq!(names(&find_cover_teachers(&t, wed, &request, &[]).unwrap()), vec!["최교사", "박교사"]);
+
+        assert!(find_cover_teachers(&t, d("2026-03-07"), &request, &[]).is_err());
+    }
+
+    #[test]
+    fn cover_history_affects_ranking_and_availability() {
+        let t = timetable();
+        let wed = d("2026-03-04");
+        let request = CoverRequest { period: 3, subject: Some("수학"), ..Default::default() };
+        let covers = [
+            cover("2026-03-02", 1, "최교사"),
+            cover("2026-03-03", 2, "최교사"),
+            cover("2026-03-20", 2, "최교사"),
+        ];
+        let found = find_cover_teachers(&t, wed, &request, &covers).unwrap();
+        assert_eq!(names(&found), vec!["박교사", "최교사", "이교사", "김교사"]);
+        // 그날 뒤의 보강(3/20)은 세지 않는다.
+        assert_eq!(found[1].covers_this_month, 2);
+
+        // 그날 같은 시간 보강을 맡은 교사는 빠지고, 다른 시간 보강은 수업 수에 들어간다.
+        // 수업 수가 같으면 이번 달 보강이 적은 교사가 먼저.
+        let covers = [cover("2026-03-04", 3, "최교사"), cover("2026-03-04", 5, "이교사")];
+        let found = find_cover_teachers(&t, wed, &request, &covers).unwrap();
+        assert_eq!(names(&found), vec!["박교사", "김교사", "이교사"]);
+        assert_eq!((found[1].lessons_today, found[2].lessons_today), (2, 2));
+    }
+
+    #[test]
+    fn records_and_lists_covers() {
+        let conn = Connection::open_in_memory().unwrap();
+        let mut c = cover("2026-03-04", 4, "이교사");
+        c.class_name = Some("2-3".to_string());
+        let id = record_cover(&conn, &c).unwrap();
+        assert!(record_cover(&conn, &c).is_err());
+        record_cover(&conn, &cover("2026-04-01", 1, "이교사")).unwrap();
+
+        let (first, last) = month_range(d("2026-03-15"));
+        assert_eq!((ymd(first), ymd(last)), ("2026-03-01".to_string(), "2026-03-31".to_string()));
+        let march = list_covers(&conn, first, last).unwrap();
+        assert_eq!(march.len(), 1);
+        assert_eq!((march[0].id, march[0].class_name.as_deref()), (id, Some("2-3")));
+        assert_eq!(month_range(d("2026-12-10")).1, d("2026-12-31"));
+    }
+}
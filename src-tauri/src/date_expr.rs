@@ -0,0 +1,880 @@
+//! 한국어 날짜·요일·시각·교시 표현 추출기.
+//!
+//! "다음 주 화요일 6교시까지", "3/15(금)", "이번 달 말" 같은 표현을 기준일(Asia/Seoul 달력 날짜)에
+//! 대해 절대 날짜로 풀고, 원문 위치(바이트 구간)와 확신도를 함께 돌려준다.
+//! 브리핑 에이전트가 프롬프트 사전 힌트와 LLM 출력 검증에 쓰며, 규칙 기반 백엔드의 날짜 인식도
+//! 이 모듈을 그대로 쓴다. 외부 상태 없이 입력 문자열과 기준일만으로 결정적으로 동작한다.
+
+use std::sync::OnceLock;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+
+/// 검증·힌트에 "확실한 근거"로 쓰는 최소 확신도.
+pub const CONFIDENT: f32 = 0.8;
+
+/// 찾은 표현 하나. 날짜에 붙은 시각·교시·범위는 한 표현으로 합쳐진다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateExpr {
+    /// 원문 바이트 구간 [start, end)
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub date: Option<NaiveDate>,
+    /// 기간 표현의 끝 날짜("3/15~3/17", "다음 주", "이번 달 초")
+    pub end_date: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub period: Option<u32>,
+    pub end_period: Option<u32>,
+    /// 바로 뒤에 "까지"가 붙은 기한 표현인지
+    pub deadline: bool,
+    /// 0.0~1.0. 연도·월 추정, 요일 불일치, 모호한 표기일수록 낮다.
+    pub confidence: f32,
+}
+
+impl DateExpr {
+    /// 프롬프트 힌트용 한 줄 요약: `「다음 주 화요일 6교시까지」 → 2026-03-10(화) 6교시 기한`
+    pub fn describe(&self) -> String {
+        let mut out = format!("「{}」 →", self.text.trim());
+        if let Some(d) = self.date {
+            out.push_str(&format!(" {}({})", d.format("%Y-%m-%d"), weekday_name(d)));
+        }
+        if let Some(e) = self.end_date {
+            out.push_str(&format!(" ~ {}({})", e.format("%Y-%m-%d"), weekday_name(e)));
+        }
+        if let Some(t) = self.time {
+            out.push_str(&format!(" {}", t.format("%H:%M")));
+            if let Some(et) = self.end_time {
+                out.push_str(&format!("~{}", et.format("%H:%M")));
+            }
+        }
+        if let Some(p) = self.period {
+            match self.end_period {
+                Some(ep) => out.push_str(&format!(" {}~{}교시", p, ep)),
+                None => out.push_str(&format!(" {}교시", p)),
+            }
+        }
+        if self.deadline {
+            out.push_str(" 기한");
+        }
+        out
+    }
+
+    fn covers(&self, d: NaiveDate) -> bool {
+        match (self.date, self.end_date) {
+            (Some(s), Some(e)) => s <= d && d <= e,
+            (Some(s), None) => s == d,
+            _ => false,
+        }
+    }
+}
+
+/// LLM 이 고른 날짜를 원문 표현과 대조한 결과.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateCheck {
+    /// 원문에 같은 날짜(또는 그 날짜를 포함하는 기간)가 있다.
+    Supported,
+    /// 확실한 날짜 표현이 있는데 어느 것과도 맞지 않는다. 하나로 좁혀지면 제안 날짜를 준다.
+    Contradicted { suggestion: Option<NaiveDate> },
+    /// 원문에 확실한 날짜 표현이 없어 판단하지 않는다.
+    NoEvidence,
+}
+
+const WEEKDAYS: [char; 7] = ['월', '화', '수', '목', '금', '토', '일'];
+
+fn weekday_index(c: char) -> Option<u32> {
+    WEEKDAYS.iter().position(|&w| w == c).map(|i| i as u32)
+}
+
+fn weekday_name(d: NaiveDate) -> char {
+    WEEKDAYS[d.weekday().num_days_from_monday() as usize]
+}
+
+fn is_hangul(c: char) -> bool {
+    ('\u{AC00}'..='\u{D7A3}').contains(&c)
+}
+
+// ─── 정규식 ───────────────────────────────────────────────────────────────────
+
+struct Patterns {
+    full_date: regex::Regex,
+    month_day: regex::Regex,
+    slash_date: regex::Regex,
+    dotted_date: regex::Regex,
+    bare_day: regex::Regex,
+    relative_day: regex::Regex,
+    offset: regex::Regex,
+    weekend: regex::Regex,
+    week: regex::Regex,
+    bare_weekday: regex::Regex,
+    month_rel: regex::Regex,
+    month_part: regex::Regex,
+    month_end: regex::Regex,
+    clock: regex::Regex,
+    hour: regex::Regex,
+    noon: regex::Regex,
+    period: regex::Regex,
+    weekday_paren: regex::Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+fn patterns() -> &'static Patterns {
+    PATTERNS.get_or_init(|| {
+        let re = |p: &str| regex::Regex::new(p).unwrap();
+        Patterns {
+            full_date: re(r"(\d{4})\s*(?:년|[-./])\s*(\d{1,2})\s*(?:월|[-./])\s*(\d{1,2})\s*(?:일|\.)?"),
+            month_day: re(r"(\d{1,2})\s*월\s*(\d{1,2})\s*일"),
+            slash_date: re(r"(\d{1,2})\s*/\s*(\d{1,2})"),
+            dotted_date: re(r"(\d{1,2})\.\s?(\d{1,2})(\s*\.)?"),
+            bare_day: re(r"(\d{1,2})\s*일"),
+            relative_day: re(r"오늘|금일|내일|명일|모레|글피|어제|그저께|그제"),
+            offset: re(r"(\d{1,3})\s*(일|주|개월|달)\s*(후|뒤|전)"),
+            weekend: re(r"(?:(이번|다음|지난)\s*)?주말"),
+            week: re(r"(다다음\s*주|이번\s*주|금주|다음\s*주|차주|내주|지난\s*주|저번\s*주)(?:\s*([월화수목금토일])(요일)?)?"),
+            bare_weekday: re(r"([월화수목금토일])요일"),
+            month_rel: re(r"(이번\s*달|이달|금월|다음\s*달|내달|익월|지난\s*달|전월)(?:\s*(말|초|중순)|\s*(\d{1,2})\s*일)?"),
+            month_part: re(r"(\d{1,2})\s*월\s*(말|초|중순)"),
+            month_end: re(r"월말"),
+            clock: re(r"(?:(오전|오후|아침|저녁|밤|낮)\s*)?(\d{1,2}):(\d{2})"),
+            hour: re(r"(?:(오전|오후|아침|저녁|밤|낮)\s*)?(\d{1,2})\s*시(?:\s*(\d{1,2})\s*분|\s*(반))?"),
+            noon: re(r"정오"),
+            period: re(r"(\d{1,2})\s*(?:[~\-,]\s*(\d{1,2})\s*)?교시"),
+            weekday_paren: re(r"^\s*\(\s*([월화수목금토일])(?:요일)?\s*\)"),
+        }
+    })
+}
+
+// ─── 토큰 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Date {
+        date: NaiveDate,
+        end: Option<NaiveDate>,
+        /// 연도를 기준일로 추정했는지(요일 괄호로 보정 가능)
+        year_inferred: bool,
+    },
+    /// 월이 없는 "15일" — 앞 날짜 또는 기준일의 달로 푼다.
+    Day(u32),
+    Time(NaiveTime),
+    Period(u32, Option<u32>),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    start: usize,
+    end: usize,
+    kind: Kind,
+    conf: f32,
+}
+
+fn prev_char(text: &str, at: usize) -> Option<char> {
+    text[..at].chars().next_back()
+}
+
+fn next_char(text: &str, at: usize) -> Option<char> {
+    text[at..].chars().next()
+}
+
+/// 숫자·소수점 한가운데서 시작하거나 끝나는 매치(전화번호·문서번호·버전)를 거른다.
+fn digit_bounded(text: &str, start: usize, end: usize) -> bool {
+    let before_ok = !prev_char(text, start).is_some_and(|c| c.is_ascii_digit() || c == '.' || c == '-' || c == '/');
+    let after_ok = !next_char(text, end).is_some_and(|c| c.is_ascii_digit());
+    before_ok && after_ok
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let (ny, nm) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(ny, nm, 1).map(|d| d - Duration::days(1))
+}
+
+fn add_months(year: i32, month: u32, delta: i32) -> (i32, u32) {
+    let idx = year * 12 + month as i32 - 1 + delta;
+    (idx.div_euclid(12), idx.rem_euclid(12) as u32 + 1)
+}
+
+/// 연도 없는 월·일: 기준일 30일 전 이후인 가장 이른 후보(작년·올해·내년 중)를 고른다.
+/// 1월에 받은 "12월 28일"은 작년, 12월에 받은 "1월 5일"은 내년이 된다.
+fn infer_year(reference: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let floor = reference - Duration::days(30);
+    (reference.year() - 1..=reference.year() + 1)
+        .filter_map(|y| NaiveDate::from_ymd_opt(y, month, day))
+        .find(|d| *d >= floor)
+}
+
+fn monday_of(d: NaiveDate) -> NaiveDate {
+    d - Duration::days(d.weekday().num_days_from_monday() as i64)
+}
+
+/// 오전/오후 표기와 시를 24시간제로. 표기가 없으면 학교 일과 기준으로 1~6시는 오후로 본다.
+fn to_24h(marker: Option<&str>, hour: u32) -> (u32, f32) {
+    match marker {
+        Some("오후" | "저녁" | "밤") if hour < 12 => (hour + 12, 0.9),
+        Some("낮") if (1..=6).contains(&hour) => (hour + 12, 0.9),
+        Some(_) => (hour, 0.9),
+        None if (1..=6).contains(&hour) => (hour + 12, 0.7),
+        None => (hour, 0.8),
+    }
+}
+
+fn collect_tokens(text: &str, reference: NaiveDate) -> Vec<Token> {
+    let p = patterns();
+    let mut toks: Vec<Token> = Vec::new();
+    let mut push = |start: usize, end: usize, kind: Kind, conf: f32| {
+        toks.push(Token { start, end, kind, conf });
+    };
+
+    for c in p.full_date.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let end = m.as_str().trim_end().len() + m.start();
+        if !digit_bounded(text, m.start(), end) {
+            continue;
+        }
+        let (y, mo, d) = (c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0), c[3].parse().unwrap_or(0));
+        if let Some(date) = NaiveDate::from_ymd_opt(y, mo, d) {
+            push(m.start(), end, Kind::Date { date, end: None, year_inferred: false }, 1.0);
+        }
+    }
+    for c in p.month_day.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) {
+            continue;
+        }
+        if let Some(date) = infer_year(reference, c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0)) {
+            push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: true }, 0.9);
+        }
+    }
+    for c in p.slash_date.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) || next_char(text, m.end()) == Some('/') {
+            continue;
+        }
+        if let Some(date) = infer_year(reference, c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0)) {
+            push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: true }, 0.8);
+        }
+    }
+    for c in p.dotted_date.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let end = m.as_str().trim_end().len() + m.start();
+        if !digit_bounded(text, m.start(), end) {
+            continue;
+        }
+        // "1.5배" 같은 소수를 피하려고 끝 점이나 요일 괄호가 있을 때만 날짜로 본다.
+        let trailing_dot = c.get(3).is_some();
+        if !trailing_dot && !p.weekday_paren.is_match(&text[end..]) {
+            continue;
+        }
+        if let Some(date) = infer_year(reference, c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0)) {
+            push(m.start(), end, Kind::Date { date, end: None, year_inferred: true }, 0.8);
+        }
+    }
+    for c in p.bare_day.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) {
+            continue;
+        }
+        // "3일 후", "2일간" 같은 기간·상대 표현은 날짜가 아니다.
+        let rest = text[m.end()..].trim_start();
+        if ["후", "뒤", "전", "간", "동안", "째"].iter().any(|s| rest.starts_with(s)) {
+            continue;
+        }
+        let day: u32 = c[1].parse().unwrap_or(0);
+        if (1..=31).contains(&day) {
+            push(m.start(), m.end(), Kind::Day(day), 0.6);
+        }
+    }
+    for m in p.relative_day.find_iter(text) {
+        let delta = match m.as_str() {
+            "오늘" | "금일" => 0,
+            "내일" | "명일" => 1,
+            "모레" => 2,
+            "글피" => 3,
+            "어제" => -1,
+            _ => -2,
+        };
+        let date = reference + Duration::days(delta);
+        push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: false }, 0.9);
+    }
+    for c in p.offset.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) {
+            continue;
+        }
+        let n: i64 = c[1].parse().unwrap_or(0);
+        let sign = if &c[3] == "전" { -1 } else { 1 };
+        let date = match &c[2] {
+            "일" => Some(reference + Duration::days(sign * n)),
+            "주" => Some(reference + Duration::days(sign * n * 7)),
+            _ => {
+                let (y, mo) = add_months(reference.year(), reference.month(), (sign * n) as i32);
+                NaiveDate::from_ymd_opt(y, mo, reference.day())
+                    .or_else(|| last_day_of_month(y, mo))
+            }
+        };
+        if let Some(date) = date {
+            push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: false }, 0.85);
+        }
+    }
+    for c in p.weekend.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let weeks = match c.get(1).map(|x| x.as_str()) {
+            Some("다음") => 1,
+            Some("지난") => -1,
+            _ => 0,
+        };
+        let sat = monday_of(reference) + Duration::days(5 + 7 * weeks);
+        push(m.start(), m.end(), Kind::Date { date: sat, end: Some(sat + Duration::days(1)), year_inferred: false }, 0.7);
+    }
+    for c in p.week.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let word: String = c[1].chars().filter(|ch| !ch.is_whitespace()).collect();
+        let weeks = match word.as_str() {
+            "다다음주" => 2,
+            "다음주" | "차주" | "내주" => 1,
+            "지난주" | "저번주" => -1,
+            _ => 0,
+        };
+        let monday = monday_of(reference) + Duration::days(7 * weeks);
+        match c.get(2) {
+            Some(wd) => {
+                // "다음 주 목표"의 '목' 처럼 단어 일부인 요일 글자는 요일이 아니다.
+                let with_yoil = c.get(3).is_some();
+                let rest = &text[m.end()..];
+                let standalone = !next_char(text, m.end()).is_some_and(is_hangul)
+                    || ["까지", "부터", "에", "은", "는"].iter().any(|s| rest.starts_with(s));
+                if with_yoil || standalone {
+                    let idx = weekday_index(wd.as_str().chars().next().unwrap_or(' ')).unwrap_or(0);
+                    let date = monday + Duration::days(idx as i64);
+                    push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: false }, 0.85);
+                } else {
+                    let end = c.get(1).unwrap().end();
+                    push(m.start(), end, Kind::Date { date: monday, end: Some(monday + Duration::days(6)), year_inferred: false }, 0.6);
+                }
+            }
+            None => {
+                // "다음 주말"은 weekend 패턴이 더 길게 잡는다.
+                if text[m.end()..].starts_with('말') {
+                    continue;
+                }
+                push(m.start(), m.end(), Kind::Date { date: monday, end: Some(monday + Duration::days(6)), year_inferred: false }, 0.6);
+            }
+        }
+    }
+    for c in p.bare_weekday.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let idx = weekday_index(c[1].chars().next().unwrap_or(' ')).unwrap_or(0);
+        let today_idx = reference.weekday().num_days_from_monday();
+        let ahead = (idx + 7 - today_idx) % 7;
+        let date = reference + Duration::days(ahead as i64);
+        push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: false }, 0.7);
+    }
+    for c in p.month_rel.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let word: String = c[1].chars().filter(|ch| !ch.is_whitespace()).collect();
+        let delta = match word.as_str() {
+            "다음달" | "내달" | "익월" => 1,
+            "지난달" | "전월" => -1,
+            _ => 0,
+        };
+        let (y, mo) = add_months(reference.year(), reference.month(), delta);
+        let resolved = match (c.get(2).map(|x| x.as_str()), c.get(3)) {
+            (Some("말"), _) => last_day_of_month(y, mo).map(|d| (d, None, 0.8)),
+            (Some("초"), _) => NaiveDate::from_ymd_opt(y, mo, 1)
+                .map(|d| (d, NaiveDate::from_ymd_opt(y, mo, 10), 0.5)),
+            (Some(_), _) => NaiveDate::from_ymd_opt(y, mo, 11)
+                .map(|d| (d, NaiveDate::from_ymd_opt(y, mo, 20), 0.5)),
+            (None, Some(day)) => NaiveDate::from_ymd_opt(y, mo, day.as_str().parse().unwrap_or(0))
+                .map(|d| (d, None, 0.85)),
+            (None, None) => NaiveDate::from_ymd_opt(y, mo, 1)
+                .map(|d| (d, last_day_of_month(y, mo), 0.5)),
+        };
+        if let Some((date, end, conf)) = resolved {
+            push(m.start(), m.end(), Kind::Date { date, end, year_inferred: false }, conf);
+        }
+    }
+    for c in p.month_part.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) {
+            continue;
+        }
+        let mo: u32 = c[1].parse().unwrap_or(0);
+        // 달의 마지막 날 기준으로 연도를 골라야 "2월 말"이 3월 초에 내년으로 넘어가지 않는다.
+        let floor = reference - Duration::days(30);
+        let Some(last) = (reference.year() - 1..=reference.year() + 1)
+            .filter_map(|y| last_day_of_month(y, mo))
+            .find(|d| *d >= floor)
+        else {
+            continue;
+        };
+        let (y, mo) = (last.year(), last.month());
+        let Some(first) = NaiveDate::from_ymd_opt(y, mo, 1) else { continue };
+        let resolved = match &c[2] {
+            "말" => Some((last, None, 0.7)),
+            "초" => Some((first, NaiveDate::from_ymd_opt(y, mo, 10), 0.5)),
+            _ => NaiveDate::from_ymd_opt(y, mo, 11).map(|d| (d, NaiveDate::from_ymd_opt(y, mo, 20), 0.5)),
+        };
+        if let Some((date, end, conf)) = resolved {
+            push(m.start(), m.end(), Kind::Date { date, end, year_inferred: true }, conf);
+        }
+    }
+    for m in p.month_end.find_iter(text) {
+        if let Some(date) = last_day_of_month(reference.year(), reference.month()) {
+            push(m.start(), m.end(), Kind::Date { date, end: None, year_inferred: false }, 0.7);
+        }
+    }
+
+    for c in p.clock.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let digits = c.get(2).unwrap();
+        if !digit_bounded(text, digits.start(), m.end()) {
+            continue;
+        }
+        let (h, mi): (u32, u32) = (c[2].parse().unwrap_or(99), c[3].parse().unwrap_or(99));
+        let (h, conf) = match c.get(1) {
+            Some(marker) => to_24h(Some(marker.as_str()), h),
+            None => (h, 0.95),
+        };
+        if let Some(t) = NaiveTime::from_hms_opt(h, mi, 0) {
+            push(m.start(), m.end(), Kind::Time(t), conf);
+        }
+    }
+    for c in p.hour.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        let digits = c.get(2).unwrap();
+        if !digit_bounded(text, digits.start(), m.end()) {
+            continue;
+        }
+        // "2시간"은 시각이 아니라 기간.
+        if c.get(3).is_none() && c.get(4).is_none() && text[m.end()..].starts_with('간') {
+            continue;
+        }
+        let h: u32 = c[2].parse().unwrap_or(99);
+        let mi: u32 = match (c.get(3), c.get(4)) {
+            (Some(x), _) => x.as_str().parse().unwrap_or(99),
+            (None, Some(_)) => 30,
+            _ => 0,
+        };
+        let (h, conf) = to_24h(c.get(1).map(|x| x.as_str()), h);
+        if let Some(t) = NaiveTime::from_hms_opt(h, mi, 0) {
+            push(m.start(), m.end(), Kind::Time(t), conf);
+        }
+    }
+    for m in p.noon.find_iter(text) {
+        push(m.start(), m.end(), Kind::Time(NaiveTime::from_hms_opt(12, 0, 0).unwrap()), 0.9);
+    }
+    for c in p.period.captures_iter(text) {
+        let m = c.get(0).unwrap();
+        if !digit_bounded(text, m.start(), m.end()) {
+            continue;
+        }
+        let from: u32 = c[1].parse().unwrap_or(0);
+        let to = c.get(2).and_then(|x| x.as_str().parse().ok());
+        if (1..=10).contains(&from) {
+            push(m.start(), m.end(), Kind::Period(from, to), 0.9);
+        }
+    }
+
+    // 겹치는 후보는 더 긴(같으면 먼저 나온) 쪽만 남긴다.
+    toks.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+    let mut kept: Vec<Token> = Vec::new();
+    for t in toks {
+        if let Some(last) = kept.last() {
+            if t.start < last.end {
+                continue;
+            }
+        }
+        kept.push(t);
+    }
+    kept
+}
+
+// ─── 병합 ─────────────────────────────────────────────────────────────────────
+
+/// 토큰 사이 글자가 공백·쉼표뿐인지(같은 표현의 구성 요소로 합친다)
+fn joinable(gap: &str) -> bool {
+    gap.chars().all(|c| c.is_whitespace() || c == ',')
+}
+
+/// 범위 연결어("~", "-", "부터")인지
+fn range_gap(gap: &str) -> bool {
+    let g = gap.trim();
+    matches!(g, "~" | "-" | "–" | "∼" | "부터" | "에서")
+}
+
+/// 날짜 뒤 "(금)" 요일 괄호를 소비하고, 연도 추정 날짜면 요일에 맞는 연도로 보정한다.
+fn apply_weekday_paren(text: &str, end: &mut usize, date: &mut NaiveDate, year_inferred: bool, conf: &mut f32) {
+    let Some(c) = patterns().weekday_paren.captures(&text[*end..]) else { return };
+    let idx = weekday_index(c[1].chars().next().unwrap_or(' ')).unwrap_or(7);
+    *end += c.get(0).unwrap().end();
+    if date.weekday().num_days_from_monday() == idx {
+        *conf = conf.max(0.95);
+        return;
+    }
+    if year_inferred {
+        let fixed = [date.year() + 1, date.year() - 1]
+            .into_iter()
+            .filter_map(|y| NaiveDate::from_ymd_opt(y, date.month(), date.day()))
+            .find(|d| d.weekday().num_days_from_monday() == idx);
+        if let Some(d) = fixed {
+            *date = d;
+            *conf = 0.9;
+            return;
+        }
+    }
+    // 날짜와 요일이 어긋난 원문(오타 가능성) — 어느 쪽이 맞는지 모르므로 확신도를 낮춘다.
+    *conf = conf.min(0.5);
+}
+
+fn resolve_bare_day(anchor: Option<NaiveDate>, reference: NaiveDate, day: u32) -> Option<NaiveDate> {
+    match anchor {
+        Some(a) => {
+            let same = NaiveDate::from_ymd_opt(a.year(), a.month(), day);
+            match same {
+                Some(d) if d >= a => Some(d),
+                _ => {
+                    let (y, m) = add_months(a.year(), a.month(), 1);
+                    NaiveDate::from_ymd_opt(y, m, day)
+                }
+            }
+        }
+        None => {
+            let this = NaiveDate::from_ymd_opt(reference.year(), reference.month(), day);
+            match this {
+                Some(d) if d + Duration::days(7) >= reference => Some(d),
+                _ => {
+                    let (y, m) = add_months(reference.year(), reference.month(), 1);
+                    NaiveDate::from_ymd_opt(y, m, day)
+                }
+            }
+        }
+    }
+}
+
+/// 본문에서 날짜·시각·교시 표현을 찾아 기준일 기준으로 푼다. 결과는 원문 순서.
+pub fn extract(text: &str, reference: NaiveDate) -> Vec<DateExpr> {
+    let toks = collect_tokens(text, reference);
+    let mut out: Vec<DateExpr> = Vec::new();
+    let mut i = 0;
+
+    while i < toks.len() {
+        let first = toks[i];
+        let mut expr = DateExpr {
+            start: first.start,
+            end: first.end,
+            text: String::new(),
+            date: None,
+            end_date: None,
+            time: None,
+            end_time: None,
+            period: None,
+            end_period: None,
+            deadline: false,
+            confidence: first.conf,
+        };
+        match first.kind {
+            Kind::Date { date, end, year_inferred } => {
+                let mut date = date;
+                let mut conf = first.conf;
+                apply_weekday_paren(text, &mut expr.end, &mut date, year_inferred, &mut conf);
+                expr.date = Some(date);
+                expr.end_date = end;
+                expr.confidence = conf;
+            }
+            Kind::Day(day) => {
+                let mut date = match resolve_bare_day(None, reference, day) {
+                    Some(d) => d,
+                    None => {
+                        i += 1;
+                        continue;
+                    }
+                };
+                let mut conf = first.conf;
+                apply_weekday_paren(text, &mut expr.end, &mut date, false, &mut conf);
+                expr.date = Some(date);
+                expr.confidence = conf;
+            }
+            Kind::Time(t) => expr.time = Some(t),
+            Kind::Period(from, to) => {
+                expr.period = Some(from);
+                expr.end_period = to;
+            }
+        }
+        i += 1;
+
+        // 뒤따르는 구성 요소 흡수: 범위 끝 날짜, 시각(+범위), 교시.
+        while i < toks.len() {
+            let next = toks[i];
+            let gap = &text[expr.end..next.start];
+            match next.kind {
+                Kind::Date { date, year_inferred, .. } if expr.date.is_some() && expr.end_date.is_none() && expr.time.is_none() && range_gap(gap) => {
+                    let mut end = next.end;
+                    let mut date = date;
+                    let mut conf = next.conf;
+                    apply_weekday_paren(text, &mut end, &mut date, year_inferred, &mut conf);
+                    if Some(date) < expr.date {
+                        break;
+                    }
+                    expr.end_date = Some(date);
+                    expr.end = end;
+                    expr.confidence = expr.confidence.min(conf);
+                }
+                Kind::Day(day) if expr.date.is_some() && expr.end_date.is_none() && expr.time.is_none() && range_gap(gap) => {
+                    let Some(mut date) = resolve_bare_day(expr.date, reference, day) else { break };
+                    let mut end = next.end;
+                    let mut conf = expr.confidence;
+                    apply_weekday_paren(text, &mut end, &mut date, false, &mut conf);
+                    expr.end_date = Some(date);
+                    expr.end = end;
+                    expr.confidence = expr.confidence.min(conf);
+                }
+                Kind::Time(t) if expr.time.is_none() && expr.period.is_none() && joinable(gap) => {
+                    expr.time = Some(t);
+                    expr.end = next.end;
+                    expr.confidence = expr.confidence.min(next.conf.max(0.8));
+                }
+                Kind::Time(t) if expr.time.is_some() && expr.end_time.is_none() && range_gap(gap) => {
+                    expr.end_time = Some(t);
+                    expr.end = next.end;
+                }
+                Kind::Period(from, to) if expr.period.is_none() && expr.time.is_none() && joinable(gap) => {
+                    expr.period = Some(from);
+                    expr.end_period = to;
+                    expr.end = next.end;
+                }
+                _ => break,
+            }
+            i += 1;
+        }
+
+        let rest = text[expr.end..].trim_start();
+        expr.deadline = rest.starts_with("까지");
+        expr.text = text[expr.start..expr.end].to_string();
+        // 날짜 없는 시각·교시만 있는 표현은 당일 여부를 모르므로 확신도를 낮춘다.
+        if expr.date.is_none() {
+            expr.confidence = expr.confidence.min(0.5);
+        }
+        out.push(expr);
+    }
+    out
+}
+
+/// LLM 이 고른 날짜가 원문 표현과 맞는지 확인한다.
+/// 제안 날짜는 확실한 "까지" 기한 표현이 하나뿐이거나, 확실한 날짜 표현이 하나뿐일 때만 준다.
+pub fn check_date(candidate: NaiveDate, text: &str, reference: NaiveDate) -> DateCheck {
+    let exprs: Vec<DateExpr> = extract(text, reference)
+        .into_iter()
+        .filter(|e| e.date.is_some())
+        .collect();
+    if exprs.iter().any(|e| e.covers(candidate)) {
+        return DateCheck::Supported;
+    }
+    let confident: Vec<&DateExpr> = exprs.iter().filter(|e| e.confidence >= CONFIDENT).collect();
+    if confident.is_empty() {
+        return DateCheck::NoEvidence;
+    }
+    DateCheck::Contradicted {
+        suggestion: suggest_date(&confident),
+    }
+}
+
+/// 확실한 표현들 중 하나로 좁혀지는 날짜(기한 표현 우선).
+pub fn suggest_date(confident: &[&DateExpr]) -> Option<NaiveDate> {
+    let deadlines: Vec<&&DateExpr> = confident.iter().filter(|e| e.deadline).collect();
+    match (deadlines.as_slice(), confident) {
+        ([only], _) => only.date,
+        ([], [only]) => only.date,
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn t(s: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(s, "%H:%M").unwrap()
+    }
+
+    /// 기준일 2026-03-04(수) 에서의 단일 날짜 해석 코퍼스: (본문, 기대 날짜, 기대 기간 끝).
+    const DATE_CORPUS: &[(&str, &str, Option<&str>)] = &[
+        ("2026-03-15 회의", "2026-03-15", None),
+        ("2026.3.15. 제출", "2026-03-15", None),
+        ("2026년 4월 1일 시행", "2026-04-01", None),
+        ("2027/01/05 예정", "2027-01-05", None),
+        ("3월 15일까지 제출", "2026-03-15", None),
+        ("3월15일 개학식", "2026-03-15", None),
+        ("3/15(일) 예배", "2026-03-15", None),
+        ("3/20 마감", "2026-03-20", None),
+        ("3.13.(금) 행사", "2026-03-13", None),
+        ("3.13(금) 행사", "2026-03-13", None),
+        ("12월 28일 방학식", "2026-12-28", None),
+        ("1월 5일 개학", "2027-01-05", None),
+        ("2월 20일 졸업식", "2026-02-20", None),
+        ("오늘까지 회신", "2026-03-04", None),
+        ("금일 중 제출", "2026-03-04", None),
+        ("내일 오전 협의회", "2026-03-05", None),
+        ("모레 출장", "2026-03-06", None),
+        ("글피 연수", "2026-03-07", None),
+        ("어제 보낸 공문", "2026-03-03", None),
+        ("3일 후 재안내", "2026-03-07", None),
+        ("2주 뒤 평가", "2026-03-18", None),
+        ("1개월 후 점검", "2026-04-04", None),
+        ("이번 주 금요일까지", "2026-03-06", None),
+        ("이번주 금까지", "2026-03-06", None),
+        ("다음 주 화요일 6교시까지", "2026-03-10", None),
+        ("다음주 월요일 회의", "2026-03-09", None),
+        ("차주 수요일", "2026-03-11", None),
+        ("다다음 주 목요일", "2026-03-19", None),
+        ("지난 주 금요일 공문", "2026-02-27", None),
+        ("금요일까지 회신", "2026-03-06", None),
+        ("수요일 오후", "2026-03-04", None),
+        ("월요일에 보고", "2026-03-09", None),
+        ("이번 달 말까지", "2026-03-31", None),
+        ("이달 말 마감", "2026-03-31", None),
+        ("다음 달 5일", "2026-04-05", None),
+        ("월말 정산", "2026-03-31", None),
+        ("4월 말까지", "2026-04-30", None),
+        ("2월 말 결과", "2026-02-28", None),
+        ("다음 주 중 제출", "2026-03-09", Some("2026-03-15")),
+        ("이번 주말 행사", "2026-03-07", Some("2026-03-08")),
+        ("다음 주말", "2026-03-14", Some("2026-03-15")),
+        ("다음 달 초", "2026-04-01", Some("2026-04-10")),
+        ("3월 중순", "2026-03-11", Some("2026-03-20")),
+        ("3/16~3/18 수련회", "2026-03-16", Some("2026-03-18")),
+        ("3월 16일부터 18일까지", "2026-03-16", Some("2026-03-18")),
+        ("3.30.(월)~4.2.(목)", "2026-03-30", Some("2026-04-02")),
+        ("15일까지 입력", "2026-03-15", None),
+        ("2일 등록", "2026-03-02", None),
+    ];
+
+    #[test]
+    fn date_corpus_resolves_against_reference() {
+        let reference = d("2026-03-04");
+        for (text, date, end) in DATE_CORPUS {
+            let exprs = extract(text, reference);
+            let e = exprs
+                .iter()
+                .find(|e| e.date.is_some())
+                .unwrap_or_else(|| panic!("날짜 없음: {}", text));
+            assert_eq!(e.date, Some(d(date)), "{}", text);
+            assert_eq!(e.end_date, end.map(d), "{}", text);
+        }
+    }
+
+    /// 날짜로 잡으면 안 되는 본문.
+    const NEGATIVE_CORPUS: &[&str] = &[
+        "문의 010-1234-5678",
+        "내선 02-123-4567",
+        "경쟁률 1.5배",
+        "버전 2.3.1 배포",
+        "3일간 진행",
+        "2시간 소요",
+        "다음 주 목표 공유",
+        "학년도 예산 1,200,000원",
+        "문서번호 교무-1234",
+    ];
+
+    #[test]
+    fn negative_corpus_yields_no_dates() {
+        let reference = d("2026-03-04");
+        for text in NEGATIVE_CORPUS {
+            let dated: Vec<DateExpr> = extract(text, reference)
+                .into_iter()
+                .filter(|e| e.date.is_some() && e.end_date.is_none())
+                .collect();
+            assert!(dated.is_empty(), "{} → {:?}", text, dated);
+        }
+    }
+
+    #[test]
+    fn time_and_period_attach_to_date() {
+        let reference = d("2026-03-04");
+        let e = &extract("다음 주 화요일 6교시까지 제출", reference)[0];
+        assert_eq!(e.date, Some(d("2026-03-10")));
+        assert_eq!(e.period, Some(6));
+        assert!(e.deadline);
+        assert_eq!(e.text, "다음 주 화요일 6교시");
+
+        let e = &extract("3월 6일 오후 4시까지 회신", reference)[0];
+        assert_eq!(e.time, Some(t("16:00")));
+        assert!(e.deadline);
+
+        let e = &extract("3/12(목) 13:20~14:10 협의회", reference)[0];
+        assert_eq!(e.date, Some(d("2026-03-12")));
+        assert_eq!(e.time, Some(t("13:20")));
+        assert_eq!(e.end_time, Some(t("14:10")));
+        assert!(e.confidence >= 0.9);
+
+        let e = &extract("내일 5~6교시 공개수업", reference)[0];
+        assert_eq!((e.period, e.end_period), (Some(5), Some(6)));
+    }
+
+    #[test]
+    fn time_expressions() {
+        let reference = d("2026-03-04");
+        let time_of = |s: &str| extract(s, reference).iter().find_map(|e| e.time);
+        assert_eq!(time_of("오전 9시 반 집합"), Some(t("09:30")));
+        assert_eq!(time_of("오후 2시 10분"), Some(t("14:10")));
+        assert_eq!(time_of("3시까지"), Some(t("15:00")));
+        assert_eq!(time_of("저녁 7시"), Some(t("19:00")));
+        assert_eq!(time_of("정오"), Some(t("12:00")));
+        assert_eq!(time_of("08:40 등교"), Some(t("08:40")));
+        assert_eq!(time_of("10시 회의"), Some(t("10:00")));
+    }
+
+    #[test]
+    fn weekday_paren_corrects_inferred_year_and_flags_mismatch() {
+        // 2026-12-20 기준 "1.8.(금)" → 2027-01-08(금)
+        let e = &extract("1.8.(금) 마감", d("2026-12-20"))[0];
+        assert_eq!(e.date, Some(d("2027-01-08")));
+        assert!(e.confidence >= 0.9);
+
+        // 3/15 는 일요일인데 (금) 으로 적힌 오타 → 날짜는 유지, 확신도는 낮춤
+        let e = &extract("3/15(금) 제출", d("2026-03-04"))[0];
+        assert_eq!(e.date, Some(d("2026-03-15")));
+        assert!(e.confidence < CONFIDENT);
+        assert_eq!(e.text, "3/15(금)");
+    }
+
+    #[test]
+    fn spans_point_into_original_text() {
+        let text = "학부모 상담은 3월 16일(월)부터, 신청은 3월 6일까지";
+        let exprs = extract(text, d("2026-03-04"));
+        assert_eq!(exprs.len(), 2);
+        assert_eq!(&text[exprs[0].start..exprs[0].end], "3월 16일(월)");
+        assert!(!exprs[0].deadline);
+        assert_eq!(&text[exprs[1].start..exprs[1].end], "3월 6일");
+        assert!(exprs[1].deadline);
+    }
+
+    #[test]
+    fn check_date_validates_llm_choice() {
+        let reference = d("2026-03-04");
+        let text = "상담 주간은 3월 16일(월)부터이며 희망 시간은 3월 6일까지 회신";
+        assert_eq!(check_date(d("2026-03-06"), text, reference), DateCheck::Supported);
+        assert_eq!(check_date(d("2026-03-16"), text, reference), DateCheck::Supported);
+        assert_eq!(
+            check_date(d("2026-03-07"), text, reference),
+            DateCheck::Contradicted { suggestion: Some(d("2026-03-06")) }
+        );
+        assert_eq!(check_date(d("2026-03-07"), "곧 안내 예정", reference), DateCheck::NoEvidence);
+        // 기간 안의 날짜는 근거가 있다.
+        assert_eq!(check_date(d("2026-03-17"), "3/16~3/18 수련회", reference), DateCheck::Supported);
+        // 확실한 표현이 둘 이상이고 기한이 없으면 제안하지 않는다.
+        assert_eq!(
+            check_date(d("2026-03-20"), "3월 9일 설명회, 3월 12일 공개수업", reference),
+            DateCheck::Contradicted { suggestion: None }
+        );
+    }
+
+    #[test]
+    fn describe_formats_hint_line() {
+        let e = &extract("다음 주 화요일 6교시까지", d("2026-03-04"))[0];
+        assert_eq!(e.describe(), "「다음 주 화요일 6교시」 → 2026-03-10(화) 6교시 기한");
+    }
+}
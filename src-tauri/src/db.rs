@@ -1,4 +1,4 @@
-use rusqlite::{params, Connection, Result};
+use rusqlite::{params, Connection, OptionalExtension, Result};
 use tauri::AppHandle;
 use tauri::Manager;
 use serde::{Serialize, Deserialize};
@@ -34,6 +34,32 @@ pub struct ScheduleItem {
     pub updated_at: String,
     #[serde(rename = "isDeleted")]
     pub is_deleted: bool,
+    /// 중요도("high" | "medium" | "low"). AI 추출 일정에만 채워진다.
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// 항목 성격("event" | "deadline" | "task" | "notice"). AI 추출 일정에만 채워진다.
+    #[serde(rename = "itemKind", default)]
+    pub item_kind: Option<String>,
+    /// 할 일 목록에 보이는 마지막 날(YYYY-MM-DD). 없으면 시작일 기준.
+    #[serde(rename = "visibleUntil", default)]
+    pub visible_until: Option<String>,
+    /// 출처 메시지에 첨부가 있는지. 첨부 목록은 referenceId 로 `get_message_attachments` 에서 조회.
+    #[serde(rename = "hasAttachment", default)]
+    pub has_attachment: Option<bool>,
+}
+
+/// `get_schedules` 추가 조건. 비어 있는 조건은 적용하지 않는다.
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleFilter {
+    /// 이 중 하나인 항목 성격만(예: ["deadline", "event"]).
+    pub item_kinds: Option<Vec<String>>,
+    /// 이 중 하나인 중요도만.
+    pub priorities: Option<Vec<String>>,
+    /// 첨부 있는/없는 항목만.
+    pub has_attachment: Option<bool>,
+    /// 이 날짜(YYYY-MM-DD)에 아직 보여야 하는 항목만(visibleUntil 이 없거나 이 날짜 이후).
+    pub visible_on: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -77,7 +103,17 @@ pub fn init_db(app: &AppHandle) -> Result<(), String> {
     let db_path = app_dir.join("hypercool.db");
     
     let conn = Connection::open(db_path).map_err(|e| e.to_string())?;
-    
+    ensure_schedule_table(&conn)
+}
+
+/// 앱 DB(`hypercool.db`)에 연결한다. 모듈별 테이블은 각 모듈이 보장한다.
+pub fn open_app_db(app: &AppHandle) -> Result<Connection, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Connection::open(dir.join("hypercool.db")).map_err(|e| format!("DB 연결 실패: {}", e))
+}
+
+/// 일정 테이블을 보장한다(없으면 생성 + 컬럼 마이그레이션). 멱등.
+pub fn ensure_schedule_table(conn: &Connection) -> Result<(), String> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS tbl_schedules (
             id TEXT PRIMARY KEY,
@@ -96,25 +132,76 @@ pub fn init_db(app: &AppHandle) -> Result<(), String> {
         )",
         [],
     ).map_err(|e| e.to_string())?;
-    
-    Ok(())
+    migrate_schedule_columns(conn)
 }
 
-fn get_connection(app: &AppHandle) -> Result<Connection, String> {
-    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = app_dir.join("hypercool.db");
-    Connection::open(db_path).map_err(|e| e.to_string())
+/// AI 추출 메타데이터 컬럼(priority, item_kind, visible_until, has_attachment)과
+/// 학사일정 휴업일 표시(is_day_off)가 없는 기존 DB 에 컬럼을 추가한다. 멱등.
+pub fn migrate_schedule_columns(conn: &Connection) -> Result<(), String> {
+    let existing: Vec<String> = {
+        let mut stmt = conn
+            .prepare("PRAGMA table_info(tbl_schedules)")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| e.to_string())?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    for (column, decl) in [
+        ("priority", "TEXT"),
+        ("item_kind", "TEXT"),
+        ("visible_until", "TEXT"),
+        ("has_attachment", "BOOLEAN"),
+        ("is_day_off", "BOOLEAN NOT NULL DEFAULT 0"),
+    ] {
+        if !existing.iter().any(|c| c == column) {
+            conn.execute(&format!("ALTER TABLE tbl_schedules ADD COLUMN {} {}", column, decl), [])
+                .map_err(|e| format!("일정 테이블 컬럼 추가 실패({}): {}", column, e))?;
+        }
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub fn get_schedules(app: AppHandle, start: String, end: String, include_deleted: Option<bool>) -> Result<Vec<ScheduleItem>, String> {
-    let conn = get_connection(&app)?;
-    get_schedules_impl(&conn, start, end, include_deleted.unwrap_or(false))
+pub fn get_schedules(
+    app: AppHandle,
+    start: String,
+    end: String,
+    include_deleted: Option<bool>,
+    filter: Option<ScheduleFilter>,
+) -> Result<Vec<ScheduleItem>, String> {
+    let conn = open_app_db(&app)?;
+    let items = get_schedules_impl(&conn, start, end, include_deleted.unwrap_or(false))?;
+    Ok(match filter {
+        Some(f) => filter_schedules(items, &f),
+        None => items,
+    })
+}
+
+pub fn filter_schedules(items: Vec<ScheduleItem>, filter: &ScheduleFilter) -> Vec<ScheduleItem> {
+    let in_list = |value: &Option<String>, list: &Option<Vec<String>>| match list {
+        Some(list) => value.as_ref().is_some_and(|v| list.iter().any(|l| l == v)),
+        None => true,
+    };
+    items
+        .into_iter()
+        .filter(|item| in_list(&item.item_kind, &filter.item_kinds))
+        .filter(|item| in_list(&item.priority, &filter.priorities))
+        .filter(|item| match filter.has_attachment {
+            Some(want) => item.has_attachment.unwrap_or(false) == want,
+            None => true,
+        })
+        .filter(|item| match (&filter.visible_on, &item.visible_until) {
+            (Some(day), Some(until)) => until.as_str() >= day.as_str(),
+            _ => true,
+        })
+        .collect()
 }
 
 pub fn get_schedules_impl(conn: &Connection, start: String, end: String, include_deleted: bool) -> Result<Vec<ScheduleItem>, String> {
     let query_str = if include_deleted {
-        "SELECT id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted 
+        "SELECT id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted,
+                priority, item_kind, visible_until, has_attachment
          FROM tbl_schedules 
          WHERE (
             (start_date BETWEEN ?1 AND ?2) OR 
@@ -122,7 +209,8 @@ pub fn get_schedules_impl(conn: &Connection, start: String, end: String, include
             (start_date <= ?1 AND end_date >= ?2)
          )"
     } else {
-        "SELECT id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted 
+        "SELECT id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted,
+                priority, item_kind, visible_until, has_attachment
          FROM tbl_schedules 
          WHERE is_deleted = 0 AND (
             (start_date BETWEEN ?1 AND ?2) OR 
@@ -133,23 +221,7 @@ pub fn get_schedules_impl(conn: &Connection, start: String, end: String, include
 
     let mut stmt = conn.prepare(query_str).map_err(|e| e.to_string())?;
 
-    let rows = stmt.query_map(params![start, end], |row| {
-        Ok(ScheduleItem {
-            id: row.get(0)?,
-            schedule_type: row.get(1)?,
-            title: row.get(2)?,
-            content: row.get(3)?,
-            start_date: row.get(4)?,
-            end_date: row.get(5)?,
-            is_all_day: row.get(6)?,
-            reference_id: row.get(7)?,
-            color: row.get(8)?,
-            is_completed: row.get(9)?,
-            created_at: row.get(10)?,
-            updated_at: row.get(11)?,
-            is_deleted: row.get(12)?,
-        })
-    }).map_err(|e| e.to_string())?;
+    let rows = stmt.query_map(params![start, end], schedule_from_row).map_err(|e| e.to_string())?;
 
     let mut schedules = Vec::new();
     for row in rows {
@@ -159,6 +231,41 @@ pub fn get_schedules_impl(conn: &Connection, start: String, end: String, include
     Ok(schedules)
 }
 
+/// id 로 일정 하나를 조회한다(삭제된 일정 포함).
+pub fn get_schedule_by_id_impl(conn: &Connection, id: &str) -> Result<Option<ScheduleItem>, String> {
+    conn.query_row(
+        "SELECT id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted,
+                priority, item_kind, visible_until, has_attachment
+         FROM tbl_schedules WHERE id = ?1",
+        [id],
+        schedule_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn schedule_from_row(row: &rusqlite::Row) -> rusqlite::Result<ScheduleItem> {
+    Ok(ScheduleItem {
+        id: row.get(0)?,
+        schedule_type: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        start_date: row.get(4)?,
+        end_date: row.get(5)?,
+        is_all_day: row.get(6)?,
+        reference_id: row.get(7)?,
+        color: row.get(8)?,
+        is_completed: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+        is_deleted: row.get(12)?,
+        priority: row.get(13)?,
+        item_kind: row.get(14)?,
+        visible_until: row.get(15)?,
+        has_attachment: row.get(16)?,
+    })
+}
+
 fn trigger_desktopcal_sync(app: &AppHandle) {
     let app_handle = app.clone();
     tauri::async_runtime::spawn(async move {
@@ -173,7 +280,7 @@ fn trigger_desktopcal_sync(app: &AppHandle) {
 
 #[tauri::command]
 pub fn create_schedule(app: AppHandle, item: ScheduleItem) -> Result<ScheduleItem, String> {
-    let conn = get_connection(&app)?;
+    let conn = open_app_db(&app)?;
     let result = create_schedule_impl(&conn, item);
     if result.is_ok() {
         trigger_desktopcal_sync(&app);
@@ -183,45 +290,130 @@ pub fn create_schedule(app: AppHandle, item: ScheduleItem) -> Result<ScheduleIte
 
 pub fn create_schedule_impl(conn: &Connection, item: ScheduleItem) -> Result<ScheduleItem, String> {
     conn.execute(
-        "INSERT INTO tbl_schedules (id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        "INSERT INTO tbl_schedules (id, type, title, content, start_date, end_date, is_all_day, reference_id, color, is_completed, created_at, updated_at, is_deleted,
+                                    priority, item_kind, visible_until, has_attachment)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
         params![
             item.id, item.schedule_type, item.title, item.content, item.start_date, item.end_date, 
-            item.is_all_day, item.reference_id, item.color, item.is_completed, item.created_at, item.updated_at, item.is_deleted
+            item.is_all_day, item.reference_id, item.color, item.is_completed, item.created_at, item.updated_at, item.is_deleted,
+            item.priority, item.item_kind, item.visible_until, item.has_attachment
         ],
     ).map_err(|e| e.to_string())?;
 
     Ok(item)
 }
 
+/// 일정 수정 시 AI 메타데이터 변경분. 바깥 None 은 키를 보내지 않은 것(보존),
+/// `Some(None)` 은 null 을 보낸 것(지움)이다. 구버전 클라이언트·동기화가 빠뜨린 값을 보존하면서도
+/// 명시적으로 지울 수 있게 한다.
+#[derive(Debug, Deserialize, Default, Clone, PartialEq)]
+pub struct ScheduleMetaPatch {
+    #[serde(default, deserialize_with = "present")]
+    pub priority: Option<Option<String>>,
+    #[serde(rename = "itemKind", default, deserialize_with = "present")]
+    pub item_kind: Option<Option<String>>,
+    #[serde(rename = "visibleUntil", default, deserialize_with = "present")]
+    pub visible_until: Option<Option<String>>,
+    #[serde(rename = "hasAttachment", default, deserialize_with = "present")]
+    pub has_attachment: Option<Option<bool>>,
+}
+
+/// 키가 있으면(값이 null 이어도) `Some` 으로 감싼다.
+fn present<'de, D, T>(d: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(d).map(Some)
+}
+
+impl ScheduleMetaPatch {
+    /// 값이 있는 필드만 바꾸는 변경분(메타데이터를 모르는 호출자용).
+    pub fn from_item(item: &ScheduleItem) -> Self {
+        ScheduleMetaPatch {
+            priority: item.priority.clone().map(Some),
+            item_kind: item.item_kind.clone().map(Some),
+            visible_until: item.visible_until.clone().map(Some),
+            has_attachment: item.has_attachment.map(Some),
+        }
+    }
+}
+
 #[tauri::command]
-pub fn update_schedule(app: AppHandle, id: String, item: ScheduleItem) -> Result<ScheduleItem, String> {
-    let conn = get_connection(&app)?;
-    let result = update_schedule_impl(&conn, id, item);
+pub fn update_schedule(app: AppHandle, id: String, item: serde_json::Value) -> Result<ScheduleItem, String> {
+    let patch: ScheduleMetaPatch =
+        serde_json::from_value(item.clone()).map_err(|e| format!("일정 형식 오류: {}", e))?;
+    let item: ScheduleItem = serde_json::from_value(item).map_err(|e| format!("일정 형식 오류: {}", e))?;
+    let conn = open_app_db(&app)?;
+    let result = update_schedule_impl(&conn, id, item, &patch);
     if result.is_ok() {
         trigger_desktopcal_sync(&app);
     }
     result
 }
 
-pub fn update_schedule_impl(conn: &Connection, id: String, item: ScheduleItem) -> Result<ScheduleItem, String> {
+/// 외부 원본을 그대로 옮겨 온 읽기 전용 일정 종류(원본에서 다시 가져올 때만 바뀐다).
+pub const READ_ONLY_SCHEDULE_TYPES: &[&str] = &["school_schedule"];
+
+/// 읽기 전용 일정이면 수정·삭제를 막는다.
+fn ensure_writable(conn: &Connection, id: &str) -> Result<(), String> {
+    let schedule_type: Option<String> = conn
+        .query_row("SELECT type FROM tbl_schedules WHERE id = ?1", params![id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match schedule_type {
+        Some(t) if READ_ONLY_SCHEDULE_TYPES.contains(&t.as_str()) => {
+            Err("학사일정처럼 가져온 일정은 수정하거나 삭제할 수 없습니다.".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// AI 메타데이터는 `patch` 에 든 필드만 바꾼다(`ScheduleMetaPatch` 참고).
+/// 돌려주는 일정의 메타데이터는 반영 후 DB 값이다.
+pub fn update_schedule_impl(
+    conn: &Connection,
+    id: String,
+    mut item: ScheduleItem,
+    patch: &ScheduleMetaPatch,
+) -> Result<ScheduleItem, String> {
+    ensure_writable(conn, &id)?;
     conn.execute(
         "UPDATE tbl_schedules SET 
             type = ?1, title = ?2, content = ?3, start_date = ?4, end_date = ?5, is_all_day = ?6, 
-            reference_id = ?7, color = ?8, is_completed = ?9, updated_at = ?10, is_deleted = ?11
+            reference_id = ?7, color = ?8, is_completed = ?9, updated_at = ?10, is_deleted = ?11,
+            priority = CASE WHEN ?13 THEN ?14 ELSE priority END,
+            item_kind = CASE WHEN ?15 THEN ?16 ELSE item_kind END,
+            visible_until = CASE WHEN ?17 THEN ?18 ELSE visible_until END,
+            has_attachment = CASE WHEN ?19 THEN ?20 ELSE has_attachment END
          WHERE id = ?12",
         params![
             item.schedule_type, item.title, item.content, item.start_date, item.end_date, 
             item.is_all_day, item.reference_id, item.color, item.is_completed, item.updated_at, item.is_deleted,
-            id
+            id,
+            patch.priority.is_some(), patch.priority.clone().flatten(),
+            patch.item_kind.is_some(), patch.item_kind.clone().flatten(),
+            patch.visible_until.is_some(), patch.visible_until.clone().flatten(),
+            patch.has_attachment.is_some(), patch.has_attachment.flatten()
         ],
     ).map_err(|e| e.to_string())?;
+    if let Some(row) = conn
+        .query_row(
+            "SELECT priority, item_kind, visible_until, has_attachment FROM tbl_schedules WHERE id = ?1",
+            params![id],
+            |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+    {
+        (item.priority, item.item_kind, item.visible_until, item.has_attachment) = row;
+    }
     Ok(item)
 }
 
 #[tauri::command]
 pub fn delete_schedule(app: AppHandle, id: String) -> Result<(), String> {
-    let conn = get_connection(&app)?;
+    let conn = open_app_db(&app)?;
     let result = delete_schedule_impl(&conn, id);
     if result.is_ok() {
         trigger_desktopcal_sync(&app);
@@ -230,6 +422,7 @@ pub fn delete_schedule(app: AppHandle, id: String) -> Result<(), String> {
 }
 
 pub fn delete_schedule_impl(conn: &Connection, id: String) -> Result<(), String> {
+    ensure_writable(conn, &id)?;
     let now = Utc::now().to_rfc3339();
     conn.execute(
         "UPDATE tbl_schedules SET is_deleted = 1, updated_at = ?1 WHERE id = ?2",
@@ -431,7 +624,7 @@ fn extract_local_date_with_tz<Tz: chrono::TimeZone>(s: &str, tz: &Tz) -> Option<
 /// Import calendar data from a DeskTopCal .db file into Hypercool's tbl_schedules.
 #[tauri::command]
 pub fn import_desktopcal_db(app: AppHandle, db_path: String) -> Result<ImportResult, String> {
-    let conn = get_connection(&app)?;
+    let conn = open_app_db(&app)?;
 
     // Open DeskTopCal DB as read-only
     let ext_conn = Connection::open_with_flags(
@@ -576,6 +769,10 @@ pub fn import_desktopcal_db_impl(conn: &Connection, ext_conn: &Connection) -> Re
                 created_at: if cdate.is_empty() { now.clone() } else { cdate },
                 updated_at: if mdate.is_empty() { now } else { mdate },
                 is_deleted: false,
+                priority: None,
+                item_kind: None,
+                visible_until: None,
+                has_attachment: None,
             };
 
             conn.execute(
@@ -675,6 +872,10 @@ pub fn import_desktopcal_db_impl(conn: &Connection, ext_conn: &Connection) -> Re
                     created_at: if ev_cdate.is_empty() { now.clone() } else { ev_cdate.clone() },
                     updated_at: if ev_mdate.is_empty() { now } else { ev_mdate.clone() },
                     is_deleted: false,
+                    priority: None,
+                    item_kind: None,
+                    visible_until: None,
+                    has_attachment: None,
                 };
 
                 conn.execute(
@@ -708,7 +909,7 @@ pub fn import_desktopcal_db_impl(conn: &Connection, ext_conn: &Connection) -> Re
 /// Export Hypercool schedule data to a DeskTopCal-format .db file.
 #[tauri::command]
 pub fn export_desktopcal_db(app: AppHandle, db_path: String) -> Result<ExportResult, String> {
-    let conn = get_connection(&app)?;
+    let conn = open_app_db(&app)?;
 
     // Create/overwrite the export DB
     let ext_conn = Connection::open(&db_path).map_err(|e| format!("내보내기 DB 생성 실패: {}", e))?;
@@ -720,7 +921,7 @@ pub fn export_desktopcal_db(app: AppHandle, db_path: String) -> Result<ExportRes
 /// Only writes items that don't already exist. Skips desktopcal-origin items.
 #[tauri::command]
 pub fn sync_to_desktopcal(app: AppHandle, db_path: String) -> Result<ExportResult, String> {
-    let conn = get_connection(&app)?;
+    let conn = open_app_db(&app)?;
     let ext_conn = Connection::open(&db_path)
         .map_err(|e| format!("탁상달력 DB 열기 실패: {}", e))?;
     export_schedules_to_desktopcal_db(&conn, &ext_conn)
@@ -946,6 +1147,7 @@ mod tests {
             )",
             [],
         ).unwrap();
+        migrate_schedule_columns(&conn).unwrap();
         conn
     }
 
@@ -969,6 +1171,10 @@ mod tests {
             created_at: now.clone(),
             updated_at: now.clone(),
             is_deleted: false,
+            priority: None,
+            item_kind: None,
+            visible_until: None,
+            has_attachment: None,
         };
 
         // Create
@@ -982,7 +1188,7 @@ mod tests {
         // Update
         let mut updated_item = schedules[0].clone();
         updated_item.title = "Updated Title".to_string();
-        update_schedule_impl(&conn, id.clone(), updated_item.clone()).unwrap();
+        update_schedule_impl(&conn, id.clone(), updated_item.clone(), &ScheduleMetaPatch::from_item(&updated_item)).unwrap();
 
         let schedules_after_update = get_schedules_impl(&conn, "2000-01-01".to_string(), "2100-01-01".to_string(), false).unwrap();
         assert_eq!(schedules_after_update[0].title, "Updated Title");
@@ -993,6 +1199,107 @@ mod tests {
         assert!(schedules_after_delete.is_empty());
     }
 
+    fn ai_item(id: &str, kind: &str, priority: &str, visible_until: Option<&str>, has_attachment: bool) -> ScheduleItem {
+        let now = Utc::now().to_rfc3339();
+        ScheduleItem {
+            id: id.to_string(),
+            schedule_type: "manual_todo".to_string(),
+            title: id.to_string(),
+            content: None,
+            start_date: Some("2026-07-10".to_string()),
+            end_date: Some("2026-07-10".to_string()),
+            is_all_day: true,
+            reference_id: None,
+            color: None,
+            is_completed: false,
+            created_at: now.clone(),
+            updated_at: now,
+            is_deleted: false,
+            priority: Some(priority.to_string()),
+            item_kind: Some(kind.to_string()),
+            visible_until: visible_until.map(str::to_string),
+            has_attachment: Some(has_attachment),
+        }
+    }
+
+    #[test]
+    fn test_schedule_metadata_roundtrip_and_filter() {
+        let conn = setup_db();
+        create_schedule_impl(&conn, ai_item("a", "deadline", "high", Some("2026-07-10"), true)).unwrap();
+        create_schedule_impl(&conn, ai_item("b", "event", "medium", None, false)).unwrap();
+        create_schedule_impl(&conn, ai_item("c", "notice", "low", Some("2026-07-17"), false)).unwrap();
+
+        let rows = get_schedules_impl(&conn, "2000-01-01".to_string(), "2100-01-01".to_string(), false).unwrap();
+        let a = rows.iter().find(|r| r.id == "a").unwrap();
+        assert_eq!(a.priority.as_deref(), Some("high"));
+        assert_eq!(a.item_kind.as_deref(), Some("deadline"));
+        assert_eq!(a.has_attachment, Some(true));
+
+        let ids = |f: ScheduleFilter| {
+            let mut v: Vec<String> = filter_schedules(rows.clone(), &f).into_iter().map(|r| r.id).collect();
+            v.sort();
+            v
+        };
+        assert_eq!(ids(ScheduleFilter::default()), vec!["a", "b", "c"]);
+        assert_eq!(
+            ids(ScheduleFilter { item_kinds: Some(vec!["deadline".into(), "event".into()]), ..Default::default() }),
+            vec!["a", "b"]
+        );
+        assert_eq!(ids(ScheduleFilter { priorities: Some(vec!["low".into()]), ..Default::default() }), vec!["c"]);
+        assert_eq!(ids(ScheduleFilter { has_attachment: Some(true), ..Default::default() }), vec!["a"]);
+        // 노출 기간이 지난 항목(a)은 빠지고, 기간이 없는 항목(b)은 남는다.
+        assert_eq!(ids(ScheduleFilter { visible_on: Some("2026-07-12".into()), ..Default::default() }), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_update_without_metadata_preserves_it() {
+        let conn = setup_db();
+        create_schedule_impl(&conn, ai_item("a", "deadline", "high", Some("2026-07-10"), true)).unwrap();
+        // 메타데이터를 모르는 클라이언트가 제목만 고쳐 보낸다.
+        let mut edited = ai_item("a", "deadline", "high", None, true);
+        edited.title = "고친 제목".to_string();
+        edited.priority = None;
+        edited.item_kind = None;
+        edited.has_attachment = None;
+        let patch = ScheduleMetaPatch::from_item(&edited);
+        update_schedule_impl(&conn, "a".to_string(), edited, &patch).unwrap();
+
+        let rows = get_schedules_impl(&conn, "2000-01-01".to_string(), "2100-01-01".to_string(), false).unwrap();
+        assert_eq!(rows[0].title, "고친 제목");
+        assert_eq!(rows[0].priority.as_deref(), Some("high"));
+        assert_eq!(rows[0].visible_until.as_deref(), Some("2026-07-10"));
+        assert_eq!(rows[0].has_attachment, Some(true));
+        // 컬럼 추가는 멱등.
+        migrate_schedule_columns(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_update_with_null_metadata_clears_it() {
+        let conn = setup_db();
+        create_schedule_impl(&conn, ai_item("a", "deadline", "high", Some("2026-07-10"), true)).unwrap();
+        // 클라이언트가 priority·visibleUntil 을 null 로 보내고 나머지 메타데이터 키는 빠뜨렸다.
+        let mut json = serde_json::to_value(ai_item("a", "deadline", "high", None, true)).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.insert("priority".into(), serde_json::Value::Null);
+        obj.insert("visibleUntil".into(), serde_json::Value::Null);
+        obj.remove("itemKind");
+        obj.remove("hasAttachment");
+        let patch: ScheduleMetaPatch = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(patch.priority, Some(None));
+        assert_eq!(patch.item_kind, None);
+        let item: ScheduleItem = serde_json::from_value(json).unwrap();
+
+        let saved = update_schedule_impl(&conn, "a".to_string(), item, &patch).unwrap();
+        assert_eq!(saved.priority, None);
+        assert_eq!(saved.item_kind.as_deref(), Some("deadline"));
+
+        let rows = get_schedules_impl(&conn, "2000-01-01".to_string(), "2100-01-01".to_string(), false).unwrap();
+        assert_eq!(rows[0].priority, None);
+        assert_eq!(rows[0].visible_until, None);
+        assert_eq!(rows[0].item_kind.as_deref(), Some("deadline"));
+        assert_eq!(rows[0].has_attachment, Some(true));
+    }
+
     fn kst() -> chrono::FixedOffset {
         chrono::FixedOffset::east_opt(9 * 3600).unwrap()
     }
@@ -1036,6 +1343,10 @@ mod tests {
             created_at: "2025-05-15T00:00:00Z".to_string(),
             updated_at: "2025-05-15T00:00:00Z".to_string(),
             is_deleted: false,
+            priority: None,
+            item_kind: None,
+            visible_until: None,
+            has_attachment: None,
         }).unwrap();
 
         ext_conn.execute(
@@ -1077,6 +1388,10 @@ mod tests {
             created_at: "2025-05-15T00:00:00Z".to_string(),
             updated_at: "2025-05-15T00:00:00Z".to_string(),
             is_deleted: false,
+            priority: None,
+            item_kind: None,
+            visible_until: None,
+            has_attachment: None,
         }).unwrap();
 
         ext_conn.execute(
@@ -1128,6 +1443,10 @@ mod tests {
             created_at: "2025-05-14T00:00:00Z".to_string(),
             updated_at: "2025-05-14T00:00:00Z".to_string(),
             is_deleted: false,
+            priority: None,
+            item_kind: None,
+            visible_until: None,
+            has_attachment: None,
         }).unwrap();
 
         for (it_id, date_key) in [(10, "20250514"), (11, "20250515"), (12, "20250516")] {
@@ -1179,6 +1498,10 @@ pub fn migrate_registry_to_db_command(app: AppHandle) -> Result<String, String>
                     created_at: todo.created_at,
                     updated_at: todo.updated_at,
                     is_deleted: todo.is_deleted.unwrap_or(false),
+                    priority: None,
+                    item_kind: None,
+                    visible_until: None,
+                    has_attachment: None,
                 };
                 let _ = create_schedule(app.clone(), item);
                 count += 1;
@@ -1204,6 +1527,10 @@ pub fn migrate_registry_to_db_command(app: AppHandle) -> Result<String, String>
                     created_at: schedule.created_at,
                     updated_at: schedule.updated_at,
                     is_deleted: schedule.is_deleted.unwrap_or(false),
+                    priority: None,
+                    item_kind: None,
+                    visible_until: None,
+                    has_attachment: None,
                 };
                 let _ = create_schedule(app.clone(), item);
                 count += 1;
@@ -1254,6 +1581,10 @@ pub fn migrate_registry_to_db_command(app: AppHandle) -> Result<String, String>
                 created_at: now.clone(),
                 updated_at: now,
                 is_deleted: false,
+                priority: None,
+                item_kind: None,
+                visible_until: None,
+                has_attachment: None,
             };
             let _ = create_schedule(app.clone(), item);
             count += 1;
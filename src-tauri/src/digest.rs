@@ -0,0 +1,1081 @@
+//! 아침 다이제스트.
+//!
+//! 수업일 아침 정해진 시각(기본 07:50)에 오늘 일정, 다가오는 마감, 전날 할 일로 만들지 않은
+//! 업무성 메시지, 오늘 급식과 내 수업을 한 장으로 모은다. 결과는 Markdown/HTML 로 만들어
+//! `hypercool.db` 의 기록에 남기고 `digest-ready` 이벤트로 위젯에 알린다.
+//! 주간 모드를 켜면 월요일에는 한 주 일정을 모은 주간 다이제스트를 만든다.
+//!
+//! 기본은 템플릿만으로 만든다. 문장 요약(LLM)은 OpenAI 호환 백엔드가 설정돼 있을 때만
+//! 덧붙이며, 실패해도 다이제스트 자체는 그대로 남긴다. 사용량은 브리핑 예산에 함께 잡히고,
+//! 예산을 넘긴 동안에는 문장 요약 없이 만든다.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::bell_schedule::{self, BellSchedule};
+use crate::briefing_backend::{self, BackendKind, ExtractionBackend, OpenAiCompatBackend};
+use crate::briefing_runs::{RunEnd, RunRecorder};
+use crate::briefing_usage;
+use crate::briefing_rules::{self, Gate, RuleSet, RuleSubject};
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::db::{open_app_db, ScheduleItem};
+use crate::meal::MealData;
+use crate::timetable::{TimetableProvider, TimetableSource};
+
+const REG_ENABLED: &str = "DigestEnabled";
+const REG_TIME: &str = "DigestTime";
+const REG_WEEKLY: &str = "DigestWeekly";
+const REG_USE_LLM: &str = "DigestUseLlm";
+const REG_LAST_DATE: &str = "DigestLastDate";
+
+const DEFAULT_TIME: &str = "07:50";
+/// 스케줄러 확인 주기(초).
+const TICK_SECS: u64 = 30;
+/// 보관하는 다이제스트 수(초과분은 오래된 것부터 지운다).
+const KEEP_DIGESTS: i64 = 120;
+/// 다가오는 마감을 볼 기간(일): 일간 / 주간.
+const DEADLINE_DAYS: i64 = 7;
+const WEEKLY_DEADLINE_DAYS: i64 = 14;
+/// 바로 앞 수업일을 찾을 때 거슬러 보는 최대 일수(방학이면 주말만 건너뛴 날로 대신한다).
+const LOOKBACK_DAYS: i64 = 31;
+/// 미처리 메시지 최대 표시 수.
+const UNHANDLED_LIMIT: usize = 10;
+/// 미처리 메시지 미리보기 글자 수.
+const PREVIEW_CHARS: usize = 60;
+
+static GENERATING: AtomicBool = AtomicBool::new(false);
+
+// ─── 설정 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestSettings {
+    pub enabled: bool,
+    /// 생성 시각("HH:MM", 한국 시간).
+    pub time: String,
+    /// 월요일에는 주간 다이제스트를 만든다.
+    pub weekly: bool,
+    /// OpenAI 호환 백엔드로 문장 요약을 덧붙인다.
+    pub use_llm: bool,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        DigestSettings {
+            enabled: false,
+            time: DEFAULT_TIME.to_string(),
+            weekly: false,
+            use_llm: false,
+        }
+    }
+}
+
+fn read_reg(key: &str) -> Option<String> {
+    get_registry_value(key.to_string())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+pub fn load_settings() -> DigestSettings {
+    let flag = |key: &str| read_reg(key).is_some_and(|v| v == "true");
+    DigestSettings {
+        enabled: flag(REG_ENABLED),
+        time: read_reg(REG_TIME)
+            .filter(|t| parse_time(t).is_some())
+            .unwrap_or_else(|| DEFAULT_TIME.to_string()),
+        weekly: flag(REG_WEEKLY),
+        use_llm: flag(REG_USE_LLM),
+    }
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+#[tauri::command]
+pub fn get_digest_settings() -> DigestSettings {
+    load_settings()
+}
+
+#[tauri::command]
+pub fn set_digest_settings(settings: DigestSettings) -> Result<(), String> {
+    let time = parse_time(&settings.time)
+        .ok_or_else(|| format!("생성 시각 형식이 올바르지 않습니다(HH:MM): {}", settings.time))?;
+    let flag = |v: bool| if v { "true" } else { "false" }.to_string();
+    set_registry_value(REG_ENABLED.to_string(), flag(settings.enabled))?;
+    set_registry_value(REG_TIME.to_string(), time.format("%H:%M").to_string())?;
+    set_registry_value(REG_WEEKLY.to_string(), flag(settings.weekly))?;
+    set_registry_value(REG_USE_LLM.to_string(), flag(settings.use_llm))
+}
+
+// ─── 스케줄 ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestKind {
+    Daily,
+    Weekly,
+}
+
+impl DigestKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            DigestKind::Daily => "daily",
+            DigestKind::Weekly => "weekly",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        if s == "weekly" {
+            DigestKind::Weekly
+        } else {
+            DigestKind::Daily
+        }
+    }
+}
+
+fn is_weekday(d: NaiveDate) -> bool {
+    !matches!(d.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// 수업일 판정: 주말, 학사일정의 휴업일, 시간표상 방학을 뺀다.
+#[derive(Default)]
+struct SchoolCalendar {
+    holidays: HashSet<NaiveDate>,
+    timetable: Option<Arc<dyn TimetableProvider>>,
+}
+
+impl SchoolCalendar {
+    /// `from`~`to` 의 휴업일과 설정된 시간표를 읽는다. 읽지 못한 쪽은 없는 것으로 본다.
+    fn load(app: &AppHandle, from: NaiveDate, to: NaiveDate) -> Self {
+        SchoolCalendar {
+            holidays: open_app_db(app)
+                .and_then(|conn| crate::timetable_export::load_holidays(&conn, from, to))
+                .unwrap_or_default(),
+            timetable: TimetableSource::from_registry().load().ok(),
+        }
+    }
+
+    fn is_school_day(&self, d: NaiveDate) -> bool {
+        is_weekday(d) && !self.holidays.contains(&d) && !self.timetable.as_ref().is_some_and(|t| t.is_break(d))
+    }
+}
+
+/// 지금 만들어야 할 다이제스트 종류. 수업일에 설정 시각이 지났고 오늘 아직 만들지
+/// 않았으면 만든다. 앱이 늦게 켜져도 그날 한 번은 만든다.
+/// 수업일 판정(`is_school_day`)은 DB 를 읽으므로 나머지 조건을 모두 통과했을 때만 부른다.
+fn due_kind(
+    now: NaiveDateTime,
+    settings: &DigestSettings,
+    last_date: Option<&str>,
+    is_school_day: impl FnOnce(NaiveDate) -> bool,
+) -> Option<DigestKind> {
+    if !settings.enabled || !is_weekday(now.date()) {
+        return None;
+    }
+    let at = parse_time(&settings.time)?;
+    if now.time() < at {
+        return None;
+    }
+    let today = now.date().format("%Y-%m-%d").to_string();
+    if last_date == Some(today.as_str()) || !is_school_day(now.date()) {
+        return None;
+    }
+    Some(if settings.weekly && now.date().weekday() == Weekday::Mon {
+        DigestKind::Weekly
+    } else {
+        DigestKind::Daily
+    })
+}
+
+/// 바로 앞 수업일(월요일이면 지난 금요일, 휴업일·방학은 건너뛴다).
+/// `LOOKBACK_DAYS` 안에 없으면 주말만 건너뛴 앞날을 쓴다.
+fn previous_school_day(d: NaiveDate, calendar: &SchoolCalendar) -> NaiveDate {
+    let before = |f: &dyn Fn(NaiveDate) -> bool| {
+        (1..=LOOKBACK_DAYS).map(|n| d - chrono::Duration::days(n)).find(|p| f(*p))
+    };
+    before(&|p| calendar.is_school_day(p))
+        .or_else(|| before(&is_weekday))
+        .unwrap_or(d)
+}
+
+fn seoul_now() -> NaiveDateTime {
+    let kst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+    chrono::Utc::now().with_timezone(&kst).naive_local()
+}
+
+/// 앱 시작 시 한 번 호출한다. 주기적으로 확인해 때가 되면 다이제스트를 만든다.
+pub fn start_scheduler(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(TICK_SECS));
+        let settings = load_settings();
+        let now = seoul_now();
+        let last = read_reg(REG_LAST_DATE);
+        let is_school_day = |d| SchoolCalendar::load(&app, d, d).is_school_day(d);
+        let Some(kind) = due_kind(now, &settings, last.as_deref(), is_school_day) else {
+            continue;
+        };
+        let date = now.date().format("%Y-%m-%d").to_string();
+        // 실패해도 같은 날 계속 재시도하지 않도록 먼저 기록한다.
+        let _ = set_registry_value(REG_LAST_DATE.to_string(), date);
+        if let Err(e) = generate(&app, now.date(), kind, &settings) {
+            eprintln!("[digest] 다이제스트 생성 실패: {}", e);
+        }
+    });
+}
+
+// ─── 수집 ─────────────────────────────────────────────────────────────────────
+
+/// 다이제스트에 넣는 일정 한 건.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestEntry {
+    pub id: String,
+    /// YYYY-MM-DD
+    pub date: String,
+    /// HH:MM(종일이면 없음)
+    pub time: Option<String>,
+    pub title: String,
+    pub item_kind: Option<String>,
+    pub priority: Option<String>,
+}
+
+/// 할 일로 만들지 않은 업무성 메시지 한 건.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestMessage {
+    pub id: i64,
+    pub sender: String,
+    pub preview: String,
+}
+
+/// 오늘 내 수업 한 칸.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestLesson {
+    pub period: u32,
+    pub start: Option<String>,
+    pub subject: String,
+    pub room: String,
+}
+
+/// 렌더링 전 다이제스트 내용.
+#[derive(Debug, Default)]
+struct DigestData {
+    date: Option<NaiveDate>,
+    weekly: bool,
+    agenda: Vec<DigestEntry>,
+    deadlines: Vec<DigestEntry>,
+    unhandled_day: Option<NaiveDate>,
+    unhandled: Vec<DigestMessage>,
+    unhandled_total: usize,
+    meal: Option<MealData>,
+    teacher: Option<String>,
+    lessons: Vec<DigestLesson>,
+    /// 수집하지 못한 항목 등 참고 사항.
+    notes: Vec<String>,
+}
+
+fn entry_of(s: &ScheduleItem) -> Option<DigestEntry> {
+    let start = s.start_date.as_deref()?;
+    let (date, time) = match chrono::DateTime::parse_from_rfc3339(start) {
+        Ok(dt) => {
+            let kst = dt.with_timezone(&chrono::FixedOffset::east_opt(9 * 3600).unwrap());
+            let time = (!s.is_all_day).then(|| kst.format("%H:%M").to_string());
+            (kst.date_naive().format("%Y-%m-%d").to_string(), time)
+        }
+        Err(_) => (start.get(..10)?.to_string(), None),
+    };
+    Some(DigestEntry {
+        id: s.id.clone(),
+        date,
+        time,
+        title: s.title.clone(),
+        item_kind: s.item_kind.clone(),
+        priority: s.priority.clone(),
+    })
+}
+
+fn sort_entries(v: &mut [DigestEntry]) {
+    v.sort_by(|a, b| {
+        (&a.date, a.time.is_some(), &a.time, &a.title).cmp(&(&b.date, b.time.is_some(), &b.time, &b.title))
+    });
+}
+
+/// (오늘 또는 이번 주 일정, 다가오는 마감). 완료·삭제된 일정은 뺀다.
+/// 마감은 기한형이거나 중요도가 높은 일정 중 일정 목록에 이미 들어간 것을 제외한다.
+fn collect_schedules(
+    conn: &Connection,
+    date: NaiveDate,
+    kind: DigestKind,
+) -> Result<(Vec<DigestEntry>, Vec<DigestEntry>), String> {
+    let (agenda_days, deadline_days) = match kind {
+        DigestKind::Daily => (1, DEADLINE_DAYS),
+        DigestKind::Weekly => (7, WEEKLY_DEADLINE_DAYS),
+    };
+    let day = |n: i64| (date + chrono::Duration::days(n)).format("%Y-%m-%d").to_string();
+    let range = |from: i64, to: i64| -> Result<Vec<DigestEntry>, String> {
+        let items = crate::db::get_schedules_impl(conn, day(from), format!("{}T23:59:59", day(to)), false)?;
+        Ok(items
+            .iter()
+            .filter(|s| !s.is_completed)
+            .filter_map(entry_of)
+            .collect())
+    };
+
+    let mut agenda = range(0, agenda_days - 1)?;
+    // 여러 날에 걸친 일정이 범위 앞쪽에서 시작했을 수 있으므로 날짜는 범위 안으로 당긴다.
+    let first = day(0);
+    for e in &mut agenda {
+        if e.date < first {
+            e.date = first.clone();
+            e.time = None;
+        }
+    }
+    sort_entries(&mut agenda);
+
+    let mut deadlines: Vec<DigestEntry> = range(1, deadline_days)?
+        .into_iter()
+        .filter(|e| e.item_kind.as_deref() == Some("deadline") || e.priority.as_deref() == Some("high"))
+        .filter(|e| !agenda.iter().any(|a| a.id == e.id))
+        .collect();
+    sort_entries(&mut deadlines);
+    Ok((agenda, deadlines))
+}
+
+/// `day` 에 받은 메시지 중 기한·참석 표현이 있는데 할 일로 만들어지지 않은 것.
+/// 브리핑 규칙에서 제외한 메시지는 넣지 않는다. 반환: (앞쪽 일부, 전체 수).
+fn collect_unhandled(
+    search: &Connection,
+    schedules: &Connection,
+    day: NaiveDate,
+    rules: &RuleSet,
+) -> Result<(Vec<DigestMessage>, usize), String> {
+    let mut stmt = search
+        .prepare(
+            "SELECT id, sender, content_text, COALESCE(file_paths, '') FROM messages
+             WHERE substr(COALESCE(receive_date, ''), 1, 10) = ?1 ORDER BY id",
+        )
+        .map_err(|e| format!("메시지 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map([day.format("%Y-%m-%d").to_string()], |r| {
+            Ok((r.get::<_, i64>(0)?, r.get::<_, String>(1)?, r.get::<_, String>(2)?, r.get::<_, String>(3)?))
+        })
+        .map_err(|e| format!("메시지 조회 실패: {}", e))?;
+
+    let mut handled = schedules
+        .prepare("SELECT 1 FROM tbl_schedules WHERE reference_id = ?1 OR id = ?2 LIMIT 1")
+        .map_err(|e| format!("일정 조회 실패: {}", e))?;
+    let mut out = Vec::new();
+    let mut total = 0;
+    for (id, sender, text, files) in rows.flatten() {
+        if !briefing_backend::looks_actionable(&text) {
+            continue;
+        }
+        let attachments: Vec<String> = serde_json::from_str(&files).unwrap_or_default();
+        let subject = RuleSubject { sender: &sender, text: &text, attachments: &attachments };
+        if matches!(rules.gate(&subject), Gate::Skip(_)) {
+            continue;
+        }
+        let has_todo = handled
+            .query_row(params![id.to_string(), format!("msg-{}", id)], |_| Ok(()))
+            .optional()
+            .map_err(|e| format!("일정 조회 실패: {}", e))?
+            .is_some();
+        if has_todo {
+            continue;
+        }
+        total += 1;
+        if out.len() < UNHANDLED_LIMIT {
+            out.push(DigestMessage { id, sender, preview: preview_of(&text) });
+        }
+    }
+    Ok((out, total))
+}
+
+fn preview_of(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+    let mut p: String = line.chars().take(PREVIEW_CHARS).collect();
+    if line.chars().count() > PREVIEW_CHARS {
+        p.push('…');
+    }
+    p
+}
+
+/// `teacher` 의 그날 수업(교실이 없으면 반 이름을 쓴다). 시작 시각은 그날 종 시간표로 채운다.
+fn teacher_lessons(
+    timetable: &dyn TimetableProvider,
+    bells: &BellSchedule,
+    teacher: &str,
+    date: NaiveDate,
+) -> Vec<DigestLesson> {
+    let bells = bells.profile_for(date);
+    timetable
+        .teacher_lessons(teacher, date)
+        .iter()
+        .map(|l| DigestLesson {
+            period: l.period,
+            start: bells.period_start(l.period).map(|t| t.format("%H:%M").to_string()),
+            subject: l.subject.clone(),
+            room: l.place().unwrap_or("").to_string(),
+        })
+        .collect()
+}
+
+fn collect(app: &AppHandle, date: NaiveDate, kind: DigestKind) -> Result<DigestData, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let conn = open_app_db(app)?;
+    let mut data = DigestData { date: Some(date), weekly: kind == DigestKind::Weekly, ..Default::default() };
+
+    let (agenda, deadlines) = collect_schedules(&conn, date, kind)?;
+    data.agenda = agenda;
+    data.deadlines = deadlines;
+
+    let calendar = SchoolCalendar::load(app, date - chrono::Duration::days(LOOKBACK_DAYS), date);
+    let prev = previous_school_day(date, &calendar);
+    data.unhandled_day = Some(prev);
+    let search_path = dir.join("hypercool_search.db");
+    if search_path.exists() {
+        let rules = briefing_rules::load_rules(app);
+        match Connection::open(&search_path)
+            .map_err(|e| format!("검색 DB 연결 실패: {}", e))
+            .and_then(|search| collect_unhandled(&search, &conn, prev, &rules))
+        {
+            Ok((messages, total)) => {
+                data.unhandled = messages;
+                data.unhandled_total = total;
+            }
+            Err(e) => data.notes.push(e),
+        }
+    } else {
+        data.notes.push("검색 DB 가 없어 미처리 메시지를 확인하지 못했습니다.".to_string());
+    }
+
+    if crate::neis::SchoolInfo::load().is_some() {
+        match crate::meal::load_meals(&conn, date, date) {
+            Ok(mut days) => data.meal = days.pop(),
+            Err(e) => data.notes.push(format!("급식 정보를 가져오지 못했습니다: {}", e)),
+        }
+    } else {
+        data.notes.push("학교 위젯 설정에 학교가 없어 급식을 넣지 않았습니다.".to_string());
+    }
+
+    if let Some(teacher) = read_reg("SchoolDefaultTeacher") {
+        let bells = bell_schedule::load();
+        match TimetableSource::from_registry().load().map(|t| teacher_lessons(t.as_ref(), &bells, &teacher, date)) {
+            Ok(l) => data.lessons = l,
+            Err(e) => data.notes.push(format!("시간표를 읽지 못했습니다: {}", e)),
+        }
+        data.teacher = Some(teacher);
+    }
+    Ok(data)
+}
+
+// ─── 렌더링 ───────────────────────────────────────────────────────────────────
+
+const WEEKDAYS: [&str; 7] = ["월", "화", "수", "목", "금", "토", "일"];
+
+fn md_date(d: NaiveDate) -> String {
+    format!("{}/{}({})", d.month(), d.day(), WEEKDAYS[d.weekday().num_days_from_monday() as usize])
+}
+
+fn entry_date(e: &DigestEntry) -> String {
+    NaiveDate::parse_from_str(&e.date, "%Y-%m-%d").map(md_date).unwrap_or_else(|_| e.date.clone())
+}
+
+fn entry_line(e: &DigestEntry, with_date: bool) -> String {
+    let mut s = String::new();
+    if with_date {
+        s.push_str(&entry_date(e));
+        s.push(' ');
+    }
+    s.push_str(e.time.as_deref().unwrap_or("종일"));
+    s.push(' ');
+    s.push_str(&e.title);
+    if e.priority.as_deref() == Some("high") {
+        s.push_str(" (중요)");
+    }
+    s
+}
+
+fn lesson_line(l: &DigestLesson) -> String {
+    let mut s = format!("{}교시", l.period);
+    if let Some(t) = &l.start {
+        s.push_str(&format!(" {}", t));
+    }
+    s.push_str(&format!(" {}", l.subject));
+    if !l.room.is_empty() {
+        s.push_str(&format!(" ({})", l.room));
+    }
+    s
+}
+
+/// 섹션(제목, 줄 목록). 두 렌더러가 같은 내용을 쓰도록 한 곳에서 만든다.
+struct Section {
+    heading: String,
+    items: Vec<String>,
+}
+
+fn title_of(data: &DigestData) -> String {
+    let date = data.date.map(md_date).unwrap_or_default();
+    if data.weekly {
+        format!("{} 주간 브리핑", date)
+    } else {
+        format!("{} 아침 브리핑", date)
+    }
+}
+
+fn sections(data: &DigestData) -> Vec<Section> {
+    let mut out = Vec::new();
+    let none = || vec!["없음".to_string()];
+
+    let agenda: Vec<String> = data.agenda.iter().map(|e| entry_line(e, data.weekly)).collect();
+    out.push(Section {
+        heading: if data.weekly { "이번 주 일정" } else { "오늘 일정" }.to_string(),
+        items: if agenda.is_empty() { none() } else { agenda },
+    });
+
+    if !data.deadlines.is_empty() {
+        let days = if data.weekly { WEEKLY_DEADLINE_DAYS } else { DEADLINE_DAYS };
+        out.push(Section {
+            heading: format!("다가오는 마감 ({}일)", days),
+            items: data.deadlines.iter().map(|e| entry_line(e, true)).collect(),
+        });
+    }
+
+    if let Some(day) = data.unhandled_day {
+        if data.unhandled_total > 0 {
+            let mut items: Vec<String> =
+                data.unhandled.iter().map(|m| format!("[{}] {}", m.sender, m.preview)).collect();
+            if data.unhandled_total > data.unhandled.len() {
+                items.push(format!("외 {}건", data.unhandled_total - data.unhandled.len()));
+            }
+            out.push(Section {
+                heading: format!("{} 받은 메시지 중 할 일로 만들지 않은 것 ({}건)", md_date(day), data.unhandled_total),
+                items,
+            });
+        }
+    }
+
+    if let Some(meal) = &data.meal {
+        let mut items: Vec<String> = meal.meals.iter().map(|m| format!("{}: {}", m.label, m.menu())).collect();
+        if items.is_empty() {
+            items.push("급식 없음".to_string());
+        }
+        items.extend(meal.alerts.iter().map(|a| {
+            let names: Vec<&str> = a.allergens.iter().map(|x| x.name.as_str()).collect();
+            format!("알레르기 주의: {} ({})", a.dish, names.join(", "))
+        }));
+        out.push(Section { heading: "오늘 급식".to_string(), items });
+    }
+
+    if let Some(teacher) = &data.teacher {
+        let items: Vec<String> = data.lessons.iter().map(lesson_line).collect();
+        out.push(Section {
+            heading: format!("오늘 수업 ({})", teacher),
+            items: if items.is_empty() { vec!["수업 없음".to_string()] } else { items },
+        });
+    }
+
+    if !data.notes.is_empty() {
+        out.push(Section { heading: "참고".to_string(), items: data.notes.clone() });
+    }
+    out
+}
+
+fn render_markdown(data: &DigestData, prose: Option<&str>) -> String {
+    let mut md = format!("# {}\n", title_of(data));
+    if let Some(p) = prose.map(str::trim).filter(|p| !p.is_empty()) {
+        md.push('\n');
+        md.push_str(p);
+        md.push('\n');
+    }
+    for s in sections(data) {
+        md.push_str(&format!("\n## {}\n", s.heading));
+        for item in &s.items {
+            md.push_str(&format!("- {}\n", item));
+        }
+    }
+    md
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn render_html(data: &DigestData, prose: Option<&str>) -> String {
+    let mut html = format!("<h1>{}</h1>", html_escape(&title_of(data)));
+    if let Some(p) = prose.map(str::trim).filter(|p| !p.is_empty()) {
+        for para in p.split("\n\n").map(str::trim).filter(|x| !x.is_empty()) {
+            html.push_str(&format!("<p>{}</p>", html_escape(para).replace('\n', "<br>")));
+        }
+    }
+    for s in sections(data) {
+        html.push_str(&format!("<h2>{}</h2><ul>", html_escape(&s.heading)));
+        for item in &s.items {
+            html.push_str(&format!("<li>{}</li>", html_escape(item)));
+        }
+        html.push_str("</ul>");
+    }
+    html
+}
+
+/// 문장 요약 요청 프롬프트. 목록에 없는 사실을 만들지 않게 한다.
+fn prose_prompt(markdown: &str) -> String {
+    format!(
+        "아래는 교사 한 명의 오늘 아침 브리핑이다. 출근한 교사에게 건네는 말처럼 3~4문장으로 \
+         요약해라. 가장 급한 일부터 말하고, 목록에 없는 사실·날짜는 절대 지어내지 않는다. \
+         인사말이나 머리말 없이 요약 문장만 출력한다.\n\n{}",
+        markdown
+    )
+}
+
+/// 문장 요약을 만든다. 브리핑 예산을 넘었으면 부르지 않고, 사용량은 "digest" 실행 기록으로 남긴다.
+fn llm_prose(app: &AppHandle, markdown: &str) -> Result<String, String> {
+    let settings = briefing_backend::load_settings();
+    if settings.kind != BackendKind::OpenaiCompat {
+        return Err("문장 요약은 OpenAI 호환 백엔드를 설정했을 때만 쓸 수 있습니다.".to_string());
+    }
+    if let Some(reason) = briefing_usage::paused_reason(app) {
+        return Err(reason);
+    }
+    let backend = OpenAiCompatBackend::from_settings(&settings);
+    let recorder = RunRecorder::start(app, "digest", backend.name(), 0);
+    let result = backend.complete(&prose_prompt(markdown));
+    recorder.finish(&match &result {
+        Ok((text, usage)) => RunEnd { raw_output: Some(text.clone()), usage: usage.clone(), ..Default::default() },
+        Err(e) => RunEnd { error: Some(e.clone()), ..Default::default() },
+    });
+    result.map(|(text, _)| text)
+}
+
+// ─── 기록 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Digest {
+    pub id: i64,
+    pub kind: DigestKind,
+    /// 대상 날짜(YYYY-MM-DD)
+    pub digest_date: String,
+    pub created_at: String,
+    pub markdown: String,
+    pub html: String,
+    /// LLM 문장 요약(있을 때만).
+    pub prose: Option<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestSummary {
+    pub id: i64,
+    pub kind: DigestKind,
+    pub digest_date: String,
+    pub created_at: String,
+    pub has_prose: bool,
+}
+
+pub fn ensure_table(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_digests (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            digest_date TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            markdown TEXT NOT NULL,
+            html TEXT NOT NULL,
+            prose TEXT,
+            notes TEXT NOT NULL DEFAULT '[]'
+        );
+        CREATE INDEX IF NOT EXISTS idx_digests_date ON tbl_digests(digest_date);",
+    )
+    .map_err(|e| format!("다이제스트 테이블 생성 실패: {}", e))
+}
+
+fn save_digest(conn: &Connection, mut digest: Digest) -> Result<Digest, String> {
+    ensure_table(conn)?;
+    conn.execute(
+        "INSERT INTO tbl_digests (kind, digest_date, created_at, markdown, html, prose, notes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            digest.kind.as_str(),
+            digest.digest_date,
+            digest.created_at,
+            digest.markdown,
+            digest.html,
+            digest.prose,
+            serde_json::to_string(&digest.notes).unwrap_or_else(|_| "[]".to_string()),
+        ],
+    )
+    .map_err(|e| format!("다이제스트 저장 실패: {}", e))?;
+    digest.id = conn.last_insert_rowid();
+    conn.execute(
+        "DELETE FROM tbl_digests WHERE id NOT IN (SELECT id FROM tbl_digests ORDER BY id DESC LIMIT ?1)",
+        [KEEP_DIGESTS],
+    )
+    .map_err(|e| format!("오래된 다이제스트 정리 실패: {}", e))?;
+    Ok(digest)
+}
+
+fn list_digests_in(conn: &Connection, limit: i64) -> Result<Vec<DigestSummary>, String> {
+    ensure_table(conn)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, kind, digest_date, created_at, prose IS NOT NULL AND prose != ''
+             FROM tbl_digests ORDER BY id DESC LIMIT ?1",
+        )
+        .map_err(|e| format!("다이제스트 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map([limit], |r| {
+            Ok(DigestSummary {
+                id: r.get(0)?,
+                kind: DigestKind::parse(&r.get::<_, String>(1)?),
+                digest_date: r.get(2)?,
+                created_at: r.get(3)?,
+                has_prose: r.get(4)?,
+            })
+        })
+        .map_err(|e| format!("다이제스트 조회 실패: {}", e))?;
+    Ok(rows.flatten().collect())
+}
+
+/// `id` 가 없으면 가장 최근 것.
+fn get_digest_in(conn: &Connection, id: Option<i64>) -> Result<Option<Digest>, String> {
+    ensure_table(conn)?;
+    conn.query_row(
+        "SELECT id, kind, digest_date, created_at, markdown, html, prose, notes FROM tbl_digests
+         WHERE ?1 IS NULL OR id = ?1 ORDER BY id DESC LIMIT 1",
+        [id],
+        |r| {
+            Ok(Digest {
+                id: r.get(0)?,
+                kind: DigestKind::parse(&r.get::<_, String>(1)?),
+                digest_date: r.get(2)?,
+                created_at: r.get(3)?,
+                markdown: r.get(4)?,
+                html: r.get(5)?,
+                prose: r.get(6)?,
+                notes: serde_json::from_str(&r.get::<_, String>(7)?).unwrap_or_default(),
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| format!("다이제스트 조회 실패: {}", e))
+}
+
+// ─── 생성 ─────────────────────────────────────────────────────────────────────
+
+/// 다이제스트를 만들어 기록하고 `digest-ready` 이벤트를 보낸다(동시에 한 번만).
+fn generate(app: &AppHandle, date: NaiveDate, kind: DigestKind, settings: &DigestSettings) -> Result<Digest, String> {
+    if GENERATING.swap(true, Ordering::SeqCst) {
+        return Err("다이제스트를 이미 만드는 중입니다.".to_string());
+    }
+    let result = generate_inner(app, date, kind, settings);
+    GENERATING.store(false, Ordering::SeqCst);
+    let digest = result?;
+    let _ = app.emit("digest-ready", &digest);
+    Ok(digest)
+}
+
+fn generate_inner(app: &AppHandle, date: NaiveDate, kind: DigestKind, settings: &DigestSettings) -> Result<Digest, String> {
+    let mut data = collect(app, date, kind)?;
+    let mut prose = None;
+    if settings.use_llm {
+        match llm_prose(app, &render_markdown(&data, None)) {
+            Ok(p) => prose = Some(p.trim().to_string()).filter(|p| !p.is_empty()),
+            Err(e) => data.notes.push(format!("문장 요약을 만들지 못했습니다: {}", e)),
+        }
+    }
+    let digest = Digest {
+        id: 0,
+        kind,
+        digest_date: date.format("%Y-%m-%d").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        markdown: render_markdown(&data, prose.as_deref()),
+        html: render_html(&data, prose.as_deref()),
+        prose,
+        notes: data.notes,
+    };
+    save_digest(&open_app_db(app)?, digest)
+}
+
+/// 지금 바로 만든다(설정의 on/off 와 무관). `kind` 가 없으면 일간.
+#[tauri::command]
+pub async fn generate_digest_now(app: AppHandle, kind: Option<DigestKind>) -> Result<Digest, String> {
+    let app2 = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let now = seoul_now();
+        generate(&app2, now.date(), kind.unwrap_or(DigestKind::Daily), &load_settings())
+    })
+    .await
+    .map_err(|e| format!("실행 작업 실패: {}", e))?
+}
+
+#[tauri::command]
+pub fn list_digests(app: AppHandle, limit: Option<i64>) -> Result<Vec<DigestSummary>, String> {
+    list_digests_in(&open_app_db(&app)?, limit.unwrap_or(30).clamp(1, KEEP_DIGESTS))
+}
+
+/// `id` 가 없으면 가장 최근 다이제스트(위젯 첫 표시용).
+#[tauri::command]
+pub fn get_digest(app: AppHandle, id: Option<i64>) -> Result<Option<Digest>, String> {
+    get_digest_in(&open_app_db(&app)?, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn due_only_on_school_days_after_time_once_per_day() {
+        let s = DigestSettings { enabled: true, weekly: true, ..Default::default() };
+        assert_eq!(due_kind(at("2026-03-04 07:49"), &s, None, is_weekday), None);
+        assert_eq!(due_kind(at("2026-03-04 07:50"), &s, None, is_weekday), Some(DigestKind::Daily));
+        assert_eq!(due_kind(at("2026-03-04 15:00"), &s, Some("2026-03-03"), is_weekday), Some(DigestKind::Daily));
+        assert_eq!(due_kind(at("2026-03-04 15:00"), &s, Some("2026-03-04"), is_weekday), None);
+        // 월요일은 주간, 주말·휴업일은 만들지 않는다.
+        assert_eq!(due_kind(at("2026-03-02 08:00"), &s, None, is_weekday), Some(DigestKind::Weekly));
+        assert_eq!(due_kind(at("2026-03-07 08:00"), &s, None, is_weekday), None);
+        assert_eq!(due_kind(at("2026-03-04 08:00"), &s, None, |_| false), None);
+        assert_eq!(due_kind(at("2026-03-04 08:00"), &DigestSettings::default(), None, is_weekday), None);
+
+        let calendar = SchoolCalendar::default();
+        assert_eq!(previous_school_day(d("2026-03-02"), &calendar), d("2026-02-27"));
+        assert_eq!(previous_school_day(d("2026-03-04"), &calendar), d("2026-03-03"));
+    }
+
+    #[test]
+    fn school_days_skip_holidays_and_timetable_breaks() {
+        let appin = crate::appin_parser::AppinTimetableData {
+            teachers: Vec::new(),
+            subjects: Vec::new(),
+            classes: Vec::new(),
+            events: Vec::new(),
+            days: Default::default(),
+            events_by_date_class: Default::default(),
+            events_by_date_grade: Default::default(),
+            start_date: "2026-03-02".to_string(),
+            terms: Vec::new(),
+        };
+        let calendar = SchoolCalendar {
+            holidays: [d("2026-03-03")].into_iter().collect(),
+            timetable: Some(Arc::new(appin)),
+        };
+        assert!(calendar.is_school_day(d("2026-03-04")));
+        assert!(!calendar.is_school_day(d("2026-03-03")));
+        // 학년도 시작 전은 시간표상 방학이다.
+        assert!(!calendar.is_school_day(d("2026-02-27")));
+        assert_eq!(previous_school_day(d("2026-03-04"), &calendar), d("2026-03-02"));
+        // 거슬러 볼 범위에 수업일이 없으면 주말만 건너뛴다.
+        assert_eq!(previous_school_day(d("2026-03-02"), &calendar), d("2026-02-27"));
+    }
+
+    fn sched_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::ensure_schedule_table(&conn).unwrap();
+        conn
+    }
+
+    fn add(conn: &Connection, id: &str, title: &str, start: &str, all_day: bool, kind: Option<&str>, done: bool) {
+        let now = "2026-03-01T00:00:00.000Z".to_string();
+        crate::db::create_schedule_impl(
+            conn,
+            ScheduleItem {
+                id: id.to_string(),
+                schedule_type: "manual_todo".to_string(),
+                title: title.to_string(),
+                content: None,
+                start_date: Some(start.to_string()),
+                end_date: Some(start.to_string()),
+                is_all_day: all_day,
+                reference_id: id.strip_prefix("msg-").map(str::to_string),
+                color: None,
+                is_completed: done,
+                created_at: now.clone(),
+                updated_at: now,
+                is_deleted: false,
+                priority: None,
+                item_kind: kind.map(str::to_string),
+                visible_until: None,
+                has_attachment: None,
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn collects_today_and_upcoming_deadlines() {
+        let conn = sched_conn();
+        add(&conn, "a", "학부모 총회 안내", "2026-03-04", true, Some("event"), false);
+        add(&conn, "b", "교직원 회의", "2026-03-04T15:00:00+09:00", false, Some("event"), false);
+        add(&conn, "c", "끝난 일", "2026-03-04", true, None, true);
+        add(&conn, "d", "보고서 제출", "2026-03-06", true, Some("deadline"), false);
+        add(&conn, "e", "연수", "2026-03-06", true, Some("event"), false);
+        add(&conn, "f", "먼 마감", "2026-03-20", true, Some("deadline"), false);
+
+        let (agenda, deadlines) = collect_schedules(&conn, d("2026-03-04"), DigestKind::Daily).unwrap();
+        let titles: Vec<_> = agenda.iter().map(|e| (e.title.as_str(), e.time.as_deref())).collect();
+        assert_eq!(titles, vec![("학부모 총회 안내", None), ("교직원 회의", Some("15:00"))]);
+        assert_eq!(deadlines.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), vec!["d"]);
+
+        let (week, _) = collect_schedules(&conn, d("2026-03-02"), DigestKind::Weekly).unwrap();
+        assert_eq!(week.len(), 4);
+    }
+
+    #[test]
+    fn unhandled_skips_messages_with_todo_or_rule() {
+        let sched = sched_conn();
+        add(&sched, "msg-11", "보고서 제출", "2026-03-06", true, Some("deadline"), false);
+        let search = Connection::open_in_memory().unwrap();
+        search
+            .execute_batch(
+                "CREATE TABLE messages (id INTEGER PRIMARY KEY, sender TEXT, content_text TEXT,
+                    receive_date TEXT, file_paths TEXT);
+                 INSERT INTO messages VALUES (10, '교무부', '수업 공개 계획서를 금요일까지 제출해 주세요.', '2026-03-03 09:00:00', '[]');
+                 INSERT INTO messages VALUES (11, '교무부', '보고서를 3/6까지 제출', '2026-03-03 10:00:00', '[]');
+                 INSERT INTO messages VALUES (12, '행정실', '급식 설문 제출 부탁드립니다', '2026-03-03 11:00:00', '[]');
+                 INSERT INTO messages VALUES (13, '동료', '오늘 고생 많으셨습니다', '2026-03-03 17:00:00', '[]');
+                 INSERT INTO messages VALUES (14, '교무부', '연수 신청 안내', '2026-03-04 08:00:00', '[]');",
+            )
+            .unwrap();
+        let rules = RuleSet::new(vec![briefing_rules::BriefingRule {
+            id: 0,
+            name: "행정실".to_string(),
+            enabled: true,
+            position: 0,
+            sender: Some("행정실".to_string()),
+            keywords: None,
+            pattern: None,
+            attachment_ext: None,
+            action: briefing_rules::RuleAction::Skip,
+            value: None,
+        }]);
+        let (msgs, total) = collect_unhandled(&search, &sched, d("2026-03-03"), &rules).unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(msgs[0].id, 10);
+        assert_eq!(msgs[0].preview, "수업 공개 계획서를 금요일까지 제출해 주세요.");
+    }
+
+    #[test]
+    fn lessons_from_both_timetable_sources() {
+        let mut timetables = std::collections::HashMap::new();
+        let mut grid = vec![vec![vec![String::new(), String::new()]; 5]; 8];
+        grid[0][2] = vec!["국어".to_string(), "3반".to_string()];
+        grid[5][2] = vec!["국어".to_string(), "5반".to_string()];
+        timetables.insert("김교사".to_string(), grid);
+        let comcigan = crate::timetable_parser::TimetableData {
+            teachers: vec!["김교사".to_string()],
+            subjects: vec!["국어".to_string()],
+            timetables,
+        };
+        let bells = BellSchedule::default();
+        let lessons = teacher_lessons(&comcigan, &bells, "김교사", d("2026-03-04"));
+        assert_eq!(lessons.iter().map(lesson_line).collect::<Vec<_>>(), vec!["1교시 08:30 국어 (3반)", "6교시 14:20 국어 (5반)"]);
+        assert!(teacher_lessons(&comcigan, &bells, "박교사", d("2026-03-04")).is_empty());
+
+        let slot = |s: usize, t: usize| crate::appin_parser::AppinSlot { subject: Some(s), teacher: Some(t), room: None };
+        let mut periods = std::collections::HashMap::new();
+        periods.insert("3".to_string(), slot(0, 1));
+        periods.insert("1".to_string(), slot(0, 0));
+        let mut classes = std::collections::HashMap::new();
+        classes.insert("2-1".to_string(), periods);
+        let mut days = std::collections::HashMap::new();
+        days.insert("2026-03-04".to_string(), classes);
+        let appin = crate::appin_parser::AppinTimetableData {
+            teachers: vec!["박교사".to_string(), "김교사".to_string()],
+            subjects: vec!["수학".to_string()],
+            classes: vec!["2-1".to_string()],
+            events: Vec::new(),
+            days,
+            events_by_date_class: Default::default(),
+            events_by_date_grade: Default::default(),
+            start_date: "2026-03-01".to_string(),
+            terms: Vec::new(),
+        };
+        let lessons = teacher_lessons(&appin, &bells, "김교사", d("2026-03-04"));
+        assert_eq!(lessons.iter().map(lesson_line).collect::<Vec<_>>(), vec!["3교시 10:30 수학 (2-1)"]);
+    }
+
+    #[test]
+    fn renders_template_sections() {
+        let data = DigestData {
+            date: Some(d("2026-03-04")),
+            agenda: vec![DigestEntry {
+                id: "b".to_string(),
+                date: "2026-03-04".to_string(),
+                time: Some("15:00".to_string()),
+                title: "교직원 회의 <시청각실>".to_string(),
+                item_kind: None,
+                priority: Some("high".to_string()),
+            }],
+            unhandled_day: Some(d("2026-03-03")),
+            meal: Some(MealData {
+                date: "2026-03-04".to_string(),
+                meals: vec![crate::meal::Meal {
+                    kind: crate::meal::MealKind::Lunch,
+                    label: "중식".to_string(),
+                    dishes: vec![crate::meal::parse_dish("현미밥"), crate::meal::parse_dish("된장국 (5.)")],
+                    calories: None,
+                    nutrition: Vec::new(),
+                    origin: Vec::new(),
+                }],
+                alerts: Vec::new(),
+            }),
+            ..Default::default()
+        };
+        let md = render_markdown(&data, None);
+        assert!(md.starts_with("# 3/4(수) 아침 브리핑\n"));
+        assert!(md.contains("## 오늘 일정\n- 15:00 교직원 회의 <시청각실> (중요)\n"));
+        assert!(md.contains("- 중식: 현미밥, 된장국\n"));
+        // 비어 있는 마감·미처리·수업 섹션은 넣지 않는다.
+        assert!(!md.contains("마감"));
+        assert!(!md.contains("할 일로 만들지 않은"));
+        assert!(!md.contains("오늘 수업"));
+
+        let html = render_html(&data, Some("오늘은 회의가 있습니다."));
+        assert!(html.starts_with("<h1>3/4(수) 아침 브리핑</h1><p>오늘은 회의가 있습니다.</p>"));
+        assert!(html.contains("<li>15:00 교직원 회의 &lt;시청각실&gt; (중요)</li>"));
+    }
+
+    #[test]
+    fn history_keeps_latest_and_reads_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        let digest = |date: &str| Digest {
+            id: 0,
+            kind: DigestKind::Daily,
+            digest_date: date.to_string(),
+            created_at: format!("{}T07:50:00.000Z", date),
+            markdown: "# 브리핑".to_string(),
+            html: "<h1>브리핑</h1>".to_string(),
+            prose: None,
+            notes: vec!["급식 없음".to_string()],
+        };
+        let first = save_digest(&conn, digest("2026-03-03")).unwrap();
+        let second = save_digest(&conn, digest("2026-03-04")).unwrap();
+        assert!(second.id > first.id);
+
+        let latest = get_digest_in(&conn, None).unwrap().unwrap();
+        assert_eq!(latest.digest_date, "2026-03-04");
+        assert_eq!(latest.notes, vec!["급식 없음".to_string()]);
+        assert_eq!(get_digest_in(&conn, Some(first.id)).unwrap().unwrap().digest_date, "2026-03-03");
+        assert_eq!(list_digests_in(&conn, 10).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,1183 @@
+//! 표 구조를 보존하는 문서 텍스트 추출.
+//!
+//! 공문·일정표·명렬표처럼 표가 대부분인 문서를 평문으로 펼치면 셀 내용이 뒤섞인다.
+//! 이 모듈은 hwp(5.0 바이너리)/hwpx/odt/xlsx/pptx 를 문단과 표 블록으로 읽어
+//! 표는 행·열이 유지된 Markdown 표로 내보낸다. MCP `read_attachment` 와
+//! Edufine 공문 수집이 같은 추출기를 공유한다.
+
+use std::io::Read;
+use std::path::Path;
+
+use crate::attachment_sandbox::CancelToken;
+
+/// 문서 블록: 문단 또는 표(행 × 열 셀 텍스트)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Paragraph(String),
+    Table(Vec<Vec<String>>),
+}
+
+/// Markdown 변환 시 한 셀 안의 줄바꿈 표기
+const CELL_BREAK: &str = "<br>";
+/// ODT 의 `number-columns-repeated` 로 늘어나는 빈 셀 상한
+const MAX_REPEATED_CELLS: usize = 64;
+/// HWP 본문 스트림 압축 해제 상한 (압축 폭탄 방지)
+const MAX_SECTION_BYTES: u64 = 64 * 1024 * 1024;
+/// ZIP 기반 문서(HWPX/DOCX/ODT) 항목 하나의 압축 해제 상한
+const MAX_ZIP_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+// ── Markdown 렌더링 ───────────────────────────────────────────────────────────
+
+/// 블록 목록을 Markdown 으로 변환. 문단은 줄 단위, 표 앞뒤에는 빈 줄을 둔다.
+pub fn blocks_to_markdown(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Paragraph(text) => {
+                out.push_str(text);
+                out.push('\n');
+            }
+            Block::Table(rows) => {
+                let table = table_to_markdown(rows);
+                if table.is_empty() {
+                    continue;
+                }
+                if !out.is_empty() && !out.ends_with("\n\n") {
+                    out.push('\n');
+                }
+                out.push_str(&table);
+                out.push_str("\n\n");
+            }
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// 행 × 열 셀을 Markdown 표로 변환. 첫 행을 머리글로 쓰고,
+/// 완전히 빈 행과 오른쪽 끝의 빈 열은 잘라낸다.
+pub fn table_to_markdown(rows: &[Vec<String>]) -> String {
+    let rows: Vec<&Vec<String>> = rows
+        .iter()
+        .filter(|r| r.iter().any(|c| !c.trim().is_empty()))
+        .collect();
+    let cols = rows
+        .iter()
+        .map(|r| {
+            r.iter()
+                .rposition(|c| !c.trim().is_empty())
+                .map(|i| i + 1)
+                .unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0);
+    if cols == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        out.push('|');
+        for c in 0..cols {
+            let cell = row.get(c).map(|s| escape_cell(s)).unwrap_or_default();
+            out.push(' ');
+            out.push_str(&cell);
+            out.push_str(" |");
+        }
+        out.push('\n');
+        if i == 0 {
+            out.push('|');
+            for _ in 0..cols {
+                out.push_str(" --- |");
+            }
+            out.push('\n');
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn escape_cell(text: &str) -> String {
+    text.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", CELL_BREAK)
+        .replace(['\n', '\r'], CELL_BREAK)
+}
+
+// ── 블록 조립기 ───────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct CellBuild {
+    lines: Vec<String>,
+    para: String,
+    row: Option<usize>,
+    col: Option<usize>,
+    row_span: usize,
+    col_span: usize,
+    seq_row: usize,
+}
+
+impl CellBuild {
+    fn flush(&mut self) {
+        let text = self.para.trim().to_string();
+        if !text.is_empty() {
+            self.lines.push(text);
+        }
+        self.para.clear();
+    }
+}
+
+#[derive(Default)]
+struct TableBuild {
+    cells: Vec<CellBuild>,
+    open: Option<CellBuild>,
+    row_count: usize,
+}
+
+impl TableBuild {
+    /// 셀을 격자에 배치. 주소가 있으면 그대로, 없으면 행 순서대로 빈 칸을 채운다.
+    /// 병합 셀은 왼쪽 위 칸에만 내용을 두고 나머지 칸은 비워 둔다.
+    fn into_grid(self) -> Vec<Vec<String>> {
+        let mut grid: Vec<Vec<Option<String>>> = Vec::new();
+        let mut cursor: Vec<usize> = Vec::new();
+
+        for cell in self.cells {
+            let row = cell.row.unwrap_or(cell.seq_row);
+            let row_span = cell.row_span.max(1);
+            let col_span = cell.col_span.max(1);
+            while grid.len() < row + row_span {
+                grid.push(Vec::new());
+                cursor.push(0);
+            }
+            let col = match cell.col {
+                Some(c) => c,
+                None => {
+                    let mut c = cursor[row];
+                    while grid[row].get(c).map(|s| s.is_some()).unwrap_or(false) {
+                        c += 1;
+                    }
+                    c
+                }
+            };
+            for (r, grid_row) in grid.iter_mut().enumerate().skip(row).take(row_span) {
+                if grid_row.len() < col + col_span {
+                    grid_row.resize(col + col_span, None);
+                }
+                for (c, slot) in grid_row.iter_mut().enumerate().skip(col).take(col_span) {
+                    if slot.is_none() {
+                        *slot = Some(if r == row && c == col {
+                            cell.lines.join("\n")
+                        } else {
+                            String::new()
+                        });
+                    }
+                }
+            }
+            cursor[row] = col + col_span;
+        }
+
+        grid.into_iter()
+            .map(|r| r.into_iter().map(|c| c.unwrap_or_default()).collect())
+            .collect()
+    }
+}
+
+/// 형식별 파서가 문단·표 이벤트를 넘기면 중첩을 처리해 블록 목록을 만든다.
+/// 표 안의 표는 Markdown 으로 중첩할 수 없으므로 바깥 셀 안의 텍스트로 펼친다.
+#[derive(Default)]
+struct DocBuilder {
+    blocks: Vec<Block>,
+    para: String,
+    tables: Vec<TableBuild>,
+}
+
+impl DocBuilder {
+    fn current_cell(&mut self) -> Option<&mut CellBuild> {
+        self.tables.last_mut().and_then(|t| t.open.as_mut())
+    }
+
+    fn text(&mut self, s: &str) {
+        if let Some(cell) = self.current_cell() {
+            cell.para.push_str(s);
+        } else if self.tables.is_empty() {
+            self.para.push_str(s);
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        if let Some(cell) = self.current_cell() {
+            cell.flush();
+        } else if self.tables.is_empty() {
+            let text = self.para.trim().to_string();
+            if !text.is_empty() {
+                self.blocks.push(Block::Paragraph(text));
+            }
+            self.para.clear();
+        }
+    }
+
+    fn begin_table(&mut self) {
+        // 표 앞에 같은 문단의 글자가 있으면 먼저 내보낸다
+        self.end_paragraph();
+        self.tables.push(TableBuild::default());
+    }
+
+    fn end_table(&mut self) {
+        self.end_cell();
+        let Some(table) = self.tables.pop() else { return };
+        let grid = table.into_grid();
+        if let Some(cell) = self.current_cell() {
+            cell.flush();
+            for row in grid {
+                let line = row
+                    .iter()
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                if !line.is_empty() {
+                    cell.lines.push(line);
+                }
+            }
+        } else if self.tables.is_empty() && !grid.is_empty() {
+            self.blocks.push(Block::Table(grid));
+        }
+    }
+
+    fn begin_row(&mut self) {
+        if let Some(t) = self.tables.last_mut() {
+            t.row_count += 1;
+        }
+    }
+
+    fn begin_cell(&mut self) {
+        self.end_cell();
+        if let Some(t) = self.tables.last_mut() {
+            t.open = Some(CellBuild {
+                seq_row: t.row_count.saturating_sub(1),
+                row_span: 1,
+                col_span: 1,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn set_cell_addr(&mut self, row: usize, col: usize) {
+        if let Some(cell) = self.current_cell() {
+            cell.row = Some(row);
+            cell.col = Some(col);
+        }
+    }
+
+    fn set_cell_span(&mut self, row_span: usize, col_span: usize) {
+        if let Some(cell) = self.current_cell() {
+            cell.row_span = row_span.max(1);
+            cell.col_span = col_span.max(1);
+        }
+    }
+
+    fn end_cell(&mut self) {
+        if let Some(t) = self.tables.last_mut() {
+            if let Some(mut cell) = t.open.take() {
+                cell.flush();
+                t.cells.push(cell);
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        while !self.tables.is_empty() {
+            self.end_table();
+        }
+        self.end_paragraph();
+        self.blocks
+    }
+}
+
+// ── XML 토크나이저 ────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum XmlEvent<'a> {
+    Start(&'a str, &'a str),
+    Empty(&'a str, &'a str),
+    End(&'a str),
+    Text(String),
+}
+
+/// 네임스페이스 접두어를 뗀 태그 이름
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// 문서 XML 용 최소 토크나이저. 주석·선언·DTD 는 건너뛰고 CDATA 는 텍스트로 돌려준다.
+fn xml_events(xml: &str) -> Vec<XmlEvent<'_>> {
+    let mut events = Vec::new();
+    let bytes = xml.as_bytes();
+    let mut pos = 0;
+
+    while pos < xml.len() {
+        let Some(lt) = xml[pos..].find('<').map(|i| pos + i) else {
+            push_text(&mut events, &xml[pos..]);
+            break;
+        };
+        if lt > pos {
+            push_text(&mut events, &xml[pos..lt]);
+        }
+        let rest = &xml[lt..];
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map(|i| lt + i + 3).unwrap_or(xml.len());
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("<![CDATA[") {
+            let end = body.find("]]>").unwrap_or(body.len());
+            events.push(XmlEvent::Text(body[..end].to_string()));
+            pos = (lt + 9 + end + 3).min(xml.len());
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            pos = rest.find('>').map(|i| lt + i + 1).unwrap_or(xml.len());
+            continue;
+        }
+
+        // 따옴표 안의 '>' 는 태그 끝이 아니다
+        let mut end = lt + 1;
+        let mut quote: Option<u8> = None;
+        while end < bytes.len() {
+            let b = bytes[end];
+            match quote {
+                Some(q) if b == q => quote = None,
+                Some(_) => {}
+                None if b == b'"' || b == b'\'' => quote = Some(b),
+                None if b == b'>' => break,
+                None => {}
+            }
+            end += 1;
+        }
+        if end >= bytes.len() {
+            break;
+        }
+        let tag = &xml[lt + 1..end];
+        pos = end + 1;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            events.push(XmlEvent::End(name.trim()));
+            continue;
+        }
+        let (tag, empty) = match tag.strip_suffix('/') {
+            Some(t) => (t, true),
+            None => (tag, false),
+        };
+        let split = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let (name, attrs) = tag.split_at(split);
+        events.push(if empty {
+            XmlEvent::Empty(name, attrs)
+        } else {
+            XmlEvent::Start(name, attrs)
+        });
+    }
+    events
+}
+
+fn push_text(events: &mut Vec<XmlEvent<'_>>, raw: &str) {
+    if !raw.is_empty() {
+        events.push(XmlEvent::Text(decode_entities(raw)));
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let tail = &rest[amp..];
+        let Some(semi) = tail.find(';').filter(|&i| i <= 10) else {
+            out.push('&');
+            rest = &tail[1..];
+            continue;
+        };
+        let entity = &tail[1..semi];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &tail[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 속성 문자열에서 값 찾기. 접두어가 있는 이름도 로컬 이름으로 비교한다.
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let close = after[1..].find(quote)? + 1;
+        let value = &after[1..close];
+        if key == name || local_name(key) == name {
+            return Some(value);
+        }
+        rest = &after[close + 1..];
+    }
+}
+
+fn attr_usize(attrs: &str, name: &str) -> Option<usize> {
+    attr(attrs, name).and_then(|v| v.trim().parse().ok())
+}
+
+// ── HWPX / ODT / PPTX (XML) ───────────────────────────────────────────────────
+
+/// HWPX 본문(Contents/sectionN.xml) 하나를 블록으로 변환.
+/// 셀 위치는 `hp:cellAddr`, 병합은 `hp:cellSpan` 을 따른다.
+pub fn parse_hwpx_section(xml: &str) -> Vec<Block> {
+    let mut b = DocBuilder::default();
+    let mut in_text = 0usize;
+
+    for ev in xml_events(xml) {
+        match ev {
+            XmlEvent::Start(name, attrs) => match local_name(name) {
+                "t" => in_text += 1,
+                "tbl" => b.begin_table(),
+                "tr" => b.begin_row(),
+                "tc" => b.begin_cell(),
+                _ => handle_hwpx_empty(&mut b, name, attrs, in_text > 0),
+            },
+            XmlEvent::Empty(name, attrs) => handle_hwpx_empty(&mut b, name, attrs, in_text > 0),
+            XmlEvent::End(name) => match local_name(name) {
+                "t" => in_text = in_text.saturating_sub(1),
+                "p" => b.end_paragraph(),
+                "tc" => b.end_cell(),
+                "tbl" => b.end_table(),
+                _ => {}
+            },
+            XmlEvent::Text(t) if in_text > 0 => b.text(&t),
+            XmlEvent::Text(_) => {}
+        }
+    }
+    b.finish()
+}
+
+fn handle_hwpx_empty(b: &mut DocBuilder, name: &str, attrs: &str, in_text: bool) {
+    match local_name(name) {
+        "cellAddr" => {
+            if let (Some(col), Some(row)) = (attr_usize(attrs, "colAddr"), attr_usize(attrs, "rowAddr")) {
+                b.set_cell_addr(row, col);
+            }
+        }
+        "cellSpan" => b.set_cell_span(
+            attr_usize(attrs, "rowSpan").unwrap_or(1),
+            attr_usize(attrs, "colSpan").unwrap_or(1),
+        ),
+        "lineBreak" if in_text => b.text("\n"),
+        "tab" if in_text => b.text("\t"),
+        _ => {}
+    }
+}
+
+/// ODT content.xml 을 블록으로 변환. 병합으로 가려진 칸은
+/// `table:covered-table-cell` 로 명시되므로 순서대로 채우기만 하면 된다.
+pub fn parse_odt_content(xml: &str) -> Vec<Block> {
+    let mut b = DocBuilder::default();
+    let mut in_para = 0usize;
+    let mut repeats: Vec<usize> = Vec::new();
+
+    for ev in xml_events(xml) {
+        match ev {
+            XmlEvent::Start(name, attrs) => match name {
+                "text:p" | "text:h" => in_para += 1,
+                "table:table" => b.begin_table(),
+                "table:table-row" => b.begin_row(),
+                "table:table-cell" | "table:covered-table-cell" => {
+                    let repeat = attr_usize(attrs, "number-columns-repeated").unwrap_or(1);
+                    repeats.push(repeat.clamp(1, MAX_REPEATED_CELLS));
+                    b.begin_cell();
+                }
+                _ => {}
+            },
+            XmlEvent::Empty(name, attrs) => match name {
+                "table:table-cell" | "table:covered-table-cell" => {
+                    let repeat = attr_usize(attrs, "number-columns-repeated").unwrap_or(1);
+                    for _ in 0..repeat.min(MAX_REPEATED_CELLS) {
+                        b.begin_cell();
+                    }
+                    b.end_cell();
+                }
+                "text:s" if in_para > 0 => {
+                    let n = attr_usize(attrs, "c").unwrap_or(1).min(MAX_REPEATED_CELLS);
+                    b.text(&" ".repeat(n));
+                }
+                "text:tab" if in_para > 0 => b.text("\t"),
+                "text:line-break" if in_para > 0 => b.text("\n"),
+                _ => {}
+            },
+            XmlEvent::End(name) => match name {
+                "text:p" | "text:h" => {
+                    in_para = in_para.saturating_sub(1);
+                    if in_para == 0 {
+                        b.end_paragraph();
+                    }
+                }
+                "table:table-cell" | "table:covered-table-cell" => {
+                    b.end_cell();
+                    // 반복 셀은 내용을 첫 칸에만 담고 나머지는 빈 칸으로 둔다
+                    for _ in 1..repeats.pop().unwrap_or(1) {
+                        b.begin_cell();
+                    }
+                    b.end_cell();
+                }
+                "table:table" => b.end_table(),
+                _ => {}
+            },
+            XmlEvent::Text(t) if in_para > 0 => b.text(&t),
+            XmlEvent::Text(_) => {}
+        }
+    }
+    b.finish()
+}
+
+/// PPTX 슬라이드 XML 을 블록으로 변환. `hMerge`/`vMerge` 칸도 셀로 나오므로
+/// 순서대로 채우고 병합된 칸은 비워 둔다.
+pub fn parse_pptx_slide(xml: &str) -> Vec<Block> {
+    let mut b = DocBuilder::default();
+    let mut in_text = 0usize;
+
+    for ev in xml_events(xml) {
+        match ev {
+            XmlEvent::Start(name, _) => match name {
+                "a:t" => in_text += 1,
+                "a:tbl" => b.begin_table(),
+                "a:tr" => b.begin_row(),
+                "a:tc" => b.begin_cell(),
+                _ => {}
+            },
+            XmlEvent::Empty(name, _) => match name {
+                "a:br" => b.text("\n"),
+                "a:tc" => {
+                    b.begin_cell();
+                    b.end_cell();
+                }
+                _ => {}
+            },
+            XmlEvent::End(name) => match name {
+                "a:t" => in_text = in_text.saturating_sub(1),
+                "a:p" => b.end_paragraph(),
+                "a:tc" => b.end_cell(),
+                "a:tbl" => b.end_table(),
+                _ => {}
+            },
+            XmlEvent::Text(t) if in_text > 0 => b.text(&t),
+            XmlEvent::Text(_) => {}
+        }
+    }
+    b.finish()
+}
+
+// ── HWP 5.0 바이너리 ──────────────────────────────────────────────────────────
+
+const HWPTAG_BEGIN: u16 = 0x010;
+const HWPTAG_PARA_HEADER: u16 = HWPTAG_BEGIN + 50;
+const HWPTAG_PARA_TEXT: u16 = HWPTAG_BEGIN + 51;
+const HWPTAG_CTRL_HEADER: u16 = HWPTAG_BEGIN + 55;
+const HWPTAG_LIST_HEADER: u16 = HWPTAG_BEGIN + 56;
+/// 컨트롤 ID 'tbl ' (MAKE_4CHID)
+const CTRL_ID_TABLE: u32 = u32::from_be_bytes(*b"tbl ");
+
+#[derive(Debug)]
+struct HwpRecord<'a> {
+    tag: u16,
+    level: u16,
+    data: &'a [u8],
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// 본문 스트림을 레코드로 분해. 헤더는 태그 10비트, 레벨 10비트, 크기 12비트이며
+/// 크기가 0xFFF 이면 다음 4바이트가 실제 크기다.
+fn split_records(stream: &[u8]) -> Vec<HwpRecord<'_>> {
+    let mut records = Vec::new();
+    let mut pos = 0;
+    while let Some(header) = read_u32(stream, pos) {
+        pos += 4;
+        let tag = (header & 0x3FF) as u16;
+        let level = ((header >> 10) & 0x3FF) as u16;
+        let mut size = (header >> 20) as usize;
+        if size == 0xFFF {
+            let Some(ext) = read_u32(stream, pos) else { break };
+            size = ext as usize;
+            pos += 4;
+        }
+        let Some(data) = stream.get(pos..pos + size) else { break };
+        records.push(HwpRecord { tag, level, data });
+        pos += size;
+    }
+    records
+}
+
+/// PARA_TEXT(UTF-16LE) 디코딩. 0~31 은 제어 문자로, 인라인·확장 컨트롤은
+/// 8 WCHAR 를 차지하므로 건너뛴다.
+fn decode_para_text(data: &[u8]) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    let mut out: Vec<u16> = Vec::with_capacity(units.len());
+    let mut i = 0;
+    while i < units.len() {
+        let c = units[i];
+        match c {
+            9 => {
+                out.push(u16::from(b'\t'));
+                i += 8;
+            }
+            1..=8 | 11 | 12 | 14..=23 => i += 8,
+            10 => {
+                out.push(u16::from(b'\n'));
+                i += 1;
+            }
+            24 => {
+                out.push(u16::from(b'-'));
+                i += 1;
+            }
+            30 | 31 => {
+                out.push(u16::from(b' '));
+                i += 1;
+            }
+            0..=31 => i += 1,
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    char::decode_utf16(out)
+        .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// 레코드 목록을 블록으로 변환
+fn hwp_records_to_blocks(records: &[HwpRecord<'_>]) -> Vec<Block> {
+    let mut b = DocBuilder::default();
+    let mut i = 0;
+    while i < records.len() {
+        let level = records[i].level;
+        hwp_paragraph_list(records, &mut i, level, &mut b);
+        // 예상 밖 레코드가 남으면 건너뛰고 계속 진행
+        if i < records.len() && records[i].level == level && records[i].tag != HWPTAG_PARA_HEADER {
+            i += 1;
+        }
+    }
+    b.finish()
+}
+
+/// 같은 레벨의 PARA_HEADER 가 이어지는 동안 문단을 읽는다.
+/// 표 셀 목록에서는 다음 LIST_HEADER 를 만나면 멈춘다.
+fn hwp_paragraph_list(records: &[HwpRecord<'_>], i: &mut usize, level: u16, b: &mut DocBuilder) {
+    while *i < records.len() {
+        let rec = &records[*i];
+        if rec.level < level || (rec.level == level && rec.tag != HWPTAG_PARA_HEADER) {
+            return;
+        }
+        if rec.level > level {
+            *i += 1;
+            continue;
+        }
+        *i += 1;
+        while *i < records.len() && records[*i].level > level {
+            let child = &records[*i];
+            if child.level == level + 1 && child.tag == HWPTAG_PARA_TEXT {
+                b.text(&decode_para_text(child.data));
+                *i += 1;
+            } else if child.level == level + 1
+                && child.tag == HWPTAG_CTRL_HEADER
+                && read_u32(child.data, 0) == Some(CTRL_ID_TABLE)
+            {
+                hwp_table(records, i, b);
+            } else {
+                *i += 1;
+            }
+        }
+        b.end_paragraph();
+    }
+}
+
+/// CTRL_HEADER('tbl ') 부터 그 하위 레코드를 표로 읽는다.
+/// 셀은 LIST_HEADER 로 시작하며 8바이트 목록 헤더 뒤에 열·행 주소와 병합 수가 온다.
+fn hwp_table(records: &[HwpRecord<'_>], i: &mut usize, b: &mut DocBuilder) {
+    let ctrl_level = records[*i].level;
+    *i += 1;
+    b.begin_table();
+    while *i < records.len() && records[*i].level > ctrl_level {
+        let rec = &records[*i];
+        if rec.level == ctrl_level + 1 && rec.tag == HWPTAG_LIST_HEADER {
+            b.begin_cell();
+            if let (Some(col), Some(row)) = (read_u16(rec.data, 8), read_u16(rec.data, 10)) {
+                b.set_cell_addr(row as usize, col as usize);
+            }
+            if let (Some(col_span), Some(row_span)) = (read_u16(rec.data, 12), read_u16(rec.data, 14)) {
+                b.set_cell_span(row_span as usize, col_span as usize);
+            }
+            *i += 1;
+            hwp_paragraph_list(records, i, ctrl_level + 1, b);
+            b.end_cell();
+        } else {
+            // HWPTAG_TABLE 등 표 속성 레코드는 셀 주소로 충분하므로 건너뛴다
+            *i += 1;
+        }
+    }
+    b.end_table();
+}
+
+/// HWP 5.0 바이너리(CFB) 문서를 블록으로 변환. 섹션마다 `cancel` 을 확인한다.
+pub fn extract_hwp_blocks(path: &Path, cancel: &CancelToken) -> Result<Vec<Block>, String> {
+    let mut comp = cfb::open(path).map_err(|e| format!("HWP 컨테이너 열기 실패: {}", e))?;
+
+    let mut header = Vec::new();
+    comp.open_stream("/FileHeader")
+        .and_then(|mut s| s.read_to_end(&mut header))
+        .map_err(|e| format!("HWP 파일 헤더 읽기 실패: {}", e))?;
+    if !header.starts_with(b"HWP Document File") {
+        return Err("HWP 5.0 문서가 아닙니다".to_string());
+    }
+    let props = read_u32(&header, 36).unwrap_or(0);
+    if props & 0b110 != 0 {
+        return Err("암호화되었거나 배포용 HWP 문서입니다".to_string());
+    }
+    let compressed = props & 0b1 != 0;
+
+    let mut blocks = Vec::new();
+    for n in 0.. {
+        let name = format!("/BodyText/Section{}", n);
+        if !comp.is_stream(&name) {
+            break;
+        }
+        cancel.check()?;
+        let mut raw = Vec::new();
+        comp.open_stream(&name)
+            .and_then(|mut s| s.read_to_end(&mut raw))
+            .map_err(|e| format!("HWP 본문 읽기 실패: {}", e))?;
+        let stream = if compressed {
+            let mut out = Vec::new();
+            flate2::read::DeflateDecoder::new(raw.as_slice())
+                .take(MAX_SECTION_BYTES)
+                .read_to_end(&mut out)
+                .map_err(|e| format!("HWP 본문 압축 해제 실패: {}", e))?;
+            out
+        } else {
+            raw
+        };
+        blocks.extend(hwp_records_to_blocks(&split_records(&stream)));
+    }
+    if blocks.is_empty() {
+        return Err("HWP 본문을 찾을 수 없습니다".to_string());
+    }
+    Ok(blocks)
+}
+
+// ── 파일 단위 진입점 ──────────────────────────────────────────────────────────
+
+fn read_zip_entry<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| format!("{}을(를) 찾을 수 없습니다", name))?;
+    let mut bytes = Vec::new();
+    (&mut entry)
+        .take(MAX_ZIP_ENTRY_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("읽기 실패: {}", e))?;
+    if bytes.len() as u64 > MAX_ZIP_ENTRY_BYTES {
+        return Err(format!("{}이(가) 너무 큽니다", name));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn open_zip(path: &Path) -> Result<zip::ZipArchive<std::fs::File>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("파일 열기 실패: {}", e))?;
+    zip::ZipArchive::new(file).map_err(|e| format!("ZIP 열기 실패: {}", e))
+}
+
+/// 숫자 순서로 정렬된 압축 항목 이름 (section10 이 section2 뒤에 오도록)
+fn numbered_entries<R: Read + std::io::Seek>(
+    archive: &zip::ZipArchive<R>,
+    prefix: &str,
+) -> Vec<String> {
+    let mut names: Vec<(u32, String)> = archive
+        .file_names()
+        .filter(|n| n.starts_with(prefix) && n.ends_with(".xml"))
+        .map(|n| {
+            let num = n[prefix.len()..n.len() - 4].parse().unwrap_or(u32::MAX);
+            (num, n.to_string())
+        })
+        .collect();
+    names.sort();
+    names.into_iter().map(|(_, n)| n).collect()
+}
+
+/// HWPX 의 모든 본문 섹션을 블록으로 변환
+pub fn extract_hwpx_blocks(path: &Path, cancel: &CancelToken) -> Result<Vec<Block>, String> {
+    let mut archive = open_zip(path)?;
+    let mut blocks = Vec::new();
+    for name in numbered_entries(&archive, "Contents/section") {
+        cancel.check()?;
+        let xml = read_zip_entry(&mut archive, &name)?;
+        blocks.extend(parse_hwpx_section(&xml));
+    }
+    Ok(blocks)
+}
+
+/// ODT 본문을 블록으로 변환
+pub fn extract_odt_blocks(path: &Path, cancel: &CancelToken) -> Result<Vec<Block>, String> {
+    let mut archive = open_zip(path)?;
+    let xml = read_zip_entry(&mut archive, "content.xml")?;
+    cancel.check()?;
+    Ok(parse_odt_content(&xml))
+}
+
+/// PPTX 슬라이드를 순서대로 블록으로 변환. 슬라이드마다 제목 문단을 앞에 둔다.
+pub fn extract_pptx_blocks(path: &Path, cancel: &CancelToken) -> Result<Vec<Block>, String> {
+    let mut archive = open_zip(path)?;
+    let mut blocks = Vec::new();
+    for (idx, name) in numbered_entries(&archive, "ppt/slides/slide").iter().enumerate() {
+        cancel.check()?;
+        let xml = read_zip_entry(&mut archive, name)?;
+        let slide = parse_pptx_slide(&xml);
+        if slide.is_empty() {
+            continue;
+        }
+        blocks.push(Block::Paragraph(format!("## 슬라이드 {}", idx + 1)));
+        blocks.extend(slide);
+    }
+    Ok(blocks)
+}
+
+/// 엑셀 시트마다 제목 문단과 표 하나를 만든다
+pub fn extract_excel_blocks(path: &Path, cancel: &CancelToken) -> Result<Vec<Block>, String> {
+    use calamine::{open_workbook_auto, Data, Reader};
+    let mut wb = open_workbook_auto(path).map_err(|e| format!("Excel 로드 실패: {}", e))?;
+    let sheet_names = wb.sheet_names().to_owned();
+    let mut blocks = Vec::new();
+
+    for name in sheet_names {
+        cancel.check()?;
+        blocks.push(Block::Paragraph(format!("## {}", name)));
+        if let Ok(range) = wb.worksheet_range(&name) {
+            let rows: Vec<Vec<String>> = range
+                .rows()
+                .map(|row| {
+                    row.iter()
+                        .map(|c| match c {
+                            Data::Empty => String::new(),
+                            Data::String(s) => s.clone(),
+                            Data::Int(i) => i.to_string(),
+                            Data::Float(f) => f.to_string(),
+                            Data::Bool(b) => b.to_string(),
+                            other => format!("{:?}", other),
+                        })
+                        .collect()
+                })
+                .collect();
+            blocks.push(Block::Table(rows));
+        }
+    }
+    Ok(blocks)
+}
+
+/// 확장자에 맞는 추출기로 문서를 Markdown 으로 변환. MCP `read_attachment` 와
+/// Edufine 공문 수집이 모두 이 함수를 거친다. 표 인식을 지원하지 않는 형식이면 `None`.
+///
+/// hwp/hwpx 는 표 인식 추출이 실패하거나 비어 있으면(배포용 문서 등) unhwp 렌더러로,
+/// 그마저 실패한 hwpx 는 미리보기 텍스트(Preview/PrvText.txt)로 대체한다.
+pub fn extract_markdown(path: &Path, ext: &str, cancel: &CancelToken) -> Option<Result<String, String>> {
+    let ext = ext.to_ascii_lowercase();
+    let blocks = match ext.as_str() {
+        "hwp" => extract_hwp_blocks(path, cancel),
+        "hwpx" => extract_hwpx_blocks(path, cancel),
+        "odt" => extract_odt_blocks(path, cancel),
+        "pptx" => extract_pptx_blocks(path, cancel),
+        "xlsx" | "xls" | "xlsm" | "xlsb" => extract_excel_blocks(path, cancel),
+        _ => return None,
+    };
+    let hwp_family = ext == "hwp" || ext == "hwpx";
+    Some(match blocks {
+        Ok(b) if !b.is_empty() || !hwp_family => Ok(blocks_to_markdown(&b)),
+        other => {
+            if let Err(e) = other {
+                eprintln!("[doc_extract] 표 인식 추출 실패, unhwp 로 대체: {}", e);
+            }
+            cancel.check().and_then(|_| render_with_unhwp(path)).or_else(|e| {
+                if ext == "hwpx" {
+                    read_hwpx_preview(path).ok_or(e)
+                } else {
+                    Err(e)
+                }
+            })
+        }
+    })
+}
+
+/// 표 인식 추출을 지원하는 확장자인지(소문자 기준).
+pub fn is_supported(ext: &str) -> bool {
+    matches!(
+        ext.to_ascii_lowercase().as_str(),
+        "hwp" | "hwpx" | "odt" | "pptx" | "xlsx" | "xls" | "xlsm" | "xlsb"
+    )
+}
+
+fn render_with_unhwp(path: &Path) -> Result<String, String> {
+    use unhwp::{parse_file, render::render_markdown, RenderOptions};
+    let document = parse_file(path).map_err(|e| format!("HWP 파싱 실패: {}", e))?;
+    render_markdown(&document, &RenderOptions::default())
+        .map_err(|e| format!("HWP 렌더링 실패: {}", e))
+}
+
+/// HWPX 에 들어 있는 미리보기 평문. 비어 있으면 None.
+fn read_hwpx_preview(path: &Path) -> Option<String> {
+    let mut archive = open_zip(path).ok()?;
+    let text = read_zip_entry(&mut archive, "Preview/PrvText.txt").ok()?;
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HWPTAG_TABLE: u16 = HWPTAG_BEGIN + 61;
+
+    fn table(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|r| r.iter().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn markdown_table_pads_and_escapes() {
+        let md = table_to_markdown(&table(&[
+            &["이름", "과목", ""],
+            &["김철수", "수학|과학"],
+            &["", "", ""],
+            &["이영희", "줄1\n줄2", ""],
+        ]));
+        assert_eq!(
+            md,
+            "| 이름 | 과목 |\n| --- | --- |\n| 김철수 | 수학\\|과학 |\n| 이영희 | 줄1<br>줄2 |"
+        );
+    }
+
+    #[test]
+    fn xml_events_decode_entities_and_skip_comments() {
+        let ev = xml_events(r#"<?xml version="1.0"?><!-- c --><a:t x="1>2">A &amp; B&#x41;</a:t><br/>"#);
+        assert_eq!(
+            ev,
+            vec![
+                XmlEvent::Start("a:t", r#" x="1>2""#),
+                XmlEvent::Text("A & BA".to_string()),
+                XmlEvent::End("a:t"),
+                XmlEvent::Empty("br", ""),
+            ]
+        );
+        assert_eq!(attr(r#" hp:colAddr="3" rowAddr='1'"#, "colAddr"), Some("3"));
+        assert_eq!(attr(r#" rowAddr='1'"#, "rowAddr"), Some("1"));
+    }
+
+    #[test]
+    fn hwpx_table_keeps_cell_positions_and_spans() {
+        let xml = r#"<hs:sec xmlns:hp="x">
+<hp:p><hp:run><hp:t>학년별 일정</hp:t></hp:run></hp:p>
+<hp:p><hp:run><hp:tbl rowCnt="3" colCnt="3">
+<hp:tr>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>구분</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="0" rowAddr="0"/><hp:cellSpan colSpan="1" rowSpan="2"/></hp:tc>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>기간</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="1" rowAddr="0"/><hp:cellSpan colSpan="2" rowSpan="1"/></hp:tc>
+</hp:tr>
+<hp:tr>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>시작</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="1" rowAddr="1"/><hp:cellSpan colSpan="1" rowSpan="1"/></hp:tc>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>종료</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="2" rowAddr="1"/><hp:cellSpan colSpan="1" rowSpan="1"/></hp:tc>
+</hp:tr>
+<hp:tr>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>1학년</hp:t></hp:run></hp:p><hp:p><hp:run><hp:t>(수련회)</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="0" rowAddr="2"/><hp:cellSpan colSpan="1" rowSpan="1"/></hp:tc>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>3.4</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="1" rowAddr="2"/><hp:cellSpan colSpan="1" rowSpan="1"/></hp:tc>
+ <hp:tc><hp:subList><hp:p><hp:run><hp:t>3.6</hp:t></hp:run></hp:p></hp:subList><hp:cellAddr colAddr="2" rowAddr="2"/><hp:cellSpan colSpan="1" rowSpan="1"/></hp:tc>
+</hp:tr>
+</hp:tbl></hp:run></hp:p>
+<hp:p><hp:run><hp:t>끝.</hp:t></hp:run></hp:p>
+</hs:sec>"#;
+        let blocks = parse_hwpx_section(xml);
+        assert_eq!(blocks[0], Block::Paragraph("학년별 일정".to_string()));
+        assert_eq!(
+            blocks[1],
+            Block::Table(table(&[
+                &["구분", "기간", ""],
+                &["", "시작", "종료"],
+                &["1학년\n(수련회)", "3.4", "3.6"],
+            ]))
+        );
+        assert_eq!(blocks[2], Block::Paragraph("끝.".to_string()));
+        assert_eq!(
+            blocks_to_markdown(&blocks),
+            "학년별 일정\n\n| 구분 | 기간 |  |\n| --- | --- | --- |\n|  | 시작 | 종료 |\n| 1학년<br>(수련회) | 3.4 | 3.6 |\n\n끝."
+        );
+    }
+
+    #[test]
+    fn odt_table_with_covered_and_repeated_cells() {
+        let xml = r#"<office:text>
+<text:h>예산</text:h>
+<table:table table:name="t1">
+<table:table-row><table:table-cell table:number-columns-spanned="2"><text:p>항목</text:p></table:table-cell><table:covered-table-cell/><table:table-cell><text:p>금액</text:p></table:table-cell></table:table-row>
+<table:table-row><table:table-cell><text:p>교재<text:s text:c="2"/>구입</text:p></table:table-cell><table:table-cell table:number-columns-repeated="2"/></table:table-row>
+</table:table>
+<text:p>이상</text:p>
+</office:text>"#;
+        let blocks = parse_odt_content(xml);
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph("예산".to_string()),
+                Block::Table(table(&[&["항목", "", "금액"], &["교재  구입", "", ""]])),
+                Block::Paragraph("이상".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn pptx_table_and_nested_text() {
+        let xml = r#"<p:sld><p:cSld><p:spTree>
+<p:sp><p:txBody><a:p><a:r><a:t>주간 계획</a:t></a:r></a:p></p:txBody></p:sp>
+<p:graphicFrame><a:graphic><a:graphicData><a:tbl>
+<a:tr><a:tc gridSpan="2"><a:txBody><a:p><a:r><a:t>월</a:t></a:r></a:p></a:txBody></a:tc><a:tc hMerge="1"/></a:tr>
+<a:tr><a:tc><a:txBody><a:p><a:r><a:t>국어</a:t></a:r></a:p></a:txBody></a:tc><a:tc><a:txBody><a:p><a:r><a:t>수학</a:t></a:r></a:p></a:txBody></a:tc></a:tr>
+</a:tbl></a:graphicData></a:graphic></p:graphicFrame>
+</p:spTree></p:cSld></p:sld>"#;
+        let blocks = parse_pptx_slide(xml);
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph("주간 계획".to_string()),
+                Block::Table(table(&[&["월", ""], &["국어", "수학"]])),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_table_is_flattened_into_cell() {
+        let mut b = DocBuilder::default();
+        b.begin_table();
+        b.begin_row();
+        b.begin_cell();
+        b.text("바깥");
+        b.end_paragraph();
+        b.begin_table();
+        b.begin_row();
+        b.begin_cell();
+        b.text("가");
+        b.begin_cell();
+        b.text("나");
+        b.end_table();
+        b.end_cell();
+        b.end_table();
+        assert_eq!(
+            b.finish(),
+            vec![Block::Table(vec![vec!["바깥\n가 / 나".to_string()]])]
+        );
+    }
+
+    fn record(tag: u16, level: u16, data: &[u8]) -> Vec<u8> {
+        let header = u32::from(tag) | (u32::from(level) << 10) | ((data.len() as u32) << 20);
+        let mut out = header.to_le_bytes().to_vec();
+        out.extend_from_slice(data);
+        out
+    }
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()
+    }
+
+    fn cell_header(col: u16, row: u16, col_span: u16, row_span: u16) -> Vec<u8> {
+        let mut d = vec![1, 0, 0, 0, 0, 0, 0, 0];
+        for v in [col, row, col_span, row_span] {
+            d.extend_from_slice(&v.to_le_bytes());
+        }
+        d
+    }
+
+    #[test]
+    fn hwp_records_build_table() {
+        let mut para_with_ctrl = utf16("표:");
+        // 확장 컨트롤(11) 8 WCHAR + 문단 끝(13)
+        para_with_ctrl.extend_from_slice(&[11, 0]);
+        para_with_ctrl.extend_from_slice(&[0u8; 14]);
+        para_with_ctrl.extend_from_slice(&[13, 0]);
+
+        let mut stream = Vec::new();
+        stream.extend(record(HWPTAG_PARA_HEADER, 0, &[0; 4]));
+        stream.extend(record(HWPTAG_PARA_TEXT, 1, &para_with_ctrl));
+        let mut ctrl = CTRL_ID_TABLE.to_le_bytes().to_vec();
+        ctrl.extend_from_slice(&[0; 4]);
+        stream.extend(record(HWPTAG_CTRL_HEADER, 1, &ctrl));
+        stream.extend(record(HWPTAG_TABLE, 2, &[0, 0, 0, 0, 2, 0, 2, 0]));
+        for (col, row, text) in [(0u16, 0u16, "요일"), (1, 0, "시간"), (0, 1, "월"), (1, 1, "1교시")] {
+            stream.extend(record(HWPTAG_LIST_HEADER, 2, &cell_header(col, row, 1, 1)));
+            stream.extend(record(HWPTAG_PARA_HEADER, 2, &[0; 4]));
+            stream.extend(record(HWPTAG_PARA_TEXT, 3, &utf16(text)));
+        }
+        stream.extend(record(HWPTAG_PARA_HEADER, 0, &[0; 4]));
+        stream.extend(record(HWPTAG_PARA_TEXT, 1, &utf16("끝.")));
+
+        let blocks = hwp_records_to_blocks(&split_records(&stream));
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph("표:".to_string()),
+                Block::Table(table(&[&["요일", "시간"], &["월", "1교시"]])),
+                Block::Paragraph("끝.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn hwp_para_text_skips_inline_controls() {
+        let mut data = utf16("가");
+        data.extend_from_slice(&[9, 0]);
+        data.extend_from_slice(&[0u8; 14]);
+        data.extend(utf16("나"));
+        data.extend_from_slice(&[10, 0]);
+        data.extend(utf16("다"));
+        assert_eq!(decode_para_text(&data), "가\t나\n다");
+    }
+}
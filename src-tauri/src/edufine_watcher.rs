@@ -4,6 +4,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
+use crate::attachment_sandbox::CancelToken;
+use crate::doc_extract;
 use crate::edufine_db;
 
 static EDUFINE_ENABLED: AtomicBool = AtomicBool::new(false);
@@ -85,7 +87,7 @@ pub fn start(db_path: PathBuf) {
                             .and_then(|e| e.to_str())
                             .unwrap_or("")
                             .to_lowercase();
-                        if ext == "hwpx" || ext == "odt" {
+                        if doc_extract::is_supported(&ext) {
                             let path_clone = path.clone();
                             let db_clone = db_path_clone.clone();
                             std::thread::spawn(move || {
@@ -145,18 +147,17 @@ fn process_file(path: &PathBuf, db_path: &PathBuf) {
         .unwrap_or("")
         .to_lowercase();
 
-    let content = match ext.as_str() {
-        "hwpx" => parse_hwpx(path),
-        "odt" => parse_odt(path),
-        _ => return,
-    };
-
-    let content = match content {
-        Some(c) if !c.trim().is_empty() => c,
-        _ => {
+    let content = match doc_extract::extract_markdown(path, &ext, &CancelToken::none()) {
+        None => return,
+        Some(Ok(c)) if !c.trim().is_empty() => c,
+        Some(Ok(_)) => {
             eprintln!("[Edufine] 내용 없음: {:?}", path);
             return;
         }
+        Some(Err(e)) => {
+            eprintln!("[Edufine] 본문 추출 실패: {:?} ({})", path, e);
+            return;
+        }
     };
 
     // 결재 전 공문(작성 중) 필터: "시행 XXX-@N" 패턴이 있으면 건너뜀
@@ -179,85 +180,8 @@ fn process_file(path: &PathBuf, db_path: &PathBuf) {
     }
 }
 
-// ── HWPX 파싱 ─────────────────────────────────────────────────────────────────
-
-fn parse_hwpx(path: &PathBuf) -> Option<String> {
-    use std::io::Read;
-    let file = std::fs::File::open(path).ok()?;
-    let mut zip = zip::ZipArchive::new(file).ok()?;
-
-    // Preview/PrvText.txt 우선 (빠른 경로)
-    if let Ok(mut entry) = zip.by_name("Preview/PrvText.txt") {
-        let mut bytes = Vec::new();
-        if entry.read_to_end(&mut bytes).is_ok() {
-            let text = String::from_utf8_lossy(&bytes).trim().to_string();
-            if !text.is_empty() {
-                return Some(text);
-            }
-        }
-    }
-
-    // 없으면 section XML 파싱
-    let names: Vec<String> = zip.file_names().map(|s| s.to_string()).collect();
-    let mut sections: Vec<String> = names
-        .iter()
-        .filter(|n| n.starts_with("Contents/section") && n.ends_with(".xml"))
-        .cloned()
-        .collect();
-    sections.sort();
-
-    let mut parts = Vec::new();
-    for sec in &sections {
-        if let Ok(mut entry) = zip.by_name(sec) {
-            let mut bytes = Vec::new();
-            if entry.read_to_end(&mut bytes).is_ok() {
-                if let Ok(xml) = std::str::from_utf8(&bytes) {
-                    let text = strip_xml_tags(xml);
-                    if !text.is_empty() {
-                        parts.push(text);
-                    }
-                }
-            }
-        }
-    }
-
-    if parts.is_empty() {
-        None
-    } else {
-        Some(parts.join("\n\n"))
-    }
-}
-
-// ── ODT 파싱 ──────────────────────────────────────────────────────────────────
-
-fn parse_odt(path: &PathBuf) -> Option<String> {
-    use std::io::Read;
-    let file = std::fs::File::open(path).ok()?;
-    let mut zip = zip::ZipArchive::new(file).ok()?;
-    let mut entry = zip.by_name("content.xml").ok()?;
-    let mut bytes = Vec::new();
-    entry.read_to_end(&mut bytes).ok()?;
-    let xml = String::from_utf8_lossy(&bytes);
-    let text = strip_xml_tags(&xml);
-    if text.is_empty() {
-        None
-    } else {
-        Some(text)
-    }
-}
-
 // ── 헬퍼 ──────────────────────────────────────────────────────────────────────
 
-fn strip_xml_tags(xml: &str) -> String {
-    let re = regex::Regex::new(r"<[^>]+>").unwrap();
-    let text = re.replace_all(xml, " ");
-    text.lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
 fn extract_title(content: &str, file_name: &str) -> String {
     // 4~80자 사이의 첫 번째 의미있는 줄을 제목으로 사용
     for line in content.lines() {
@@ -0,0 +1,672 @@
+//! 학교 내부망(출결·상벌점) 연결.
+//!
+//! 학교마다 내부망 주소와 계정이 다르므로 소스에 두지 않고 설정(레지스트리 `IntranetConfig`)에 둔다.
+//! 계정이 든 설정은 Windows DPAPI 로 현재 사용자에게 묶어 암호화해 저장한다.
+//!
+//! 내부망 종류마다 `IntranetConnector` 를 구현한다. 지금은 ASP.NET WebForms 로 된 출결·상벌점
+//! 시스템(`WebFormsConnector`)만 있다. 연결은 설정이 바뀌기 전까지 재사용해 로그인 세션과 쿠키를
+//! 호출 사이에 유지하고, 세션이 끊겨 로그인 화면이 돌아오면 한 번 다시 로그인한다.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{Datelike, NaiveDate};
+use reqwest::blocking::Client;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::secret_store;
+use crate::school_data::{LatecomerData, PointsData};
+
+const REG_CONFIG: &str = "IntranetConfig";
+/// 이 시간 안에는 다시 로그인하지 않는다(내부망 세션 만료 20분보다 짧게).
+const SESSION_TTL: Duration = Duration::from_secs(15 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const ATTENDANCE_PATH: &str = "/Pages/Student/AttendanceCertify.aspx";
+const POINTS_PATH: &str = "/Pages/Point/PointStudent.aspx";
+
+/// 내부망 종류.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntranetKind {
+    /// ASP.NET WebForms 출결·상벌점 시스템.
+    WebForms,
+}
+
+impl IntranetKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "" | "webforms" => Some(IntranetKind::WebForms),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IntranetKind::WebForms => "webforms",
+        }
+    }
+}
+
+/// 내부망 설정. 비밀번호가 들어 있으므로 저장할 때 `seal` 로 암호화한다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntranetConfig {
+    #[serde(default)]
+    pub kind: String,
+    /// 예: "http://10.0.0.1"
+    pub base_url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl IntranetConfig {
+    fn validate(&self) -> Result<(), String> {
+        IntranetKind::parse(&self.kind).ok_or_else(|| format!("알 수 없는 내부망 종류입니다: {}", self.kind))?;
+        let url = self.base_url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) || url.len() <= "https://".len() {
+            return Err("내부망 주소는 http:// 또는 https:// 로 시작해야 합니다.".to_string());
+        }
+        if self.username.trim().is_empty() || self.password.is_empty() {
+            return Err("내부망 아이디와 비밀번호를 입력해 주세요.".to_string());
+        }
+        Ok(())
+    }
+
+    fn base(&self) -> &str {
+        self.base_url.trim().trim_end_matches('/')
+    }
+
+    /// 저장된 설정. 없으면 None.
+    pub fn load() -> Result<Option<Self>, String> {
+        match get_registry_value(REG_CONFIG.to_string())?.filter(|v| !v.trim().is_empty()) {
+            Some(sealed) => open(&sealed).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        self.validate()?;
+        set_registry_value(REG_CONFIG.to_string(), seal(self)?)?;
+        reset_session();
+        Ok(())
+    }
+}
+
+/// 설정을 JSON 으로 만들어 암호화한다.
+fn seal(config: &IntranetConfig) -> Result<String, String> {
+    let json = serde_json::to_vec(config).map_err(|e| format!("내부망 설정 직렬화 실패: {}", e))?;
+    secret_store::seal(&json).map_err(|e| format!("내부망 설정 {}", e))
+}
+
+fn open(value: &str) -> Result<IntranetConfig, String> {
+    let json = secret_store::open(value).map_err(|e| format!("내부망 설정: {}", e))?;
+    serde_json::from_slice(&json).map_err(|e| format!("내부망 설정 손상: {}", e))
+}
+
+// ─── 연결 ─────────────────────────────────────────────────────────────────────
+
+/// 내부망 한 종류. 조회 결과와 함께 마지막 응답 HTML 을 돌려준다(학교 위젯 디버깅용).
+pub trait IntranetConnector: Send + Sync {
+    fn kind(&self) -> IntranetKind;
+
+    /// 로그인만 해 본다(설정 확인용).
+    fn check_login(&self) -> Result<(), String>;
+
+    /// `date` 의 지각·결석 학생.
+    fn attendance(&self, grade: &str, class: &str, date: NaiveDate) -> Result<(Vec<LatecomerData>, String), String>;
+
+    /// `from`~`to` 상벌점 합계.
+    fn points(&self, grade: &str, class: &str, from: NaiveDate, to: NaiveDate) -> Result<(Vec<PointsData>, String), String>;
+}
+
+static SESSION: Mutex<Option<(IntranetConfig, Arc<dyn IntranetConnector>)>> = Mutex::new(None);
+
+fn reset_session() {
+    if let Ok(mut session) = SESSION.lock() {
+        *session = None;
+    }
+}
+
+/// 저장된 설정의 연결. 설정이 같으면 이전 연결(세션·쿠키)을 그대로 쓴다.
+pub fn connector() -> Result<Arc<dyn IntranetConnector>, String> {
+    let config = IntranetConfig::load()?.ok_or("학교 위젯 설정에서 내부망 주소와 계정을 먼저 입력해 주세요.")?;
+    let mut session = SESSION.lock().map_err(|_| "내부망 세션 잠금 실패".to_string())?;
+    if let Some((saved, conn)) = session.as_ref() {
+        if *saved == config {
+            return Ok(conn.clone());
+        }
+    }
+    let conn = connect(&config)?;
+    *session = Some((config, conn.clone()));
+    Ok(conn)
+}
+
+fn connect(config: &IntranetConfig) -> Result<Arc<dyn IntranetConnector>, String> {
+    config.validate()?;
+    match IntranetKind::parse(&config.kind) {
+        Some(IntranetKind::WebForms) | None => Ok(Arc::new(WebFormsConnector::new(config)?)),
+    }
+}
+
+/// ASP.NET WebForms 내부망. 페이지마다 숨은 필드(__VIEWSTATE 등)를 받아 되돌려 보내야 한다.
+pub struct WebFormsConnector {
+    base_url: String,
+    username: String,
+    password: String,
+    client: Client,
+    logged_in_at: Mutex<Option<Instant>>,
+}
+
+impl WebFormsConnector {
+    pub fn new(config: &IntranetConfig) -> Result<Self, String> {
+        let jar = Arc::new(reqwest::cookie::Jar::default());
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .cookie_provider(jar)
+            .build()
+            .map_err(|e| format!("클라이언트 생성 실패: {}", e))?;
+        Ok(Self {
+            base_url: config.base().to_string(),
+            username: config.username.trim().to_string(),
+            password: config.password.clone(),
+            client,
+            logged_in_at: Mutex::new(None),
+        })
+    }
+
+    fn get(&self, url: &str) -> Result<String, String> {
+        self.client
+            .get(url)
+            .send()
+            .map_err(|e| format!("내부망 접속 실패: {}", e))?
+            .text()
+            .map_err(|e| format!("응답 읽기 실패: {}", e))
+    }
+
+    fn post(&self, url: &str, form: &[(&str, String)]) -> Result<String, String> {
+        self.client
+            .post(url)
+            .form(form)
+            .send()
+            .map_err(|e| format!("내부망 요청 실패: {}", e))?
+            .text()
+            .map_err(|e| format!("응답 읽기 실패: {}", e))
+    }
+
+    fn login(&self, force: bool) -> Result<(), String> {
+        let mut logged_in_at = self.logged_in_at.lock().map_err(|_| "내부망 세션 잠금 실패".to_string())?;
+        if !force && logged_in_at.is_some_and(|t| t.elapsed() < SESSION_TTL) {
+            return Ok(());
+        }
+        *logged_in_at = None;
+        let login_page = self.get(&self.base_url)?;
+        let mut form = hidden_fields(&login_page);
+        form.push(("txt_id", self.username.clone()));
+        form.push(("txt_pw", self.password.clone()));
+        form.push(("btn_login", "로그인".to_string()));
+        let response = self.post(&self.base_url, &form)?;
+        if is_login_page(&response) {
+            return Err("내부망 로그인 실패: 아이디와 비밀번호를 확인해 주세요.".to_string());
+        }
+        *logged_in_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// 로그인한 세션으로 `path` 를 열고, 그 페이지의 숨은 필드에 `fields` 를 더해 조회한다.
+    fn query(&self, path: &str, fields: Vec<(&str, String)>) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+        self.login(false)?;
+        let mut page = self.get(&url)?;
+        if is_login_page(&page) {
+            // 세션이 서버에서 끊겼다.
+            self.login(true)?;
+            page = self.get(&url)?;
+            if is_login_page(&page) {
+                return Err("내부망 세션을 유지하지 못했습니다.".to_string());
+            }
+        }
+        let mut form = hidden_fields(&page);
+        form.extend(fields);
+        let result = self.post(&url, &form)?;
+        if is_login_page(&result) {
+            return Err("내부망 세션이 끊겼습니다. 다시 시도해 주세요.".to_string());
+        }
+        Ok(result)
+    }
+}
+
+impl IntranetConnector for WebFormsConnector {
+    fn kind(&self) -> IntranetKind {
+        IntranetKind::WebForms
+    }
+
+    fn check_login(&self) -> Result<(), String> {
+        self.login(true)
+    }
+
+    fn attendance(&self, grade: &str, class: &str, date: NaiveDate) -> Result<(Vec<LatecomerData>, String), String> {
+        let field = |name: &str| format!("ctl00$ContentPlaceHolder1${}", name);
+        let mut fields: Vec<(String, String)> = vec![
+            ("__EVENTTARGET".to_string(), String::new()),
+            ("__EVENTARGUMENT".to_string(), String::new()),
+            (field("txt_date"), date.format("%Y-%m-%d").to_string()),
+            (field("ddl_grade"), grade.to_string()),
+            (field("ddl_class"), class.to_string()),
+            (field("btn_select"), "선택".to_string()),
+            (field("rbg"), "rboDL".to_string()),
+            (field("ddl_AttType"), "L".to_string()),
+        ];
+        for empty in ["txt_inTime", "MaskedEditExtender1_ClientState", "hhd_idx", "hhd_gradeno", "hhd_attidx", "hhd_OriType", "hid_checkedIDs"] {
+            fields.push((field(empty), String::new()));
+        }
+        let html = self.query(ATTENDANCE_PATH, fields.iter().map(|(k, v)| (k.as_str(), v.clone())).collect())?;
+        Ok((parse_attendance(&html), html))
+    }
+
+    fn points(&self, grade: &str, class: &str, from: NaiveDate, to: NaiveDate) -> Result<(Vec<PointsData>, String), String> {
+        let field = |name: &str| format!("ctl00$ContentPlaceHolder1${}", name);
+        let fields: Vec<(String, String)> = vec![
+            ("__EVENTTARGET".to_string(), String::new()),
+            ("__EVENTARGUMENT".to_string(), String::new()),
+            (field("txt_start"), from.format("%Y-%m-%d").to_string()),
+            (field("txt_end"), to.format("%Y-%m-%d").to_string()),
+            (field("ddl_grade"), grade.to_string()),
+            (field("ddl_class"), class.to_string()),
+            (field("txt_name"), String::new()),
+            (field("btn_select"), "조회".to_string()),
+        ];
+        let html = self.query(POINTS_PATH, fields.iter().map(|(k, v)| (k.as_str(), v.clone())).collect())?;
+        Ok((parse_points(&html), html))
+    }
+}
+
+// ─── HTML ─────────────────────────────────────────────────────────────────────
+
+fn hidden_fields(html: &str) -> Vec<(&'static str, String)> {
+    let document = Html::parse_document(html);
+    ["__VIEWSTATE", "__VIEWSTATEGENERATOR", "__EVENTVALIDATION"]
+        .into_iter()
+        .map(|name| {
+            let value = Selector::parse(&format!("input[name='{}']", name))
+                .ok()
+                .and_then(|s| document.select(&s).next().and_then(|el| el.value().attr("value")).map(str::to_string))
+                .unwrap_or_default();
+            (name, value)
+        })
+        .collect()
+}
+
+fn is_login_page(html: &str) -> bool {
+    let document = Html::parse_document(html);
+    Selector::parse("input[name='txt_pw']").is_ok_and(|s| document.select(&s).next().is_some())
+}
+
+/// 학생 칸으로 보이는 값("1학년 3반 5번 홍길동")만 남긴다. 머리글 행을 거른다.
+fn is_student_cell(s: &str) -> bool {
+    !s.is_empty()
+        && s.contains("학년")
+        && s.contains("반")
+        && !s.contains("학생정보")
+        && !s.contains("학생 이름")
+        && !s.contains("출석종류")
+        && !s.contains("등교종류")
+        && !s.contains("번호")
+        && s.len() > 5
+}
+
+fn cell_text(cell: &scraper::ElementRef) -> String {
+    cell.text().collect::<String>().trim().to_string()
+}
+
+/// 출결 조회 결과. 첫 행이 머리글("출결사항"·"등교시간")이면 열 위치를 거기서 찾는다.
+pub fn parse_attendance(html: &str) -> Vec<LatecomerData> {
+    let document = Html::parse_document(html);
+    let (Ok(table_selector), Ok(row_selector), Ok(cell_selector)) =
+        (Selector::parse("table"), Selector::parse("tr"), Selector::parse("td, th"))
+    else {
+        return Vec::new();
+    };
+
+    let mut latecomers = Vec::new();
+    for table in document.select(&table_selector) {
+        let (mut student_idx, mut arrival_idx, mut status_idx) = (0, 1, 2);
+        let mut header_found = false;
+        for (idx, row) in table.select(&row_selector).enumerate() {
+            let cells: Vec<_> = row.select(&cell_selector).collect();
+            if cells.is_empty() {
+                continue;
+            }
+            if idx == 0 {
+                let headers: Vec<String> = cells.iter().map(cell_text).collect();
+                if headers.iter().any(|h| h.contains("출결사항") || h.contains("등교시간")) {
+                    header_found = true;
+                    for (i, text) in headers.iter().enumerate() {
+                        if text.contains("학생정보") || text.contains("학생 이름") {
+                            student_idx = i;
+                        } else if text.contains("등교시간") {
+                            arrival_idx = i;
+                        } else if text.contains("출결사항") || text.contains("출석종류") {
+                            status_idx = i;
+                        }
+                    }
+                    continue;
+                }
+            }
+            if cells.len() < 3 || !(header_found || idx > 0) {
+                continue;
+            }
+            let text_at = |i: usize| cells.get(i).map(cell_text).unwrap_or_default();
+            let student_info = text_at(student_idx);
+            if is_student_cell(&student_info) {
+                latecomers.push(LatecomerData {
+                    student_info,
+                    arrival_time: text_at(arrival_idx),
+                    attendance_status: text_at(status_idx),
+                });
+            }
+        }
+        // 첫 번째로 학생이 나온 표만 쓴다.
+        if !latecomers.is_empty() {
+            break;
+        }
+    }
+    latecomers
+}
+
+/// 상벌점 조회 결과(`ContentPlaceHolder1_gv_pointList`). 상점·벌점·상쇄는 칸 안의 `p` 를 우선 읽는다.
+pub fn parse_points(html: &str) -> Vec<PointsData> {
+    let document = Html::parse_document(html);
+    let (Ok(table_selector), Ok(row_selector), Ok(cell_selector), Ok(p_selector)) = (
+        Selector::parse("table[id='ContentPlaceHolder1_gv_pointList']"),
+        Selector::parse("tr"),
+        Selector::parse("td"),
+        Selector::parse("p"),
+    ) else {
+        return Vec::new();
+    };
+    let Some(table) = document.select(&table_selector).next() else {
+        return Vec::new();
+    };
+
+    let number = |cell: Option<&scraper::ElementRef>| -> i32 {
+        let text = match cell {
+            Some(c) => c.select(&p_selector).next().map(|p| cell_text(&p)).unwrap_or_else(|| cell_text(c)),
+            None => String::new(),
+        };
+        text.chars().filter(|c| c.is_ascii_digit()).collect::<String>().parse().unwrap_or(0)
+    };
+
+    let mut points = Vec::new();
+    for (idx, row) in table.select(&row_selector).enumerate() {
+        let cells: Vec<_> = row.select(&cell_selector).collect();
+        if cells.len() < 6 || idx == 0 {
+            continue;
+        }
+        let student_info = cell_text(&cells[0]);
+        if !is_student_cell(&student_info) {
+            continue;
+        }
+        let reward = number(cells.get(2));
+        // 벌점은 표기와 관계없이 음수로 센다.
+        let penalty = -number(cells.get(3));
+        let offset = number(cells.get(4));
+        points.push(PointsData { student_info, reward, penalty, offset, total: reward + penalty + offset });
+    }
+    points
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+/// 설정 화면에 보여 줄 값. 비밀번호는 돌려주지 않는다.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntranetSettings {
+    pub kind: String,
+    pub base_url: String,
+    pub username: String,
+    pub has_password: bool,
+}
+
+#[tauri::command]
+pub fn get_intranet_settings() -> Result<Option<IntranetSettings>, String> {
+    Ok(IntranetConfig::load()?.map(|c| IntranetSettings {
+        kind: IntranetKind::parse(&c.kind).unwrap_or(IntranetKind::WebForms).as_str().to_string(),
+        base_url: c.base_url,
+        username: c.username,
+        has_password: !c.password.is_empty(),
+    }))
+}
+
+/// 비밀번호를 비워 두면 저장된 비밀번호를 그대로 쓴다.
+#[tauri::command]
+pub fn set_intranet_settings(
+    kind: Option<String>,
+    base_url: String,
+    username: String,
+    password: Option<String>,
+) -> Result<(), String> {
+    let password = match password.filter(|p| !p.is_empty()) {
+        Some(p) => p,
+        None => IntranetConfig::load().ok().flatten().map(|c| c.password).unwrap_or_default(),
+    };
+    IntranetConfig {
+        kind: kind.unwrap_or_else(|| IntranetKind::WebForms.as_str().to_string()),
+        base_url: base_url.trim().to_string(),
+        username: username.trim().to_string(),
+        password,
+    }
+    .save()
+}
+
+#[tauri::command]
+pub fn test_intranet_login() -> Result<(), String> {
+    connector()?.check_login()
+}
+
+/// 오늘 지각·결석 학생.
+pub fn fetch_attendance(grade: &str, class: &str) -> Result<(Vec<LatecomerData>, String), String> {
+    connector()?.attendance(grade, class, chrono::Local::now().date_naive())
+}
+
+/// 올해 1월 1일부터 오늘까지 상벌점.
+pub fn fetch_points(grade: &str, class: &str) -> Result<(Vec<PointsData>, String), String> {
+    let today = chrono::Local::now().date_naive();
+    let from = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today);
+    connector()?.points(grade, class, from, today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+
+    fn fixture(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("intranet").join(name);
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+    }
+
+    #[derive(Default)]
+    struct Stand {
+        logins: usize,
+        sessions: HashSet<String>,
+        /// 조회 POST 본문(숨은 필드가 오가는지 확인).
+        queries: Vec<String>,
+    }
+
+    /// 내부망 흉내: 로그인하면 세션 쿠키를 주고, 쿠키가 없거나 끊긴 세션이면 로그인 화면을 준다.
+    fn stand_in() -> (String, Arc<Mutex<Stand>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(Stand::default()));
+        let shared = state.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let (mut length, mut cookie) = (0usize, String::new());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) <= 2 {
+                        break;
+                    }
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(v) = lower.strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap_or(0);
+                    } else if lower.starts_with("cookie:") {
+                        cookie = line["cookie:".len()..].trim().to_string();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8_lossy(&body).to_string();
+                let mut parts = request_line.split_whitespace();
+                let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+                let mut st = shared.lock().unwrap();
+                let session = cookie
+                    .split(';')
+                    .find_map(|c| c.trim().strip_prefix("ASP.NET_SessionId=").map(str::to_string))
+                    .filter(|s| st.sessions.contains(s));
+                let mut set_cookie = None;
+                let page = match (method, path, session) {
+                    ("GET", "/", _) => fixture("login.html"),
+                    ("POST", "/", _) => {
+                        if body.contains("txt_id=teacher") && body.contains("txt_pw=s3cret%21") && body.contains("__VIEWSTATE=login-vs") {
+                            st.logins += 1;
+                            let id = format!("s{}", st.logins);
+                            st.sessions.insert(id.clone());
+                            set_cookie = Some(id);
+                            "<html><body>환영합니다</body></html>".to_string()
+                        } else {
+                            fixture("login.html")
+                        }
+                    }
+                    (_, _, None) => fixture("login.html"),
+                    ("GET", ATTENDANCE_PATH, Some(_)) => fixture("attendance_form.html"),
+                    ("POST", ATTENDANCE_PATH, Some(_)) => {
+                        st.queries.push(body);
+                        fixture("attendance_result.html")
+                    }
+                    ("GET", POINTS_PATH, Some(_)) => fixture("points_form.html"),
+                    ("POST", POINTS_PATH, Some(_)) => {
+                        st.queries.push(body);
+                        fixture("points_result.html")
+                    }
+                    _ => String::new(),
+                };
+                drop(st);
+                let cookie_header = set_cookie
+                    .map(|id| format!("Set-Cookie: ASP.NET_SessionId={}; path=/; HttpOnly\r\n", id))
+                    .unwrap_or_default();
+                let mut stream = stream;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    cookie_header,
+                    page.len(),
+                    page
+                );
+            }
+        });
+        (format!("http://{}", addr), state)
+    }
+
+    fn config(base_url: &str, password: &str) -> IntranetConfig {
+        IntranetConfig {
+            kind: "webforms".to_string(),
+            base_url: format!("{}/", base_url),
+            username: "teacher".to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn session_is_reused_across_calls_and_renewed_when_dropped() {
+        let (base, stand) = stand_in();
+        let conn = WebFormsConnector::new(&config(&base, "s3cret!")).unwrap();
+
+        let (late, _) = conn.attendance("1", "3", d("2026-03-04")).unwrap();
+        let (points, _) = conn.points("1", "3", d("2026-01-01"), d("2026-03-04")).unwrap();
+        assert_eq!(stand.lock().unwrap().logins, 1);
+        assert_eq!(late.len(), 2);
+        assert_eq!(points.len(), 2);
+        {
+            let st = stand.lock().unwrap();
+            assert!(st.queries[0].contains("__VIEWSTATE=att-vs"), "{}", st.queries[0]);
+            assert!(st.queries[0].contains("txt_date=2026-03-04"));
+            assert!(st.queries[1].contains("__VIEWSTATE=pt-vs"));
+        }
+
+        // 서버에서 세션이 끊기면 로그인 화면이 오고, 한 번 다시 로그인한다.
+        stand.lock().unwrap().sessions.clear();
+        let (again, _) = conn.attendance("1", "3", d("2026-03-04")).unwrap();
+        assert_eq!(again, late);
+        assert_eq!(stand.lock().unwrap().logins, 2);
+    }
+
+    #[test]
+    fn wrong_password_is_reported() {
+        let (base, stand) = stand_in();
+        let conn = WebFormsConnector::new(&config(&base, "wrong")).unwrap();
+        let err = conn.attendance("1", "3", d("2026-03-04")).unwrap_err();
+        assert!(err.contains("로그인 실패"), "{}", err);
+        assert_eq!(stand.lock().unwrap().logins, 0);
+    }
+
+    #[test]
+    fn pages_are_parsed() {
+        let late = parse_attendance(&fixture("attendance_result.html"));
+        assert_eq!(
+            late,
+            [
+                LatecomerData {
+                    student_info: "1학년 3반 5번 김하늘".to_string(),
+                    arrival_time: "08:47".to_string(),
+                    attendance_status: "지각".to_string(),
+                },
+                LatecomerData {
+                    student_info: "1학년 3반 12번 이바다".to_string(),
+                    arrival_time: "".to_string(),
+                    attendance_status: "질병결석".to_string(),
+                },
+            ]
+        );
+
+        let points = parse_points(&fixture("points_result.html"));
+        assert_eq!(
+            points,
+            [
+                PointsData { student_info: "1학년 3반 5번 김하늘".to_string(), reward: 5, penalty: -3, offset: 1, total: 3 },
+                PointsData { student_info: "1학년 3반 12번 이바다".to_string(), reward: 0, penalty: -2, offset: 0, total: -2 },
+            ]
+        );
+        assert!(parse_points(&fixture("login.html")).is_empty());
+        assert!(is_login_page(&fixture("login.html")));
+        assert!(!is_login_page(&fixture("attendance_form.html")));
+    }
+
+    #[test]
+    fn config_is_sealed_and_validated() {
+        let c = config("http://10.0.0.1", "pw");
+        let sealed = seal(&c).unwrap();
+        assert!(secret_store::is_sealed(&sealed));
+        assert_eq!(open(&sealed).unwrap(), c);
+        assert!(open("{\"baseUrl\":\"x\"}").is_err());
+
+        assert!(c.validate().is_ok());
+        assert_eq!(WebFormsConnector::new(&c).unwrap().base_url, "http://10.0.0.1");
+        assert!(config("10.0.0.1", "pw").validate().is_err());
+        assert!(config("http://10.0.0.1", "").validate().is_err());
+        assert!(IntranetConfig { kind: "neis".to_string(), ..c }.validate().is_err());
+    }
+}
@@ -1,16 +1,36 @@
 pub mod agent;
+pub mod attachment_sandbox;
+pub mod bell_schedule;
+pub mod briefing_backend;
+pub mod briefing_eval;
+pub mod briefing_rules;
+pub mod briefing_runs;
+pub mod briefing_usage;
 pub mod commands;
+pub mod cover;
+pub mod date_expr;
 pub mod db;
+pub mod digest;
+pub mod doc_extract;
 pub mod edufine_db;
 pub mod edufine_watcher;
 pub mod gif_clipboard;
 pub mod gif_watcher;
+pub mod intranet;
 pub mod download_watcher;
 pub mod mcp_server;
+pub mod meal;
 pub mod models;
+pub mod neis;
+pub mod pii_redact;
+pub mod reminders;
 pub mod school_data;
 pub mod search_db;
+pub mod secret_store;
 pub mod tenor;
+pub mod timetable;
+pub mod timetable_export;
+pub mod timetable_history;
 pub mod timetable_parser;
 pub mod utils;
 pub mod window_blur;
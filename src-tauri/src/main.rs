@@ -2,16 +2,32 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use hypercool::agent;
+use hypercool::bell_schedule;
 use hypercool::commands::{messages, mcp as mcp_commands, system, window};
+use hypercool::cover;
 use hypercool::db;
 use hypercool::download_watcher;
 use hypercool::edufine_db;
 use hypercool::gif_clipboard;
 use hypercool::gif_watcher;
+use hypercool::intranet;
+use hypercool::meal;
 use hypercool::models::CacheState;
+use hypercool::neis;
+use hypercool::pii_redact;
+use hypercool::briefing_backend;
+use hypercool::briefing_runs;
+use hypercool::briefing_usage;
+use hypercool::briefing_rules;
+use hypercool::digest;
+use hypercool::reminders;
 use hypercool::school_data;
 use hypercool::search_db;
 use hypercool::tenor;
+use hypercool::timetable;
+use hypercool::timetable::TimetableSource;
+use hypercool::timetable_export;
+use hypercool::timetable_history;
 use hypercool::timetable_parser;
 use hypercool::appin_parser;
 use hypercool::utils::is_class_time;
@@ -46,30 +62,12 @@ fn cmd_copy_html(html: String) -> Result<(), String> {
     gif_clipboard::copy_html_to_clipboard(&html).map_err(|e| e.to_string())
 }
 
-/// 학교 위젯(types.ts PERIOD_TIMES)과 동일한 하드코딩 시간으로 현재 교시의 시간표 인덱스를 반환.
-/// 점심(12:20-13:20)은 None. 5교시 이후는 점심 슬롯을 건너뛰어 timetable 인덱스에 맞게 조정.
+/// 오늘 종 시간표(`bell_schedule`)로 현재 교시의 시간표 인덱스(교시 - 1)를 반환.
+/// 쉬는 시간·점심처럼 교시가 아닌 칸이면 None. 종 시간표를 설정하지 않았으면 기본 프로필을 쓴다.
 fn get_current_period() -> Option<usize> {
-    use chrono::Timelike;
-    // (시작분, 종료분) — types.ts PERIOD_TIMES와 동일
-    const PERIOD_TIMES: &[(u32, u32)] = &[
-        (8*60+30, 9*60+20),   // 0 → timetable[0] (1교시)
-        (9*60+30, 10*60+20),  // 1 → timetable[1] (2교시)
-        (10*60+30, 11*60+20), // 2 → timetable[2] (3교시)
-        (11*60+30, 12*60+20), // 3 → timetable[3] (4교시)
-        (12*60+20, 13*60+20), // 4 = 점심 → None
-        (13*60+20, 14*60+10), // 5 → timetable[4] (5교시)
-        (14*60+20, 15*60+10), // 6 → timetable[5] (6교시)
-        (15*60+20, 16*60+10), // 7 → timetable[6] (7교시)
-    ];
-    let now = chrono::Local::now();
-    let mins = now.hour() * 60 + now.minute();
-    for (i, &(start, end)) in PERIOD_TIMES.iter().enumerate() {
-        if mins >= start && mins <= end {
-            if i == 4 { return None; } // 점심시간
-            return Some(if i < 4 { i } else { i - 1 });
-        }
-    }
-    None
+    bell_schedule::load()
+        .period_on(chrono::Local::now().naive_local())
+        .map(|p| p as usize - 1)
 }
 
 fn parse_recipient_name(raw: &str) -> Option<String> {
@@ -99,35 +97,6 @@ fn parse_btn_slot(label: &str) -> Option<usize> {
     label.rsplit('-').next()?.parse().ok()
 }
 
-fn lookup_appin_subject(
-    data: &appin_parser::AppinTimetableData,
-    teacher_name: &str,
-    period_idx: usize,
-) -> (String, Option<String>) {
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-    let period_str = (period_idx + 1).to_string();
-    let teacher_idx = match data.teachers.iter().position(|t| t == teacher_name) {
-        Some(i) => i,
-        None => return (String::new(), None),
-    };
-    let day_data = match data.days.get(&today) {
-        Some(d) => d,
-        None => return (String::new(), None),
-    };
-    for (class_name, period_map) in day_data {
-        if let Some(slot) = period_map.get(&period_str) {
-            if slot.teacher == Some(teacher_idx) {
-                let subject = slot.subject
-                    .and_then(|si| data.subjects.get(si))
-                    .cloned()
-                    .unwrap_or_default();
-                return (subject, if class_name.is_empty() { None } else { Some(class_name.clone()) });
-            }
-        }
-    }
-    (String::new(), None)
-}
-
 #[tauri::command]
 fn get_class_status(btn_label: String, timetable_source: Option<String>) -> Result<ClassStatusResult, String> {
     let slot = parse_btn_slot(&btn_label).ok_or("invalid label")?;
@@ -160,70 +129,17 @@ fn get_class_status(btn_label: String, timetable_source: Option<String>) -> Resu
     }
 
     let period_idx = current_period.unwrap();
-    use chrono::Datelike;
-    let day_idx = match chrono::Local::now().weekday() {
-        chrono::Weekday::Mon => 0usize,
-        chrono::Weekday::Tue => 1,
-        chrono::Weekday::Wed => 2,
-        chrono::Weekday::Thu => 3,
-        chrono::Weekday::Fri => 4,
-        _ => {
-            return Ok(ClassStatusResult {
-                is_broadcast: false,
-                in_class_period: true,
-                current_period: Some(period_idx),
-                recipients: names.iter().map(|n| RecipientStatus {
-                    name: n.clone(), subject: None, room: None,
-                }).collect(),
-            });
+    let today = chrono::Local::now().date_naive();
+    let timetable = TimetableSource::parse(timetable_source.as_deref()).load().ok();
+    let recipients = names.iter().map(|name| {
+        let lesson = timetable.as_ref()
+            .and_then(|t| t.lesson_at(name, today, period_idx as u32 + 1));
+        RecipientStatus {
+            name: name.clone(),
+            subject: lesson.as_ref().map(|l| l.subject.clone()).filter(|s| !s.is_empty()),
+            room: lesson.as_ref().and_then(|l| l.place()).map(str::to_string),
         }
-    };
-
-    let use_appin = timetable_source.as_deref() == Some("appin");
-
-    let recipients = if use_appin {
-        let appin_data = appin_parser::parse_appin_timetable().ok();
-        names.iter().map(|name| {
-            let (subject, room) = appin_data.as_ref()
-                .map(|d| lookup_appin_subject(d, name, period_idx))
-                .unwrap_or((String::new(), None));
-            RecipientStatus {
-                name: name.clone(),
-                subject: if subject.is_empty() { None } else { Some(subject) },
-                room,
-            }
-        }).collect()
-    } else {
-        let timetable = match timetable_parser::parse_timetable() {
-            Ok(t) => t,
-            Err(_) => {
-                return Ok(ClassStatusResult {
-                    is_broadcast: false,
-                    in_class_period: true,
-                    current_period: Some(period_idx),
-                    recipients: names.iter().map(|n| RecipientStatus {
-                        name: n.clone(), subject: None, room: None,
-                    }).collect(),
-                });
-            }
-        };
-        names.iter().map(|name| {
-            let (subject, room) = timetable.timetables.get(name)
-                .and_then(|tt| tt.get(period_idx))
-                .and_then(|day_row| day_row.get(day_idx))
-                .and_then(|cell| {
-                    let subj = cell.first().filter(|s| !s.is_empty()).cloned()?;
-                    let rm = cell.get(1).filter(|r| !r.is_empty()).cloned();
-                    Some((subj, rm))
-                })
-                .unwrap_or((String::new(), None));
-            RecipientStatus {
-                name: name.clone(),
-                subject: if subject.is_empty() { None } else { Some(subject) },
-                room,
-            }
-        }).collect()
-    };
+    }).collect();
 
     Ok(ClassStatusResult {
         is_broadcast: false,
@@ -389,12 +305,17 @@ fn main() {
             search_db::read_cached_messages,
             search_db::get_cached_message_count,
             search_db::is_cache_ready,
+            search_db::get_message_attachments,
 
             mcp_commands::get_mcp_status,
             mcp_commands::toggle_edufine_mcp,
             mcp_commands::get_edufine_stats,
             mcp_commands::list_edufine_docs_recent,
             mcp_commands::open_edufine_watch_dir,
+            pii_redact::get_pii_redact_settings,
+            pii_redact::set_pii_redact_settings,
+            briefing_backend::get_briefing_backend_settings,
+            briefing_backend::set_briefing_backend_settings,
 
             agent::run_briefing_agent_now,
             agent::run_briefing_agent_debug,
@@ -402,12 +323,62 @@ fn main() {
             agent::set_briefing_agent_enabled,
             agent::migrate_ai_schedules_content,
             agent::get_coolmessenger_status,
+            agent::get_schedule_revisions,
+            agent::resolve_schedule_revision,
+            briefing_runs::list_briefing_runs,
+            briefing_runs::get_briefing_run,
+            briefing_runs::trace_briefing_message,
+            briefing_usage::get_briefing_usage,
+            briefing_usage::get_briefing_budget,
+            briefing_usage::set_briefing_budget,
+            briefing_rules::get_briefing_rules,
+            briefing_rules::save_briefing_rule,
+            briefing_rules::delete_briefing_rule,
+            digest::get_digest_settings,
+            digest::set_digest_settings,
+            digest::generate_digest_now,
+            digest::list_digests,
+            digest::get_digest,
+            reminders::get_reminder_settings,
+            reminders::set_reminder_settings,
+            reminders::list_pending_reminders,
+            reminders::snooze_reminder,
+            reminders::dismiss_reminder,
+            reminders::get_schedule_reminders,
+            reminders::set_schedule_reminders,
 
             timetable_parser::get_timetable_data,
             appin_parser::get_appin_timetable_data,
-            school_data::get_meal_data,
+            appin_parser::get_appin_settings,
+            appin_parser::set_appin_settings,
+            timetable::get_timetable_teachers,
+            timetable::get_teacher_week,
+            timetable_export::export_timetable,
+            timetable_history::check_timetable_changes,
+            timetable_history::get_timetable_changes,
+            cover::find_substitute_teachers,
+            cover::assign_cover_lesson,
+            cover::get_cover_lessons,
+            cover::delete_cover_lesson,
+            bell_schedule::get_bell_schedule,
+            bell_schedule::set_bell_schedule,
+            bell_schedule::get_bell_profile_for,
+            neis::get_neis_timetable,
+            neis::get_neis_school_schedule,
+            neis::import_neis_school_schedule,
+            neis::search_schools,
+            neis::get_school_profile,
+            neis::set_school_profile,
+            meal::get_meal_data,
+            meal::get_meals,
+            meal::list_allergens,
+            meal::get_meal_allergens,
+            meal::set_meal_allergens,
             school_data::get_attendance_data,
             school_data::get_points_data,
+            intranet::get_intranet_settings,
+            intranet::set_intranet_settings,
+            intranet::test_intranet_login,
             school_data::get_stock_quotes,
 
             cmd_search_tenor,
@@ -458,6 +429,12 @@ fn main() {
                 // 브리핑 에이전트 활성 상태 복원(기본 OFF, 옵트인)
                 agent::restore_state(app.app_handle());
 
+                // 아침 다이제스트 스케줄러(설정이 꺼져 있으면 확인만 한다)
+                digest::start_scheduler(app.app_handle().clone());
+                reminders::start_scheduler(app.app_handle().clone());
+                // 시간표 파일 변경(보강·교체) 감시
+                timetable_history::start_watcher(app.app_handle().clone());
+
                 // 기등록 AI 일정 content 를 최신 형식(원문 전체·이모지 마커 제거)으로 보정.
                 // 멱등이라 매 시작 시 백그라운드로 안전하게 재실행 — 검색 DB 색인이 끝난 뒤에도
                 // 자기치유(변경분만 UPDATE)된다.
@@ -12,10 +12,16 @@ use std::path::PathBuf;
 use std::sync::{Arc, OnceLock};
 use winreg::{enums::HKEY_CURRENT_USER, RegKey};
 
+use crate::attachment_sandbox::{self, CancelToken, SandboxError};
+use crate::doc_extract;
 use crate::edufine_db;
 use crate::edufine_watcher;
+use crate::pii_redact;
+use crate::search_db;
+
+/// 지원하는 MCP 프로토콜 버전(최신순). `structuredContent`/`outputSchema` 는 2025-06-18 부터.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
 
-static XML_TAG_REGEX: OnceLock<regex::Regex> = OnceLock::new();
 static INLINE_IMG_REGEX: OnceLock<regex::Regex> = OnceLock::new();
 
 struct McpState {
@@ -55,6 +61,39 @@ fn err_response(code: i32, message: &str, id: Option<Value>) -> JsonRpcResponse
     }
 }
 
+/// 도구 실행 오류. 샌드박스 거부는 전용 코드와 `data.reason` 을 싣고, 나머지는 -32603.
+struct ToolError {
+    code: i32,
+    message: String,
+    reason: Option<&'static str>,
+}
+
+impl From<String> for ToolError {
+    fn from(message: String) -> Self {
+        ToolError { code: -32603, message, reason: None }
+    }
+}
+
+impl From<&str> for ToolError {
+    fn from(message: &str) -> Self {
+        ToolError::from(message.to_string())
+    }
+}
+
+impl From<SandboxError> for ToolError {
+    fn from(e: SandboxError) -> Self {
+        ToolError { code: e.code(), message: e.message(), reason: Some(e.reason()) }
+    }
+}
+
+fn tool_err_response(e: ToolError, id: Option<Value>) -> JsonRpcResponse {
+    let mut error = json!({ "code": e.code, "message": e.message });
+    if let Some(reason) = e.reason {
+        error["data"] = json!({ "reason": reason });
+    }
+    JsonRpcResponse { jsonrpc: "2.0".into(), result: None, error: Some(error), id }
+}
+
 async fn handle_mcp(
     State(state): State<Arc<McpState>>,
     Json(req): Json<JsonRpcRequest>,
@@ -67,14 +106,28 @@ async fn handle_mcp(
     let id = req.id.clone();
 
     let response = match req.method.as_str() {
-        "initialize" => ok_response(
-            json!({
-                "protocolVersion": "2024-11-05",
-                "capabilities": { "tools": {} },
-                "serverInfo": { "name": "hypercool-mcp", "version": "1.0.0" }
-            }),
-            id,
-        ),
+        "initialize" => {
+            // 클라이언트가 요청한 버전을 지원하면 그대로, 아니면 가장 오래된 호환 버전으로 응답.
+            // 구버전 클라이언트는 structuredContent 를 무시하고 text 블록만 읽는다.
+            let requested = req
+                .params
+                .as_ref()
+                .and_then(|p| p["protocolVersion"].as_str())
+                .unwrap_or("");
+            let version = SUPPORTED_PROTOCOL_VERSIONS
+                .iter()
+                .find(|v| **v == requested)
+                .copied()
+                .unwrap_or("2024-11-05");
+            ok_response(
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": {} },
+                    "serverInfo": { "name": "hypercool-mcp", "version": "1.0.0" }
+                }),
+                id,
+            )
+        }
 
         "tools/list" => {
             let mut tools = vec![
@@ -119,13 +172,14 @@ async fn handle_mcp(
                 }),
                 json!({
                     "name": "list_attachments",
-                    "description": "수신 첨부 파일 목록 조회 (list received file attachments). 파일명 검색·확장자 필터 지원 (pdf, hwpx, xlsx 등).",
+                    "description": "수신 첨부 파일 목록 조회 (list received file attachments). 각 파일을 전달한 메시지(ID·발신자·날짜)와 다운로드 여부를 함께 반환. 파일명 검색·확장자·다운로드 상태 필터 지원 (pdf, hwpx, xlsx 등).",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
-                            "query": { "type": "string", "description": "파일명 검색어 (filename search, 부분 일치)" },
-                            "ext":   { "type": "string", "description": "확장자 필터 (예: pdf, hwpx, xlsx)" },
-                            "limit": { "type": "number", "description": "최대 결과 수 (기본값: 50, 최대: 200)" }
+                            "query":  { "type": "string", "description": "파일명 검색어 (filename search, 부분 일치)" },
+                            "ext":    { "type": "string", "description": "확장자 필터 (예: pdf, hwpx, xlsx)" },
+                            "status": { "type": "string", "enum": ["all", "downloaded", "not_downloaded"], "description": "다운로드 상태 필터 (기본값: all)" },
+                            "limit":  { "type": "number", "description": "최대 결과 수 (기본값: 50, 최대: 200)" }
                         }
                     }
                 }),
@@ -192,6 +246,12 @@ async fn handle_mcp(
                 }));
             }
 
+            for tool in tools.iter_mut() {
+                if let Some(schema) = output_schema(tool["name"].as_str().unwrap_or("")) {
+                    tool["outputSchema"] = schema;
+                }
+            }
+
             ok_response(json!({ "tools": tools }), id)
         }
 
@@ -207,8 +267,13 @@ async fn handle_mcp(
             let args = params["arguments"].clone();
 
             match call_tool(&state.db_path, &state.edufine_db_path, &name, &args) {
-                Ok(result) => ok_response(result, id),
-                Err(e) => err_response(-32603, &e, id),
+                Ok(mut result) => {
+                    if let Some(cfg) = pii_redact::active_config() {
+                        pii_redact::redact_tool_result(&cfg, &mut result);
+                    }
+                    ok_response(result, id)
+                }
+                Err(e) => tool_err_response(e, id),
             }
         }
 
@@ -220,6 +285,213 @@ async fn handle_mcp(
     Json(response).into_response()
 }
 
+// ─── 구조화 출력 ──────────────────────────────────────────────────────────────
+
+/// 도구 결과: 구조화 결과(`structuredContent`)와 사람이 읽는 text 블록(구버전 클라이언트용 폴백).
+fn tool_result(text: String, structured: Value) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "structuredContent": structured
+    })
+}
+
+/// 메시지 요약 객체(검색·목록 공통).
+fn message_json(id: i64, sender: &str, date: Option<&str>, preview: &str, attachments: &[String]) -> Value {
+    json!({
+        "id": id,
+        "sender": sender,
+        "receive_date": date,
+        "preview": preview,
+        "attachments": attachments
+    })
+}
+
+fn message_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id":           { "type": "integer", "description": "메시지 ID" },
+            "sender":       { "type": "string" },
+            "receive_date": { "type": ["string", "null"] },
+            "preview":      { "type": "string", "description": "본문 미리보기(HTML 제거)" },
+            "attachments":  { "type": "array", "items": { "type": "string" }, "description": "첨부 파일명" },
+            "images": {
+                "type": "array",
+                "description": "images_only=true 일 때만: 첨부/인라인 이미지",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "kind":     { "type": "string", "enum": ["attachment", "inline"] },
+                        "filename": { "type": "string" },
+                        "index":    { "type": "integer" },
+                        "mime":     { "type": "string" },
+                        "verified": { "type": "boolean", "description": "magic bytes 확인 여부" }
+                    },
+                    "required": ["kind", "mime", "verified"]
+                }
+            }
+        },
+        "required": ["id", "sender", "receive_date", "attachments"]
+    })
+}
+
+fn doc_preview_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id":          { "type": "integer" },
+            "title":       { "type": ["string", "null"] },
+            "file_name":   { "type": "string" },
+            "preview":     { "type": "string" },
+            "detected_at": { "type": "string" }
+        },
+        "required": ["id", "title", "file_name", "preview", "detected_at"]
+    })
+}
+
+/// 도구별 `outputSchema`. 이미지(view_image)처럼 구조화 결과가 없는 도구는 None.
+fn output_schema(name: &str) -> Option<Value> {
+    let schema = match name {
+        "search_messages" => json!({
+            "type": "object",
+            "properties": {
+                "query":    { "type": "string" },
+                "count":    { "type": "integer" },
+                "messages": { "type": "array", "items": message_schema() }
+            },
+            "required": ["query", "count", "messages"]
+        }),
+        "get_messages" => json!({
+            "type": "object",
+            "description": "stats=true 이면 stats 만, 아니면 messages 목록",
+            "properties": {
+                "total":    { "type": "integer", "description": "조건에 맞는 전체 메시지 수" },
+                "offset":   { "type": "integer" },
+                "filters": {
+                    "type": "object",
+                    "properties": {
+                        "sender":    { "type": ["string", "null"] },
+                        "date_from": { "type": ["string", "null"] },
+                        "date_to":   { "type": ["string", "null"] }
+                    }
+                },
+                "messages": { "type": "array", "items": message_schema() },
+                "stats": {
+                    "type": "object",
+                    "properties": {
+                        "total_messages":  { "type": "integer" },
+                        "last_message_id": { "type": "integer" },
+                        "last_sync":       { "type": ["string", "null"] },
+                        "db_size_kb":      { "type": "integer" }
+                    },
+                    "required": ["total_messages", "last_message_id", "last_sync", "db_size_kb"]
+                }
+            }
+        }),
+        "get_message_by_id" => json!({
+            "type": "object",
+            "properties": {
+                "found": { "type": "boolean" },
+                "id":    { "type": "integer" },
+                "message": {
+                    "type": "object",
+                    "properties": {
+                        "id":           { "type": "integer" },
+                        "sender":       { "type": "string" },
+                        "receive_date": { "type": ["string", "null"] },
+                        "attachments":  { "type": "array", "items": { "type": "string" } },
+                        "attachment_files": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "filename":   { "type": "string" },
+                                    "downloaded": { "type": "boolean" },
+                                    "also_in":    { "type": "array", "items": { "type": "integer" }, "description": "같은 파일을 전달한 다른 메시지 ID" }
+                                },
+                                "required": ["filename", "downloaded", "also_in"]
+                            }
+                        },
+                        "content":      { "type": "string", "description": "전체 본문(HTML 제거)" }
+                    },
+                    "required": ["id", "sender", "receive_date", "attachments", "attachment_files", "content"]
+                }
+            },
+            "required": ["found", "id"]
+        }),
+        "list_attachments" => json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer" },
+                "files": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "filename":   { "type": "string" },
+                            "downloaded": { "type": "boolean", "description": "수신 폴더에 파일이 있는지" },
+                            "size":       { "type": ["integer", "null"], "description": "바이트 (미다운로드면 null)" },
+                            "modified":   { "type": ["string", "null"], "description": "YYYY-MM-DD HH:MM:SS (로컬)" },
+                            "messages": {
+                                "type": "array",
+                                "description": "이 파일을 전달한 메시지(최신순)",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "message_id":   { "type": "integer" },
+                                        "sender":       { "type": "string" },
+                                        "receive_date": { "type": ["string", "null"] }
+                                    },
+                                    "required": ["message_id", "sender", "receive_date"]
+                                }
+                            }
+                        },
+                        "required": ["filename", "downloaded", "size", "modified", "messages"]
+                    }
+                }
+            },
+            "required": ["count", "files"]
+        }),
+        "read_attachment" => json!({
+            "type": "object",
+            "properties": {
+                "filename":  { "type": "string" },
+                "format":    { "type": "string", "description": "확장자(소문자)" },
+                "text":      { "type": "string" },
+                "truncated": { "type": "boolean", "description": "15000자 초과로 잘렸는지" }
+            },
+            "required": ["filename", "format", "text", "truncated"]
+        }),
+        "search_edufine_docs" | "list_edufine_docs" => json!({
+            "type": "object",
+            "properties": {
+                "docs": { "type": "array", "items": doc_preview_schema() }
+            },
+            "required": ["docs"]
+        }),
+        "get_edufine_doc" => json!({
+            "type": "object",
+            "properties": {
+                "doc": {
+                    "type": "object",
+                    "properties": {
+                        "id":          { "type": "integer" },
+                        "title":       { "type": ["string", "null"] },
+                        "file_name":   { "type": "string" },
+                        "detected_at": { "type": "string" },
+                        "content":     { "type": "string" }
+                    },
+                    "required": ["id", "title", "file_name", "detected_at", "content"]
+                },
+                "truncated": { "type": "boolean" }
+            },
+            "required": ["doc", "truncated"]
+        }),
+        _ => return None,
+    };
+    Some(schema)
+}
+
 /// HTML 태그를 제거하고 순수 텍스트를 반환합니다.
 fn strip_html(html: &str) -> String {
     use scraper::Html;
@@ -407,8 +679,12 @@ fn classify_image(path: &std::path::Path) -> ImageConfidence {
     }
 }
 
-fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Value) -> Result<Value, String> {
-    match name {
+fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Value) -> Result<Value, ToolError> {
+    // 결과에서 가린 토큰([NAME_1] 등)을 모델이 인자로 돌려보내면 원문으로 되돌려 조회한다.
+    let mut args = args.clone();
+    pii_redact::restore_tool_args(&mut args);
+    let args = &args;
+    let result: Result<Value, String> = match name {
         "search_messages" => {
             let query = args["query"].as_str().ok_or("query required")?;
             let limit = args["limit"].as_i64().unwrap_or(20).clamp(1, 100);
@@ -438,17 +714,18 @@ fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Va
         }
         "list_attachments" => {
             let query = args["query"].as_str();
-            let ext   = args["ext"].as_str();
-            let limit = args["limit"].as_i64().unwrap_or(50).clamp(1, 200);
-            tool_list_attachments(query, ext, limit)
+            let ext    = args["ext"].as_str();
+            let status = args["status"].as_str();
+            let limit  = args["limit"].as_i64().unwrap_or(50).clamp(1, 200);
+            tool_list_attachments(db_path, query, ext, status, limit)
         }
         "read_attachment" => {
             let filename = args["filename"].as_str().ok_or("filename required")?;
-            tool_read_attachment(filename)
+            return tool_read_attachment(filename);
         }
         "view_image" => {
             if let Some(filename) = args["filename"].as_str() {
-                tool_read_image(filename)
+                return tool_read_image(filename);
             } else {
                 let message_id = args["message_id"].as_i64().ok_or("filename 또는 message_id 필요")?;
                 let index = args["index"].as_u64().unwrap_or(0) as usize;
@@ -473,12 +750,10 @@ fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Va
                     })
                 })
                 .collect();
-            Ok(json!({
-                "content": [{
-                    "type": "text",
-                    "text": serde_json::to_string_pretty(&items).unwrap_or_default()
-                }]
-            }))
+            Ok(tool_result(
+                serde_json::to_string_pretty(&items).unwrap_or_default(),
+                json!({ "docs": items }),
+            ))
         }
 
         "get_edufine_doc" => {
@@ -492,10 +767,18 @@ fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Va
                         doc.detected_at,
                         doc.content
                     );
-                    let truncated = truncate_text(&text, 15000);
-                    Ok(json!({
-                        "content": [{ "type": "text", "text": truncated }]
-                    }))
+                    let content = truncate_text(&doc.content, 15000);
+                    let structured = json!({
+                        "doc": {
+                            "id": doc.id,
+                            "title": doc.title,
+                            "file_name": doc.file_name,
+                            "detected_at": doc.detected_at,
+                            "content": content
+                        },
+                        "truncated": doc.content.chars().count() > 15000
+                    });
+                    Ok(tool_result(truncate_text(&text, 15000), structured))
                 }
                 None => Err(format!("공문을 찾을 수 없습니다 (id={})", id)),
             }
@@ -518,16 +801,15 @@ fn call_tool(db_path: &PathBuf, edufine_db_path: &PathBuf, name: &str, args: &Va
                     })
                 })
                 .collect();
-            Ok(json!({
-                "content": [{
-                    "type": "text",
-                    "text": serde_json::to_string_pretty(&items).unwrap_or_default()
-                }]
-            }))
+            Ok(tool_result(
+                serde_json::to_string_pretty(&items).unwrap_or_default(),
+                json!({ "docs": items }),
+            ))
         }
 
         _ => Err(format!("Unknown tool: {}", name)),
-    }
+    };
+    result.map_err(ToolError::from)
 }
 
 fn open_db(db_path: &PathBuf) -> Result<Connection, String> {
@@ -544,7 +826,10 @@ fn tool_search_messages(db_path: &PathBuf, query: &str, limit: i64) -> Result<Va
     let plan = match crate::search_db::plan_search_query(query) {
         Some(p) => p,
         None => {
-            return Ok(json!({ "content": [{ "type": "text", "text": "검색어가 비어 있습니다." }] }));
+            return Ok(tool_result(
+                "검색어가 비어 있습니다.".to_string(),
+                json!({ "query": query, "count": 0, "messages": [] }),
+            ));
         }
     };
 
@@ -605,12 +890,14 @@ fn tool_search_messages(db_path: &PathBuf, query: &str, limit: i64) -> Result<Va
             .collect();
     }
 
+    let mut messages: Vec<Value> = Vec::with_capacity(rows.len());
     let text = if rows.is_empty() {
         format!("\"{}\" 검색 결과가 없습니다.", query)
     } else {
         let mut out = format!("\"{}\" 검색 결과 {}개:\n\n", query, rows.len());
         for (id, sender, content, date, file_paths) in &rows {
             let preview = truncate_text(&strip_html(content), 300);
+            messages.push(message_json(*id, sender, date.as_deref(), &preview, file_paths));
             out.push_str(&format!(
                 "ID: {} | 발신: {} | 날짜: {}\n{}",
                 id,
@@ -626,7 +913,8 @@ fn tool_search_messages(db_path: &PathBuf, query: &str, limit: i64) -> Result<Va
         out
     };
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    let structured = json!({ "query": query, "count": messages.len(), "messages": messages });
+    Ok(tool_result(text, structured))
 }
 
 fn tool_get_messages(
@@ -705,6 +993,19 @@ fn tool_get_messages(
         )
     };
 
+    let messages: Vec<Value> = rows
+        .iter()
+        .map(|(id, sndr, preview, date, file_paths)| {
+            message_json(*id, sndr, date.as_deref(), preview.trim(), file_paths)
+        })
+        .collect();
+    let structured = json!({
+        "total": total,
+        "offset": offset,
+        "filters": { "sender": sender, "date_from": date_from, "date_to": date_to },
+        "messages": messages
+    });
+
     let text = if rows.is_empty() {
         if params_desc.is_empty() {
             "메시지가 없습니다.".to_string()
@@ -729,7 +1030,7 @@ fn tool_get_messages(
         out
     };
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    Ok(tool_result(text, structured))
 }
 
 fn tool_get_message_by_id(db_path: &PathBuf, id: i64) -> Result<Value, String> {
@@ -751,8 +1052,22 @@ fn tool_get_message_by_id(db_path: &PathBuf, id: i64) -> Result<Value, String> {
         },
     );
 
-    let text = match result {
+    let (text, structured) = match result {
         Ok((id, sender, content, date, file_paths)) => {
+            let body = strip_html(&content);
+            let dir = get_attachments_dir();
+            let attachment_files: Vec<Value> = file_paths
+                .iter()
+                .map(|f| {
+                    let also_in: Vec<i64> = search_db::attachment_links(&conn, f)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|l| l.message_id)
+                        .filter(|mid| *mid != id)
+                        .collect();
+                    json!({ "filename": f, "downloaded": is_downloaded(dir.as_ref(), f), "also_in": also_in })
+                })
+                .collect();
             let mut out = format!(
                 "메시지 ID: {}\n발신: {}\n날짜: {}",
                 id,
@@ -760,18 +1075,38 @@ fn tool_get_message_by_id(db_path: &PathBuf, id: i64) -> Result<Value, String> {
                 date.as_deref().unwrap_or("날짜 없음"),
             );
             if !file_paths.is_empty() {
-                out.push_str(&format!("\n첨부: {}", file_paths.join(", ")));
+                let labels: Vec<String> = attachment_files
+                    .iter()
+                    .map(|a| {
+                        let state = if a["downloaded"].as_bool().unwrap_or(false) { "다운로드됨" } else { "미다운로드" };
+                        format!("{} ({})", a["filename"].as_str().unwrap_or(""), state)
+                    })
+                    .collect();
+                out.push_str(&format!("\n첨부: {}", labels.join(", ")));
             }
-            out.push_str(&format!("\n\n{}", strip_html(&content)));
-            out
-        }
-        Err(rusqlite::Error::QueryReturnedNoRows) => {
-            format!("ID {}인 메시지를 찾을 수 없습니다.", id)
+            out.push_str(&format!("\n\n{}", body));
+            let structured = json!({
+                "found": true,
+                "id": id,
+                "message": {
+                    "id": id,
+                    "sender": sender,
+                    "receive_date": date,
+                    "attachments": file_paths,
+                    "attachment_files": attachment_files,
+                    "content": body
+                }
+            });
+            (out, structured)
         }
+        Err(rusqlite::Error::QueryReturnedNoRows) => (
+            format!("ID {}인 메시지를 찾을 수 없습니다.", id),
+            json!({ "found": false, "id": id }),
+        ),
         Err(e) => return Err(format!("메시지 조회 실패: {}", e)),
     };
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    Ok(tool_result(text, structured))
 }
 
 fn tool_get_db_stats(db_path: &PathBuf) -> Result<Value, String> {
@@ -793,19 +1128,32 @@ fn tool_get_db_stats(db_path: &PathBuf) -> Result<Value, String> {
 
     let sync_time = if last_sync > 0 {
         let dt = std::time::UNIX_EPOCH + std::time::Duration::from_secs(last_sync as u64);
-        chrono::DateTime::<chrono::Local>::from(dt)
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string()
+        Some(
+            chrono::DateTime::<chrono::Local>::from(dt)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        )
     } else {
-        "동기화 없음".to_string()
+        None
     };
 
     let text = format!(
         "쿨메신저 DB 통계\n총 메시지: {}개\n마지막 메시지 ID: {}\n마지막 동기화: {}\nDB 크기: {} KB",
-        total, last_id, sync_time, db_size_kb
+        total,
+        last_id,
+        sync_time.as_deref().unwrap_or("동기화 없음"),
+        db_size_kb
     );
+    let structured = json!({
+        "stats": {
+            "total_messages": total,
+            "last_message_id": last_id,
+            "last_sync": sync_time,
+            "db_size_kb": db_size_kb
+        }
+    });
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    Ok(tool_result(text, structured))
 }
 
 // ─── 이미지 도구 ──────────────────────────────────────────────────────────────
@@ -857,10 +1205,12 @@ fn tool_get_messages_with_images(
 
     let mut out = String::new();
     let mut image_msg_count = 0usize;
+    let mut messages: Vec<Value> = Vec::new();
 
     for row in &rows {
         let mut has_image = false;
         let mut lines = String::new();
+        let mut images: Vec<Value> = Vec::new();
 
         // ── 1. 첨부 파일 이미지 ──────────────────────────────────────
         for fname in row.file_paths.iter().filter(|f| is_image_by_ext(f)) {
@@ -878,10 +1228,12 @@ fn tool_get_messages_with_images(
             match confidence {
                 ImageConfidence::Verified(mime) => {
                     lines.push_str(&format!("  [첨부이미지] {} | {} | magic bytes 확인됨\n", fname, mime));
+                    images.push(json!({ "kind": "attachment", "filename": fname, "mime": mime, "verified": true }));
                     has_image = true;
                 }
                 ImageConfidence::ExtOnly(mime) => {
                     lines.push_str(&format!("  [첨부이미지] {} | {} | 확장자 추정 (파일 없음)\n", fname, mime));
+                    images.push(json!({ "kind": "attachment", "filename": fname, "mime": mime, "verified": false }));
                     has_image = true;
                 }
                 ImageConfidence::NotImage => {}
@@ -895,6 +1247,7 @@ fn tool_get_messages_with_images(
                 "  [인라인이미지 #{}] {} | magic bytes 확인됨 | view_image로 읽기 가능\n",
                 idx, mime
             ));
+            images.push(json!({ "kind": "inline", "index": idx, "mime": mime, "verified": true }));
             has_image = true;
         }
 
@@ -912,6 +1265,16 @@ fn tool_get_messages_with_images(
         ));
         out.push_str(&lines);
         out.push('\n');
+
+        let mut msg = message_json(
+            row.id,
+            &row.sender,
+            row.date.as_deref(),
+            row.preview.trim(),
+            &row.file_paths,
+        );
+        msg["images"] = Value::Array(images);
+        messages.push(msg);
     }
 
     let text = if out.is_empty() {
@@ -920,7 +1283,7 @@ fn tool_get_messages_with_images(
         format!("이미지 첨부 메시지 {}건:\n\n{}", image_msg_count, out)
     };
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    Ok(tool_result(text, json!({ "offset": offset, "messages": messages })))
 }
 
 /// Claude가 네이티브로 지원하는 이미지 MIME 타입
@@ -981,16 +1344,10 @@ fn prepare_image_for_claude(data: &[u8], detected_mime: &'static str) -> Result<
     ))
 }
 
-fn tool_read_image(filename: &str) -> Result<Value, String> {
+fn tool_read_image(filename: &str) -> Result<Value, ToolError> {
     use base64::Engine;
 
-    let dir = get_attachments_dir()
-        .ok_or_else(|| "쿨메신저 수신 파일 경로를 찾을 수 없습니다.".to_string())?;
-    let path = dir.join(filename);
-
-    if !path.exists() {
-        return Err(format!("파일을 찾을 수 없습니다: {}", filename));
-    }
+    let path = resolve_attachment(filename)?;
 
     // magic bytes로 이미지 여부 + MIME 타입 판별
     let detected_mime = match classify_image(&path) {
@@ -1000,22 +1357,28 @@ fn tool_read_image(filename: &str) -> Result<Value, String> {
             return Err(format!(
                 "이미지 파일이 아닙니다: {} (magic bytes 검사 실패)",
                 filename
-            ));
+            )
+            .into());
         }
     };
 
-    let raw = std::fs::read(&path)
-        .map_err(|e| format!("파일 읽기 실패: {}", e))?;
-
     // HEIC/AVIF 등 image 크레이트 미지원 포맷은 사전 차단
     if matches!(detected_mime, "image/heic" | "image/avif") {
         return Err(format!(
             "{} 형식은 자동 변환이 불가합니다 ({}). JPG 또는 PNG로 변환 후 다시 시도하세요.",
             detected_mime, filename
-        ));
+        )
+        .into());
     }
 
-    let (data, mime) = prepare_image_for_claude(&raw, detected_mime)?;
+    // 확장자가 없거나 달라도 이미지 시그니처가 확인됐으면 이미지 예산을 적용한다.
+    let budget = attachment_sandbox::budget_for_ext("png")
+        .ok_or_else(|| SandboxError::UnsupportedType("png".to_string()))?;
+    attachment_sandbox::check_size(&path, &budget)?;
+    let (data, mime) = attachment_sandbox::run_with_budget(&budget, move |_| {
+        let raw = std::fs::read(&path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+        prepare_image_for_claude(&raw, detected_mime)
+    })??;
 
     let encoded = base64::engine::general_purpose::STANDARD.encode(&data);
 
@@ -1093,25 +1456,82 @@ fn get_attachments_dir() -> Option<PathBuf> {
     Some(PathBuf::from(path))
 }
 
-fn tool_list_attachments(query: Option<&str>, ext: Option<&str>, limit: i64) -> Result<Value, String> {
-    let dir = get_attachments_dir()
-        .ok_or_else(|| "쿨메신저 수신 파일 경로를 찾을 수 없습니다. 쿨메신저가 설치·실행됐는지 확인하세요.".to_string())?;
+/// 카탈로그에서 한 번에 훑어볼 최대 행 수(디스크 목록과 병합 후 limit 로 자른다).
+const CATALOG_SCAN_LIMIT: i64 = 2000;
 
-    if !dir.exists() {
-        return Err(format!("수신 파일 디렉토리가 없습니다: {}", dir.display()));
+/// 첨부 파일이 수신 폴더에 실제로 내려받아져 있는지.
+fn is_downloaded(dir: Option<&PathBuf>, file_name: &str) -> bool {
+    match dir {
+        Some(d) => {
+            attachment_sandbox::validate_file_name(file_name).is_ok() && d.join(file_name).is_file()
+        }
+        None => false,
     }
+}
 
-    let entries = std::fs::read_dir(&dir).map_err(|e| format!("디렉토리 읽기 실패: {}", e))?;
+/// list_attachments 한 줄: 파일(디스크) 정보 + 전달한 메시지 링크.
+struct AttachmentEntry {
+    filename: String,
+    downloaded: bool,
+    size: Option<u64>,
+    modified: Option<String>,
+    links: Vec<search_db::AttachmentLink>,
+}
 
-    // (수정 시각, 파일명) 쌍으로 수집한 뒤 최신순 정렬
-    let mut files: Vec<(std::time::SystemTime, String)> = entries
-        .filter_map(|e| e.ok())
-        .filter_map(|e| {
-            let name = e.file_name().to_string_lossy().to_string();
-            let meta = e.metadata().ok()?;
-            if !meta.is_file() {
-                return None;
+impl AttachmentEntry {
+    /// 정렬 기준: 가장 최근 수신일, 카탈로그에 없으면 파일 수정 시각.
+    fn sort_key(&self) -> String {
+        self.links
+            .first()
+            .and_then(|l| l.receive_date.as_deref())
+            .map(|d| d.replace('/', "-"))
+            .or_else(|| self.modified.clone())
+            .unwrap_or_default()
+    }
+}
+
+fn tool_list_attachments(
+    db_path: &PathBuf,
+    query: Option<&str>,
+    ext: Option<&str>,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Value, String> {
+    let dir = get_attachments_dir().filter(|d| d.exists());
+
+    // 1. 카탈로그(메시지가 전달한 파일) — 파일명 소문자 기준으로 묶는다.
+    let mut entries: Vec<AttachmentEntry> = Vec::new();
+    let mut index: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let catalog = open_db(db_path)
+        .ok()
+        .and_then(|conn| search_db::list_attachment_catalog(&conn, query, ext, CATALOG_SCAN_LIMIT).ok())
+        .unwrap_or_default();
+    for link in catalog {
+        let key = link.file_name.to_lowercase();
+        match index.get(&key) {
+            Some(&i) => entries[i].links.push(link),
+            None => {
+                index.insert(key, entries.len());
+                entries.push(AttachmentEntry {
+                    filename: link.file_name.clone(),
+                    downloaded: false,
+                    size: None,
+                    modified: None,
+                    links: vec![link],
+                });
             }
+        }
+    }
+
+    // 2. 수신 폴더 — 카탈로그 항목에 다운로드 정보를 붙이고, 카탈로그에 없는 파일도 추가.
+    if let Some(d) = &dir {
+        let read = std::fs::read_dir(d).map_err(|e| format!("디렉토리 읽기 실패: {}", e))?;
+        for e in read.filter_map(|e| e.ok()) {
+            let name = e.file_name().to_string_lossy().to_string();
+            let meta = match e.metadata() {
+                Ok(m) if m.is_file() => m,
+                _ => continue,
+            };
 
             // 확장자 필터
             if let Some(ext_filter) = ext {
@@ -1119,48 +1539,112 @@ fn tool_list_attachments(query: Option<&str>, ext: Option<&str>, limit: i64) ->
                     .extension()
                     .and_then(|x| x.to_str())
                     .unwrap_or("");
-                if !file_ext.eq_ignore_ascii_case(ext_filter) {
-                    return None;
+                if !file_ext.eq_ignore_ascii_case(ext_filter.trim_start_matches('.')) {
+                    continue;
                 }
             }
 
             // 파일명 검색
             if let Some(q) = query {
                 if !name.to_lowercase().contains(&q.to_lowercase()) {
-                    return None;
+                    continue;
                 }
             }
 
-            let modified = meta.modified().unwrap_or(std::time::UNIX_EPOCH);
-            Some((modified, name))
-        })
-        .collect();
+            let modified = chrono::DateTime::<chrono::Local>::from(
+                meta.modified().unwrap_or(std::time::UNIX_EPOCH),
+            )
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+            let i = match index.get(&name.to_lowercase()) {
+                Some(&i) => i,
+                None => {
+                    entries.push(AttachmentEntry {
+                        filename: name.clone(),
+                        downloaded: false,
+                        size: None,
+                        modified: None,
+                        links: Vec::new(),
+                    });
+                    entries.len() - 1
+                }
+            };
+            let entry = &mut entries[i];
+            entry.downloaded = true;
+            entry.size = Some(meta.len());
+            entry.modified = Some(modified);
+        }
+    } else if entries.is_empty() {
+        return Err("쿨메신저 수신 파일 경로를 찾을 수 없습니다. 쿨메신저가 설치·실행됐는지 확인하세요.".to_string());
+    }
 
-    files.sort_by(|a, b| b.0.cmp(&a.0));
-    files.truncate(limit as usize);
+    // 3. 다운로드 상태 필터 + 최신순 정렬
+    match status.unwrap_or("all") {
+        "downloaded" => entries.retain(|e| e.downloaded),
+        "not_downloaded" => entries.retain(|e| !e.downloaded),
+        _ => {}
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.sort_key()));
+    entries.truncate(limit as usize);
 
-    let text = if files.is_empty() {
+    let text = if entries.is_empty() {
         "조건에 맞는 파일이 없습니다.".to_string()
     } else {
-        let mut out = format!("수신 파일 {}개 (최신순):\n\n", files.len());
-        for (_, name) in &files {
-            out.push_str(name);
+        let mut out = format!("수신 파일 {}개 (최신순):\n\n", entries.len());
+        for e in &entries {
+            out.push_str(&e.filename);
+            out.push_str(if e.downloaded { " | 다운로드됨" } else { " | 미다운로드" });
+            if let Some(l) = e.links.first() {
+                out.push_str(&format!(
+                    " | 메시지 ID: {} ({}, {})",
+                    l.message_id,
+                    l.sender,
+                    l.receive_date.as_deref().unwrap_or("날짜 없음")
+                ));
+                if e.links.len() > 1 {
+                    out.push_str(&format!(" 외 {}건", e.links.len() - 1));
+                }
+            }
             out.push('\n');
         }
         out
     };
 
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
+    let items: Vec<Value> = entries
+        .iter()
+        .map(|e| {
+            let messages: Vec<Value> = e
+                .links
+                .iter()
+                .map(|l| {
+                    json!({
+                        "message_id": l.message_id,
+                        "sender": l.sender,
+                        "receive_date": l.receive_date
+                    })
+                })
+                .collect();
+            json!({
+                "filename": e.filename,
+                "downloaded": e.downloaded,
+                "size": e.size,
+                "modified": e.modified,
+                "messages": messages
+            })
+        })
+        .collect();
+
+    Ok(tool_result(text, json!({ "count": items.len(), "files": items })))
 }
 
-fn tool_read_attachment(filename: &str) -> Result<Value, String> {
-    let dir = get_attachments_dir()
-        .ok_or_else(|| "쿨메신저 수신 파일 경로를 찾을 수 없습니다.".to_string())?;
-    let path = dir.join(filename);
+/// 호출자가 준 파일명을 수신 폴더 안의 실제 파일 경로로 해석한다(샌드박스 검사 포함).
+fn resolve_attachment(filename: &str) -> Result<PathBuf, SandboxError> {
+    let dir = get_attachments_dir().ok_or(SandboxError::RootUnavailable)?;
+    attachment_sandbox::resolve_in_root(&dir, filename, attachment_sandbox::load_symlink_policy())
+}
 
-    if !path.exists() {
-        return Err(format!("파일을 찾을 수 없습니다: {}", filename));
-    }
+fn tool_read_attachment(filename: &str) -> Result<Value, ToolError> {
+    let path = resolve_attachment(filename)?;
 
     let ext = path
         .extension()
@@ -1168,129 +1652,56 @@ fn tool_read_attachment(filename: &str) -> Result<Value, String> {
         .unwrap_or("")
         .to_lowercase();
 
-    let content = match ext.as_str() {
-        "hwp" | "hwpx" => read_hwp_file(&path)?,
-        "pdf"          => read_pdf_file(&path)?,
-        "xlsx" | "xls" | "xlsm" | "xlsb" => read_excel_file(&path)?,
-        "odt"          => read_odt_file(&path)?,
-        "pptx"         => read_pptx_file(&path)?,
-        "md" | "txt" | "csv" => {
-            std::fs::read_to_string(&path).map_err(|e| format!("파일 읽기 실패: {}", e))?
+    let budget = attachment_sandbox::budget_for_ext(&ext)
+        .filter(|_| !is_image_by_ext(filename))
+        .ok_or_else(|| SandboxError::UnsupportedType(ext.clone()))?;
+    attachment_sandbox::check_size(&path, &budget)?;
+
+    let format = ext.clone();
+    let content = attachment_sandbox::run_with_budget(&budget, move |cancel| {
+        // hwp/hwpx/odt/xlsx/pptx 는 Edufine 수집과 같은 표 인식 추출기를 쓴다.
+        if let Some(result) = doc_extract::extract_markdown(&path, &ext, cancel) {
+            return result;
         }
-        "html" | "htm" => {
-            let raw = std::fs::read_to_string(&path)
-                .map_err(|e| format!("파일 읽기 실패: {}", e))?;
-            strip_html(&raw)
+        match ext.as_str() {
+            "pdf" => read_pdf_file(&path, cancel),
+            "md" | "txt" | "csv" => {
+                std::fs::read_to_string(&path).map_err(|e| format!("파일 읽기 실패: {}", e))
+            }
+            "html" | "htm" => {
+                let raw = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+                Ok(strip_html(&raw))
+            }
+            _ => Err(format!("지원하지 않는 파일 형식입니다: .{}", ext)),
         }
-        _ => return Err(format!("지원하지 않는 파일 형식입니다: .{}", ext)),
-    };
+    })??;
 
     let truncated = truncate_text(&content, 15000);
     let text = format!("파일: {}\n\n{}", filename, truncated);
-    Ok(json!({ "content": [{ "type": "text", "text": text }] }))
-}
-
-fn read_hwp_file(path: &PathBuf) -> Result<String, String> {
-    use unhwp::{parse_file, render::render_markdown, RenderOptions};
-    let document = parse_file(path).map_err(|e| format!("HWP 파싱 실패: {}", e))?;
-    let options = RenderOptions::default();
-    render_markdown(&document, &options).map_err(|e| format!("HWP 렌더링 실패: {}", e))
+    let structured = json!({
+        "filename": filename,
+        "format": format,
+        "truncated": content.chars().count() > 15000,
+        "text": truncated
+    });
+    Ok(tool_result(text, structured))
 }
 
-fn read_pdf_file(path: &PathBuf) -> Result<String, String> {
+/// 페이지 단위로 추출해 페이지 사이에서 취소를 확인한다.
+fn read_pdf_file(path: &PathBuf, cancel: &CancelToken) -> Result<String, String> {
     let doc = lopdf::Document::load(path).map_err(|e| format!("PDF 로드 실패: {}", e))?;
     let mut page_numbers: Vec<u32> = doc.get_pages().keys().cloned().collect();
     page_numbers.sort_unstable();
-    doc.extract_text(&page_numbers).map_err(|e| format!("PDF 텍스트 추출 실패: {}", e))
-}
-
-fn read_excel_file(path: &PathBuf) -> Result<String, String> {
-    use calamine::{open_workbook_auto, Data, Reader};
-    let mut wb = open_workbook_auto(path).map_err(|e| format!("Excel 로드 실패: {}", e))?;
-    let sheet_names = wb.sheet_names().to_owned();
-    let mut out = String::new();
-
-    for name in sheet_names {
-        out.push_str(&format!("## {}\n", name));
-        if let Ok(range) = wb.worksheet_range(&name) {
-            for row in range.rows() {
-                let cells: Vec<String> = row
-                    .iter()
-                    .map(|c| match c {
-                        Data::Empty => String::new(),
-                        Data::String(s) => s.clone(),
-                        Data::Int(i) => i.to_string(),
-                        Data::Float(f) => f.to_string(),
-                        Data::Bool(b) => b.to_string(),
-                        other => format!("{:?}", other),
-                    })
-                    .collect();
-                if cells.iter().all(|c| c.is_empty()) {
-                    continue;
-                }
-                out.push_str(&cells.join("\t"));
-                out.push('\n');
-            }
-        }
-        out.push('\n');
+    let mut text = String::new();
+    for page in page_numbers {
+        cancel.check()?;
+        let page_text = doc
+            .extract_text(&[page])
+            .map_err(|e| format!("PDF 텍스트 추출 실패: {}", e))?;
+        text.push_str(&page_text);
     }
-
-    Ok(out)
-}
-
-fn read_odt_file(path: &PathBuf) -> Result<String, String> {
-    use std::io::Read;
-    use zip::ZipArchive;
-
-    let file = std::fs::File::open(path).map_err(|e| format!("파일 열기 실패: {}", e))?;
-    let mut archive = ZipArchive::new(file).map_err(|e| format!("ZIP 열기 실패: {}", e))?;
-    let mut entry = archive
-        .by_name("content.xml")
-        .map_err(|_| "content.xml을 찾을 수 없습니다".to_string())?;
-    let mut xml = String::new();
-    entry.read_to_string(&mut xml).map_err(|e| format!("읽기 실패: {}", e))?;
-    Ok(extract_xml_text(&xml))
-}
-
-fn read_pptx_file(path: &PathBuf) -> Result<String, String> {
-    use std::io::Read;
-    use zip::ZipArchive;
-
-    let file = std::fs::File::open(path).map_err(|e| format!("파일 열기 실패: {}", e))?;
-    let mut archive = ZipArchive::new(file).map_err(|e| format!("ZIP 열기 실패: {}", e))?;
-
-    let mut slide_names: Vec<String> = archive
-        .file_names()
-        .filter(|n| n.starts_with("ppt/slides/slide") && n.ends_with(".xml"))
-        .map(|s| s.to_string())
-        .collect();
-    slide_names.sort();
-
-    let mut out = String::new();
-    for name in slide_names {
-        if let Ok(mut entry) = archive.by_name(&name) {
-            let mut xml = String::new();
-            let _ = entry.read_to_string(&mut xml);
-            let text = extract_xml_text(&xml);
-            if !text.is_empty() {
-                out.push_str(&text);
-                out.push('\n');
-            }
-        }
-    }
-    Ok(out)
-}
-
-/// XML 태그를 제거하고 텍스트만 추출
-fn extract_xml_text(xml: &str) -> String {
-    let re = XML_TAG_REGEX.get_or_init(|| regex::Regex::new(r"<[^>]+>").unwrap());
-    let text = re.replace_all(xml, "");
-    let lines: Vec<&str> = text
-        .lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .collect();
-    lines.join("\n")
+    Ok(text)
 }
 
 pub fn start(db_path: PathBuf, edufine_db_path: PathBuf, port: u16) {
@@ -1322,3 +1733,42 @@ pub fn start(db_path: PathBuf, edufine_db_path: PathBuf, port: u16) {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_tool_args_round_trip_through_call_tool() {
+        let db_path = std::env::temp_dir().join(format!("hypercool-mcp-args-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE messages (id INTEGER PRIMARY KEY, sender TEXT, content TEXT, content_preview TEXT,
+                                        receive_date TEXT, file_paths TEXT);
+                 INSERT INTO messages VALUES (1, '홍길동', '본문', '현장학습 안내', '2026-03-02 09:00:00', '[]');
+                 INSERT INTO messages VALUES (2, '김철수', '본문', '회의 안내', '2026-03-02 10:00:00', '[]');",
+            )
+            .unwrap();
+
+        let _scope = pii_redact::begin_run();
+        let cfg = pii_redact::RedactConfig { kinds: vec![pii_redact::PiiKind::Name], roster: vec!["홍길동".to_string()] };
+        let mut listed = json!({
+            "content": [{ "type": "text", "text": "홍길동" }],
+            "structuredContent": { "messages": [{ "sender": "홍길동" }] }
+        });
+        pii_redact::redact_tool_result(&cfg, &mut listed);
+        let token = listed["structuredContent"]["messages"][0]["sender"].as_str().unwrap().to_string();
+        assert!(token.starts_with("[NAME_"));
+
+        // 모델이 가려진 발신자를 그대로 돌려보내도 원래 이름으로 찾는다.
+        let Ok(mut result) = call_tool(&db_path, &db_path, "get_messages", &json!({ "sender": token })) else {
+            panic!("get_messages 실패");
+        };
+        assert_eq!(result["structuredContent"]["total"], 1);
+        pii_redact::redact_tool_result(&cfg, &mut result);
+        assert_eq!(result["structuredContent"]["messages"][0]["sender"], token.as_str());
+        let _ = std::fs::remove_file(&db_path);
+    }
+}
@@ -0,0 +1,494 @@
+//! 급식(나이스 `mealServiceDietInfo`).
+//!
+//! 메뉴(`DDISH_NM`)의 알레르기 번호를 떼어 요리별 알레르기로 풀고, 칼로리(`CAL_INFO`)·영양(`NTR_INFO`)·
+//! 원산지(`ORPLC_INFO`)도 함께 돌려준다. 조식·중식·석식을 모두 다룬다.
+//!
+//! 기간 조회는 나이스에 한 번만 묻고 결과를 날짜별로 `neis_cache` 에 나눠 담는다. 다음 조회에서는
+//! 캐시가 없거나 오래된 날만 다시 묻는다.
+//!
+//! 사용자가 등록한 알레르기(레지스트리 `MealAllergens`, 번호 목록)가 든 요리는 `alerts` 로 알려 준다.
+
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::db::open_app_db;
+use crate::neis::{self, NeisClient, NeisSchool, SchoolInfo};
+
+const SERVICE: &str = "mealServiceDietInfo";
+const MEAL_TTL_HOURS: i64 = 12;
+/// 한 번에 조회할 수 있는 최대 일수.
+const MAX_RANGE_DAYS: i64 = 31;
+const REG_ALLERGENS: &str = "MealAllergens";
+
+/// 식품의약품안전처 알레르기 유발 식품 번호(1~19) 순서.
+pub const ALLERGENS: [&str; 19] = [
+    "난류", "우유", "메밀", "땅콩", "대두", "밀", "고등어", "게", "새우", "돼지고기",
+    "복숭아", "토마토", "아황산류", "호두", "닭고기", "쇠고기", "오징어", "조개류", "잣",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Allergen {
+    pub code: u8,
+    pub name: String,
+}
+
+impl Allergen {
+    pub fn from_code(code: u8) -> Option<Self> {
+        let name = ALLERGENS.get((code as usize).checked_sub(1)?)?;
+        Some(Self { code, name: name.to_string() })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Dish {
+    pub name: String,
+    pub allergens: Vec<Allergen>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MealKind {
+    Breakfast,
+    Lunch,
+    Dinner,
+}
+
+impl MealKind {
+    fn parse(code: Option<&str>, label: &str) -> Option<Self> {
+        match code {
+            Some("1") => Some(MealKind::Breakfast),
+            Some("2") => Some(MealKind::Lunch),
+            Some("3") => Some(MealKind::Dinner),
+            _ if label.contains("조식") => Some(MealKind::Breakfast),
+            _ if label.contains("중식") => Some(MealKind::Lunch),
+            _ if label.contains("석식") => Some(MealKind::Dinner),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Nutrient {
+    /// 예: "탄수화물"
+    pub name: String,
+    /// 예: "g", "mg", "R.E"
+    pub unit: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Origin {
+    pub item: String,
+    pub origin: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Meal {
+    pub kind: MealKind,
+    /// 나이스 표기("조식"·"중식"·"석식").
+    pub label: String,
+    pub dishes: Vec<Dish>,
+    /// kcal
+    pub calories: Option<f64>,
+    pub nutrition: Vec<Nutrient>,
+    pub origin: Vec<Origin>,
+}
+
+impl Meal {
+    /// 요리 이름을 ", " 로 이은 메뉴.
+    pub fn menu(&self) -> String {
+        self.dishes.iter().map(|d| d.name.as_str()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// 등록한 알레르기가 든 요리.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllergenAlert {
+    pub meal: MealKind,
+    pub dish: String,
+    pub allergens: Vec<Allergen>,
+}
+
+/// 하루 급식.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MealData {
+    /// YYYY-MM-DD
+    pub date: String,
+    /// 조식·중식·석식 순. 급식이 없는 날은 비어 있다.
+    pub meals: Vec<Meal>,
+    pub alerts: Vec<AllergenAlert>,
+}
+
+impl MealData {
+    pub fn meal(&self, kind: MealKind) -> Option<&Meal> {
+        self.meals.iter().find(|m| m.kind == kind)
+    }
+
+    /// `registered` 알레르기 번호가 든 요리로 `alerts` 를 채운다.
+    pub fn flag_allergens(&mut self, registered: &[u8]) {
+        self.alerts = self
+            .meals
+            .iter()
+            .flat_map(|m| m.dishes.iter().map(move |d| (m.kind, d)))
+            .filter_map(|(meal, d)| {
+                let hits: Vec<Allergen> = d.allergens.iter().filter(|a| registered.contains(&a.code)).cloned().collect();
+                (!hits.is_empty()).then(|| AllergenAlert { meal, dish: d.name.clone(), allergens: hits })
+            })
+            .collect();
+    }
+}
+
+// ─── 파싱 ─────────────────────────────────────────────────────────────────────
+
+fn split_br(s: &str) -> impl Iterator<Item = &str> {
+    s.split("<br/>").flat_map(|p| p.split("<br>")).map(str::trim).filter(|p| !p.is_empty())
+}
+
+/// 이름 끝의 알레르기 번호 묶음: 숫자·점만 든 마지막 괄호, 또는 점으로 끝나는 번호 나열.
+static ALLERGEN_TAIL_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+
+/// "쇠고기미역국 (5.6.16.)" · "쇠고기미역국5.6.16." → ("쇠고기미역국", [5, 6, 16]).
+/// 마지막 번호 묶음만 알레르기로 보고, 그 앞의 숫자("흰우유200 (2.)" 의 200)는 이름에 둔다.
+/// 학교가 붙이는 표시(`*`, `#` 등)는 이름에서 뗀다.
+pub fn parse_dish(raw: &str) -> Dish {
+    let raw = raw.trim();
+    let re = ALLERGEN_TAIL_REGEX.get_or_init(|| {
+        regex::Regex::new(r"(?:\(\s*((?:\d{1,2}\.)*\d{1,2}\.?)\s*\)|(?:^|[^0-9])((?:\d{1,2}\.)+))\s*$").unwrap()
+    });
+    // 괄호 꼴은 괄호부터, 나열 꼴은 첫 번호부터 뗀다(나열 앞 글자는 이름).
+    let tail = re.captures(raw).and_then(|caps| match (caps.get(1), caps.get(2)) {
+        (Some(group), _) => Some((caps.get(0)?.start(), group)),
+        (None, group) => group.map(|g| (g.start(), g)),
+    });
+    let (name, codes) = match tail {
+        Some((cut, group)) => (&raw[..cut], group.as_str().split('.').filter_map(|n| n.parse::<u8>().ok()).collect()),
+        None => (raw, Vec::new()),
+    };
+    let name = name.trim_end_matches(['*', '#', '&', '@', '+', ' ']).trim();
+    let mut allergens: Vec<Allergen> = codes.into_iter().filter_map(Allergen::from_code).collect();
+    allergens.dedup_by_key(|a| a.code);
+    Dish { name: if name.is_empty() { raw.to_string() } else { name.to_string() }, allergens }
+}
+
+/// "탄수화물(g) : 129.2" → Nutrient.
+fn parse_nutrient(line: &str) -> Option<Nutrient> {
+    let (label, amount) = line.split_once(':')?;
+    let label = label.trim();
+    let (name, unit) = match label.split_once('(') {
+        Some((name, rest)) => (name.trim(), rest.trim_end_matches(')').trim()),
+        None => (label, ""),
+    };
+    Some(Nutrient { name: name.to_string(), unit: unit.to_string(), amount: amount.trim().parse().ok()? })
+}
+
+fn parse_origin(line: &str) -> Option<Origin> {
+    let (item, origin) = line.split_once(':')?;
+    let (item, origin) = (item.trim(), origin.trim());
+    (!item.is_empty() && !origin.is_empty()).then(|| Origin { item: item.to_string(), origin: origin.to_string() })
+}
+
+fn meal_from_row(row: &Value) -> Option<(NaiveDate, Meal)> {
+    let date = neis::ymd(neis::str_field(row, "MLSV_YMD")?)?;
+    let label = neis::str_field(row, "MMEAL_SC_NM").unwrap_or("").to_string();
+    let kind = MealKind::parse(neis::str_field(row, "MMEAL_SC_CODE"), &label)?;
+    let meal = Meal {
+        kind,
+        label,
+        dishes: neis::str_field(row, "DDISH_NM").map(|m| split_br(m).map(parse_dish).collect()).unwrap_or_default(),
+        calories: neis::str_field(row, "CAL_INFO")
+            .and_then(|c| c.split_whitespace().next())
+            .and_then(|c| c.parse().ok()),
+        nutrition: neis::str_field(row, "NTR_INFO").map(|n| split_br(n).filter_map(parse_nutrient).collect()).unwrap_or_default(),
+        origin: neis::str_field(row, "ORPLC_INFO").map(|o| split_br(o).filter_map(parse_origin).collect()).unwrap_or_default(),
+    };
+    Some((date, meal))
+}
+
+fn day_data(date: NaiveDate, rows: &[Value]) -> MealData {
+    let mut meals: Vec<Meal> = rows
+        .iter()
+        .filter_map(meal_from_row)
+        .filter(|(d, _)| *d == date)
+        .map(|(_, m)| m)
+        .collect();
+    meals.sort_by_key(|m| m.kind);
+    meals.dedup_by_key(|m| m.kind);
+    MealData { date: date.format("%Y-%m-%d").to_string(), meals, alerts: Vec::new() }
+}
+
+// ─── 조회 ─────────────────────────────────────────────────────────────────────
+
+fn day_key(school: &NeisSchool, date: NaiveDate) -> String {
+    let mut params = school.params();
+    params.push(("MLSV_YMD", date.format("%Y%m%d").to_string()));
+    neis::cache_key(SERVICE, &params)
+}
+
+/// `from`~`to`(양 끝 포함)의 급식. 캐시가 없거나 오래된 날들만 한 번에 나이스에 묻고 날짜별로 캐시한다.
+/// 나이스에 닿지 않으면 오래된 캐시를 쓰고, 캐시도 없는 날은 빈 급식으로 둔다(모든 날이 그렇다면 오류).
+pub fn fetch_meals(
+    conn: &Connection,
+    client: &NeisClient,
+    school: &NeisSchool,
+    from: NaiveDate,
+    to: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<Vec<MealData>, String> {
+    if from > to {
+        return Err(format!("기간이 거꾸로입니다: {} ~ {}", from, to));
+    }
+    if (to - from).num_days() >= MAX_RANGE_DAYS {
+        return Err(format!("급식은 한 번에 {}일까지 조회할 수 있습니다.", MAX_RANGE_DAYS));
+    }
+    let ttl = chrono::Duration::hours(MEAL_TTL_HOURS);
+    let days: Vec<NaiveDate> = from.iter_days().take_while(|d| *d <= to).collect();
+    let mut cached = Vec::with_capacity(days.len());
+    for day in &days {
+        cached.push(neis::cache_get(conn, &day_key(school, *day))?);
+    }
+    let stale: Vec<NaiveDate> = days
+        .iter()
+        .zip(&cached)
+        .filter(|(_, c)| !c.as_ref().is_some_and(|c| c.is_fresh(ttl, now)))
+        .map(|(d, _)| *d)
+        .collect();
+
+    let mut fetched: Option<Vec<Value>> = None;
+    if let (Some(first), Some(last)) = (stale.first(), stale.last()) {
+        let mut params = school.params();
+        params.push(("MLSV_FROM_YMD", first.format("%Y%m%d").to_string()));
+        params.push(("MLSV_TO_YMD", last.format("%Y%m%d").to_string()));
+        match client.fetch_all(SERVICE, &params) {
+            Ok(rows) => {
+                for day in first.iter_days().take_while(|d| d <= last) {
+                    let ymd = day.format("%Y%m%d").to_string();
+                    let day_rows: Vec<Value> = rows
+                        .iter()
+                        .filter(|r| neis::str_field(r, "MLSV_YMD") == Some(ymd.as_str()))
+                        .cloned()
+                        .collect();
+                    neis::cache_put(conn, &day_key(school, day), SERVICE, &day_rows, now)?;
+                }
+                fetched = Some(rows);
+            }
+            Err(e) => {
+                if !days.iter().zip(&cached).any(|(d, c)| c.is_some() && stale.contains(d)) {
+                    return Err(e);
+                }
+                eprintln!("[meal] 급식 받기 실패, 캐시 사용: {}", e);
+            }
+        }
+    }
+
+    Ok(days
+        .iter()
+        .zip(cached)
+        .map(|(day, cached)| match (&fetched, stale.contains(day)) {
+            (Some(rows), true) => day_data(*day, rows),
+            _ => day_data(*day, cached.map(|c| c.rows).as_deref().unwrap_or(&[])),
+        })
+        .collect())
+}
+
+/// 등록한 알레르기 번호(레지스트리 `MealAllergens`, 쉼표 구분).
+pub fn registered_allergens() -> Vec<u8> {
+    get_registry_value(REG_ALLERGENS.to_string())
+        .ok()
+        .flatten()
+        .map(|v| parse_allergen_codes(&v))
+        .unwrap_or_default()
+}
+
+fn parse_allergen_codes(v: &str) -> Vec<u8> {
+    let mut codes: Vec<u8> = v
+        .split(',')
+        .filter_map(|c| c.trim().parse().ok())
+        .filter(|c| Allergen::from_code(*c).is_some())
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+    codes
+}
+
+/// 저장된 학교 프로필로 기간 급식을 가져와 등록한 알레르기를 표시한다.
+pub fn load_meals(conn: &Connection, from: NaiveDate, to: NaiveDate) -> Result<Vec<MealData>, String> {
+    let school = SchoolInfo::load().ok_or("학교 위젯 설정에서 학교를 먼저 골라 주세요.")?.neis_school();
+    let mut days = fetch_meals(conn, &NeisClient::from_registry(), &school, from, to, chrono::Local::now().naive_local())?;
+    let registered = registered_allergens();
+    for day in &mut days {
+        day.flag_allergens(&registered);
+    }
+    Ok(days)
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    let s = s.trim();
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, "%Y%m%d"))
+        .map_err(|_| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", s))
+}
+
+/// 하루 급식(`date`: YYYY-MM-DD 또는 YYYYMMDD).
+#[tauri::command]
+pub fn get_meal_data(app: AppHandle, date: String) -> Result<MealData, String> {
+    let date = parse_date(&date)?;
+    let conn = open_app_db(&app)?;
+    load_meals(&conn, date, date)?.pop().ok_or_else(|| "급식 정보가 없습니다.".to_string())
+}
+
+/// 기간 급식. `to` 를 생략하면 `from` 이 든 주(월~금).
+#[tauri::command]
+pub fn get_meals(app: AppHandle, from: String, to: Option<String>) -> Result<Vec<MealData>, String> {
+    let from = parse_date(&from)?;
+    let (from, to) = match to {
+        Some(to) => (from, parse_date(&to)?),
+        None => {
+            let monday = from - chrono::Duration::days(from.weekday().num_days_from_monday() as i64);
+            (monday, monday + chrono::Duration::days(4))
+        }
+    };
+    let conn = open_app_db(&app)?;
+    load_meals(&conn, from, to)
+}
+
+#[tauri::command]
+pub fn list_allergens() -> Vec<Allergen> {
+    (1..=ALLERGENS.len() as u8).filter_map(Allergen::from_code).collect()
+}
+
+#[tauri::command]
+pub fn get_meal_allergens() -> Vec<u8> {
+    registered_allergens()
+}
+
+#[tauri::command]
+pub fn set_meal_allergens(codes: Vec<u8>) -> Result<(), String> {
+    let codes: Vec<String> = parse_allergen_codes(&codes.iter().map(u8::to_string).collect::<Vec<_>>().join(","))
+        .iter()
+        .map(u8::to_string)
+        .collect();
+    set_registry_value(REG_ALLERGENS.to_string(), codes.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neis::tests::{fixture, serve};
+    use std::sync::atomic::Ordering;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    fn school() -> NeisSchool {
+        SchoolInfo {
+            atpt_code: "C10".to_string(),
+            atpt_name: String::new(),
+            school_code: "7150451".to_string(),
+            name: String::new(),
+            kind: String::new(),
+            level: Some("his".to_string()),
+            address: None,
+        }
+        .neis_school()
+    }
+
+    fn codes(dish: &Dish) -> Vec<u8> {
+        dish.allergens.iter().map(|a| a.code).collect()
+    }
+
+    #[test]
+    fn dishes_keep_allergen_codes() {
+        let dish = parse_dish("쇠고기미역국 (5.6.16.)");
+        assert_eq!(dish.name, "쇠고기미역국");
+        assert_eq!(dish.allergens[2], Allergen { code: 16, name: "쇠고기".to_string() });
+        assert_eq!(codes(&parse_dish("돈육장조림*5.6.10.13.")), [5, 6, 10, 13]);
+        assert_eq!(parse_dish("돈육장조림*5.6.10.13.").name, "돈육장조림");
+        // 알레르기 표기가 아닌 숫자·괄호는 이름으로 둔다.
+        assert_eq!(parse_dish("흰우유200").name, "흰우유200");
+        // 마지막 번호 묶음만 알레르기다. 이름 속 숫자는 버리지 않는다.
+        let milk = parse_dish("흰우유200 (2.)");
+        assert_eq!((milk.name.as_str(), codes(&milk)), ("흰우유200", vec![2]));
+        let milk = parse_dish("흰우유200 2.");
+        assert_eq!((milk.name.as_str(), codes(&milk)), ("흰우유200", vec![2]));
+        assert_eq!(codes(&parse_dish("쇠고기미역국5.6.16.")), [5, 6, 16]);
+        assert_eq!(parse_dish("쇠고기미역국5.6.16.").name, "쇠고기미역국");
+        assert_eq!(parse_dish("비빔밥(완)").name, "비빔밥(완)");
+        assert!(parse_dish("백미밥").allergens.is_empty());
+    }
+
+    #[test]
+    fn week_is_fetched_once_and_cached_per_day() {
+        let (base, hits) = serve(|target| {
+            target.starts_with("/mealServiceDietInfo?").then(|| fixture("meal_week.json"))
+        });
+        let client = NeisClient::new(&base, None);
+        let conn = Connection::open_in_memory().unwrap();
+
+        let week = fetch_meals(&conn, &client, &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-02T07:00:00")).unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(week.len(), 5);
+        // 3/2 는 중식·석식, 3/3 은 중식만, 나머지는 급식 없음.
+        let monday = &week[0];
+        assert_eq!(monday.date, "2026-03-02");
+        assert_eq!(monday.meals.iter().map(|m| m.kind).collect::<Vec<_>>(), [MealKind::Lunch, MealKind::Dinner]);
+        let lunch = monday.meal(MealKind::Lunch).unwrap();
+        assert_eq!(lunch.menu(), "발아현미밥, 쇠고기미역국, 돈육장조림, 배추김치, 흰우유");
+        assert_eq!(lunch.calories, Some(836.4));
+        assert_eq!(lunch.nutrition[0], Nutrient { name: "탄수화물".to_string(), unit: "g".to_string(), amount: 120.5 });
+        assert_eq!(lunch.origin[0], Origin { item: "쌀".to_string(), origin: "국내산".to_string() });
+        assert_eq!(week[1].meals.len(), 1);
+        assert!(week[4].meals.is_empty());
+
+        // 하루만 다시 물어도 캐시에서 나온다.
+        let tuesday = fetch_meals(&conn, &client, &school(), d("2026-03-03"), d("2026-03-03"), at("2026-03-02T12:00:00")).unwrap();
+        assert_eq!(tuesday[0], week[1]);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // 캐시가 오래되면 다시 묻고, 나이스가 안 되면 오래된 캐시를 쓴다.
+        let (down, down_hits) = serve(|_| None);
+        let stale = fetch_meals(&conn, &NeisClient::new(&down, None), &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-03T07:00:00")).unwrap();
+        assert_eq!(down_hits.load(Ordering::SeqCst), 1);
+        assert_eq!(stale, week);
+        assert!(fetch_meals(&conn, &NeisClient::new(&down, None), &school(), d("2026-03-09"), d("2026-03-09"), at("2026-03-03T07:00:00")).is_err());
+    }
+
+    #[test]
+    fn registered_allergens_raise_alerts() {
+        let (base, _) = serve(|target| target.starts_with("/mealServiceDietInfo?").then(|| fixture("meal_week.json")));
+        let conn = Connection::open_in_memory().unwrap();
+        let mut monday = fetch_meals(&conn, &NeisClient::new(&base, None), &school(), d("2026-03-02"), d("2026-03-02"), at("2026-03-02T07:00:00"))
+            .unwrap()
+            .remove(0);
+
+        monday.flag_allergens(&parse_allergen_codes("16, 2,99,x"));
+        let flagged: Vec<(MealKind, &str, Vec<u8>)> = monday
+            .alerts
+            .iter()
+            .map(|a| (a.meal, a.dish.as_str(), a.allergens.iter().map(|x| x.code).collect()))
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                (MealKind::Lunch, "쇠고기미역국", vec![16]),
+                (MealKind::Lunch, "흰우유", vec![2]),
+                (MealKind::Dinner, "불고기덮밥", vec![16]),
+            ]
+        );
+        monday.flag_allergens(&[]);
+        assert!(monday.alerts.is_empty());
+    }
+}
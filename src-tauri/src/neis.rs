@@ -0,0 +1,990 @@
+//! 나이스(NEIS) 교육정보 개방 포털 Open API — 학교 검색, 시간표, 학사일정.
+//!
+//! 교사는 교육청·학교 코드를 모르므로 `schoolInfo` 로 이름 검색해 고른 학교를 프로필
+//! (레지스트리 `SchoolProfile`)로 저장하고, 급식·나이스 시간표·학사일정이 모두 이 프로필을 쓴다.
+//!
+//! 알림이·압핀이 없는 학교도 나이스 시간표(`hisTimetable`·`misTimetable`·`elsTimetable`)로
+//! 반별 수업을 볼 수 있고, 학사일정(`SchoolSchedule`)은 `tbl_schedules` 에 읽기 전용
+//! `school_schedule` 일정으로 가져온다.
+//!
+//! 응답은 `pIndex`/`pSize` 로 나눠 받아 모두 모은 뒤 `hypercool.db` 의 `neis_cache` 에 요청 단위로
+//! 보관한다. 시간표는 주 단위, 학사일정은 월 단위로 요청해 캐시가 겹치지 않게 하고, 나이스에
+//! 닿지 않으면 오래된 캐시라도 돌려준다.
+//!
+//! 인증키(레지스트리 `NeisApiKey`)가 없으면 나이스가 샘플 5건만 주므로 설정을 권한다.
+
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use reqwest::blocking::Client;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::db::{open_app_db, ScheduleItem};
+
+pub const DEFAULT_BASE_URL: &str = "https://open.neis.go.kr/hub";
+/// 나이스가 허용하는 한 번의 최대 건수.
+const PAGE_SIZE: usize = 1000;
+/// 한 요청에서 따라갈 최대 페이지(응답이 이상할 때 끝없이 돌지 않게).
+const MAX_PAGES: usize = 50;
+const TIMETABLE_TTL_HOURS: i64 = 12;
+const SCHEDULE_TTL_HOURS: i64 = 24;
+/// 가져온 학사일정의 `tbl_schedules.type`.
+pub const SCHOOL_SCHEDULE_TYPE: &str = "school_schedule";
+/// 휴업일·공휴일 학사일정 제목 끝에 붙이는 표시(보기용). 휴업일 판정은 `is_day_off` 컬럼으로 한다.
+pub const DAY_OFF_SUFFIX: &str = " (휴업)";
+
+const REG_API_KEY: &str = "NeisApiKey";
+const REG_REGION_CODE: &str = "SchoolRegionCode";
+const REG_SCHOOL_CODE: &str = "SchoolCode";
+const REG_SCHOOL_LEVEL: &str = "SchoolLevel";
+const REG_SCHOOL_PROFILE: &str = "SchoolProfile";
+/// 학교 검색 결과 최대 건수.
+const MAX_SEARCH_RESULTS: usize = 100;
+
+/// 학교급. 시간표 서비스 이름이 학교급마다 다르다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchoolLevel {
+    Elementary,
+    Middle,
+    High,
+}
+
+impl SchoolLevel {
+    /// "els"·"mis"·"his" 또는 "초등학교"·"중학교"·"고등학교". 모르면 None.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        match s {
+            "els" => Some(SchoolLevel::Elementary),
+            "mis" => Some(SchoolLevel::Middle),
+            "his" => Some(SchoolLevel::High),
+            _ if s.contains("초등") => Some(SchoolLevel::Elementary),
+            _ if s.contains("중학") => Some(SchoolLevel::Middle),
+            _ if s.contains("고등") => Some(SchoolLevel::High),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchoolLevel::Elementary => "els",
+            SchoolLevel::Middle => "mis",
+            SchoolLevel::High => "his",
+        }
+    }
+
+    pub fn timetable_service(self) -> &'static str {
+        match self {
+            SchoolLevel::Elementary => "elsTimetable",
+            SchoolLevel::Middle => "misTimetable",
+            SchoolLevel::High => "hisTimetable",
+        }
+    }
+}
+
+/// 나이스 학교 식별자.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeisSchool {
+    /// 시도교육청 코드(ATPT_OFCDC_SC_CODE, 예: "C10").
+    pub atpt_code: String,
+    /// 표준학교코드(SD_SCHUL_CODE).
+    pub school_code: String,
+    pub level: SchoolLevel,
+}
+
+impl NeisSchool {
+    /// 저장된 학교 프로필(`SchoolInfo::load`). 학교급을 모르면 고등학교.
+    pub fn from_registry() -> Result<Self, String> {
+        let profile = SchoolInfo::load().ok_or("학교 위젯 설정에서 학교를 먼저 골라 주세요.")?;
+        Ok(profile.neis_school())
+    }
+
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        vec![("ATPT_OFCDC_SC_CODE", self.atpt_code.clone()), ("SD_SCHUL_CODE", self.school_code.clone())]
+    }
+}
+
+fn read_reg(key: &str) -> Option<String> {
+    get_registry_value(key.to_string())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+// ─── 학교 검색·프로필 ─────────────────────────────────────────────────────────
+
+/// 나이스 `schoolInfo` 의 학교 한 곳. 고른 학교는 그대로 학교 프로필로 저장해 급식·나이스 시간표·
+/// 학사일정이 같이 쓴다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchoolInfo {
+    /// 시도교육청 코드(ATPT_OFCDC_SC_CODE).
+    pub atpt_code: String,
+    /// 시도교육청 이름(예: "서울특별시교육청").
+    #[serde(default)]
+    pub atpt_name: String,
+    /// 표준학교코드(SD_SCHUL_CODE).
+    pub school_code: String,
+    #[serde(default)]
+    pub name: String,
+    /// 학교 종류(SCHUL_KND_SC_NM, 예: "고등학교", "특수학교").
+    #[serde(default)]
+    pub kind: String,
+    /// "els" | "mis" | "his". 특수학교처럼 정할 수 없으면 None.
+    #[serde(default)]
+    pub level: Option<String>,
+    /// 도로명 주소.
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+impl SchoolInfo {
+    fn from_row(row: &Value) -> Option<Self> {
+        let kind = str_field(row, "SCHUL_KND_SC_NM").unwrap_or("").to_string();
+        let address = match (str_field(row, "ORG_RDNMA"), str_field(row, "ORG_RDNDA")) {
+            (Some(road), Some(detail)) => Some(format!("{} {}", road, detail)),
+            (road, _) => road.map(str::to_string),
+        };
+        Some(Self {
+            atpt_code: str_field(row, "ATPT_OFCDC_SC_CODE")?.to_string(),
+            atpt_name: str_field(row, "ATPT_OFCDC_SC_NM").unwrap_or("").to_string(),
+            school_code: str_field(row, "SD_SCHUL_CODE")?.to_string(),
+            name: str_field(row, "SCHUL_NM")?.to_string(),
+            level: SchoolLevel::parse(&kind).map(|l| l.as_str().to_string()),
+            kind,
+            address,
+        })
+    }
+
+    pub fn neis_school(&self) -> NeisSchool {
+        NeisSchool {
+            atpt_code: self.atpt_code.clone(),
+            school_code: self.school_code.clone(),
+            level: self.level.as_deref().and_then(SchoolLevel::parse).unwrap_or(SchoolLevel::High),
+        }
+    }
+
+    /// 저장된 학교 프로필(레지스트리 `SchoolProfile`). 없으면 예전 설정(SchoolRegionCode·SchoolCode·
+    /// SchoolLevel)으로 만든다.
+    pub fn load() -> Option<Self> {
+        if let Some(profile) = read_reg(REG_SCHOOL_PROFILE).and_then(|json| serde_json::from_str::<Self>(&json).ok()) {
+            return Some(profile);
+        }
+        Some(Self {
+            atpt_code: read_reg(REG_REGION_CODE)?,
+            atpt_name: String::new(),
+            school_code: read_reg(REG_SCHOOL_CODE)?,
+            name: String::new(),
+            kind: String::new(),
+            level: read_reg(REG_SCHOOL_LEVEL).and_then(|v| SchoolLevel::parse(&v)).map(|l| l.as_str().to_string()),
+            address: None,
+        })
+    }
+
+    /// 프로필과 함께 예전 키도 맞춰 둔다(학교 위젯이 코드로 읽는다).
+    pub fn save(&self) -> Result<(), String> {
+        if self.atpt_code.trim().is_empty() || self.school_code.trim().is_empty() {
+            return Err("교육청 코드와 학교 코드가 필요합니다.".to_string());
+        }
+        let json = serde_json::to_string(self).map_err(|e| format!("학교 프로필 직렬화 실패: {}", e))?;
+        set_registry_value(REG_SCHOOL_PROFILE.to_string(), json)?;
+        set_registry_value(REG_REGION_CODE.to_string(), self.atpt_code.trim().to_string())?;
+        set_registry_value(REG_SCHOOL_CODE.to_string(), self.school_code.trim().to_string())?;
+        set_registry_value(REG_SCHOOL_LEVEL.to_string(), self.level.clone().unwrap_or_default())
+    }
+}
+
+/// 이름으로 학교를 찾는다(나이스 부분 일치). 이름·교육청 순.
+pub fn find_schools(client: &NeisClient, name: &str) -> Result<Vec<SchoolInfo>, String> {
+    let name = name.trim();
+    if name.chars().count() < 2 {
+        return Err("학교 이름을 두 글자 이상 입력해 주세요.".to_string());
+    }
+    let rows = client.fetch_all("schoolInfo", &[("SCHUL_NM", name.to_string())])?;
+    let mut schools: Vec<SchoolInfo> = rows.iter().filter_map(SchoolInfo::from_row).collect();
+    schools.sort_by(|a, b| (a.name.as_str(), a.atpt_code.as_str()).cmp(&(b.name.as_str(), b.atpt_code.as_str())));
+    schools.truncate(MAX_SEARCH_RESULTS);
+    Ok(schools)
+}
+
+// ─── 클라이언트 ───────────────────────────────────────────────────────────────
+
+pub struct NeisClient {
+    base_url: String,
+    api_key: Option<String>,
+    page_size: usize,
+    http: Client,
+}
+
+impl NeisClient {
+    pub fn new(base_url: &str, api_key: Option<String>) -> Self {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|k| !k.trim().is_empty()),
+            page_size: PAGE_SIZE,
+            http,
+        }
+    }
+
+    /// 기본 주소 + 레지스트리 인증키.
+    pub fn from_registry() -> Self {
+        Self::new(DEFAULT_BASE_URL, read_reg(REG_API_KEY))
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    fn page(&self, service: &str, params: &[(&str, String)], index: usize) -> Result<(Vec<Value>, Option<usize>), String> {
+        let mut query: Vec<(&str, String)> = vec![
+            ("Type", "json".to_string()),
+            ("pIndex", index.to_string()),
+            ("pSize", self.page_size.to_string()),
+        ];
+        if let Some(key) = &self.api_key {
+            query.push(("KEY", key.clone()));
+        }
+        query.extend(params.iter().cloned());
+        let body = self
+            .http
+            .get(format!("{}/{}", self.base_url, service))
+            .query(&query)
+            .send()
+            .map_err(|e| format!("나이스 요청 실패: {}", e))?
+            .text()
+            .map_err(|e| format!("나이스 응답 읽기 실패: {}", e))?;
+        parse_page(service, &body)
+    }
+
+    /// 모든 페이지의 `row` 를 모은다.
+    pub fn fetch_all(&self, service: &str, params: &[(&str, String)]) -> Result<Vec<Value>, String> {
+        let mut rows = Vec::new();
+        for index in 1..=MAX_PAGES {
+            let (page, total) = self.page(service, params, index)?;
+            let got = page.len();
+            rows.extend(page);
+            let done = match total {
+                Some(total) => rows.len() >= total,
+                None => true,
+            };
+            if done || got < self.page_size || got == 0 {
+                break;
+            }
+        }
+        Ok(rows)
+    }
+}
+
+/// 한 페이지 응답: (`row` 목록, `list_total_count`). 자료 없음(INFO-200)은 빈 목록.
+fn parse_page(service: &str, body: &str) -> Result<(Vec<Value>, Option<usize>), String> {
+    let json: Value = serde_json::from_str(body).map_err(|e| format!("나이스 응답 JSON 파싱 실패: {}", e))?;
+    // 오류·자료 없음은 서비스 이름 없이 RESULT 만 온다.
+    if let Some(result) = json.get("RESULT") {
+        return match result["CODE"].as_str() {
+            Some("INFO-200") => Ok((Vec::new(), Some(0))),
+            code => Err(format!(
+                "나이스 오류({}): {}",
+                code.unwrap_or("?"),
+                result["MESSAGE"].as_str().unwrap_or("")
+            )),
+        };
+    }
+    let sections = json[service].as_array().ok_or_else(|| format!("나이스 응답에 {} 가 없습니다", service))?;
+    let head = sections.iter().find_map(|s| s.get("head")).and_then(Value::as_array);
+    let total = head
+        .and_then(|h| h.iter().find_map(|e| e.get("list_total_count")))
+        .and_then(Value::as_u64)
+        .map(|n| n as usize);
+    if let Some(result) = head.and_then(|h| h.iter().find_map(|e| e.get("RESULT"))) {
+        let code = result["CODE"].as_str().unwrap_or("");
+        if code != "INFO-000" {
+            return Err(format!("나이스 오류({}): {}", code, result["MESSAGE"].as_str().unwrap_or("")));
+        }
+    }
+    let rows = sections
+        .iter()
+        .find_map(|s| s.get("row"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    Ok((rows, total))
+}
+
+// ─── 캐시 ─────────────────────────────────────────────────────────────────────
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS neis_cache (
+            cache_key TEXT PRIMARY KEY,
+            service TEXT NOT NULL,
+            rows TEXT NOT NULL,
+            fetched_at TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("나이스 캐시 테이블 생성 실패: {}", e))
+}
+
+pub(crate) fn cache_key(service: &str, params: &[(&str, String)]) -> String {
+    let mut sorted: Vec<String> = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    sorted.sort();
+    format!("{}?{}", service, sorted.join("&"))
+}
+
+/// 캐시에 저장된 행과 받은 시각.
+pub(crate) struct CachedRows {
+    pub rows: Vec<Value>,
+    pub fetched_at: Option<NaiveDateTime>,
+}
+
+impl CachedRows {
+    pub fn is_fresh(&self, ttl: chrono::Duration, now: NaiveDateTime) -> bool {
+        self.fetched_at.is_some_and(|t| now - t < ttl)
+    }
+}
+
+pub(crate) fn cache_get(conn: &Connection, key: &str) -> Result<Option<CachedRows>, String> {
+    ensure_tables(conn)?;
+    let cached: Option<(String, String)> = conn
+        .query_row(
+            "SELECT rows, fetched_at FROM neis_cache WHERE cache_key = ?1",
+            params![key],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| format!("나이스 캐시 조회 실패: {}", e))?;
+    cached
+        .map(|(rows, fetched_at)| {
+            Ok(CachedRows {
+                rows: serde_json::from_str(&rows).map_err(|e| format!("나이스 캐시 손상: {}", e))?,
+                fetched_at: NaiveDateTime::parse_from_str(&fetched_at, "%Y-%m-%dT%H:%M:%S").ok(),
+            })
+        })
+        .transpose()
+}
+
+pub(crate) fn cache_put(conn: &Connection, key: &str, service: &str, rows: &[Value], now: NaiveDateTime) -> Result<(), String> {
+    ensure_tables(conn)?;
+    let json = serde_json::to_string(rows).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO neis_cache (cache_key, service, rows, fetched_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(cache_key) DO UPDATE SET rows = excluded.rows, fetched_at = excluded.fetched_at",
+        params![key, service, json, now.format("%Y-%m-%dT%H:%M:%S").to_string()],
+    )
+    .map_err(|e| format!("나이스 캐시 저장 실패: {}", e))?;
+    Ok(())
+}
+
+/// 캐시가 `ttl` 안이면 캐시를, 아니면 새로 받아 저장한다. 받기에 실패하면 오래된 캐시라도 쓴다.
+pub fn fetch_cached(
+    conn: &Connection,
+    client: &NeisClient,
+    service: &str,
+    params: &[(&str, String)],
+    ttl: chrono::Duration,
+    now: NaiveDateTime,
+) -> Result<Vec<Value>, String> {
+    let key = cache_key(service, params);
+    let cached = cache_get(conn, &key)?;
+    if let Some(c) = &cached {
+        if c.is_fresh(ttl, now) {
+            return Ok(c.rows.clone());
+        }
+    }
+    match client.fetch_all(service, params) {
+        Ok(rows) => {
+            cache_put(conn, &key, service, &rows, now)?;
+            Ok(rows)
+        }
+        Err(e) => match cached {
+            Some(c) => {
+                eprintln!("[neis] {} 받기 실패, 캐시 사용: {}", service, e);
+                Ok(c.rows)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+// ─── 시간표 ───────────────────────────────────────────────────────────────────
+
+/// 나이스 시간표의 한 칸. 나이스 시간표에는 교사 정보가 없다.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NeisLesson {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub grade: u32,
+    /// "학년-반"(예: "2-1").
+    pub class_name: String,
+    pub period: u32,
+    pub subject: String,
+    pub room: Option<String>,
+}
+
+pub(crate) fn str_field<'a>(row: &'a Value, key: &str) -> Option<&'a str> {
+    row.get(key).and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty())
+}
+
+pub(crate) fn ymd(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y%m%d").ok()
+}
+
+fn lesson_from_row(row: &Value) -> Option<NeisLesson> {
+    let date = ymd(str_field(row, "ALL_TI_YMD")?)?;
+    let grade: u32 = str_field(row, "GRADE")?.parse().ok()?;
+    let class = str_field(row, "CLASS_NM")?;
+    // 초등학교 시간표는 과목 앞에 "-" 가 붙어 오기도 한다.
+    let subject = str_field(row, "ITRT_CNTNT")?.trim_start_matches('-').trim().to_string();
+    if subject.is_empty() {
+        return None;
+    }
+    Some(NeisLesson {
+        date: date.format("%Y-%m-%d").to_string(),
+        grade,
+        class_name: format!("{}-{}", grade, class),
+        period: str_field(row, "PERIO")?.parse().ok()?,
+        subject,
+        room: str_field(row, "CLRM_NM").filter(|r| *r != class).map(str::to_string),
+    })
+}
+
+fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// `from`~`to`(양 끝 포함)의 시간표. 주 단위로 요청·캐시한다.
+pub fn timetable(
+    conn: &Connection,
+    client: &NeisClient,
+    school: &NeisSchool,
+    from: NaiveDate,
+    to: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<Vec<NeisLesson>, String> {
+    let mut out = Vec::new();
+    let mut week = monday_of(from);
+    while week <= to {
+        let mut params = school.params();
+        params.push(("TI_FROM_YMD", week.format("%Y%m%d").to_string()));
+        params.push(("TI_TO_YMD", (week + chrono::Duration::days(6)).format("%Y%m%d").to_string()));
+        let rows = fetch_cached(
+            conn,
+            client,
+            school.level.timetable_service(),
+            &params,
+            chrono::Duration::hours(TIMETABLE_TTL_HOURS),
+            now,
+        )?;
+        out.extend(rows.iter().filter_map(lesson_from_row));
+        week += chrono::Duration::days(7);
+    }
+    let (from, to) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+    out.retain(|l| l.date >= from && l.date <= to);
+    out.sort_by(|a, b| {
+        (a.date.as_str(), a.grade, a.class_name.as_str(), a.period)
+            .cmp(&(b.date.as_str(), b.grade, b.class_name.as_str(), b.period))
+    });
+    out.dedup();
+    Ok(out)
+}
+
+// ─── 학사일정 ─────────────────────────────────────────────────────────────────
+
+/// 학사일정 하나.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchoolEvent {
+    /// YYYY-MM-DD
+    pub date: String,
+    pub name: String,
+    pub content: Option<String>,
+    /// 해당 학년(비어 있으면 학년 구분 없음).
+    pub grades: Vec<u32>,
+    /// 휴업일·공휴일.
+    pub day_off: bool,
+}
+
+const GRADE_FLAGS: [&str; 6] = [
+    "ONE_GRADE_EVENT_YN",
+    "TW_GRADE_EVENT_YN",
+    "THREE_GRADE_EVENT_YN",
+    "FR_GRADE_EVENT_YN",
+    "FIV_GRADE_EVENT_YN",
+    "SIX_GRADE_EVENT_YN",
+];
+
+fn event_from_row(row: &Value) -> Option<SchoolEvent> {
+    let date = ymd(str_field(row, "AA_YMD")?)?;
+    let name = str_field(row, "EVENT_NM")?.to_string();
+    let grades = GRADE_FLAGS
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| str_field(row, k) == Some("Y"))
+        .map(|(i, _)| i as u32 + 1)
+        .collect();
+    Some(SchoolEvent {
+        date: date.format("%Y-%m-%d").to_string(),
+        name,
+        content: str_field(row, "EVENT_CNTNT").map(str::to_string),
+        grades,
+        day_off: str_field(row, "SBTR_DD_SC_NM").is_some_and(|s| s.contains("휴업") || s.contains("공휴")),
+    })
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn next_month(first: NaiveDate) -> NaiveDate {
+    first.checked_add_months(chrono::Months::new(1)).unwrap_or(first)
+}
+
+/// `from`~`to`(양 끝 포함)의 학사일정. 월 단위로 요청·캐시한다.
+pub fn school_schedule(
+    conn: &Connection,
+    client: &NeisClient,
+    school: &NeisSchool,
+    from: NaiveDate,
+    to: NaiveDate,
+    now: NaiveDateTime,
+) -> Result<Vec<SchoolEvent>, String> {
+    let mut out = Vec::new();
+    let mut month = first_of_month(from);
+    while month <= to {
+        let next = next_month(month);
+        if next == month {
+            break;
+        }
+        let mut params = school.params();
+        params.push(("AA_FROM_YMD", month.format("%Y%m%d").to_string()));
+        params.push(("AA_TO_YMD", (next - chrono::Duration::days(1)).format("%Y%m%d").to_string()));
+        let rows = fetch_cached(
+            conn,
+            client,
+            "SchoolSchedule",
+            &params,
+            chrono::Duration::hours(SCHEDULE_TTL_HOURS),
+            now,
+        )?;
+        out.extend(rows.iter().filter_map(event_from_row));
+        month = next;
+    }
+    let (from, to) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+    out.retain(|e| e.date >= from && e.date <= to);
+    out.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+    out.dedup();
+    Ok(out)
+}
+
+/// 학사일정 가져오기 결과.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SchoolScheduleImport {
+    pub imported: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    /// 나이스에서 사라져 지운 일정.
+    pub removed: u32,
+}
+
+fn event_reference(school: &NeisSchool, e: &SchoolEvent) -> String {
+    format!("neis:{}:{}:{}", school.school_code, e.date, e.name)
+}
+
+/// 학사일정을 `tbl_schedules` 에 읽기 전용 `school_schedule` 로 반영한다. 같은 날·같은 이름은
+/// 같은 일정으로 보고, `from`~`to` 안에서 나이스에서 사라진 일정은 지운다.
+pub fn import_school_schedule(
+    conn: &Connection,
+    school: &NeisSchool,
+    events: &[SchoolEvent],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<SchoolScheduleImport, String> {
+    crate::db::ensure_schedule_table(conn)?;
+    let mut result = SchoolScheduleImport::default();
+    let now = chrono::Utc::now().to_rfc3339();
+    let prefix = format!("neis:{}:", school.school_code);
+
+    let existing: Vec<(String, String, String, Option<String>, bool, bool)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, reference_id, title, content, is_day_off, is_deleted FROM tbl_schedules
+                 WHERE type = ?1 AND reference_id LIKE ?2 || '%'",
+            )
+            .map_err(|e| format!("학사일정 조회 실패: {}", e))?;
+        let rows = stmt
+            .query_map(params![SCHOOL_SCHEDULE_TYPE, prefix], |r| {
+                Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?))
+            })
+            .map_err(|e| format!("학사일정 조회 실패: {}", e))?;
+        rows.flatten().collect()
+    };
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    let mut seen = std::collections::HashSet::new();
+    for e in events {
+        let reference = event_reference(school, e);
+        if !seen.insert(reference.clone()) {
+            continue;
+        }
+        let title = if e.day_off { format!("{}{}", e.name, DAY_OFF_SUFFIX) } else { e.name.clone() };
+        let content = e.content.clone().filter(|c| c != &e.name);
+        match existing.iter().find(|(_, r, ..)| *r == reference) {
+            Some((_, _, t, c, day_off, deleted)) if *t == title && *c == content && *day_off == e.day_off && !*deleted => {
+                result.unchanged += 1
+            }
+            Some((id, ..)) => {
+                tx.execute(
+                    "UPDATE tbl_schedules SET title = ?1, content = ?2, is_day_off = ?3, is_deleted = 0, updated_at = ?4 WHERE id = ?5",
+                    params![title, content, e.day_off, now, id],
+                )
+                .map_err(|e| format!("학사일정 갱신 실패: {}", e))?;
+                result.updated += 1;
+            }
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                let item = ScheduleItem {
+                    id: id.clone(),
+                    schedule_type: SCHOOL_SCHEDULE_TYPE.to_string(),
+                    title,
+                    content,
+                    start_date: Some(e.date.clone()),
+                    end_date: Some(e.date.clone()),
+                    is_all_day: true,
+                    reference_id: Some(reference),
+                    color: None,
+                    is_completed: false,
+                    created_at: now.clone(),
+                    updated_at: now.clone(),
+                    is_deleted: false,
+                    priority: None,
+                    item_kind: Some("event".to_string()),
+                    visible_until: None,
+                    has_attachment: None,
+                };
+                crate::db::create_schedule_impl(&tx, item)?;
+                tx.execute("UPDATE tbl_schedules SET is_day_off = ?1 WHERE id = ?2", params![e.day_off, id])
+                    .map_err(|e| format!("학사일정 저장 실패: {}", e))?;
+                result.imported += 1;
+            }
+        }
+    }
+
+    let (from, to) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+    for (id, reference, ..) in existing.iter().filter(|(_, r, .., deleted)| !*deleted && !seen.contains(r)) {
+        let date = reference.strip_prefix(&prefix).and_then(|r| r.get(..10)).unwrap_or("");
+        if date >= from.as_str() && date <= to.as_str() {
+            tx.execute(
+                "UPDATE tbl_schedules SET is_deleted = 1, updated_at = ?1 WHERE id = ?2",
+                params![now, id],
+            )
+            .map_err(|e| format!("학사일정 정리 실패: {}", e))?;
+            result.removed += 1;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+fn parse_range(from: &str, to: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let parse = |s: &str| {
+        NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", s))
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    if from > to {
+        return Err(format!("기간이 거꾸로입니다: {} ~ {}", from, to));
+    }
+    if (to - from).num_days() > 400 {
+        return Err("한 번에 400일까지 조회할 수 있습니다.".to_string());
+    }
+    Ok((from, to))
+}
+
+fn now_local() -> NaiveDateTime {
+    chrono::Local::now().naive_local()
+}
+
+#[tauri::command]
+pub fn search_schools(name: String) -> Result<Vec<SchoolInfo>, String> {
+    find_schools(&NeisClient::from_registry(), &name)
+}
+
+#[tauri::command]
+pub fn get_school_profile() -> Option<SchoolInfo> {
+    SchoolInfo::load()
+}
+
+#[tauri::command]
+pub fn set_school_profile(school: SchoolInfo) -> Result<(), String> {
+    school.save()
+}
+
+/// 나이스 시간표. `class_name`("2-1")이나 `grade` 를 주면 그 반·학년만.
+#[tauri::command]
+pub fn get_neis_timetable(
+    app: AppHandle,
+    from: String,
+    to: String,
+    grade: Option<u32>,
+    class_name: Option<String>,
+) -> Result<Vec<NeisLesson>, String> {
+    let (from, to) = parse_range(&from, &to)?;
+    let school = NeisSchool::from_registry()?;
+    let conn = open_app_db(&app)?;
+    let mut lessons = timetable(&conn, &NeisClient::from_registry(), &school, from, to, now_local())?;
+    if let Some(g) = grade {
+        lessons.retain(|l| l.grade == g);
+    }
+    if let Some(c) = class_name.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        lessons.retain(|l| l.class_name == c);
+    }
+    Ok(lessons)
+}
+
+#[tauri::command]
+pub fn get_neis_school_schedule(app: AppHandle, from: String, to: String) -> Result<Vec<SchoolEvent>, String> {
+    let (from, to) = parse_range(&from, &to)?;
+    let school = NeisSchool::from_registry()?;
+    let conn = open_app_db(&app)?;
+    school_schedule(&conn, &NeisClient::from_registry(), &school, from, to, now_local())
+}
+
+/// 학사일정을 달력으로 가져온다.
+#[tauri::command]
+pub fn import_neis_school_schedule(app: AppHandle, from: String, to: String) -> Result<SchoolScheduleImport, String> {
+    let (from, to) = parse_range(&from, &to)?;
+    let school = NeisSchool::from_registry()?;
+    let conn = open_app_db(&app)?;
+    let events = school_schedule(&conn, &NeisClient::from_registry(), &school, from, to, now_local())?;
+    let result = import_school_schedule(&conn, &school, &events, from, to)?;
+    if result.imported + result.updated + result.removed > 0 {
+        let _ = app.emit("calendar-update", ());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    pub(crate) fn fixture(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("neis").join(name);
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+    }
+
+    /// 요청 경로·쿼리를 보고 녹화해 둔 응답을 돌려주는 로컬 서버. (주소, 받은 요청 수)
+    pub(crate) fn serve(route: fn(&str) -> Option<String>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let target = request_line.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = match route(target) {
+                    Some(body) => ("200 OK", body),
+                    None => ("500 Internal Server Error", String::new()),
+                };
+                let mut stream = stream;
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json;charset=UTF-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (format!("http://{}", addr), hits)
+    }
+
+    fn school() -> NeisSchool {
+        NeisSchool { atpt_code: "C10".to_string(), school_code: "7150451".to_string(), level: SchoolLevel::High }
+    }
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn timetable_follows_pages_and_caches() {
+        let (base, hits) = serve(|target| {
+            if !target.starts_with("/hisTimetable?") {
+                return None;
+            }
+            if target.contains("TI_FROM_YMD=20260309") {
+                return Some(fixture("his_timetable_empty.json"));
+            }
+            Some(fixture(if target.contains("pIndex=2") { "his_timetable_p2.json" } else { "his_timetable_p1.json" }))
+        });
+        let client = NeisClient::new(&base, None).with_page_size(2);
+        let conn = Connection::open_in_memory().unwrap();
+
+        let lessons = timetable(&conn, &client, &school(), d("2026-03-02"), d("2026-03-13"), at("2026-03-02T08:00:00")).unwrap();
+        // 첫 주 2쪽 + 둘째 주(자료 없음) 1쪽.
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert_eq!(lessons.len(), 3);
+        assert_eq!(
+            lessons[0],
+            NeisLesson {
+                date: "2026-03-02".to_string(),
+                grade: 2,
+                class_name: "2-1".to_string(),
+                period: 1,
+                subject: "수학Ⅰ".to_string(),
+                room: None,
+            }
+        );
+        assert_eq!(lessons[2].room.as_deref(), Some("과학실"));
+
+        // TTL 안이면 다시 묻지 않는다.
+        let again = timetable(&conn, &client, &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-02T12:00:00")).unwrap();
+        assert_eq!(again, lessons);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn stale_cache_is_used_when_neis_fails() {
+        let (base, _) = serve(|target| target.starts_with("/hisTimetable?").then(|| fixture("his_timetable_p1.json")));
+        let conn = Connection::open_in_memory().unwrap();
+        let first = timetable(&conn, &NeisClient::new(&base, None), &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-02T08:00:00")).unwrap();
+        assert_eq!(first.len(), 2);
+
+        let (down, hits) = serve(|_| None);
+        let later = timetable(&conn, &NeisClient::new(&down, None), &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-05T08:00:00")).unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(later, first);
+
+        // 캐시가 없으면 오류를 그대로 낸다.
+        let fresh = Connection::open_in_memory().unwrap();
+        assert!(timetable(&fresh, &NeisClient::new(&down, None), &school(), d("2026-03-02"), d("2026-03-06"), at("2026-03-05T08:00:00")).is_err());
+    }
+
+    #[test]
+    fn error_results_are_reported() {
+        let err = parse_page("hisTimetable", &fixture("error_key.json")).unwrap_err();
+        assert!(err.contains("ERROR-290"), "{}", err);
+        assert_eq!(parse_page("hisTimetable", &fixture("his_timetable_empty.json")).unwrap(), (Vec::new(), Some(0)));
+    }
+
+    #[test]
+    fn schools_are_found_by_name() {
+        let (base, _) = serve(|target| {
+            if !target.starts_with("/schoolInfo?") {
+                return None;
+            }
+            // "하이퍼"
+            Some(if target.contains("SCHUL_NM=%ED%95%98%EC%9D%B4%ED%8D%BC") {
+                fixture("school_info.json")
+            } else {
+                fixture("his_timetable_empty.json")
+            })
+        });
+        let client = NeisClient::new(&base, None);
+
+        let schools = find_schools(&client, " 하이퍼 ").unwrap();
+        let names: Vec<&str> = schools.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["하이퍼고등학교", "하이퍼중학교", "하이퍼학교"]);
+        assert_eq!(
+            schools[0],
+            SchoolInfo {
+                atpt_code: "C10".to_string(),
+                atpt_name: "부산광역시교육청".to_string(),
+                school_code: "7150451".to_string(),
+                name: "하이퍼고등학교".to_string(),
+                kind: "고등학교".to_string(),
+                level: Some("his".to_string()),
+                address: Some("부산광역시 해운대구 센텀로 1 (우동)".to_string()),
+            }
+        );
+        assert_eq!(schools[1].neis_school().level, SchoolLevel::Middle);
+        // 특수학교는 학교급을 정할 수 없어 나이스 시간표는 고등학교로 묻는다.
+        assert_eq!(schools[2].level, None);
+        assert_eq!(schools[2].neis_school().level, SchoolLevel::High);
+
+        assert!(find_schools(&client, "없는학교").unwrap().is_empty());
+        assert!(find_schools(&client, "가").is_err());
+    }
+
+    #[test]
+    fn school_schedule_imports_as_read_only_items() {
+        let (base, _) = serve(|target| {
+            if !target.starts_with("/SchoolSchedule?") {
+                return None;
+            }
+            Some(if target.contains("AA_FROM_YMD=20260301") {
+                fixture("school_schedule_202603.json")
+            } else {
+                fixture("his_timetable_empty.json")
+            })
+        });
+        let client = NeisClient::new(&base, None);
+        let conn = Connection::open_in_memory().unwrap();
+        let (from, to) = (d("2026-03-01"), d("2026-04-30"));
+
+        let events = school_schedule(&conn, &client, &school(), from, to, at("2026-03-01T08:00:00")).unwrap();
+        let names: Vec<&str> = events.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["삼일절", "입학식", "전국연합학력평가"]);
+        assert!(events[0].day_off);
+        assert_eq!(events[1].grades, [1]);
+        assert_eq!(events[2].grades, [1, 2, 3]);
+
+        let first = import_school_schedule(&conn, &school(), &events, from, to).unwrap();
+        assert_eq!(first, SchoolScheduleImport { imported: 3, ..Default::default() });
+        let again = import_school_schedule(&conn, &school(), &events, from, to).unwrap();
+        assert_eq!(again, SchoolScheduleImport { unchanged: 3, ..Default::default() });
+
+        // 나이스에서 바뀌거나 사라진 일정을 따라간다.
+        let mut changed = events.clone();
+        changed.remove(2);
+        changed[1].content = Some("강당 10:00".to_string());
+        let synced = import_school_schedule(&conn, &school(), &changed, from, to).unwrap();
+        assert_eq!(synced, SchoolScheduleImport { updated: 1, unchanged: 1, removed: 1, ..Default::default() });
+
+        let items = crate::db::get_schedules_impl(&conn, "2026-03-01".to_string(), "2026-04-30".to_string(), false).unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|i| i.schedule_type == SCHOOL_SCHEDULE_TYPE));
+        let holiday = items.iter().find(|i| i.title.starts_with("삼일절")).unwrap();
+        assert_eq!(holiday.title, "삼일절 (휴업)");
+        let day_off: Vec<String> = conn
+            .prepare("SELECT title FROM tbl_schedules WHERE is_day_off = 1 AND is_deleted = 0")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .flatten()
+            .collect();
+        assert_eq!(day_off, ["삼일절 (휴업)"]);
+
+        // 읽기 전용: 수정·삭제를 막는다.
+        assert!(crate::db::update_schedule_impl(&conn, holiday.id.clone(), holiday.clone(), &Default::default()).is_err());
+        assert!(crate::db::delete_schedule_impl(&conn, holiday.id.clone()).is_err());
+    }
+}
@@ -0,0 +1,595 @@
+//! 개인정보 가림 처리: MCP 도구 결과가 외부 LLM 으로 나가기 전에 전화번호·주민등록번호·
+//! 이메일·계좌번호·학생 이름(명단 기반)을 `[PHONE_1]` 같은 토큰으로 바꾼다.
+//!
+//! - 토큰은 브리핑 실행 1회 동안 원문과 1:1 로 고정된다(같은 번호는 항상 같은 토큰).
+//!   브리핑 에이전트는 claude 출력에서 `restore` 로 토큰을 원문으로 되돌린 뒤 등록하고,
+//!   실행이 끝나면(`begin_run` 가드 drop) 보관소를 비운다. 실행 밖에서 쌓이는 토큰은
+//!   `VAULT_MAX_ENTRIES` 로 상한을 둔다.
+//! - 설정은 레지스트리(`PiiRedactEnabled`, `PiiRedactKinds`, `PiiRoster`)에 저장한다.
+//!   기본값은 비활성 — Claude Desktop 처럼 사용자가 응답을 직접 읽는 경로에서는 토큰이
+//!   그대로 보이기 때문에 명시적으로 켜야 한다.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+
+const REG_ENABLED: &str = "PiiRedactEnabled";
+const REG_KINDS: &str = "PiiRedactKinds";
+const REG_ROSTER: &str = "PiiRoster";
+
+/// 계좌번호로 볼 최소 숫자 개수(날짜 `2026-03-02` 같은 짧은 하이픈 숫자열 제외).
+const ACCOUNT_MIN_DIGITS: usize = 10;
+/// 명단 이름 최소 글자 수(한 글자 이름은 일반 단어와 겹쳐 오탐이 많다).
+const NAME_MIN_CHARS: usize = 2;
+/// 보관소 항목 상한. 넘으면 맵을 비우되 일련번호는 이어가 이전 토큰과 겹치지 않게 한다.
+const VAULT_MAX_ENTRIES: usize = 5000;
+
+static EMAIL_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+static RRN_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+static PHONE_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+static ACCOUNT_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+static TOKEN_REGEX: OnceLock<regex::Regex> = OnceLock::new();
+
+static VAULT: OnceLock<Mutex<TokenVault>> = OnceLock::new();
+
+// ─── 종류 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    Email,
+    Rrn,
+    Phone,
+    Account,
+    Name,
+}
+
+impl PiiKind {
+    /// 적용 순서. 이메일(숫자 포함 가능) → 주민번호 → 전화 → 계좌 → 이름.
+    pub const ALL: [PiiKind; 5] = [
+        PiiKind::Email,
+        PiiKind::Rrn,
+        PiiKind::Phone,
+        PiiKind::Account,
+        PiiKind::Name,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            PiiKind::Email => "EMAIL",
+            PiiKind::Rrn => "RRN",
+            PiiKind::Phone => "PHONE",
+            PiiKind::Account => "ACCOUNT",
+            PiiKind::Name => "NAME",
+        }
+    }
+
+    /// 설정 문자열 키("phone,rrn,...")
+    fn key(self) -> &'static str {
+        match self {
+            PiiKind::Email => "email",
+            PiiKind::Rrn => "rrn",
+            PiiKind::Phone => "phone",
+            PiiKind::Account => "account",
+            PiiKind::Name => "name",
+        }
+    }
+
+    fn from_key(s: &str) -> Option<PiiKind> {
+        PiiKind::ALL.into_iter().find(|k| k.key() == s)
+    }
+}
+
+// ─── 설정 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PiiRedactSettings {
+    pub enabled: bool,
+    /// 적용할 종류 키 목록: "email" | "rrn" | "phone" | "account" | "name"
+    pub kinds: Vec<String>,
+    /// 이름 가림에 쓰는 명단(학생·학부모 이름 등)
+    pub roster: Vec<String>,
+}
+
+impl Default for PiiRedactSettings {
+    fn default() -> Self {
+        PiiRedactSettings {
+            enabled: false,
+            kinds: PiiKind::ALL.iter().map(|k| k.key().to_string()).collect(),
+            roster: Vec::new(),
+        }
+    }
+}
+
+/// 가림 처리에 실제로 쓰는 설정(문자열 키를 파싱해 둔 형태).
+#[derive(Debug, Clone)]
+pub struct RedactConfig {
+    pub kinds: Vec<PiiKind>,
+    /// 긴 이름 우선으로 정렬된 명단(부분 일치 시 긴 이름이 먼저 치환되도록).
+    pub roster: Vec<String>,
+}
+
+impl RedactConfig {
+    pub fn from_settings(s: &PiiRedactSettings) -> Option<RedactConfig> {
+        if !s.enabled {
+            return None;
+        }
+        let kinds: Vec<PiiKind> = PiiKind::ALL
+            .into_iter()
+            .filter(|k| s.kinds.iter().any(|x| x.trim() == k.key()))
+            .collect();
+        if kinds.is_empty() {
+            return None;
+        }
+        Some(RedactConfig {
+            kinds,
+            roster: normalize_roster(&s.roster),
+        })
+    }
+}
+
+fn normalize_roster(names: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = names
+        .iter()
+        .map(|n| n.trim().to_string())
+        .filter(|n| n.chars().count() >= NAME_MIN_CHARS)
+        .collect();
+    out.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+    out.dedup();
+    out
+}
+
+/// 레지스트리에서 설정을 읽는다(값이 없으면 기본값).
+pub fn load_settings() -> PiiRedactSettings {
+    let mut s = PiiRedactSettings::default();
+    if let Ok(Some(v)) = get_registry_value(REG_ENABLED.to_string()) {
+        s.enabled = v == "true";
+    }
+    if let Ok(Some(v)) = get_registry_value(REG_KINDS.to_string()) {
+        s.kinds = v
+            .split(',')
+            .map(|k| k.trim())
+            .filter(|k| PiiKind::from_key(k).is_some())
+            .map(|k| k.to_string())
+            .collect();
+    }
+    if let Ok(Some(v)) = get_registry_value(REG_ROSTER.to_string()) {
+        s.roster = v
+            .split(['\n', ','])
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .collect();
+    }
+    s
+}
+
+/// 현재 활성 설정. 비활성이면 None.
+pub fn active_config() -> Option<RedactConfig> {
+    RedactConfig::from_settings(&load_settings())
+}
+
+#[tauri::command]
+pub fn get_pii_redact_settings() -> PiiRedactSettings {
+    load_settings()
+}
+
+#[tauri::command]
+pub fn set_pii_redact_settings(settings: PiiRedactSettings) -> Result<(), String> {
+    let kinds: Vec<&str> = settings
+        .kinds
+        .iter()
+        .map(|k| k.trim())
+        .filter(|k| PiiKind::from_key(k).is_some())
+        .collect();
+    set_registry_value(
+        REG_ENABLED.to_string(),
+        if settings.enabled { "true" } else { "false" }.to_string(),
+    )?;
+    set_registry_value(REG_KINDS.to_string(), kinds.join(","))?;
+    set_registry_value(
+        REG_ROSTER.to_string(),
+        normalize_roster(&settings.roster).join("\n"),
+    )
+}
+
+// ─── 토큰 보관소 ───────────────────────────────────────────────────────────────
+
+/// 원문 ↔ 토큰 양방향 맵. 종류별 일련번호로 토큰을 발급한다.
+#[derive(Default)]
+pub struct TokenVault {
+    by_value: HashMap<(PiiKind, String), String>,
+    by_token: HashMap<String, String>,
+    counters: HashMap<PiiKind, u32>,
+}
+
+impl TokenVault {
+    pub fn token_for(&mut self, kind: PiiKind, value: &str) -> String {
+        if let Some(t) = self.by_value.get(&(kind, value.to_string())) {
+            return t.clone();
+        }
+        if self.by_value.len() >= VAULT_MAX_ENTRIES {
+            self.by_value.clear();
+            self.by_token.clear();
+        }
+        let n = self.counters.entry(kind).or_insert(0);
+        *n += 1;
+        let token = format!("[{}_{}]", kind.label(), n);
+        self.by_value.insert((kind, value.to_string()), token.clone());
+        self.by_token.insert(token.clone(), value.to_string());
+        token
+    }
+
+    pub fn lookup(&self, token: &str) -> Option<&str> {
+        self.by_token.get(token).map(|s| s.as_str())
+    }
+}
+
+fn vault() -> &'static Mutex<TokenVault> {
+    VAULT.get_or_init(|| Mutex::new(TokenVault::default()))
+}
+
+/// 진행 중인 브리핑 실행 수(디버그 실행이 자동 실행과 겹칠 수 있다).
+static ACTIVE_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// 브리핑 실행 1회의 토큰 범위. 겹친 실행이 없을 때만 시작·종료(drop) 시 보관소를 비운다.
+pub struct RunScope(());
+
+pub fn begin_run() -> RunScope {
+    let mut v = vault().lock().unwrap_or_else(|e| e.into_inner());
+    if ACTIVE_RUNS.fetch_add(1, Ordering::SeqCst) == 0 {
+        *v = TokenVault::default();
+    }
+    RunScope(())
+}
+
+impl Drop for RunScope {
+    fn drop(&mut self) {
+        let mut v = vault().lock().unwrap_or_else(|e| e.into_inner());
+        if ACTIVE_RUNS.fetch_sub(1, Ordering::SeqCst) == 1 {
+            *v = TokenVault::default();
+        }
+    }
+}
+
+// ─── 탐지 ─────────────────────────────────────────────────────────────────────
+
+fn email_regex() -> &'static regex::Regex {
+    EMAIL_REGEX.get_or_init(|| {
+        regex::Regex::new(r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9\-]+(?:\.[A-Za-z0-9\-]+)*\.[A-Za-z]{2,}")
+            .unwrap()
+    })
+}
+
+fn rrn_regex() -> &'static regex::Regex {
+    // YYMMDD-GNNNNNN (G: 1~8, 외국인등록번호 5~8 포함). 하이픈 생략·공백 허용.
+    RRN_REGEX.get_or_init(|| {
+        regex::Regex::new(r"\d{2}(?:0[1-9]|1[0-2])(?:0[1-9]|[12]\d|3[01])\s?-?\s?[1-8]\d{6}")
+            .unwrap()
+    })
+}
+
+fn phone_regex() -> &'static regex::Regex {
+    // 휴대전화(010 등), 서울(02), 지역번호(031~064), 인터넷전화(070).
+    PHONE_REGEX.get_or_init(|| {
+        regex::Regex::new(r"0(?:1[016789]|2|[3-6][1-5]|70)(?:\)\s?|[-.\s]?)\d{3,4}[-.\s]?\d{4}")
+            .unwrap()
+    })
+}
+
+fn account_regex() -> &'static regex::Regex {
+    // 은행별로 자릿수가 달라 하이픈 구분 숫자 그룹 3~4개 + 전체 자릿수 하한으로 판정.
+    ACCOUNT_REGEX.get_or_init(|| {
+        regex::Regex::new(r"\d{2,6}-\d{2,6}-\d{2,7}(?:-\d{1,3})?|\d{11,14}").unwrap()
+    })
+}
+
+fn token_regex() -> &'static regex::Regex {
+    TOKEN_REGEX.get_or_init(|| {
+        regex::Regex::new(r"\[(?:EMAIL|RRN|PHONE|ACCOUNT|NAME)_\d+\]").unwrap()
+    })
+}
+
+/// 매치 앞뒤가 숫자로 이어지면 더 긴 숫자열의 일부이므로 제외한다.
+fn digit_bounded(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.map(|c| c.is_ascii_digit()).unwrap_or(false)
+        && !after.map(|c| c.is_ascii_digit()).unwrap_or(false)
+}
+
+/// 이미 발급된 토큰 내부는 다시 치환하지 않는다(`[PHONE_12]` 의 숫자 등).
+fn overlaps_token(tokens: &[(usize, usize)], start: usize, end: usize) -> bool {
+    tokens.iter().any(|&(s, e)| start < e && s < end)
+}
+
+fn find_spans(kind: PiiKind, text: &str, roster: &[String]) -> Vec<(usize, usize)> {
+    let tokens: Vec<(usize, usize)> = token_regex()
+        .find_iter(text)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    match kind {
+        PiiKind::Name => {
+            for name in roster {
+                for (start, _) in text.match_indices(name.as_str()) {
+                    let end = start + name.len();
+                    if !overlaps_token(&tokens, start, end)
+                        && !spans.iter().any(|&(s, e)| start < e && s < end)
+                    {
+                        spans.push((start, end));
+                    }
+                }
+            }
+            spans.sort();
+        }
+        _ => {
+            let re = match kind {
+                PiiKind::Email => email_regex(),
+                PiiKind::Rrn => rrn_regex(),
+                PiiKind::Phone => phone_regex(),
+                _ => account_regex(),
+            };
+            for m in re.find_iter(text) {
+                let (start, end) = (m.start(), m.end());
+                if overlaps_token(&tokens, start, end) {
+                    continue;
+                }
+                if kind != PiiKind::Email && !digit_bounded(text, start, end) {
+                    continue;
+                }
+                if kind == PiiKind::Account
+                    && m.as_str().chars().filter(|c| c.is_ascii_digit()).count()
+                        < ACCOUNT_MIN_DIGITS
+                {
+                    continue;
+                }
+                spans.push((start, end));
+            }
+        }
+    }
+    spans
+}
+
+// ─── 가림/복원 ─────────────────────────────────────────────────────────────────
+
+/// 주어진 보관소를 사용해 텍스트를 가린다(테스트용으로 보관소를 주입할 수 있게 분리).
+pub fn redact_with(vault: &mut TokenVault, cfg: &RedactConfig, text: &str) -> String {
+    let mut out = text.to_string();
+    for kind in &cfg.kinds {
+        let spans = find_spans(*kind, &out, &cfg.roster);
+        if spans.is_empty() {
+            continue;
+        }
+        let mut next = String::with_capacity(out.len());
+        let mut cursor = 0;
+        for (start, end) in spans {
+            next.push_str(&out[cursor..start]);
+            next.push_str(&vault.token_for(*kind, &out[start..end]));
+            cursor = end;
+        }
+        next.push_str(&out[cursor..]);
+        out = next;
+    }
+    out
+}
+
+/// 토큰을 원문으로 되돌린다. 보관소에 없는 토큰은 그대로 둔다.
+pub fn restore_with(vault: &TokenVault, text: &str) -> String {
+    token_regex()
+        .replace_all(text, |caps: &regex::Captures| {
+            let t = &caps[0];
+            vault.lookup(t).unwrap_or(t).to_string()
+        })
+        .into_owned()
+}
+
+pub fn redact_text(cfg: &RedactConfig, text: &str) -> String {
+    match vault().lock() {
+        Ok(mut v) => redact_with(&mut v, cfg, text),
+        Err(_) => text.to_string(),
+    }
+}
+
+pub fn restore(text: &str) -> String {
+    match vault().lock() {
+        Ok(v) => restore_with(&v, text),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// MCP `tools/call` 결과(`{"content":[{"type":"text","text":...}, ...], "structuredContent": {...}}`)의
+/// 텍스트 블록과 구조화 결과의 모든 문자열 값을 가린다. 이미지 블록 등 텍스트가 아닌 항목은 건드리지 않는다.
+pub fn redact_tool_result(cfg: &RedactConfig, result: &mut Value) {
+    if let Ok(mut v) = vault().lock() {
+        redact_tool_result_with(&mut v, cfg, result);
+    }
+}
+
+pub fn redact_tool_result_with(vault: &mut TokenVault, cfg: &RedactConfig, result: &mut Value) {
+    if let Some(blocks) = result.get_mut("content").and_then(|c| c.as_array_mut()) {
+        for block in blocks {
+            if block.get("type").and_then(|t| t.as_str()) != Some("text") {
+                continue;
+            }
+            if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                let redacted = redact_with(vault, cfg, text);
+                block["text"] = Value::String(redacted);
+            }
+        }
+    }
+    if let Some(structured) = result.get_mut("structuredContent") {
+        map_strings(structured, &mut |s| redact_with(vault, cfg, s));
+    }
+}
+
+/// MCP `tools/call` 인자 안의 토큰을 원문으로 되돌린다. 모델은 가려진 이름·파일명을
+/// 그대로 인자로 돌려보내므로, 되돌리지 않으면 후속 조회가 아무것도 찾지 못한다.
+pub fn restore_tool_args(args: &mut Value) {
+    if let Ok(v) = vault().lock() {
+        restore_tool_args_with(&v, args);
+    }
+}
+
+pub fn restore_tool_args_with(vault: &TokenVault, args: &mut Value) {
+    map_strings(args, &mut |s| restore_with(vault, s));
+}
+
+/// JSON 값 안의 문자열을 재귀적으로 바꾼다(키는 그대로).
+fn map_strings(value: &mut Value, f: &mut dyn FnMut(&str) -> String) {
+    match value {
+        Value::String(s) => *s = f(s),
+        Value::Array(items) => items.iter_mut().for_each(|v| map_strings(v, f)),
+        Value::Object(map) => map.values_mut().for_each(|v| map_strings(v, f)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(roster: &[&str]) -> RedactConfig {
+        RedactConfig {
+            kinds: PiiKind::ALL.to_vec(),
+            roster: normalize_roster(&roster.iter().map(|s| s.to_string()).collect::<Vec<_>>()),
+        }
+    }
+
+    #[test]
+    fn redacts_phone_numbers_in_common_formats() {
+        let mut v = TokenVault::default();
+        let c = cfg(&[]);
+        let out = redact_with(&mut v, &c, "연락처 010-1234-5678, 교무실 02)123-4567, 031.987.6543");
+        assert_eq!(out, "연락처 [PHONE_1], 교무실 [PHONE_2], [PHONE_3]");
+    }
+
+    #[test]
+    fn redacts_rrn_before_phone_and_account() {
+        let mut v = TokenVault::default();
+        let out = redact_with(&mut v, &cfg(&[]), "주민번호:900101-1234567 확인");
+        assert_eq!(out, "주민번호:[RRN_1] 확인");
+    }
+
+    #[test]
+    fn redacts_email_and_account() {
+        let mut v = TokenVault::default();
+        let out = redact_with(
+            &mut v,
+            &cfg(&[]),
+            "회신: teacher.kim@school.go.kr / 국민 123456-01-123456 입금",
+        );
+        assert_eq!(out, "회신: [EMAIL_1] / 국민 [ACCOUNT_1] 입금");
+    }
+
+    #[test]
+    fn leaves_dates_times_and_short_numbers_alone() {
+        let mut v = TokenVault::default();
+        let text = "2026-03-02 09:00 3학년 2반 제출, 문의 내선 1234";
+        assert_eq!(redact_with(&mut v, &cfg(&[]), text), text);
+    }
+
+    #[test]
+    fn masks_roster_names_longest_first() {
+        let mut v = TokenVault::default();
+        let out = redact_with(&mut v, &cfg(&["김민", "김민준"]), "김민준 학생과 김민 학생 상담");
+        assert_eq!(out, "[NAME_1] 학생과 [NAME_2] 학생 상담");
+    }
+
+    #[test]
+    fn same_value_gets_same_token_and_restores() {
+        let mut v = TokenVault::default();
+        let c = cfg(&["홍길동"]);
+        let a = redact_with(&mut v, &c, "홍길동 학부모 010-1111-2222");
+        let b = redact_with(&mut v, &c, "재안내: 홍길동 010-1111-2222");
+        assert_eq!(a, "[NAME_1] 학부모 [PHONE_1]");
+        assert_eq!(b, "재안내: [NAME_1] [PHONE_1]");
+        assert_eq!(restore_with(&v, &b), "재안내: 홍길동 010-1111-2222");
+    }
+
+    #[test]
+    fn restore_keeps_unknown_tokens() {
+        let v = TokenVault::default();
+        assert_eq!(restore_with(&v, "[PHONE_9] 연락"), "[PHONE_9] 연락");
+    }
+
+    #[test]
+    fn redact_is_idempotent_on_tokens() {
+        let mut v = TokenVault::default();
+        let c = cfg(&[]);
+        let once = redact_with(&mut v, &c, "010-1234-5678");
+        assert_eq!(redact_with(&mut v, &c, &once), once);
+    }
+
+    #[test]
+    fn disabled_or_empty_kinds_yield_no_config() {
+        let mut s = PiiRedactSettings::default();
+        assert!(RedactConfig::from_settings(&s).is_none());
+        s.enabled = true;
+        assert!(RedactConfig::from_settings(&s).is_some());
+        s.kinds.clear();
+        assert!(RedactConfig::from_settings(&s).is_none());
+    }
+
+    #[test]
+    fn tool_result_only_text_blocks_are_redacted() {
+        let c = cfg(&[]);
+        let mut result = serde_json::json!({
+            "content": [
+                { "type": "text", "text": "문의 010-2222-3333" },
+                { "type": "image", "data": "010-2222-3333", "mimeType": "image/png" }
+            ]
+        });
+        let mut v = TokenVault::default();
+        redact_tool_result_with(&mut v, &c, &mut result);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("문의 [PHONE_"));
+        assert_eq!(result["content"][1]["data"], "010-2222-3333");
+        assert_eq!(restore_with(&v, text), "문의 010-2222-3333");
+    }
+
+    #[test]
+    fn structured_content_strings_are_redacted() {
+        let c = cfg(&["홍길동"]);
+        let mut result = serde_json::json!({
+            "content": [{ "type": "text", "text": "홍길동" }],
+            "structuredContent": {
+                "messages": [{ "id": 7, "sender": "홍길동", "preview": "a@b.com 회신" }]
+            }
+        });
+        redact_tool_result_with(&mut TokenVault::default(), &c, &mut result);
+        let msg = &result["structuredContent"]["messages"][0];
+        assert_eq!(msg["id"], 7);
+        assert_eq!(msg["sender"], result["content"][0]["text"]);
+        assert!(msg["preview"].as_str().unwrap().starts_with("[EMAIL_"));
+    }
+
+    #[test]
+    fn tool_args_are_restored_recursively() {
+        let mut v = TokenVault::default();
+        let c = cfg(&["홍길동"]);
+        let name = redact_with(&mut v, &c, "홍길동");
+        let mut args = serde_json::json!({ "sender": name, "files": [format!("{} 가정통신문.hwp", name)], "limit": 5 });
+        restore_tool_args_with(&v, &mut args);
+        assert_eq!(args, serde_json::json!({ "sender": "홍길동", "files": ["홍길동 가정통신문.hwp"], "limit": 5 }));
+    }
+
+    #[test]
+    fn vault_is_capped_without_reusing_tokens() {
+        let mut v = TokenVault::default();
+        let first = v.token_for(PiiKind::Phone, "010-0000-0000");
+        for i in 1..=VAULT_MAX_ENTRIES {
+            v.token_for(PiiKind::Phone, &format!("010-1000-{:04}", i));
+        }
+        // 상한을 넘기면 이전 항목은 잊히지만 새 토큰 번호는 이어진다.
+        assert_eq!(v.lookup(&first), None);
+        assert_eq!(
+            v.token_for(PiiKind::Phone, "010-0000-0000"),
+            format!("[PHONE_{}]", VAULT_MAX_ENTRIES + 2)
+        );
+    }
+}
@@ -0,0 +1,724 @@
+//! 할 일 알림.
+//!
+//! 일정 시작 전 정해 둔 시간(기본 하루 전·10분 전)에 알림을 보낸다. 알림 대기열은
+//! `hypercool.db` 의 `tbl_reminders` 에 두어 앱을 껐다 켜도 이어지고, 꺼져 있는 동안 놓친
+//! 알림은 다시 켰을 때 일정마다 가장 가까운 것 하나만 "늦은 알림"으로 보낸다.
+//! 수업 시간(`utils::is_class_time_at`, 그날 종 시간표 기준)과 사용자가 정한 방해 금지 시간에는 보내지 않고
+//! 미뤘다가 끝나면 보낸다.
+//!
+//! 시각은 모두 한국 시간 기준 naive 값(`YYYY-MM-DDTHH:MM:SS`)으로 저장·비교한다.
+//! 시계(`Clock`)와 발송(`ReminderSink`)을 트레이트로 받아 테스트에서 시각을 고정하고
+//! 보낸 알림을 확인할 수 있다.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::system::{get_registry_value, set_registry_value};
+use crate::db::{open_app_db, ScheduleItem};
+
+const REG_ENABLED: &str = "ReminderEnabled";
+const REG_LEADS: &str = "ReminderLeadMinutes";
+const REG_QUIET_CLASS: &str = "ReminderQuietDuringClass";
+const REG_QUIET_START: &str = "ReminderQuietStart";
+const REG_QUIET_END: &str = "ReminderQuietEnd";
+
+/// 기본 알림 시점(분): 하루 전, 10분 전.
+const DEFAULT_LEADS: [i64; 2] = [1440, 10];
+/// 종일 일정의 기준 시각(이 시각을 시작으로 보고 알림 시점을 계산한다).
+const ALL_DAY_ANCHOR: (u32, u32) = (8, 0);
+/// 시간 지정 일정이 시작하고 이만큼 지나면 놓친 알림을 보내지 않는다(분).
+const STALE_AFTER_START_MINUTES: i64 = 60;
+/// 알림 시점보다 이만큼 늦게 보내면 "늦은 알림"으로 표시한다(분).
+const LATE_AFTER_MINUTES: i64 = 2;
+/// 보낸·끝난 알림 기록 보관 기간(일).
+const KEEP_DONE_DAYS: i64 = 30;
+/// 스케줄러 확인 주기(초).
+const TICK_SECS: u64 = 30;
+
+const TS_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+// ─── 시계·발송 ────────────────────────────────────────────────────────────────
+
+/// 현재 시각(한국 시간 naive).
+pub trait Clock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        let kst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        chrono::Utc::now().with_timezone(&kst).naive_local()
+    }
+}
+
+/// 알림을 내보내는 곳.
+pub trait ReminderSink {
+    fn fire(&self, event: &ReminderEvent);
+}
+
+/// `reminder-due` 이벤트로 프런트엔드에 알린다.
+pub struct EventSink(pub AppHandle);
+
+impl ReminderSink for EventSink {
+    fn fire(&self, event: &ReminderEvent) {
+        let _ = self.0.emit("reminder-due", event);
+    }
+}
+
+/// 보낸 알림 한 건(이벤트 payload).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderEvent {
+    pub reminder_id: i64,
+    pub schedule_id: String,
+    pub title: String,
+    /// 일정의 원래 startDate 값
+    pub start: Option<String>,
+    pub is_all_day: bool,
+    pub lead_minutes: i64,
+    /// 앱이 꺼져 있었거나 방해 금지로 미뤄져 알림 시점보다 늦게 보냈는지
+    pub late: bool,
+}
+
+// ─── 설정 ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    /// 일정별 설정이 없을 때 쓰는 알림 시점(시작 몇 분 전).
+    pub default_leads: Vec<i64>,
+    /// 수업 시간에는 알림을 미룬다.
+    pub quiet_during_class: bool,
+    /// 방해 금지 시작·끝("HH:MM"). 둘 다 있어야 적용한다.
+    pub quiet_start: Option<String>,
+    pub quiet_end: Option<String>,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        ReminderSettings {
+            enabled: false,
+            default_leads: DEFAULT_LEADS.to_vec(),
+            quiet_during_class: true,
+            quiet_start: None,
+            quiet_end: None,
+        }
+    }
+}
+
+impl ReminderSettings {
+    /// 지금이 방해 금지 시간인지(수업 시간 포함).
+    fn is_quiet(&self, now: NaiveDateTime) -> bool {
+        if self.quiet_during_class && crate::utils::is_class_time_at(now) {
+            return true;
+        }
+        match (self.quiet_start.as_deref().and_then(parse_hm), self.quiet_end.as_deref().and_then(parse_hm)) {
+            (Some(start), Some(end)) => crate::utils::in_time_range(start, end, now.time()),
+            _ => false,
+        }
+    }
+}
+
+fn read_reg(key: &str) -> Option<String> {
+    get_registry_value(key.to_string())
+        .ok()
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_hm(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+/// 알림 시점 목록 정리: 음수 제거, 중복 제거, 큰 값(이른 알림)부터.
+fn normalize_leads(mut leads: Vec<i64>) -> Vec<i64> {
+    leads.retain(|l| *l >= 0);
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup();
+    leads
+}
+
+pub fn load_settings() -> ReminderSettings {
+    let defaults = ReminderSettings::default();
+    ReminderSettings {
+        enabled: read_reg(REG_ENABLED).is_some_and(|v| v == "true"),
+        default_leads: read_reg(REG_LEADS)
+            .and_then(|v| serde_json::from_str::<Vec<i64>>(&v).ok())
+            .map(normalize_leads)
+            .unwrap_or(defaults.default_leads),
+        quiet_during_class: read_reg(REG_QUIET_CLASS).map(|v| v == "true").unwrap_or(defaults.quiet_during_class),
+        quiet_start: read_reg(REG_QUIET_START).filter(|v| parse_hm(v).is_some()),
+        quiet_end: read_reg(REG_QUIET_END).filter(|v| parse_hm(v).is_some()),
+    }
+}
+
+#[tauri::command]
+pub fn get_reminder_settings() -> ReminderSettings {
+    load_settings()
+}
+
+#[tauri::command]
+pub fn set_reminder_settings(settings: ReminderSettings) -> Result<(), String> {
+    for t in [&settings.quiet_start, &settings.quiet_end].into_iter().flatten() {
+        if !t.trim().is_empty() && parse_hm(t).is_none() {
+            return Err(format!("방해 금지 시각 형식이 올바르지 않습니다(HH:MM): {}", t));
+        }
+    }
+    let flag = |v: bool| if v { "true" } else { "false" }.to_string();
+    let leads = serde_json::to_string(&normalize_leads(settings.default_leads)).map_err(|e| e.to_string())?;
+    set_registry_value(REG_ENABLED.to_string(), flag(settings.enabled))?;
+    set_registry_value(REG_LEADS.to_string(), leads)?;
+    set_registry_value(REG_QUIET_CLASS.to_string(), flag(settings.quiet_during_class))?;
+    set_registry_value(REG_QUIET_START.to_string(), settings.quiet_start.unwrap_or_default().trim().to_string())?;
+    set_registry_value(REG_QUIET_END.to_string(), settings.quiet_end.unwrap_or_default().trim().to_string())
+}
+
+// ─── 대기열 ───────────────────────────────────────────────────────────────────
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_reminders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            schedule_id TEXT NOT NULL,
+            lead_minutes INTEGER NOT NULL,
+            fire_at TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            snoozed_until TEXT,
+            fired_at TEXT,
+            UNIQUE(schedule_id, lead_minutes)
+        );
+        CREATE INDEX IF NOT EXISTS idx_reminders_pending ON tbl_reminders(status, fire_at);
+        CREATE TABLE IF NOT EXISTS tbl_schedule_reminders (
+            schedule_id TEXT PRIMARY KEY,
+            lead_minutes TEXT NOT NULL
+        );",
+    )
+    .map_err(|e| format!("알림 테이블 생성 실패: {}", e))
+}
+
+fn ts(t: NaiveDateTime) -> String {
+    t.format(TS_FORMAT).to_string()
+}
+
+fn parse_ts(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, TS_FORMAT).ok()
+}
+
+/// 일정 시작 시각(한국 시간). 종일 일정은 그날 `ALL_DAY_ANCHOR`.
+fn start_of(s: &ScheduleItem) -> Option<NaiveDateTime> {
+    let raw = s.start_date.as_deref()?.trim();
+    let anchor = || NaiveTime::from_hms_opt(ALL_DAY_ANCHOR.0, ALL_DAY_ANCHOR.1, 0).unwrap();
+    let date_only = |d: &str| NaiveDate::parse_from_str(d.get(..10)?, "%Y-%m-%d").ok();
+    if s.is_all_day {
+        return date_only(raw).map(|d| d.and_time(anchor()));
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+        let kst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+        return Some(dt.with_timezone(&kst).naive_local());
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(raw, f).ok())
+        .or_else(|| date_only(raw).map(|d| d.and_time(anchor())))
+}
+
+/// 이 시각이 지나면 놓친 알림을 더 보내지 않는다.
+fn stale_after(s: &ScheduleItem, start: NaiveDateTime) -> NaiveDateTime {
+    if s.is_all_day {
+        start.date().and_hms_opt(23, 59, 59).unwrap()
+    } else {
+        start + chrono::Duration::minutes(STALE_AFTER_START_MINUTES)
+    }
+}
+
+fn load_overrides(conn: &Connection) -> Result<HashMap<String, Vec<i64>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT schedule_id, lead_minutes FROM tbl_schedule_reminders")
+        .map_err(|e| format!("일정별 알림 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))
+        .map_err(|e| format!("일정별 알림 조회 실패: {}", e))?;
+    Ok(rows
+        .flatten()
+        .map(|(id, leads)| (id, normalize_leads(serde_json::from_str(&leads).unwrap_or_default())))
+        .collect())
+}
+
+/// 일정 목록에 맞춰 대기열을 맞춘다.
+/// - 앞으로 올 일정마다 알림 시점별로 한 줄씩 만든다(이미 있으면 유지).
+/// - 일정이 옮겨져 알림 시점이 바뀌면 다시 보낼 수 있게 대기 상태로 되돌린다.
+/// - 지워지거나 완료된 일정, 빠진 알림 시점의 대기 중인 알림은 지운다.
+pub fn sync_queue(conn: &Connection, now: NaiveDateTime, default_leads: &[i64]) -> Result<(), String> {
+    ensure_tables(conn)?;
+    let overrides = load_overrides(conn)?;
+    let max_lead = overrides
+        .values()
+        .flatten()
+        .chain(default_leads)
+        .copied()
+        .max()
+        .unwrap_or(0);
+    let from = (now - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    let to = (now + chrono::Duration::minutes(max_lead) + chrono::Duration::days(1))
+        .format("%Y-%m-%dT23:59:59")
+        .to_string();
+    let items = crate::db::get_schedules_impl(conn, from, to, false)?;
+
+    let mut wanted: HashSet<(String, i64)> = HashSet::new();
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    for s in items.iter().filter(|s| !s.is_completed) {
+        let Some(start) = start_of(s) else {
+            continue;
+        };
+        if stale_after(s, start) < now {
+            continue;
+        }
+        let leads = overrides.get(&s.id).map(Vec::as_slice).unwrap_or(default_leads);
+        for &lead in leads {
+            let fire_at = start - chrono::Duration::minutes(lead);
+            // 이미 지난 시점의 알림은 새로 만들지 않는다(새로 추가·이동한 일정에 뒤늦은 알림이 가지 않도록).
+            // 앱이 꺼져 있는 동안 놓친 알림은 이전에 만들어 둔 줄이 남아 있어 그대로 보낸다.
+            let sql = if fire_at < now - chrono::Duration::minutes(LATE_AFTER_MINUTES) {
+                "UPDATE tbl_reminders SET
+                    status = CASE WHEN fire_at != ?3 THEN 'expired' ELSE status END,
+                    fire_at = ?3
+                 WHERE schedule_id = ?1 AND lead_minutes = ?2"
+            } else {
+                "INSERT INTO tbl_reminders (schedule_id, lead_minutes, fire_at, status)
+                 VALUES (?1, ?2, ?3, 'pending')
+                 ON CONFLICT(schedule_id, lead_minutes) DO UPDATE SET
+                    status = CASE WHEN fire_at != excluded.fire_at THEN 'pending' ELSE status END,
+                    snoozed_until = CASE WHEN fire_at != excluded.fire_at THEN NULL ELSE snoozed_until END,
+                    fire_at = excluded.fire_at"
+            };
+            tx.execute(sql, params![s.id, lead, ts(fire_at)])
+                .map_err(|e| format!("알림 대기열 갱신 실패: {}", e))?;
+            wanted.insert((s.id.clone(), lead));
+        }
+    }
+
+    let pending: Vec<(i64, String, i64)> = {
+        let mut stmt = tx
+            .prepare("SELECT id, schedule_id, lead_minutes FROM tbl_reminders WHERE status = 'pending'")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .map_err(|e| e.to_string())?;
+        rows.flatten().collect()
+    };
+    for (id, schedule_id, lead) in pending {
+        if !wanted.contains(&(schedule_id, lead)) {
+            tx.execute("DELETE FROM tbl_reminders WHERE id = ?1", [id])
+                .map_err(|e| format!("알림 대기열 정리 실패: {}", e))?;
+        }
+    }
+    tx.execute(
+        "DELETE FROM tbl_reminders WHERE status != 'pending' AND fire_at < ?1",
+        [ts(now - chrono::Duration::days(KEEP_DONE_DAYS))],
+    )
+    .map_err(|e| format!("알림 기록 정리 실패: {}", e))?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+struct DueRow {
+    id: i64,
+    lead: i64,
+    due_at: NaiveDateTime,
+    schedule: ScheduleItem,
+}
+
+/// 때가 된 알림을 보낸다. 방해 금지 중이면 아무것도 하지 않는다(다음 확인 때 보낸다).
+/// 한 일정에 여러 알림이 한꺼번에 밀려 있으면 가장 가까운 것 하나만 보내고 나머지는 지나간 것으로 둔다.
+/// 반환: 보낸 수.
+pub fn tick(
+    conn: &Connection,
+    clock: &dyn Clock,
+    sink: &dyn ReminderSink,
+    is_quiet: &dyn Fn(NaiveDateTime) -> bool,
+) -> Result<usize, String> {
+    ensure_tables(conn)?;
+    let now = clock.now();
+    if is_quiet(now) {
+        return Ok(0);
+    }
+    let due: Vec<(i64, String, i64, String)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, schedule_id, lead_minutes, COALESCE(snoozed_until, fire_at) AS due_at
+                 FROM tbl_reminders WHERE status = 'pending' AND COALESCE(snoozed_until, fire_at) <= ?1
+                 ORDER BY schedule_id, lead_minutes",
+            )
+            .map_err(|e| format!("알림 조회 실패: {}", e))?;
+        let rows = stmt
+            .query_map([ts(now)], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))
+            .map_err(|e| format!("알림 조회 실패: {}", e))?;
+        rows.flatten().collect()
+    };
+
+    let mut by_schedule: Vec<(String, Vec<DueRow>)> = Vec::new();
+    for (id, schedule_id, lead, due_at) in due {
+        let Some(schedule) = crate::db::get_schedule_by_id_impl(conn, &schedule_id)? else {
+            set_status(conn, id, "expired", None)?;
+            continue;
+        };
+        let row = DueRow { id, lead, due_at: parse_ts(&due_at).unwrap_or(now), schedule };
+        match by_schedule.iter_mut().find(|(sid, _)| *sid == schedule_id) {
+            Some((_, rows)) => rows.push(row),
+            None => by_schedule.push((schedule_id, vec![row])),
+        }
+    }
+
+    let mut fired = 0;
+    for (_, rows) in by_schedule {
+        // lead_minutes 오름차순이므로 첫 줄이 시작에 가장 가까운 알림.
+        let mut rows = rows.into_iter();
+        let Some(first) = rows.next() else {
+            continue;
+        };
+        for r in rows {
+            set_status(conn, r.id, "fired", Some(now))?;
+        }
+        let stale = start_of(&first.schedule).map(|s| stale_after(&first.schedule, s) < now).unwrap_or(true);
+        if stale || first.schedule.is_completed || first.schedule.is_deleted {
+            set_status(conn, first.id, "expired", None)?;
+            continue;
+        }
+        sink.fire(&ReminderEvent {
+            reminder_id: first.id,
+            schedule_id: first.schedule.id.clone(),
+            title: first.schedule.title.clone(),
+            start: first.schedule.start_date.clone(),
+            is_all_day: first.schedule.is_all_day,
+            lead_minutes: first.lead,
+            late: now - first.due_at > chrono::Duration::minutes(LATE_AFTER_MINUTES),
+        });
+        set_status(conn, first.id, "fired", Some(now))?;
+        fired += 1;
+    }
+    Ok(fired)
+}
+
+fn set_status(conn: &Connection, id: i64, status: &str, fired_at: Option<NaiveDateTime>) -> Result<(), String> {
+    conn.execute(
+        "UPDATE tbl_reminders SET status = ?2, fired_at = COALESCE(?3, fired_at) WHERE id = ?1",
+        params![id, status, fired_at.map(ts)],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("알림 상태 저장 실패: {}", e))
+}
+
+/// 알림을 `until` 까지 미룬다(이미 보낸 알림도 다시 대기 상태로).
+pub fn snooze(conn: &Connection, id: i64, until: NaiveDateTime) -> Result<(), String> {
+    ensure_tables(conn)?;
+    let n = conn
+        .execute(
+            "UPDATE tbl_reminders SET status = 'pending', snoozed_until = ?2 WHERE id = ?1",
+            params![id, ts(until)],
+        )
+        .map_err(|e| format!("알림 미루기 실패: {}", e))?;
+    if n == 0 {
+        return Err(format!("알림을 찾을 수 없습니다: {}", id));
+    }
+    Ok(())
+}
+
+/// 일정별 알림 시점을 정한다. `None` 이면 기본값을 따르고, 빈 목록이면 알림을 끈다.
+pub fn set_schedule_leads(conn: &Connection, schedule_id: &str, leads: Option<Vec<i64>>) -> Result<(), String> {
+    ensure_tables(conn)?;
+    let result = match leads {
+        Some(l) => conn.execute(
+            "INSERT OR REPLACE INTO tbl_schedule_reminders (schedule_id, lead_minutes) VALUES (?1, ?2)",
+            params![schedule_id, serde_json::to_string(&normalize_leads(l)).map_err(|e| e.to_string())?],
+        ),
+        None => conn.execute("DELETE FROM tbl_schedule_reminders WHERE schedule_id = ?1", [schedule_id]),
+    };
+    result.map(|_| ()).map_err(|e| format!("일정별 알림 저장 실패: {}", e))
+}
+
+/// 대기 중인 알림(목록 화면용).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingReminder {
+    pub id: i64,
+    pub schedule_id: String,
+    pub title: String,
+    pub lead_minutes: i64,
+    /// 보낼 시각(미룬 경우 미룬 시각, 한국 시간)
+    pub due_at: String,
+    pub snoozed: bool,
+}
+
+fn list_pending(conn: &Connection) -> Result<Vec<PendingReminder>, String> {
+    ensure_tables(conn)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT r.id, r.schedule_id, s.title, r.lead_minutes, COALESCE(r.snoozed_until, r.fire_at),
+                    r.snoozed_until IS NOT NULL
+             FROM tbl_reminders r JOIN tbl_schedules s ON s.id = r.schedule_id
+             WHERE r.status = 'pending' ORDER BY 5",
+        )
+        .map_err(|e| format!("알림 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map([], |r| {
+            Ok(PendingReminder {
+                id: r.get(0)?,
+                schedule_id: r.get(1)?,
+                title: r.get(2)?,
+                lead_minutes: r.get(3)?,
+                due_at: r.get(4)?,
+                snoozed: r.get(5)?,
+            })
+        })
+        .map_err(|e| format!("알림 조회 실패: {}", e))?;
+    Ok(rows.flatten().collect())
+}
+
+// ─── 실행 ─────────────────────────────────────────────────────────────────────
+
+/// 앱 시작 시 한 번 호출한다. 주기적으로 대기열을 맞추고 때가 된 알림을 보낸다.
+/// 꺼져 있는 동안 놓친 알림은 첫 확인 때 보낸다.
+pub fn start_scheduler(app: AppHandle) {
+    std::thread::spawn(move || {
+        let sink = EventSink(app.clone());
+        loop {
+            let settings = load_settings();
+            if settings.enabled {
+                let result = open_app_db(&app).and_then(|conn| {
+                    sync_queue(&conn, SystemClock.now(), &settings.default_leads)?;
+                    tick(&conn, &SystemClock, &sink, &|now| settings.is_quiet(now))
+                });
+                if let Err(e) = result {
+                    eprintln!("[reminders] 알림 확인 실패: {}", e);
+                }
+            }
+            std::thread::sleep(Duration::from_secs(TICK_SECS));
+        }
+    });
+}
+
+#[tauri::command]
+pub fn list_pending_reminders(app: AppHandle) -> Result<Vec<PendingReminder>, String> {
+    list_pending(&open_app_db(&app)?)
+}
+
+/// 알림을 `minutes` 분 뒤로 미룬다.
+#[tauri::command]
+pub fn snooze_reminder(app: AppHandle, id: i64, minutes: i64) -> Result<(), String> {
+    if minutes <= 0 {
+        return Err("미룰 시간은 1분 이상이어야 합니다.".to_string());
+    }
+    snooze(&open_app_db(&app)?, id, SystemClock.now() + chrono::Duration::minutes(minutes))
+}
+
+/// 알림을 더 보내지 않는다(일정 자체는 그대로).
+#[tauri::command]
+pub fn dismiss_reminder(app: AppHandle, id: i64) -> Result<(), String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    set_status(&conn, id, "dismissed", None)
+}
+
+/// 일정별 알림 시점(분). `None` 이면 기본값을 따른다.
+#[tauri::command]
+pub fn get_schedule_reminders(app: AppHandle, schedule_id: String) -> Result<Option<Vec<i64>>, String> {
+    let conn = open_app_db(&app)?;
+    ensure_tables(&conn)?;
+    Ok(load_overrides(&conn)?.remove(&schedule_id))
+}
+
+#[tauri::command]
+pub fn set_schedule_reminders(app: AppHandle, schedule_id: String, leads: Option<Vec<i64>>) -> Result<(), String> {
+    let conn = open_app_db(&app)?;
+    set_schedule_leads(&conn, &schedule_id, leads)?;
+    sync_queue(&conn, SystemClock.now(), &load_settings().default_leads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    struct MockClock(Cell<NaiveDateTime>);
+
+    impl MockClock {
+        fn at(s: &str) -> Self {
+            MockClock(Cell::new(t(s)))
+        }
+        fn set(&self, s: &str) {
+            self.0.set(t(s));
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0.get()
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<ReminderEvent>>);
+
+    impl ReminderSink for Recorder {
+        fn fire(&self, event: &ReminderEvent) {
+            self.0.borrow_mut().push(event.clone());
+        }
+    }
+
+    impl Recorder {
+        fn take(&self) -> Vec<(String, i64, bool)> {
+            self.0
+                .borrow_mut()
+                .drain(..)
+                .map(|e| (e.schedule_id, e.lead_minutes, e.late))
+                .collect()
+        }
+    }
+
+    fn t(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::ensure_schedule_table(&conn).unwrap();
+        conn
+    }
+
+    fn add(conn: &Connection, id: &str, start: &str, all_day: bool) {
+        let now = "2026-03-01T00:00:00.000Z".to_string();
+        crate::db::create_schedule_impl(
+            conn,
+            ScheduleItem {
+                id: id.to_string(),
+                schedule_type: "manual_todo".to_string(),
+                title: format!("일정 {}", id),
+                content: None,
+                start_date: Some(start.to_string()),
+                end_date: Some(start.to_string()),
+                is_all_day: all_day,
+                reference_id: None,
+                color: None,
+                is_completed: false,
+                created_at: now.clone(),
+                updated_at: now,
+                is_deleted: false,
+                priority: None,
+                item_kind: None,
+                visible_until: None,
+                has_attachment: None,
+            },
+        )
+        .unwrap();
+    }
+
+    fn run(conn: &Connection, clock: &MockClock, sink: &Recorder) -> usize {
+        sync_queue(conn, clock.now(), &DEFAULT_LEADS).unwrap();
+        tick(conn, clock, sink, &|_| false).unwrap()
+    }
+
+    #[test]
+    fn fires_each_lead_once_at_its_time() {
+        let db = conn();
+        add(&db, "a", "2026-03-05T14:00:00+09:00", false);
+        let clock = MockClock::at("2026-03-04 13:59");
+        let sink = Recorder::default();
+
+        assert_eq!(run(&db, &clock, &sink), 0);
+        clock.set("2026-03-04 14:00");
+        assert_eq!(run(&db, &clock, &sink), 1);
+        assert_eq!(sink.take(), vec![("a".to_string(), 1440, false)]);
+        // 같은 알림은 다시 보내지 않는다.
+        assert_eq!(run(&db, &clock, &sink), 0);
+
+        clock.set("2026-03-05 13:50");
+        run(&db, &clock, &sink);
+        assert_eq!(sink.take(), vec![("a".to_string(), 10, false)]);
+    }
+
+    #[test]
+    fn catches_up_missed_reminders_once_and_drops_stale() {
+        let db = conn();
+        add(&db, "a", "2026-03-05T14:00:00+09:00", false);
+        add(&db, "b", "2026-03-05T09:00:00+09:00", false);
+        add(&db, "c", "2026-03-05", true);
+        // 앱이 켜져 있을 때 대기열을 만들고, 꺼졌다가 다음 날 12:00 에 켜진 상황.
+        sync_queue(&db, t("2026-03-03 12:00"), &DEFAULT_LEADS).unwrap();
+        let clock = MockClock::at("2026-03-05 12:00");
+        let sink = Recorder::default();
+        tick(&db, &clock, &sink, &|_| false).unwrap();
+        let mut fired = sink.take();
+        fired.sort();
+        // a: 하루 전 알림만 밀려 있어 늦은 알림 한 번. b: 시작 후 3시간이라 보내지 않는다.
+        // c: 종일 일정은 그날이 끝날 때까지 보낸다(10분 전 알림 한 번).
+        assert_eq!(fired, vec![("a".to_string(), 1440, true), ("c".to_string(), 10, true)]);
+        assert_eq!(tick(&db, &clock, &sink, &|_| false).unwrap(), 0);
+    }
+
+    #[test]
+    fn quiet_hours_defer_until_they_end() {
+        let db = conn();
+        add(&db, "a", "2026-03-04T10:00:00+09:00", false);
+        let clock = MockClock::at("2026-03-04 09:50");
+        let sink = Recorder::default();
+        sync_queue(&db, clock.now(), &[10]).unwrap();
+        let class = |now: NaiveDateTime| now.time() < NaiveTime::from_hms_opt(9, 55, 0).unwrap();
+        assert_eq!(tick(&db, &clock, &sink, &class).unwrap(), 0);
+        clock.set("2026-03-04 09:55");
+        assert_eq!(tick(&db, &clock, &sink, &class).unwrap(), 1);
+        assert_eq!(sink.take(), vec![("a".to_string(), 10, true)]);
+    }
+
+    #[test]
+    fn snooze_and_reschedule_refire() {
+        let db = conn();
+        add(&db, "a", "2026-03-04T10:00:00+09:00", false);
+        let clock = MockClock::at("2026-03-04 09:50");
+        let sink = Recorder::default();
+        run(&db, &clock, &sink);
+        let fired = sink.0.borrow()[0].clone();
+        sink.take();
+
+        snooze(&db, fired.reminder_id, t("2026-03-04 09:55")).unwrap();
+        clock.set("2026-03-04 09:54");
+        assert_eq!(run(&db, &clock, &sink), 0);
+        clock.set("2026-03-04 09:55");
+        assert_eq!(run(&db, &clock, &sink), 1);
+        sink.take();
+
+        // 일정을 옮기면 새 시각에 다시 알린다.
+        db.execute("UPDATE tbl_schedules SET start_date = '2026-03-04T11:00:00+09:00' WHERE id = 'a'", [])
+            .unwrap();
+        clock.set("2026-03-04 10:50");
+        assert_eq!(run(&db, &clock, &sink), 1);
+        assert_eq!(sink.take(), vec![("a".to_string(), 10, false)]);
+    }
+
+    #[test]
+    fn per_item_leads_override_defaults_and_completion_cancels() {
+        let db = conn();
+        add(&db, "a", "2026-03-04T10:00:00+09:00", false);
+        add(&db, "b", "2026-03-04T10:00:00+09:00", false);
+        add(&db, "c", "2026-03-04T10:00:00+09:00", false);
+        set_schedule_leads(&db, "a", Some(vec![30])).unwrap();
+        set_schedule_leads(&db, "b", Some(Vec::new())).unwrap();
+        let clock = MockClock::at("2026-03-04 09:00");
+        let sink = Recorder::default();
+        sync_queue(&db, clock.now(), &DEFAULT_LEADS).unwrap();
+        let pending: Vec<_> = list_pending(&db).unwrap().into_iter().map(|p| (p.schedule_id, p.lead_minutes)).collect();
+        assert_eq!(pending, vec![("a".to_string(), 30), ("c".to_string(), 10)]);
+
+        db.execute("UPDATE tbl_schedules SET is_completed = 1 WHERE id = 'c'", []).unwrap();
+        clock.set("2026-03-04 09:55");
+        run(&db, &clock, &sink);
+        assert_eq!(sink.take(), vec![("a".to_string(), 30, true)]);
+    }
+}
@@ -39,6 +39,15 @@ pub struct CachedMessage {
     pub file_paths: Vec<String>,
 }
 
+/// 첨부 카탈로그 항목: 파일명과 그 파일을 전달한 메시지
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AttachmentLink {
+    pub file_name: String,
+    pub message_id: i64,
+    pub sender: String,
+    pub receive_date: Option<String>,
+}
+
 /// Get the path to the search database
 fn get_search_db_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
@@ -80,6 +89,7 @@ pub fn init_search_db(app: &AppHandle) -> Result<(), String> {
              DROP TRIGGER IF EXISTS messages_au;
              DROP TABLE IF EXISTS messages_fts;
              DROP TABLE IF EXISTS messages;
+             DROP TABLE IF EXISTS attachments;
              DROP TABLE IF EXISTS sync_metadata;
              PRAGMA user_version = {};",
             SEARCH_DB_VERSION
@@ -161,6 +171,8 @@ pub fn init_search_db(app: &AppHandle) -> Result<(), String> {
          CREATE INDEX IF NOT EXISTS idx_messages_sender ON messages(sender);"
     ).map_err(|e| format!("인덱스 생성 실패: {}", e))?;
 
+    ensure_attachment_catalog(&conn).map_err(|e| format!("첨부 카탈로그 생성 실패: {}", e))?;
+
     // Store the connection in the global pool (ignore error if already initialized)
     let _ = SEARCH_DB.set(Mutex::new(conn));
 
@@ -366,6 +378,8 @@ pub fn sync_from_udb(app: &AppHandle, udb_path: String) -> Result<SyncStats, Str
                         now_ts,
                         now_ts
                     ]).map_err(|e| format!("메시지 삽입 실패: {}", e))?;
+                    index_attachments(&search_conn, id, &sender, receive_date.as_deref(), &file_paths)
+                        .map_err(|e| format!("첨부 카탈로그 갱신 실패: {}", e))?;
                     
                     new_count += 1;
                     if id > max_id {
@@ -412,6 +426,121 @@ pub fn sync_from_udb(app: &AppHandle, udb_path: String) -> Result<SyncStats, Str
     })
 }
 
+// ─── 첨부 카탈로그 ─────────────────────────────────────────────────────────────
+
+/// 첨부 카탈로그 테이블을 만들고, 첨부가 있는데 카탈로그 행이 없는 메시지를 messages.file_paths 로 채운다.
+/// 이전 채우기가 중간에 실패했거나 카탈로그 없이 색인된 메시지도 다음 호출 때 반영된다.
+/// (messages 와 같은 파생 캐시라 스키마 버전을 올려 전체 재구축할 필요는 없다)
+pub fn ensure_attachment_catalog(conn: &Connection) -> SqliteResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS attachments (
+            message_id INTEGER NOT NULL,
+            file_name TEXT NOT NULL COLLATE NOCASE,
+            sender TEXT NOT NULL,
+            receive_date TEXT,
+            PRIMARY KEY (message_id, file_name)
+        );
+        CREATE INDEX IF NOT EXISTS idx_attachments_file_name ON attachments(file_name);
+        CREATE INDEX IF NOT EXISTS idx_attachments_receive_date ON attachments(receive_date);",
+    )?;
+
+    let rows: Vec<(i64, String, Option<String>, Vec<String>)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, sender, receive_date, file_paths FROM messages
+             WHERE file_paths IS NOT NULL AND file_paths NOT IN ('', '[]')
+               AND NOT EXISTS (SELECT 1 FROM attachments a WHERE a.message_id = messages.id)",
+        )?;
+        let mapped = stmt.query_map([], |row| {
+            let fp_json: String = row.get::<_, Option<String>>(3)?.unwrap_or_default();
+            let files: Vec<String> = serde_json::from_str(&fp_json).unwrap_or_default();
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, files))
+        })?;
+        mapped.filter_map(|r| r.ok()).collect()
+    };
+    for (id, sender, date, files) in rows {
+        index_attachments(conn, id, &sender, date.as_deref(), &files)?;
+    }
+    Ok(())
+}
+
+/// 메시지 하나의 첨부 목록을 카탈로그에 반영한다(재동기화 시 교체).
+pub fn index_attachments(
+    conn: &Connection,
+    message_id: i64,
+    sender: &str,
+    receive_date: Option<&str>,
+    files: &[String],
+) -> SqliteResult<()> {
+    conn.execute("DELETE FROM attachments WHERE message_id = ?1", [message_id])?;
+    let mut stmt = conn.prepare_cached(
+        "INSERT OR IGNORE INTO attachments (message_id, file_name, sender, receive_date)
+         VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    for file in files {
+        stmt.execute(params![message_id, file, sender, receive_date])?;
+    }
+    Ok(())
+}
+
+fn map_attachment_link(row: &rusqlite::Row) -> SqliteResult<AttachmentLink> {
+    Ok(AttachmentLink {
+        file_name: row.get(0)?,
+        message_id: row.get(1)?,
+        sender: row.get(2)?,
+        receive_date: row.get(3)?,
+    })
+}
+
+/// 파일명(대소문자 무시)으로 그 파일을 전달한 메시지들을 최신순으로 조회한다.
+pub fn attachment_links(conn: &Connection, file_name: &str) -> SqliteResult<Vec<AttachmentLink>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT file_name, message_id, sender, receive_date FROM attachments
+         WHERE file_name = ?1
+         ORDER BY receive_date DESC, message_id DESC",
+    )?;
+    let rows = stmt.query_map([file_name], map_attachment_link)?;
+    rows.collect()
+}
+
+/// 메시지 하나에 딸린 첨부 목록(파일명순). 일정의 첨부 배지가 출처 메시지로 조회한다.
+pub fn message_attachments(conn: &Connection, message_id: i64) -> SqliteResult<Vec<AttachmentLink>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT file_name, message_id, sender, receive_date FROM attachments
+         WHERE message_id = ?1
+         ORDER BY file_name",
+    )?;
+    let rows = stmt.query_map([message_id], map_attachment_link)?;
+    rows.collect()
+}
+
+/// 메시지 첨부 목록 조회(달력 일정의 첨부 배지용).
+#[tauri::command]
+pub fn get_message_attachments(app: AppHandle, message_id: i64) -> Result<Vec<AttachmentLink>, String> {
+    let conn = get_connection(&app)?;
+    message_attachments(&conn, message_id).map_err(|e| format!("첨부 목록 조회 실패: {}", e))
+}
+
+/// 카탈로그 목록(최신 수신순). query 는 파일명 부분 일치, ext 는 확장자(점 없이) 일치.
+pub fn list_attachment_catalog(
+    conn: &Connection,
+    query: Option<&str>,
+    ext: Option<&str>,
+    limit: i64,
+) -> SqliteResult<Vec<AttachmentLink>> {
+    let name_pattern = query.map(like_pattern).unwrap_or_else(|| "%".to_string());
+    let ext_pattern = ext
+        .map(|e| format!("%.{}", escape_like(e.trim_start_matches('.'))))
+        .unwrap_or_else(|| "%".to_string());
+    let mut stmt = conn.prepare(
+        "SELECT file_name, message_id, sender, receive_date FROM attachments
+         WHERE file_name LIKE ?1 ESCAPE '\\' AND file_name LIKE ?2 ESCAPE '\\'
+         ORDER BY receive_date DESC, message_id DESC
+         LIMIT ?3",
+    )?;
+    let rows = stmt.query_map(params![name_pattern, ext_pattern, limit], map_attachment_link)?;
+    rows.collect()
+}
+
 /// Search messages using FTS5/LIKE hybrid
 #[tauri::command]
 pub async fn search_messages_fts(
@@ -456,11 +585,13 @@ pub fn plan_search_query(raw: &str) -> Option<SearchPlan> {
 
 /// LIKE 패턴 이스케이프 (%, _, \ → ESCAPE '\' 기준)
 pub fn like_pattern(term: &str) -> String {
-    let escaped = term
-        .replace('\\', "\\\\")
+    format!("%{}%", escape_like(term))
+}
+
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
         .replace('%', "\\%")
-        .replace('_', "\\_");
-    format!("%{}%", escaped)
+        .replace('_', "\\_")
 }
 
 /// 매칭 위치 주변 텍스트를 잘라 스니펫 생성 (LIKE 경로용)
@@ -842,6 +973,84 @@ mod tests {
         assert_eq!(ids, vec![2]);
     }
 
+    fn insert_message_with_files(conn: &Connection, id: i64, sender: &str, date: &str, files: &[&str]) {
+        let fp = serde_json::to_string(files).unwrap();
+        conn.execute(
+            "INSERT INTO messages (id, sender, content, content_text, content_preview, receive_date, file_paths, created_at, updated_at)
+             VALUES (?1, ?2, '', '', '', ?3, ?4, 0, 0)",
+            params![id, sender, date, fp],
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_attachment_catalog_backfills_from_messages() {
+        let conn = setup_test_db();
+        insert_message_with_files(&conn, 1, "홍길동", "2024-03-01 09:00:00", &["계획.hwp", "명단.xlsx"]);
+        insert_message_with_files(&conn, 2, "김철수", "2024-03-02 10:00:00", &["계획.HWP"]);
+        insert_message_with_files(&conn, 3, "이영희", "2024-03-03 11:00:00", &[]);
+
+        ensure_attachment_catalog(&conn).unwrap();
+
+        // 대소문자 무시, 최신 메시지 우선
+        let links = attachment_links(&conn, "계획.hwp").unwrap();
+        assert_eq!(links.iter().map(|l| l.message_id).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(links[1].sender, "홍길동");
+
+        // 두 번째 호출은 이미 채워져 있으므로 중복 삽입하지 않는다
+        ensure_attachment_catalog(&conn).unwrap();
+        let all = list_attachment_catalog(&conn, None, None, 100).unwrap();
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn test_attachment_catalog_backfills_missing_messages_only() {
+        let conn = setup_test_db();
+        ensure_attachment_catalog(&conn).unwrap();
+        insert_message_with_files(&conn, 1, "홍길동", "2024-03-01 09:00:00", &["계획.hwp"]);
+        insert_message_with_files(&conn, 2, "김철수", "2024-03-02 10:00:00", &["명단.xlsx"]);
+        // 1번만 카탈로그에 있는 상태(이전 채우기가 중간에 멈춘 경우). 1번의 기존 행은 그대로 둔다.
+        index_attachments(&conn, 1, "홍길동", Some("2024-03-01 09:00:00"), &["계획.hwp".to_string()]).unwrap();
+        conn.execute("UPDATE attachments SET sender = '보존' WHERE message_id = 1", []).unwrap();
+
+        ensure_attachment_catalog(&conn).unwrap();
+
+        let all = list_attachment_catalog(&conn, None, None, 100).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(attachment_links(&conn, "명단.xlsx").unwrap()[0].message_id, 2);
+        assert_eq!(attachment_links(&conn, "계획.hwp").unwrap()[0].sender, "보존");
+    }
+
+    #[test]
+    fn test_index_attachments_replaces_and_filters() {
+        let conn = setup_test_db();
+        ensure_attachment_catalog(&conn).unwrap();
+        index_attachments(&conn, 10, "홍길동", Some("2024-05-01"), &["a_1.pdf".to_string(), "b.hwpx".to_string()]).unwrap();
+        index_attachments(&conn, 10, "홍길동", Some("2024-05-01"), &["a_1.pdf".to_string()]).unwrap();
+
+        let all = list_attachment_catalog(&conn, None, None, 100).unwrap();
+        assert_eq!(all.len(), 1);
+
+        index_attachments(&conn, 11, "김철수", Some("2024-05-02"), &["ab1.pdf".to_string(), "c.hwp".to_string()]).unwrap();
+        // '_' 는 와일드카드가 아니라 글자 그대로 매칭
+        let hits = list_attachment_catalog(&conn, Some("a_1"), None, 100).unwrap();
+        assert_eq!(hits.iter().map(|l| l.file_name.as_str()).collect::<Vec<_>>(), vec!["a_1.pdf"]);
+        let pdfs = list_attachment_catalog(&conn, None, Some("PDF"), 100).unwrap();
+        assert_eq!(pdfs.len(), 2);
+        assert_eq!(pdfs[0].message_id, 11);
+    }
+
+    #[test]
+    fn test_message_attachments_lists_files_of_one_message() {
+        let conn = setup_test_db();
+        ensure_attachment_catalog(&conn).unwrap();
+        index_attachments(&conn, 20, "홍길동", Some("2024-05-01"), &["양식.hwpx".to_string(), "계획.pdf".to_string()]).unwrap();
+        index_attachments(&conn, 21, "김철수", Some("2024-05-02"), &["기타.xlsx".to_string()]).unwrap();
+
+        let files = message_attachments(&conn, 20).unwrap();
+        assert_eq!(files.iter().map(|l| l.file_name.as_str()).collect::<Vec<_>>(), vec!["계획.pdf", "양식.hwpx"]);
+        assert!(message_attachments(&conn, 99).unwrap().is_empty());
+    }
+
     #[test]
     fn test_like_pattern_escapes_wildcards() {
         assert_eq!(like_pattern("100%"), "%100\\%%");
@@ -0,0 +1,91 @@
+//! 레지스트리에 저장하는 비밀 값(내부망 계정, 브리핑 LLM API 키) 암호화.
+//!
+//! Windows DPAPI 로 현재 사용자에게 묶어 암호화하고 `dpapi:<base64>` 로 저장한다.
+//! 다른 사용자·PC 에서는 풀 수 없다.
+
+use base64::Engine;
+
+/// 저장 값 앞에 붙여 암호화 방식을 표시한다.
+pub const SEALED_PREFIX: &str = "dpapi:";
+
+/// 값을 암호화해 저장용 문자열로 만든다.
+pub fn seal(data: &[u8]) -> Result<String, String> {
+    let sealed = protect(data)?;
+    Ok(format!("{}{}", SEALED_PREFIX, base64::engine::general_purpose::STANDARD.encode(sealed)))
+}
+
+/// `seal` 로 만든 문자열을 원래 값으로 되돌린다.
+pub fn open(value: &str) -> Result<Vec<u8>, String> {
+    let encoded = value
+        .trim()
+        .strip_prefix(SEALED_PREFIX)
+        .ok_or("저장된 값의 형식이 올바르지 않습니다. 다시 저장해 주세요.")?;
+    let sealed = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| format!("저장된 값이 손상되었습니다: {}", e))?;
+    unprotect(&sealed)
+}
+
+/// `seal` 로 만든 값인지(암호화 도입 전 평문 값과 구분).
+pub fn is_sealed(value: &str) -> bool {
+    value.trim().starts_with(SEALED_PREFIX)
+}
+
+/// DPAPI(CryptProtectData)로 현재 Windows 사용자만 풀 수 있게 암호화한다.
+#[cfg(target_os = "windows")]
+fn protect(data: &[u8]) -> Result<Vec<u8>, String> {
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{CryptProtectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB};
+
+    let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptProtectData(&input, windows::core::w!("Hypercool"), None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .map_err(|e| format!("암호화 실패: {}", e))?;
+        let sealed = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as *mut _));
+        Ok(sealed)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn unprotect(data: &[u8]) -> Result<Vec<u8>, String> {
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB};
+
+    let input = CRYPT_INTEGER_BLOB { cbData: data.len() as u32, pbData: data.as_ptr() as *mut u8 };
+    let mut output = CRYPT_INTEGER_BLOB::default();
+    unsafe {
+        CryptUnprotectData(&input, None, None, None, None, CRYPTPROTECT_UI_FORBIDDEN, &mut output)
+            .map_err(|e| format!("저장된 값을 풀 수 없습니다(다른 사용자·PC 에서 저장됨): {}", e))?;
+        let plain = std::slice::from_raw_parts(output.pbData, output.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(output.pbData as *mut _));
+        Ok(plain)
+    }
+}
+
+// Windows 밖(개발·테스트)에는 DPAPI 가 없어 그대로 둔다. 앱은 Windows 에서만 배포한다.
+#[cfg(not(target_os = "windows"))]
+fn protect(data: &[u8]) -> Result<Vec<u8>, String> {
+    Ok(data.to_vec())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn unprotect(data: &[u8]) -> Result<Vec<u8>, String> {
+    Ok(data.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_values_round_trip() {
+        let sealed = seal("sk-비밀".as_bytes()).unwrap();
+        assert!(is_sealed(&sealed));
+        assert_eq!(open(&sealed).unwrap(), "sk-비밀".as_bytes());
+        assert!(!is_sealed("sk-plain"));
+        assert!(open("sk-plain").is_err());
+        assert!(open("dpapi:%%%").is_err());
+    }
+}
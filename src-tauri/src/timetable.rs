@@ -0,0 +1,664 @@
+//! 시간표 공통 모델.
+//!
+//! 알림이(.Gwa, `timetable_parser`)는 교사별 요일 반복 시간표를, 압핀(amc42.dat, `appin_parser`)은
+//! 날짜별·반별 시간표를 준다. 두 모양을 `TimetableProvider` 하나로 감싸 교사·반·날짜·교시로
+//! 찾는 방법을 공유한다. 수업 상태 버튼(`get_class_status`), 학교 위젯, 다이제스트는 이 트레이트만 쓴다.
+//!
+//! 교시는 1부터 센다(1교시 = 1). 점심은 교시가 아니다.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Serialize;
+
+use crate::appin_parser::AppinTimetableData;
+use crate::commands::system::get_registry_value;
+use crate::timetable_parser::TimetableData;
+
+/// 주간 보기에서 보여 주는 최대 교시.
+pub const WEEK_PERIODS: u32 = 8;
+
+/// 요일 반복 시간표에서 평소 수업을 읽을 주의 월요일(2026학년도 첫 주).
+/// 요일 반복 시간표는 날짜와 무관하게 같은 수업을 돌려주므로 어느 주든 되지만,
+/// 고정된 주를 써서 결과가 실행 날짜에 따라 달라지지 않게 한다.
+const REPEATING_WEEK_MONDAY: NaiveDate = NaiveDate::from_ymd_opt(2026, 3, 2).expect("유효한 날짜");
+
+/// `weekly_base` 의 본체. `days` 는 날짜별 자료가 있는 날(요일 반복 출처면 빈 목록),
+/// `lessons_of` 는 그날 교사·반의 수업.
+fn usual_lessons(days: Vec<NaiveDate>, lessons_of: impl Fn(NaiveDate) -> Vec<Lesson>) -> HashMap<(Weekday, u32), Lesson> {
+    let mut base = HashMap::new();
+    if days.is_empty() {
+        // 요일 반복 시간표: 아무 주의 같은 요일이 곧 평소 수업.
+        for offset in 0..5 {
+            let date = REPEATING_WEEK_MONDAY + chrono::Duration::days(offset);
+            for l in lessons_of(date) {
+                base.insert((date.weekday(), l.period), l);
+            }
+        }
+        return base;
+    }
+
+    let by_day: Vec<(NaiveDate, Vec<Lesson>)> = days
+        .into_iter()
+        .map(|d| (d, lessons_of(d)))
+        .filter(|(_, lessons)| !lessons.is_empty())
+        .collect();
+    let mut present: HashMap<Weekday, usize> = HashMap::new();
+    let mut counts: HashMap<(Weekday, u32), Vec<(Lesson, usize)>> = HashMap::new();
+    for (date, lessons) in &by_day {
+        *present.entry(date.weekday()).or_insert(0) += 1;
+        for l in lessons {
+            let seen = counts.entry((date.weekday(), l.period)).or_default();
+            match seen
+                .iter_mut()
+                .find(|(s, _)| s.subject == l.subject && s.class_name == l.class_name && s.teacher == l.teacher)
+            {
+                Some((_, n)) => *n += 1,
+                None => seen.push((l.clone(), 1)),
+            }
+        }
+    }
+    for (key, seen) in counts {
+        let threshold = present.get(&key.0).map(|n| n.div_ceil(2)).unwrap_or(0).max(2);
+        // 같은 횟수면 먼저 나온(이른 날짜) 수업을 고른다.
+        let best = seen.into_iter().rev().max_by_key(|(_, n)| *n);
+        if let Some((lesson, n)) = best {
+            if n >= threshold {
+                base.insert(key, lesson);
+            }
+        }
+    }
+    base
+}
+
+/// 시간표 출처.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimetableSource {
+    /// 알림이(컴시간) .Gwa
+    Comcigan,
+    /// 압핀 amc42.dat
+    Appin,
+}
+
+impl TimetableSource {
+    /// 학교 위젯 설정 문자열("comcigan" | "appin"). 모르는 값은 알림이로 본다.
+    pub fn parse(s: Option<&str>) -> Self {
+        match s.map(str::trim) {
+            Some("appin") => TimetableSource::Appin,
+            _ => TimetableSource::Comcigan,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TimetableSource::Comcigan => "comcigan",
+            TimetableSource::Appin => "appin",
+        }
+    }
+
+    /// 학교 위젯에 저장된 출처(레지스트리 SchoolTimetableSource).
+    pub fn from_registry() -> Self {
+        Self::parse(get_registry_value("SchoolTimetableSource".to_string()).ok().flatten().as_deref())
+    }
+
+    /// 출처 파일을 읽어 공급자를 만든다. 파일·수정 시각·학년도 첫날이 지난번과 같으면
+    /// 다시 파싱하지 않고 캐시한 공급자를 돌려준다.
+    pub fn load(self) -> Result<Arc<dyn TimetableProvider>, String> {
+        let key = match self {
+            TimetableSource::Comcigan => ProviderKey::new(
+                crate::timetable_parser::get_latest_gwa_file().ok_or("Gwa file not found")?,
+                None,
+            ),
+            TimetableSource::Appin => ProviderKey::new(
+                crate::appin_parser::find_appin_file().ok_or("amc42.dat file not found")?,
+                crate::appin_parser::configured_start(),
+            ),
+        };
+        cached_provider(self, key, |key| {
+            Ok(match self {
+                TimetableSource::Comcigan => Arc::new(crate::timetable_parser::parse_timetable_file(&key.path)?),
+                TimetableSource::Appin => Arc::new(crate::appin_parser::parse_appin_file(&key.path, key.start)?),
+            })
+        })
+    }
+}
+
+/// 캐시한 공급자가 어느 파일 상태에서 만들어졌는지.
+#[derive(Debug, Clone, PartialEq)]
+struct ProviderKey {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// 압핀 학년도 첫날 설정(날짜 계산이 달라진다). 알림이는 None.
+    start: Option<NaiveDate>,
+}
+
+impl ProviderKey {
+    fn new(path: PathBuf, start: Option<NaiveDate>) -> Self {
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        ProviderKey { path, modified, start }
+    }
+}
+
+type ProviderCache = Mutex<HashMap<TimetableSource, (ProviderKey, Arc<dyn TimetableProvider>)>>;
+
+/// 출처별로 마지막에 읽은 공급자.
+static PROVIDERS: OnceLock<ProviderCache> = OnceLock::new();
+
+fn cached_provider(
+    source: TimetableSource,
+    key: ProviderKey,
+    parse: impl FnOnce(&ProviderKey) -> Result<Arc<dyn TimetableProvider>, String>,
+) -> Result<Arc<dyn TimetableProvider>, String> {
+    let cache = PROVIDERS.get_or_init(Default::default);
+    if let Some((cached_key, provider)) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(&source) {
+        // 수정 시각을 알 수 없으면 바뀌었는지 판단할 수 없으므로 다시 읽는다.
+        if *cached_key == key && key.modified.is_some() {
+            return Ok(provider.clone());
+        }
+    }
+    // 파싱은 잠금 밖에서 한다(느린 파일 읽기 동안 다른 호출을 막지 않는다).
+    let provider = parse(&key)?;
+    cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(source, (key, provider.clone()));
+    Ok(provider)
+}
+
+/// 수업 한 칸.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lesson {
+    pub teacher: String,
+    pub subject: String,
+    /// 수업하는 반(예: "2-1", "3반").
+    pub class_name: Option<String>,
+    /// 교실. 출처에 교실 정보가 없으면 None.
+    pub room: Option<String>,
+    pub date: NaiveDate,
+    pub period: u32,
+}
+
+impl Lesson {
+    /// 화면에 "어디서" 로 보여 줄 값: 교실, 없으면 반.
+    pub fn place(&self) -> Option<&str> {
+        self.room.as_deref().or(self.class_name.as_deref())
+    }
+}
+
+/// 날짜의 행사(반 또는 학년 단위).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayEvent {
+    pub date: NaiveDate,
+    /// 반 단위 행사면 그 반.
+    pub class_name: Option<String>,
+    /// 학년 단위 행사면 그 학년.
+    pub grade: Option<u32>,
+    pub label: String,
+}
+
+/// 주간 보기의 칸.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekCell {
+    pub subject: String,
+    pub class_name: Option<String>,
+    /// 평소(기본 시간표)와 다른 칸: 바뀐 수업, 빠진 수업, 행사.
+    pub changed: bool,
+    /// 수업 대신 행사가 들어간 칸(`subject` 가 행사 이름).
+    pub event: bool,
+}
+
+/// 교사 한 명의 월~금 주간 시간표.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeacherWeek {
+    pub source: &'static str,
+    pub teacher: String,
+    /// 날짜별 자료가 있는 출처인지(주 이동·변경 표시를 쓸지).
+    pub dated: bool,
+    /// 월~금 날짜(YYYY-MM-DD).
+    pub dates: Vec<String>,
+    /// `cells[교시 - 1][요일]`.
+    pub cells: Vec<Vec<Option<WeekCell>>>,
+}
+
+/// 시간표 공급자. 출처별로 `teachers`·`classes`·`lessons_on`·`events_on`(+ 날짜별 출처면 `dated_days`)만
+/// 구현하면 나머지 조회는 공유한다.
+pub trait TimetableProvider: Send + Sync {
+    fn source(&self) -> TimetableSource;
+
+    fn teachers(&self) -> Vec<String>;
+
+    fn classes(&self) -> Vec<String>;
+
+    /// 날짜별 자료가 있는 날(오름차순). 요일마다 같은 시간표를 쓰는 출처는 빈 목록.
+    fn dated_days(&self) -> Vec<NaiveDate> {
+        Vec::new()
+    }
+
+    /// 그날의 모든 수업(교시 순).
+    fn lessons_on(&self, date: NaiveDate) -> Vec<Lesson>;
+
+    /// 그날의 행사.
+    fn events_on(&self, _date: NaiveDate) -> Vec<DayEvent> {
+        Vec::new()
+    }
+
+    /// 방학·학년도 밖이라 수업이 없는 날. 주간 보기에서 평소 시간표로 채우지 않는다.
+    fn is_break(&self, _date: NaiveDate) -> bool {
+        false
+    }
+
+    fn teacher_lessons(&self, teacher: &str, date: NaiveDate) -> Vec<Lesson> {
+        self.lessons_on(date).into_iter().filter(|l| l.teacher == teacher).collect()
+    }
+
+    fn class_lessons(&self, class_name: &str, date: NaiveDate) -> Vec<Lesson> {
+        self.lessons_on(date)
+            .into_iter()
+            .filter(|l| l.class_name.as_deref() == Some(class_name))
+            .collect()
+    }
+
+    fn lesson_at(&self, teacher: &str, date: NaiveDate, period: u32) -> Option<Lesson> {
+        self.teacher_lessons(teacher, date).into_iter().find(|l| l.period == period)
+    }
+
+    /// 반에 걸린 그날 행사: 반 단위 행사, 없으면 반 이름("2-1")의 학년 행사.
+    fn event_for_class(&self, class_name: &str, date: NaiveDate) -> Option<String> {
+        let events = self.events_on(date);
+        if let Some(e) = events.iter().find(|e| e.class_name.as_deref() == Some(class_name)) {
+            return Some(e.label.clone());
+        }
+        let grade = grade_of_class(class_name)?;
+        events
+            .into_iter()
+            .find(|e| e.class_name.is_none() && e.grade == Some(grade))
+            .map(|e| e.label)
+    }
+
+    /// 요일·교시별 평소 수업. 날짜별 출처는 그 요일에 교사가 수업한 날의 과반(최소 2일)에
+    /// 되풀이된 수업만 평소 수업으로 본다 — 한 번뿐인 보강·대체 수업이 섞이지 않게.
+    fn weekly_base(&self, teacher: &str) -> HashMap<(Weekday, u32), Lesson> {
+        usual_lessons(self.dated_days(), |d| self.teacher_lessons(teacher, d))
+    }
+
+    /// 반의 요일·교시별 평소 수업(`weekly_base` 와 같은 기준).
+    fn class_weekly_base(&self, class_name: &str) -> HashMap<(Weekday, u32), Lesson> {
+        usual_lessons(self.dated_days(), |d| self.class_lessons(class_name, d))
+    }
+
+    /// `monday` 가 속한 주의 월~금 시간표. 날짜별 출처는 평소와 달라진 칸과 행사를 표시한다.
+    fn teacher_week(&self, teacher: &str, monday: NaiveDate) -> TeacherWeek {
+        let monday = monday - chrono::Duration::days(monday.weekday().num_days_from_monday() as i64);
+        let dates: Vec<NaiveDate> = (0..5).map(|i| monday + chrono::Duration::days(i)).collect();
+        let dated = !self.dated_days().is_empty();
+        let base = self.weekly_base(teacher);
+        let mut cells = vec![vec![None; dates.len()]; WEEK_PERIODS as usize];
+        for (d, date) in dates.iter().enumerate() {
+            let lessons = self.teacher_lessons(teacher, *date);
+            let has_data = !lessons.is_empty();
+            for period in 1..=WEEK_PERIODS {
+                let usual = base.get(&(date.weekday(), period));
+                let actual = lessons.iter().find(|l| l.period == period);
+                // 행사는 평소 그 시간에 수업이 있던 칸에만 표시한다.
+                let event = usual
+                    .and_then(|u| u.class_name.as_deref())
+                    .and_then(|c| self.event_for_class(c, *date));
+                cells[period as usize - 1][d] = match (event, actual, usual) {
+                    (Some(label), _, _) => Some(WeekCell { subject: label, class_name: None, changed: true, event: true }),
+                    (None, Some(a), u) => Some(WeekCell {
+                        subject: a.subject.clone(),
+                        class_name: a.class_name.clone(),
+                        changed: dated && u.is_none_or(|u| u.subject != a.subject || u.class_name != a.class_name),
+                        event: false,
+                    }),
+                    // 그날 자료는 있는데 평소 수업이 빠졌다.
+                    (None, None, Some(_)) if has_data => {
+                        Some(WeekCell { subject: String::new(), class_name: None, changed: true, event: false })
+                    }
+                    // 아직 자료가 없는 날은 평소 시간표로 채운다(방학 제외).
+                    (None, None, Some(u)) if !self.is_break(*date) => Some(WeekCell {
+                        subject: u.subject.clone(),
+                        class_name: u.class_name.clone(),
+                        changed: false,
+                        event: false,
+                    }),
+                    (None, None, _) => None,
+                };
+            }
+        }
+        TeacherWeek {
+            source: self.source().as_str(),
+            teacher: teacher.to_string(),
+            dated,
+            dates: dates.iter().map(|d| d.format("%Y-%m-%d").to_string()).collect(),
+            cells,
+        }
+    }
+}
+
+/// "2-1" 같은 반 이름의 학년.
+fn grade_of_class(class_name: &str) -> Option<u32> {
+    let (grade, _) = class_name.split_once('-')?;
+    grade.trim().parse().ok()
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let s = s.trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+// ─── 알림이 ───────────────────────────────────────────────────────────────────
+
+impl TimetableProvider for TimetableData {
+    fn source(&self) -> TimetableSource {
+        TimetableSource::Comcigan
+    }
+
+    fn teachers(&self) -> Vec<String> {
+        self.teachers.clone()
+    }
+
+    /// 시간표에 나오는 반("3반" 등) 목록.
+    fn classes(&self) -> Vec<String> {
+        let mut out: Vec<String> = self
+            .timetables
+            .values()
+            .flatten()
+            .flatten()
+            .filter_map(|cell| cell.get(1).and_then(|c| non_empty(c)))
+            .collect();
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    fn lessons_on(&self, date: NaiveDate) -> Vec<Lesson> {
+        let day = date.weekday().num_days_from_monday() as usize;
+        if day >= 5 {
+            return Vec::new();
+        }
+        let mut out: Vec<Lesson> = self
+            .teachers
+            .iter()
+            .filter_map(|t| self.timetables.get(t).map(|grid| (t, grid)))
+            .flat_map(|(teacher, grid)| {
+                grid.iter().enumerate().filter_map(move |(i, days)| {
+                    let cell = days.get(day)?;
+                    Some(Lesson {
+                        teacher: teacher.clone(),
+                        subject: cell.first().and_then(|s| non_empty(s))?,
+                        class_name: cell.get(1).and_then(|c| non_empty(c)),
+                        room: None,
+                        date,
+                        period: i as u32 + 1,
+                    })
+                })
+            })
+            .collect();
+        out.sort_by_key(|l| l.period);
+        out
+    }
+}
+
+// ─── 압핀 ─────────────────────────────────────────────────────────────────────
+
+impl TimetableProvider for AppinTimetableData {
+    fn source(&self) -> TimetableSource {
+        TimetableSource::Appin
+    }
+
+    fn teachers(&self) -> Vec<String> {
+        self.teachers.clone()
+    }
+
+    fn classes(&self) -> Vec<String> {
+        self.classes.clone()
+    }
+
+    fn dated_days(&self) -> Vec<NaiveDate> {
+        let mut days: Vec<NaiveDate> = self
+            .days
+            .keys()
+            .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .collect();
+        days.sort();
+        days
+    }
+
+    fn lessons_on(&self, date: NaiveDate) -> Vec<Lesson> {
+        let Some(classes) = self.days.get(&date.format("%Y-%m-%d").to_string()) else {
+            return Vec::new();
+        };
+        let mut out: Vec<Lesson> = classes
+            .iter()
+            .flat_map(|(class, periods)| periods.iter().map(move |(p, slot)| (class, p, slot)))
+            .filter_map(|(class, p, slot)| {
+                Some(Lesson {
+                    teacher: self.teachers.get(slot.teacher?)?.clone(),
+                    subject: slot.subject.and_then(|i| self.subjects.get(i)).cloned().unwrap_or_default(),
+                    class_name: non_empty(class),
+                    room: None,
+                    date,
+                    period: p.parse().ok()?,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| a.period.cmp(&b.period).then_with(|| a.class_name.cmp(&b.class_name)));
+        out
+    }
+
+    fn events_on(&self, date: NaiveDate) -> Vec<DayEvent> {
+        let key = date.format("%Y-%m-%d").to_string();
+        let mut out: Vec<DayEvent> = self
+            .events_by_date_class
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .map(|(class, label)| DayEvent {
+                date,
+                class_name: Some(class.clone()),
+                grade: grade_of_class(class),
+                label: label.clone(),
+            })
+            .collect();
+        out.sort_by(|a, b| a.class_name.cmp(&b.class_name));
+        for (i, label) in self.events_by_date_grade.get(&key).into_iter().flatten().enumerate() {
+            if let Some(label) = label {
+                out.push(DayEvent { date, class_name: None, grade: Some(i as u32 + 1), label: label.clone() });
+            }
+        }
+        out
+    }
+
+    fn is_break(&self, date: NaiveDate) -> bool {
+        AppinTimetableData::is_break(self, date)
+    }
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+fn load(source: Option<String>) -> Result<Arc<dyn TimetableProvider>, String> {
+    let source = match source {
+        Some(s) => TimetableSource::parse(Some(&s)),
+        None => TimetableSource::from_registry(),
+    };
+    source.load()
+}
+
+/// 시간표의 교사 목록. `source` 가 없으면 학교 위젯 설정을 따른다.
+#[tauri::command]
+pub fn get_timetable_teachers(source: Option<String>) -> Result<Vec<String>, String> {
+    Ok(load(source)?.teachers())
+}
+
+/// 교사 한 명의 주간 시간표. `week_of`(YYYY-MM-DD)가 속한 주의 월~금.
+#[tauri::command]
+pub fn get_teacher_week(source: Option<String>, teacher: String, week_of: String) -> Result<TeacherWeek, String> {
+    let date = NaiveDate::parse_from_str(week_of.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", week_of))?;
+    Ok(load(source)?.teacher_week(&teacher, date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::appin_parser::AppinSlot;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn comcigan() -> TimetableData {
+        let mut timetables = HashMap::new();
+        let mut grid = vec![vec![vec![String::new(), String::new()]; 5]; 8];
+        grid[0][2] = vec!["국어".to_string(), "3반".to_string()];
+        grid[5][2] = vec!["국어".to_string(), "5반".to_string()];
+        timetables.insert("김교사".to_string(), grid);
+        let mut other = vec![vec![vec![String::new(), String::new()]; 5]; 8];
+        other[0][2] = vec!["수학".to_string(), "4반".to_string()];
+        timetables.insert("박교사".to_string(), other);
+        TimetableData {
+            teachers: vec!["김교사".to_string(), "박교사".to_string()],
+            subjects: vec!["국어".to_string(), "수학".to_string()],
+            timetables,
+        }
+    }
+
+    /// 2-1 반: 월요일마다 1교시 김교사 수학. 3/16 은 박교사로 바뀌고, 3/23 은 2학년 행사.
+    fn appin() -> AppinTimetableData {
+        let slot = |s: usize, t: usize| AppinSlot { subject: Some(s), teacher: Some(t), room: None };
+        let mut days = HashMap::new();
+        for (date, teacher) in [("2026-03-02", 1), ("2026-03-09", 1), ("2026-03-16", 0)] {
+            let mut periods = HashMap::new();
+            periods.insert("1".to_string(), slot(0, teacher));
+            periods.insert("3".to_string(), slot(1, 1));
+            let mut classes = HashMap::new();
+            classes.insert("2-1".to_string(), periods);
+            days.insert(date.to_string(), classes);
+        }
+        let mut periods = HashMap::new();
+        periods.insert("3".to_string(), slot(1, 1));
+        let mut classes = HashMap::new();
+        classes.insert("2-1".to_string(), periods);
+        days.insert("2026-03-23".to_string(), classes);
+        let mut events_by_date_grade = HashMap::new();
+        events_by_date_grade.insert("2026-03-23".to_string(), vec![None, Some("체육대회".to_string()), None]);
+        AppinTimetableData {
+            teachers: vec!["박교사".to_string(), "김교사".to_string()],
+            subjects: vec!["수학".to_string(), "과학".to_string()],
+            classes: vec!["2-1".to_string()],
+            events: vec!["체육대회".to_string()],
+            days,
+            events_by_date_class: HashMap::new(),
+            events_by_date_grade,
+            start_date: "2026-03-01".to_string(),
+            terms: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn comcigan_lookups_repeat_by_weekday() {
+        let t = comcigan();
+        let wed = d("2026-03-04");
+        let lessons = t.teacher_lessons("김교사", wed);
+        assert_eq!(lessons.iter().map(|l| (l.period, l.subject.as_str())).collect::<Vec<_>>(), vec![(1, "국어"), (6, "국어")]);
+        assert_eq!(lessons[1].place(), Some("5반"));
+        assert_eq!(t.lesson_at("박교사", d("2026-03-11"), 1).unwrap().subject, "수학");
+        assert!(t.teacher_lessons("김교사", d("2026-03-07")).is_empty());
+        assert_eq!(t.class_lessons("4반", wed).len(), 1);
+        assert_eq!(t.classes(), vec!["3반", "4반", "5반"]);
+
+        let week = t.teacher_week("김교사", d("2026-03-05"));
+        assert!(!week.dated);
+        assert_eq!(week.dates[0], "2026-03-02");
+        let cell = week.cells[5][2].as_ref().unwrap();
+        assert_eq!((cell.subject.as_str(), cell.changed), ("국어", false));
+        assert!(week.cells[0][0].is_none());
+    }
+
+    #[test]
+    fn appin_lookups_by_date() {
+        let t = appin();
+        assert_eq!(t.lesson_at("김교사", d("2026-03-09"), 1).unwrap().class_name.as_deref(), Some("2-1"));
+        assert_eq!(t.lesson_at("박교사", d("2026-03-16"), 1).unwrap().subject, "수학");
+        assert!(t.lesson_at("김교사", d("2026-03-16"), 1).is_none());
+        assert_eq!(t.class_lessons("2-1", d("2026-03-02")).len(), 2);
+        assert_eq!(t.event_for_class("2-1", d("2026-03-23")).as_deref(), Some("체육대회"));
+        assert_eq!(t.event_for_class("1-1", d("2026-03-23")), None);
+    }
+
+    #[test]
+    fn appin_week_marks_changes_and_events() {
+        let t = appin();
+        let base = t.weekly_base("김교사");
+        assert_eq!(base.get(&(Weekday::Mon, 1)).map(|l| l.subject.as_str()), Some("수학"));
+        // 반도 같은 기준: 3/16 한 번뿐인 박교사 수업은 평소 수업이 아니다.
+        let base = t.class_weekly_base("2-1");
+        assert_eq!(base.get(&(Weekday::Mon, 1)).map(|l| l.teacher.as_str()), Some("김교사"));
+        assert_eq!(base.get(&(Weekday::Mon, 3)).map(|l| l.subject.as_str()), Some("과학"));
+
+        // 평소대로인 주.
+        let week = t.teacher_week("김교사", d("2026-03-09"));
+        assert!(week.dated);
+        assert!(!week.cells[0][0].as_ref().unwrap().changed);
+
+        // 수업이 다른 교사에게 넘어간 주: 빈 칸으로 변경 표시.
+        let week = t.teacher_week("김교사", d("2026-03-16"));
+        let cell = week.cells[0][0].as_ref().unwrap();
+        assert!(cell.changed && cell.subject.is_empty());
+        let week = t.teacher_week("박교사", d("2026-03-16"));
+        assert!(week.cells[0][0].as_ref().unwrap().changed);
+
+        // 학년 행사.
+        let week = t.teacher_week("김교사", d("2026-03-23"));
+        let cell = week.cells[0][0].as_ref().unwrap();
+        assert!(cell.event && cell.subject == "체육대회");
+
+        // 자료가 없는 주는 평소 시간표로 채운다.
+        let week = t.teacher_week("김교사", d("2026-04-06"));
+        let cell = week.cells[0][0].as_ref().unwrap();
+        assert_eq!((cell.subject.as_str(), cell.changed), ("수학", false));
+
+        // 학년도가 끝난 뒤는 채우지 않는다.
+        let week = t.teacher_week("김교사", d("2027-03-08"));
+        assert!(week.cells[0][0].is_none());
+    }
+
+    #[test]
+    fn provider_is_reparsed_only_when_file_changes() {
+        let path = std::env::temp_dir().join(format!("hypercool-tt-cache-{}.dat", std::process::id()));
+        std::fs::write(&path, b"v1").unwrap();
+        let parses = std::cell::Cell::new(0);
+        let parse = |_: &ProviderKey| -> Result<Arc<dyn TimetableProvider>, String> {
+            parses.set(parses.get() + 1);
+            Ok(Arc::new(appin()))
+        };
+
+        let key = ProviderKey::new(path.clone(), Some(d("2026-03-01")));
+        cached_provider(TimetableSource::Appin, key.clone(), parse).unwrap();
+        cached_provider(TimetableSource::Appin, key.clone(), parse).unwrap();
+        assert_eq!(parses.get(), 1);
+
+        // 학년도 첫날 설정이 바뀌면 다시 읽는다.
+        cached_provider(TimetableSource::Appin, ProviderKey::new(path.clone(), None), parse).unwrap();
+        assert_eq!(parses.get(), 2);
+
+        // 파일이 바뀌면(수정 시각) 다시 읽는다.
+        let later = key.modified.unwrap() + std::time::Duration::from_secs(5);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+        cached_provider(TimetableSource::Appin, ProviderKey::new(path.clone(), None), parse).unwrap();
+        assert_eq!(parses.get(), 3);
+
+        // 파싱에 실패하면 캐시를 남기지 않고 오류를 그대로 돌려준다.
+        let fail = |_: &ProviderKey| -> Result<Arc<dyn TimetableProvider>, String> { Err("읽기 실패".to_string()) };
+        let missing = ProviderKey::new(path.with_extension("missing"), None);
+        assert!(missing.modified.is_none());
+        assert!(cached_provider(TimetableSource::Appin, missing, fail).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
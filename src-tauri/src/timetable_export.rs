@@ -0,0 +1,591 @@
+//! 시간표 내보내기: 매주 되풀이되는 iCalendar(.ics), CSV, 인쇄용 HTML.
+//!
+//! 교사 또는 반 하나의 시간표를 내보낸다. .ics 는 요일·교시마다 매주 반복 일정 하나를 만들고,
+//! 학사일정의 휴업일과 방학은 빼고(EXDATE), 단축수업·시험처럼 종 시간표가 다른 날은
+//! 그날만 시각을 바꾼다(RECURRENCE-ID). 시각은 모두 한국 시간(Asia/Seoul)이다.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::bell_schedule::{BellProfile, BellSchedule};
+use crate::db::open_app_db;
+use crate::timetable::{TimetableProvider, TimetableSource, WEEK_PERIODS};
+
+const TZID: &str = "Asia/Seoul";
+const WEEKDAY_NAMES: [&str; 5] = ["월", "화", "수", "목", "금"];
+
+/// 내보낼 대상.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportTarget {
+    Teacher(String),
+    Class(String),
+}
+
+impl ExportTarget {
+    /// `kind`: "teacher" | "class"
+    pub fn parse(kind: &str, name: &str) -> Result<Self, String> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err("내보낼 교사나 반을 골라 주세요.".to_string());
+        }
+        match kind.trim() {
+            "teacher" => Ok(ExportTarget::Teacher(name)),
+            "class" => Ok(ExportTarget::Class(name)),
+            other => Err(format!("알 수 없는 대상입니다: {}", other)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            ExportTarget::Teacher(n) | ExportTarget::Class(n) => n,
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            ExportTarget::Teacher(n) => format!("{} 선생님 시간표", n),
+            ExportTarget::Class(n) => format!("{} 시간표", n),
+        }
+    }
+}
+
+/// 한 칸: 과목과 덧붙일 정보(교사 시간표면 반, 반 시간표면 교사).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportCell {
+    pub subject: String,
+    pub detail: Option<String>,
+}
+
+impl ExportCell {
+    fn text(&self) -> String {
+        match &self.detail {
+            Some(d) => format!("{} ({})", self.subject, d),
+            None => self.subject.clone(),
+        }
+    }
+}
+
+/// 월~금 한 주. `cells[교시 - 1][요일]`.
+#[derive(Debug, Clone)]
+pub struct ExportWeek {
+    pub title: String,
+    pub dates: Vec<NaiveDate>,
+    pub cells: Vec<Vec<Option<ExportCell>>>,
+}
+
+fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// `week_of` 가 속한 주의 시간표. 교사는 주간 보기(`teacher_week`)와 같은 칸을 쓴다.
+pub fn export_week(provider: &dyn TimetableProvider, target: &ExportTarget, week_of: NaiveDate) -> ExportWeek {
+    let monday = monday_of(week_of);
+    let dates: Vec<NaiveDate> = (0..5).map(|i| monday + chrono::Duration::days(i)).collect();
+    let cells = match target {
+        ExportTarget::Teacher(name) => provider
+            .teacher_week(name, monday)
+            .cells
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|c| {
+                        c.filter(|c| !c.subject.is_empty())
+                            .map(|c| ExportCell { subject: c.subject, detail: c.class_name })
+                    })
+                    .collect()
+            })
+            .collect(),
+        ExportTarget::Class(name) => {
+            let mut cells = vec![vec![None; dates.len()]; WEEK_PERIODS as usize];
+            for (d, date) in dates.iter().enumerate() {
+                for l in provider.class_lessons(name, *date) {
+                    if (1..=WEEK_PERIODS).contains(&l.period) {
+                        cells[l.period as usize - 1][d] = Some(ExportCell { subject: l.subject, detail: Some(l.teacher) });
+                    }
+                }
+            }
+            cells
+        }
+    };
+    ExportWeek { title: target.title(), dates, cells }
+}
+
+/// 매주 되풀이할 요일·교시별 수업. 교사는 `weekly_base`, 반은 `class_weekly_base` 의 평소 시간표.
+pub fn weekly_pattern(provider: &dyn TimetableProvider, target: &ExportTarget) -> BTreeMap<(u32, u32), ExportCell> {
+    let base = match target {
+        ExportTarget::Teacher(name) => provider.weekly_base(name),
+        ExportTarget::Class(name) => provider.class_weekly_base(name),
+    };
+    base.into_iter()
+        .filter(|(_, l)| !l.subject.is_empty())
+        .map(|((wd, p), l)| {
+            let detail = match target {
+                ExportTarget::Teacher(_) => l.class_name,
+                ExportTarget::Class(_) => Some(l.teacher),
+            };
+            ((wd.num_days_from_monday(), p), ExportCell { subject: l.subject, detail })
+        })
+        .collect()
+}
+
+// ─── iCalendar ────────────────────────────────────────────────────────────────
+
+/// .ics 를 만들 때 쓰는 기간·종 시간표·휴업일.
+pub struct IcsOptions<'a> {
+    pub from: NaiveDate,
+    /// 이 날까지(포함) 되풀이한다.
+    pub until: NaiveDate,
+    pub bells: &'a BellSchedule,
+    pub holidays: &'a HashSet<NaiveDate>,
+    /// DTSTAMP(UTC)
+    pub stamp: NaiveDateTime,
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+/// 75 바이트가 넘는 줄을 접는다(RFC 5545 3.1). 글자 중간에서 자르지 않는다.
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut len = 0;
+    for ch in line.chars() {
+        if len + ch.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            len = 1;
+        }
+        out.push(ch);
+        len += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+fn local(date: NaiveDate, time: NaiveTime) -> String {
+    date.and_time(time).format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn period_times(profile: &BellProfile, period: u32) -> Option<(NaiveTime, NaiveTime)> {
+    let slot = profile.slots.iter().find(|s| s.period == Some(period))?;
+    Some((slot.start_time()?, slot.end_time()?))
+}
+
+/// 매주 되풀이되는 일정으로 된 .ics 본문과 일정 수.
+pub fn to_ics(provider: &dyn TimetableProvider, target: &ExportTarget, pattern: &BTreeMap<(u32, u32), ExportCell>, opts: &IcsOptions) -> (String, usize) {
+    let base = opts
+        .bells
+        .profile(&opts.bells.default_profile)
+        .cloned()
+        .unwrap_or_else(|| opts.bells.profile_for(opts.from));
+    // UNTIL 은 TZID 가 붙은 DTSTART 와 함께 쓸 때 UTC 여야 한다.
+    let until_utc = (opts.until.and_hms_opt(23, 59, 59).unwrap() - chrono::Duration::hours(9)).format("%Y%m%dT%H%M%SZ");
+    let stamp = opts.stamp.format("%Y%m%dT%H%M%SZ").to_string();
+    let uid_base: String = target.name().bytes().map(|b| format!("{:02x}", b)).collect();
+    let kind = match target {
+        ExportTarget::Teacher(_) => "t",
+        ExportTarget::Class(_) => "c",
+    };
+
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//HyperCool//Timetable//KO".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(&target.title())),
+        format!("X-WR-TIMEZONE:{}", TZID),
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", TZID),
+        "BEGIN:STANDARD".to_string(),
+        "DTSTART:19700101T000000".to_string(),
+        "TZOFFSETFROM:+0900".to_string(),
+        "TZOFFSETTO:+0900".to_string(),
+        "TZNAME:KST".to_string(),
+        "END:STANDARD".to_string(),
+        "END:VTIMEZONE".to_string(),
+    ];
+    let mut count = 0;
+    for (&(wd, period), cell) in pattern {
+        let Some((start, end)) = period_times(&base, period) else { continue };
+        let first = opts.from + chrono::Duration::days((wd as i64 - opts.from.weekday().num_days_from_monday() as i64).rem_euclid(7));
+        if first > opts.until {
+            continue;
+        }
+        let uid = format!("hypercool-{}-{}-{}{}@hypercool", kind, uid_base, wd + 1, period);
+        let summary = escape_text(&cell.text());
+        let description = escape_text(&format!("{}요일 {}교시", WEEKDAY_NAMES[wd as usize], period));
+
+        let mut exdates = Vec::new();
+        let mut moved = Vec::new();
+        let mut date = first;
+        while date <= opts.until {
+            if opts.holidays.contains(&date) || provider.is_break(date) {
+                exdates.push(local(date, start));
+            } else {
+                match period_times(&opts.bells.profile_for(date), period) {
+                    None => exdates.push(local(date, start)),
+                    Some(times) if times != (start, end) => moved.push((date, times)),
+                    Some(_) => {}
+                }
+            }
+            date += chrono::Duration::days(7);
+        }
+
+        let event_head = |lines: &mut Vec<String>| {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", uid));
+            lines.push(format!("DTSTAMP:{}", stamp));
+        };
+        let event_body = |lines: &mut Vec<String>| {
+            lines.push(format!("SUMMARY:{}", summary));
+            lines.push(format!("DESCRIPTION:{}", description));
+            if let ExportTarget::Teacher(_) = target {
+                if let Some(class) = &cell.detail {
+                    lines.push(format!("LOCATION:{}", escape_text(class)));
+                }
+            }
+            lines.push("END:VEVENT".to_string());
+        };
+
+        event_head(&mut lines);
+        lines.push(format!("DTSTART;TZID={}:{}", TZID, local(first, start)));
+        lines.push(format!("DTEND;TZID={}:{}", TZID, local(first, end)));
+        lines.push(format!("RRULE:FREQ=WEEKLY;UNTIL={}", until_utc));
+        if !exdates.is_empty() {
+            lines.push(format!("EXDATE;TZID={}:{}", TZID, exdates.join(",")));
+        }
+        event_body(&mut lines);
+        for (date, (s, e)) in moved {
+            event_head(&mut lines);
+            lines.push(format!("RECURRENCE-ID;TZID={}:{}", TZID, local(date, start)));
+            lines.push(format!("DTSTART;TZID={}:{}", TZID, local(date, s)));
+            lines.push(format!("DTEND;TZID={}:{}", TZID, local(date, e)));
+            event_body(&mut lines);
+        }
+        count += 1;
+    }
+    lines.push("END:VCALENDAR".to_string());
+    (lines.iter().map(|l| fold(l)).collect(), count)
+}
+
+// ─── CSV·HTML ─────────────────────────────────────────────────────────────────
+
+/// 내용이 있는 마지막 교시(종 시간표의 교시 수보다 적지 않게).
+fn last_period(week: &ExportWeek, profile: &BellProfile) -> usize {
+    let data = week.cells.iter().rposition(|row| row.iter().any(Option::is_some)).map_or(0, |i| i + 1);
+    let bells = profile.slots.iter().filter_map(|s| s.period).max().unwrap_or(0) as usize;
+    data.max(bells).clamp(1, WEEK_PERIODS as usize)
+}
+
+fn day_header(date: NaiveDate, i: usize) -> String {
+    format!("{} {}/{}", WEEKDAY_NAMES[i], date.month(), date.day())
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// 교시 × 요일 표. 엑셀에서 한글이 깨지지 않게 BOM 을 붙인다.
+pub fn to_csv(week: &ExportWeek, profile: &BellProfile) -> String {
+    let mut out = String::from("\u{feff}");
+    let mut header = vec!["교시".to_string(), "시작".to_string(), "끝".to_string()];
+    header.extend(week.dates.iter().enumerate().map(|(i, d)| day_header(*d, i)));
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+    for p in 1..=last_period(week, profile) {
+        let (start, end) = period_times(profile, p as u32)
+            .map(|(s, e)| (s.format("%H:%M").to_string(), e.format("%H:%M").to_string()))
+            .unwrap_or_default();
+        let mut row = vec![p.to_string(), start, end];
+        row.extend(week.cells[p - 1].iter().map(|c| csv_field(&c.as_ref().map(ExportCell::text).unwrap_or_default())));
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// A4 가로 한 장에 들어가는 인쇄용 표.
+pub fn to_html(week: &ExportWeek, profile: &BellProfile) -> String {
+    let title = escape_html(&week.title);
+    let range = match (week.dates.first(), week.dates.last()) {
+        (Some(a), Some(b)) => format!("{} ~ {}", a.format("%Y-%m-%d"), b.format("%Y-%m-%d")),
+        _ => String::new(),
+    };
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+@page {{ size: A4 landscape; margin: 12mm; }}\n\
+body {{ font-family: 'Malgun Gothic', sans-serif; color: #111; }}\n\
+h1 {{ font-size: 18pt; margin: 0 0 4px; }}\n\
+.range {{ color: #555; margin-bottom: 10px; }}\n\
+table {{ width: 100%; border-collapse: collapse; table-layout: fixed; }}\n\
+th, td {{ border: 1px solid #888; padding: 6px; text-align: center; height: 48px; }}\n\
+th {{ background: #eee; }}\n\
+td.period {{ width: 70px; background: #f6f6f6; font-weight: bold; }}\n\
+.time {{ display: block; font-size: 8pt; font-weight: normal; color: #666; }}\n\
+.subject {{ font-weight: bold; }}\n\
+.detail {{ display: block; font-size: 9pt; color: #444; }}\n\
+</style>\n</head>\n<body>\n<h1>{title}</h1>\n<div class=\"range\">{range}</div>\n<table>\n<thead><tr><th></th>"
+    );
+    for (i, d) in week.dates.iter().enumerate() {
+        let _ = write!(html, "<th>{}</th>", escape_html(&day_header(*d, i)));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+    for p in 1..=last_period(week, profile) {
+        let time = period_times(profile, p as u32)
+            .map(|(s, e)| format!("<span class=\"time\">{}~{}</span>", s.format("%H:%M"), e.format("%H:%M")))
+            .unwrap_or_default();
+        let _ = write!(html, "<tr><td class=\"period\">{}교시{}</td>", p, time);
+        for cell in &week.cells[p - 1] {
+            match cell {
+                Some(c) => {
+                    let detail = c
+                        .detail
+                        .as_deref()
+                        .map(|d| format!("<span class=\"detail\">{}</span>", escape_html(d)))
+                        .unwrap_or_default();
+                    let _ = write!(html, "<td><span class=\"subject\">{}</span>{}</td>", escape_html(&c.subject), detail);
+                }
+                None => html.push_str("<td></td>"),
+            }
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+// ─── 명령 ─────────────────────────────────────────────────────────────────────
+
+/// 학사일정에서 가져온 휴업일(`neis::import_school_schedule`).
+pub(crate) fn load_holidays(conn: &Connection, from: NaiveDate, to: NaiveDate) -> Result<HashSet<NaiveDate>, String> {
+    crate::db::ensure_schedule_table(conn)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT start_date FROM tbl_schedules
+             WHERE type = ?1 AND is_deleted = 0 AND is_day_off = 1 AND start_date >= ?2 AND start_date <= ?3",
+        )
+        .map_err(|e| format!("휴업일 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map(
+            params![
+                crate::neis::SCHOOL_SCHEDULE_TYPE,
+                from.format("%Y-%m-%d").to_string(),
+                to.format("%Y-%m-%d").to_string()
+            ],
+            |r| r.get::<_, String>(0),
+        )
+        .map_err(|e| format!("휴업일 조회 실패: {}", e))?;
+    Ok(rows
+        .flatten()
+        .filter_map(|d| NaiveDate::parse_from_str(d.get(..10).unwrap_or(&d), "%Y-%m-%d").ok())
+        .collect())
+}
+
+/// 날짜가 속한 학년도의 마지막 날(2월 말).
+fn school_year_end(date: NaiveDate) -> NaiveDate {
+    let year = if date.month() >= 3 { date.year() + 1 } else { date.year() };
+    NaiveDate::from_ymd_opt(year, 3, 1).unwrap().pred_opt().unwrap()
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").map_err(|_| format!("날짜 형식이 올바르지 않습니다(YYYY-MM-DD): {}", s))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimetableExportResult {
+    pub path: String,
+    /// 내보낸 수업 수(.ics 는 매주 반복 일정 수).
+    pub lessons: usize,
+}
+
+/// 교사·반 시간표를 `path` 에 저장한다. `format`: "ics" | "csv" | "html".
+/// `week_of` 는 CSV·HTML 의 주(기본 이번 주)이자 .ics 의 시작 주, `until` 은 .ics 반복 끝(기본 학년도 끝).
+#[tauri::command]
+pub fn export_timetable(
+    app: AppHandle,
+    kind: String,
+    name: String,
+    format: String,
+    path: String,
+    week_of: Option<String>,
+    until: Option<String>,
+) -> Result<TimetableExportResult, String> {
+    let target = ExportTarget::parse(&kind, &name)?;
+    let week_of = match week_of.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(s) => parse_date(s)?,
+        None => chrono::Local::now().date_naive(),
+    };
+    let provider = TimetableSource::from_registry().load()?;
+    let bells = crate::bell_schedule::load();
+
+    let (content, lessons) = match format.trim() {
+        "ics" => {
+            let from = monday_of(week_of);
+            let until = match until.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                Some(s) => parse_date(s)?,
+                None => school_year_end(from),
+            };
+            if until < from {
+                return Err("반복 끝 날짜가 시작보다 앞입니다.".to_string());
+            }
+            let conn = open_app_db(&app)?;
+            let holidays = load_holidays(&conn, from, until)?;
+            let pattern = weekly_pattern(provider.as_ref(), &target);
+            let opts = IcsOptions { from, until, bells: &bells, holidays: &holidays, stamp: chrono::Utc::now().naive_utc() };
+            to_ics(provider.as_ref(), &target, &pattern, &opts)
+        }
+        "csv" | "html" => {
+            let week = export_week(provider.as_ref(), &target, week_of);
+            let profile = bells.profile_for(monday_of(week_of));
+            let lessons = week.cells.iter().flatten().filter(|c| c.is_some()).count();
+            let content = if format.trim() == "csv" { to_csv(&week, &profile) } else { to_html(&week, &profile) };
+            (content, lessons)
+        }
+        other => return Err(format!("지원하지 않는 형식입니다: {}", other)),
+    };
+    if lessons == 0 {
+        return Err(format!("{} 의 수업이 없습니다.", target.name()));
+    }
+    std::fs::write(&path, content).map_err(|e| format!("{} 쓰기 실패: {}", path, e))?;
+    Ok(TimetableExportResult { path, lessons })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bell_schedule::{BellOverride, PROFILE_EXAM, PROFILE_SHORT};
+    use crate::timetable_parser::TimetableData;
+    use std::collections::HashMap;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// 김교사: 월 1교시 국어(2-1), 수 4교시 국어(2-3). 박교사: 월 2교시 수학(2-1).
+    fn timetable() -> TimetableData {
+        let empty = || vec![vec![vec![String::new(), String::new()]; 5]; 8];
+        let cell = |s: &str, c: &str| vec![s.to_string(), c.to_string()];
+        let mut kim = empty();
+        kim[0][0] = cell("국어", "2-1");
+        kim[3][2] = cell("국어", "2-3");
+        let mut park = empty();
+        park[1][0] = cell("수학", "2-1");
+        let mut timetables = HashMap::new();
+        timetables.insert("김교사".to_string(), kim);
+        timetables.insert("박교사".to_string(), park);
+        TimetableData {
+            teachers: vec!["김교사".to_string(), "박교사".to_string()],
+            subjects: vec!["국어".to_string(), "수학".to_string()],
+            timetables,
+        }
+    }
+
+    #[test]
+    fn week_grid_for_teacher_and_class() {
+        let t = timetable();
+        let week = export_week(&t, &ExportTarget::Teacher("김교사".to_string()), d("2026-03-05"));
+        assert_eq!(week.dates[0], d("2026-03-02"));
+        assert_eq!(week.cells[3][2].as_ref().unwrap().text(), "국어 (2-3)");
+        assert!(week.cells[0][1].is_none());
+
+        let week = export_week(&t, &ExportTarget::Class("2-1".to_string()), d("2026-03-02"));
+        assert_eq!(week.cells[1][0].as_ref().unwrap().text(), "수학 (박교사)");
+        assert_eq!(week.title, "2-1 시간표");
+
+        let csv = to_csv(&week, &BellSchedule::default().profile_for(d("2026-03-02")));
+        let lines: Vec<&str> = csv.trim_start_matches('\u{feff}').split("\r\n").collect();
+        assert_eq!(lines[0], "교시,시작,끝,월 3/2,화 3/3,수 3/4,목 3/5,금 3/6");
+        assert_eq!(lines[1], "1,08:30,09:20,국어 (김교사),,,,");
+        assert_eq!(lines.len(), 9); // 7교시 + 머리줄 + 끝 빈 줄
+
+        let html = to_html(&week, &BellSchedule::default().profile_for(d("2026-03-02")));
+        assert!(html.contains("<span class=\"subject\">수학</span><span class=\"detail\">박교사</span>"));
+        assert!(html.contains("2교시<span class=\"time\">09:30~10:20</span>"));
+
+        let pattern = weekly_pattern(&t, &ExportTarget::Class("2-1".to_string()));
+        assert_eq!(pattern.values().map(ExportCell::text).collect::<Vec<_>>(), vec!["국어 (김교사)", "수학 (박교사)"]);
+    }
+
+    #[test]
+    fn ics_repeats_weekly_with_holidays_and_bell_changes() {
+        let t = timetable();
+        let target = ExportTarget::Teacher("김교사".to_string());
+        let mut bells = BellSchedule::default();
+        bells.overrides.push(BellOverride { date: "2026-03-16".to_string(), profile: PROFILE_SHORT.to_string(), note: String::new() });
+        bells.overrides.push(BellOverride { date: "2026-03-18".to_string(), profile: PROFILE_EXAM.to_string(), note: String::new() });
+        let holidays: HashSet<NaiveDate> = [d("2026-03-09")].into_iter().collect();
+        let opts = IcsOptions {
+            from: d("2026-03-02"),
+            until: d("2026-03-31"),
+            bells: &bells,
+            holidays: &holidays,
+            stamp: NaiveDateTime::parse_from_str("2026-03-01T00:00:00", "%Y-%m-%dT%H:%M:%S").unwrap(),
+        };
+        let pattern = weekly_pattern(&t, &target);
+        let (ics, count) = to_ics(&t, &target, &pattern, &opts);
+        assert_eq!(count, 2);
+        let lines: Vec<&str> = ics.split("\r\n").collect();
+        assert!(lines.contains(&"DTSTART;TZID=Asia/Seoul:20260302T083000"));
+        assert!(lines.contains(&"RRULE:FREQ=WEEKLY;UNTIL=20260331T145959Z"));
+        assert!(lines.contains(&"SUMMARY:국어 (2-1)"));
+        assert!(lines.contains(&"LOCATION:2-3"));
+        // 3/9 휴업일은 빠진다.
+        assert!(lines.contains(&"EXDATE;TZID=Asia/Seoul:20260309T083000"));
+        // 3/16 단축수업: 그날만 시각이 바뀐다.
+        assert!(lines.contains(&"RECURRENCE-ID;TZID=Asia/Seoul:20260316T083000"));
+        assert!(lines.contains(&"DTEND;TZID=Asia/Seoul:20260316T091000"));
+        // 3/18 시험: 4교시가 없으므로 빠진다.
+        assert!(lines.contains(&"EXDATE;TZID=Asia/Seoul:20260318T113000"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn holidays_come_from_day_off_flag() {
+        use crate::neis::{NeisSchool, SchoolEvent, SchoolLevel};
+        let conn = Connection::open_in_memory().unwrap();
+        let school = NeisSchool { atpt_code: "C10".to_string(), school_code: "7150451".to_string(), level: SchoolLevel::High };
+        let event = |date: &str, name: &str, day_off: bool| SchoolEvent {
+            date: date.to_string(),
+            name: name.to_string(),
+            content: None,
+            grades: Vec::new(),
+            day_off,
+        };
+        let events = [
+            event("2026-03-02", "개교기념일", true),
+            // 이름이 휴업 표시처럼 끝나도 휴업일 표시가 없으면 수업일이다.
+            event("2026-03-03", "휴업일 안내 (휴업)", false),
+        ];
+        crate::neis::import_school_schedule(&conn, &school, &events, d("2026-03-01"), d("2026-03-31")).unwrap();
+        let holidays = load_holidays(&conn, d("2026-03-01"), d("2026-03-31")).unwrap();
+        assert_eq!(holidays, [d("2026-03-02")].into_iter().collect());
+    }
+
+    #[test]
+    fn ics_text_helpers() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+        let long = format!("SUMMARY:{}", "가".repeat(40));
+        let folded = fold(&long);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), format!("{}\r\n", long));
+        assert_eq!(school_year_end(d("2026-03-02")), d("2027-02-28"));
+        assert_eq!(school_year_end(d("2028-01-10")), d("2028-02-29"));
+        assert!(ExportTarget::parse("room", "1").is_err());
+    }
+}
@@ -0,0 +1,499 @@
+//! 시간표 변경 알림.
+//!
+//! 보강·교체는 알림이(.Gwa)·압핀(amc42.dat) 파일에 조용히 반영된다. 주기적으로 시간표를 읽어
+//! 수업 칸 목록(스냅샷)을 `hypercool.db` 에 저장하고, 직전 스냅샷과 달라졌으면 교사별·반별로
+//! 바뀐 칸을 찾아 기록한 뒤 `timetable-changed` 이벤트를 보낸다. 이벤트에는 기본 교사
+//! (레지스트리 SchoolDefaultTeacher)의 변경을 따로 담는다.
+//!
+//! 비교 범위: 날짜별 출처(압핀)는 오늘부터 `WINDOW_DAYS` 일 안의 자료가 있는 날, 요일 반복
+//! 출처(알림이)는 월~금. 두 스냅샷에 모두 있는 날만 비교하므로 새로 추가된 주나 지나간 날은
+//! 변경으로 보지 않는다.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::system::get_registry_value;
+use crate::db::open_app_db;
+use crate::timetable::{TimetableProvider, TimetableSource};
+
+/// 날짜별 출처에서 비교하는 기간(일).
+const WINDOW_DAYS: i64 = 28;
+/// 출처별로 남겨 두는 스냅샷 수. 변경 기록은 지우지 않는다.
+const KEEP_SNAPSHOTS: i64 = 30;
+/// 감시 주기(초).
+const TICK_SECS: u64 = 600;
+
+const TS_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const WEEKDAYS: [(Weekday, &str); 5] = [
+    (Weekday::Mon, "mon"),
+    (Weekday::Tue, "tue"),
+    (Weekday::Wed, "wed"),
+    (Weekday::Thu, "thu"),
+    (Weekday::Fri, "fri"),
+];
+
+// ─── 스냅샷 ───────────────────────────────────────────────────────────────────
+
+/// 수업 한 칸.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Slot {
+    /// 날짜별 출처면 YYYY-MM-DD, 요일 반복 출처면 "mon".."fri".
+    pub day: String,
+    pub period: u32,
+    pub teacher: String,
+    pub subject: String,
+    pub class_name: Option<String>,
+}
+
+/// 한 번 읽은 시간표.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    /// 비교 범위의 날(`Slot::day` 값, 오름차순).
+    pub days: Vec<String>,
+    /// (day, period, teacher) 순.
+    pub slots: Vec<Slot>,
+}
+
+/// 공급자에서 `today` 기준 스냅샷을 만든다.
+pub fn take_snapshot(provider: &dyn TimetableProvider, today: NaiveDate) -> Snapshot {
+    let dated = provider.dated_days();
+    let days: Vec<(NaiveDate, String)> = if dated.is_empty() {
+        let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+        WEEKDAYS
+            .iter()
+            .map(|(wd, key)| (monday + chrono::Duration::days(wd.num_days_from_monday() as i64), key.to_string()))
+            .collect()
+    } else {
+        let end = today + chrono::Duration::days(WINDOW_DAYS);
+        dated
+            .into_iter()
+            .filter(|d| *d >= today && *d < end)
+            .map(|d| (d, d.format("%Y-%m-%d").to_string()))
+            .collect()
+    };
+
+    let mut slots = Vec::new();
+    for (date, key) in &days {
+        for l in provider.lessons_on(*date) {
+            slots.push(Slot {
+                day: key.clone(),
+                period: l.period,
+                teacher: l.teacher,
+                subject: l.subject,
+                class_name: l.class_name,
+            });
+        }
+    }
+    slots.sort_by(|a, b| (&a.day, a.period, &a.teacher).cmp(&(&b.day, b.period, &b.teacher)));
+    Snapshot { days: days.into_iter().map(|(_, k)| k).collect(), slots }
+}
+
+// ─── 비교 ─────────────────────────────────────────────────────────────────────
+
+/// 변경을 누구 기준으로 본 것인지.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeScope {
+    Teacher,
+    Class,
+}
+
+impl ChangeScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChangeScope::Teacher => "teacher",
+            ChangeScope::Class => "class",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        if s == "class" {
+            ChangeScope::Class
+        } else {
+            ChangeScope::Teacher
+        }
+    }
+}
+
+/// 바뀐 칸 하나.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimetableChange {
+    pub scope: ChangeScope,
+    /// 교사 이름 또는 반.
+    pub target: String,
+    pub day: String,
+    pub period: u32,
+    /// 전후 과목. 빈 시간이면 None.
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// 예: "수 3교시: 국어 → 수학 (2-3)"
+    pub summary: String,
+}
+
+/// 요일 키나 날짜를 "수" / "3/18(수)" 로.
+fn day_label(day: &str) -> String {
+    const NAMES: [&str; 7] = ["월", "화", "수", "목", "금", "토", "일"];
+    if let Some(i) = WEEKDAYS.iter().position(|(_, k)| *k == day) {
+        return NAMES[i].to_string();
+    }
+    match NaiveDate::parse_from_str(day, "%Y-%m-%d") {
+        Ok(d) => format!("{}/{}({})", d.month(), d.day(), NAMES[d.weekday().num_days_from_monday() as usize]),
+        Err(_) => day.to_string(),
+    }
+}
+
+/// 변경 한 줄. 교사별이면 반을, 반별이면 교사를 덧붙인다. 전후가 같으면 끝에 한 번만 쓴다.
+fn summarize(scope: ChangeScope, day: &str, period: u32, before: Option<&Slot>, after: Option<&Slot>) -> String {
+    let detail = |s: &Slot| match scope {
+        ChangeScope::Teacher => s.class_name.clone().unwrap_or_default(),
+        ChangeScope::Class => s.teacher.clone(),
+    };
+    let subject = |s: Option<&Slot>| s.map_or("빈 시간".to_string(), |s| s.subject.clone());
+    let head = format!("{} {}교시: ", day_label(day), period);
+    let shared = match (before, after) {
+        (Some(b), Some(a)) if detail(b) != detail(a) => None,
+        (b, a) => b.or(a).map(detail),
+    };
+    match shared {
+        Some(d) if d.is_empty() => format!("{}{} → {}", head, subject(before), subject(after)),
+        Some(d) => format!("{}{} → {} ({})", head, subject(before), subject(after), d),
+        None => {
+            let side = |s: Option<&Slot>| s.map_or("빈 시간".to_string(), |s| format!("{} {}", s.subject, detail(s)));
+            format!("{}{} → {}", head, side(before), side(after))
+        }
+    }
+}
+
+fn diff_by<'a, F>(scope: ChangeScope, old: &'a [Slot], new: &'a [Slot], days: &BTreeSet<&str>, key: F) -> Vec<TimetableChange>
+where
+    F: Fn(&'a Slot) -> Option<&'a str>,
+{
+    let index = |slots: &'a [Slot]| {
+        let mut m: BTreeMap<(&'a str, u32, &'a str), &'a Slot> = BTreeMap::new();
+        for s in slots.iter().filter(|s| days.contains(s.day.as_str())) {
+            if let Some(k) = key(s) {
+                m.insert((s.day.as_str(), s.period, k), s);
+            }
+        }
+        m
+    };
+    let (before, after) = (index(old), index(new));
+    let keys: BTreeSet<_> = before.keys().chain(after.keys()).copied().collect();
+
+    let mut out = Vec::new();
+    for k in keys {
+        let (b, a) = (before.get(&k).copied(), after.get(&k).copied());
+        if b == a {
+            continue;
+        }
+        let (day, period, target) = k;
+        out.push(TimetableChange {
+            scope,
+            target: target.to_string(),
+            day: day.to_string(),
+            period,
+            before: b.map(|s| s.subject.clone()),
+            after: a.map(|s| s.subject.clone()),
+            summary: summarize(scope, day, period, b, a),
+        });
+    }
+    out
+}
+
+/// 두 스냅샷에 모두 있는 날의 바뀐 칸을 교사별, 반별로 찾는다.
+pub fn diff(old: &Snapshot, new: &Snapshot) -> Vec<TimetableChange> {
+    let old_days: BTreeSet<&str> = old.days.iter().map(String::as_str).collect();
+    let days: BTreeSet<&str> = new.days.iter().map(String::as_str).filter(|d| old_days.contains(d)).collect();
+    let mut out = diff_by(ChangeScope::Teacher, &old.slots, &new.slots, &days, |s| Some(s.teacher.as_str()));
+    out.extend(diff_by(ChangeScope::Class, &old.slots, &new.slots, &days, |s| s.class_name.as_deref()));
+    out
+}
+
+// ─── 저장 ─────────────────────────────────────────────────────────────────────
+
+pub fn ensure_tables(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS tbl_timetable_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source TEXT NOT NULL,
+            taken_at TEXT NOT NULL,
+            data TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS tbl_timetable_changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            snapshot_id INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            scope TEXT NOT NULL,
+            target TEXT NOT NULL,
+            day TEXT NOT NULL,
+            period INTEGER NOT NULL,
+            before_subject TEXT,
+            after_subject TEXT,
+            summary TEXT NOT NULL,
+            detected_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_timetable_changes_target ON tbl_timetable_changes(scope, target);",
+    )
+    .map_err(|e| format!("시간표 기록 테이블 생성 실패: {}", e))
+}
+
+/// 스냅샷을 기록한다. 직전 스냅샷과 같으면 아무것도 하지 않고 None,
+/// 다르면 저장하고 바뀐 칸을 돌려준다(그 출처의 첫 스냅샷이면 빈 목록).
+pub fn record(conn: &Connection, source: TimetableSource, snapshot: &Snapshot, now: NaiveDateTime) -> Result<Option<Vec<TimetableChange>>, String> {
+    ensure_tables(conn)?;
+    let data = serde_json::to_string(snapshot).map_err(|e| e.to_string())?;
+    let previous: Option<String> = conn
+        .query_row(
+            "SELECT data FROM tbl_timetable_snapshots WHERE source = ?1 ORDER BY id DESC LIMIT 1",
+            params![source.as_str()],
+            |r| r.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("시간표 스냅샷 조회 실패: {}", e))?;
+    if previous.as_deref() == Some(data.as_str()) {
+        return Ok(None);
+    }
+    // 형식이 바뀐 옛 스냅샷은 비교하지 않고 새 기준으로 삼는다.
+    let changes = previous
+        .and_then(|p| serde_json::from_str::<Snapshot>(&p).ok())
+        .map(|old| diff(&old, snapshot))
+        .unwrap_or_default();
+
+    let taken_at = now.format(TS_FORMAT).to_string();
+    conn.execute(
+        "INSERT INTO tbl_timetable_snapshots (source, taken_at, data) VALUES (?1, ?2, ?3)",
+        params![source.as_str(), taken_at, data],
+    )
+    .map_err(|e| format!("시간표 스냅샷 저장 실패: {}", e))?;
+    let snapshot_id = conn.last_insert_rowid();
+    for c in &changes {
+        conn.execute(
+            "INSERT INTO tbl_timetable_changes
+                (snapshot_id, source, scope, target, day, period, before_subject, after_subject, summary, detected_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![snapshot_id, source.as_str(), c.scope.as_str(), c.target, c.day, c.period, c.before, c.after, c.summary, taken_at],
+        )
+        .map_err(|e| format!("시간표 변경 저장 실패: {}", e))?;
+    }
+    conn.execute(
+        "DELETE FROM tbl_timetable_snapshots WHERE source = ?1 AND id NOT IN (
+            SELECT id FROM tbl_timetable_snapshots WHERE source = ?1 ORDER BY id DESC LIMIT ?2)",
+        params![source.as_str(), KEEP_SNAPSHOTS],
+    )
+    .map_err(|e| format!("시간표 스냅샷 정리 실패: {}", e))?;
+    Ok(Some(changes))
+}
+
+/// 기록된 변경 한 건.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeRecord {
+    pub id: i64,
+    pub source: String,
+    pub detected_at: String,
+    #[serde(flatten)]
+    pub change: TimetableChange,
+}
+
+/// 최근 변경 기록. `teacher`·`class_name` 을 주면 그 교사·반 기준 변경만.
+pub fn list_changes(conn: &Connection, teacher: Option<&str>, class_name: Option<&str>, limit: u32) -> Result<Vec<ChangeRecord>, String> {
+    ensure_tables(conn)?;
+    let (scope, target) = match (teacher, class_name) {
+        (Some(t), _) => (Some(ChangeScope::Teacher.as_str()), Some(t)),
+        (None, Some(c)) => (Some(ChangeScope::Class.as_str()), Some(c)),
+        (None, None) => (None, None),
+    };
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, source, scope, target, day, period, before_subject, after_subject, summary, detected_at
+             FROM tbl_timetable_changes
+             WHERE (?1 IS NULL OR scope = ?1) AND (?2 IS NULL OR target = ?2)
+             ORDER BY id DESC LIMIT ?3",
+        )
+        .map_err(|e| format!("시간표 변경 조회 실패: {}", e))?;
+    let rows = stmt
+        .query_map(params![scope, target, limit], |r| {
+            Ok(ChangeRecord {
+                id: r.get(0)?,
+                source: r.get(1)?,
+                change: TimetableChange {
+                    scope: ChangeScope::parse(&r.get::<_, String>(2)?),
+                    target: r.get(3)?,
+                    day: r.get(4)?,
+                    period: r.get(5)?,
+                    before: r.get(6)?,
+                    after: r.get(7)?,
+                    summary: r.get(8)?,
+                },
+                detected_at: r.get(9)?,
+            })
+        })
+        .map_err(|e| format!("시간표 변경 조회 실패: {}", e))?;
+    Ok(rows.flatten().collect())
+}
+
+// ─── 실행 ─────────────────────────────────────────────────────────────────────
+
+/// `timetable-changed` 이벤트 payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimetableChangeNotice {
+    pub source: &'static str,
+    /// 기본 교사. 설정하지 않았으면 None.
+    pub teacher: Option<String>,
+    /// 기본 교사의 바뀐 칸.
+    pub mine: Vec<TimetableChange>,
+    /// 이번에 찾은 전체 변경(교사별·반별).
+    pub total: usize,
+}
+
+fn seoul_now() -> NaiveDateTime {
+    let kst = chrono::FixedOffset::east_opt(9 * 3600).unwrap();
+    chrono::Utc::now().with_timezone(&kst).naive_local()
+}
+
+fn default_teacher() -> Option<String> {
+    get_registry_value("SchoolDefaultTeacher".to_string())
+        .ok()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 학교 위젯에 설정된 출처를 읽어 기록하고, 바뀐 칸이 있으면 이벤트를 보낸다.
+fn check(app: &AppHandle) -> Result<Option<TimetableChangeNotice>, String> {
+    let source = TimetableSource::from_registry();
+    let provider = source.load()?;
+    let now = seoul_now();
+    let snapshot = take_snapshot(provider.as_ref(), now.date());
+    let Some(changes) = record(&open_app_db(app)?, source, &snapshot, now)? else {
+        return Ok(None);
+    };
+    if changes.is_empty() {
+        return Ok(None);
+    }
+    let teacher = default_teacher();
+    let mine = changes
+        .iter()
+        .filter(|c| c.scope == ChangeScope::Teacher && Some(&c.target) == teacher.as_ref())
+        .cloned()
+        .collect();
+    let notice = TimetableChangeNotice { source: source.as_str(), teacher, mine, total: changes.len() };
+    let _ = app.emit("timetable-changed", &notice);
+    Ok(Some(notice))
+}
+
+/// 앱 시작 시 한 번 호출한다. 주기적으로 시간표를 읽어 변경을 기록한다.
+/// 시간표 파일이 없는 PC 에서는 조용히 넘어간다.
+pub fn start_watcher(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        if let Err(e) = check(&app) {
+            if !e.contains("not found") {
+                eprintln!("[timetable] 시간표 변경 확인 실패: {}", e);
+            }
+        }
+        std::thread::sleep(Duration::from_secs(TICK_SECS));
+    });
+}
+
+/// 지금 시간표를 다시 읽어 변경을 확인한다. 바뀐 칸이 없으면 None.
+#[tauri::command]
+pub fn check_timetable_changes(app: AppHandle) -> Result<Option<TimetableChangeNotice>, String> {
+    check(&app)
+}
+
+/// 최근 시간표 변경 기록. `teacher` 나 `class_name` 으로 거를 수 있다.
+#[tauri::command]
+pub fn get_timetable_changes(
+    app: AppHandle,
+    teacher: Option<String>,
+    class_name: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ChangeRecord>, String> {
+    list_changes(&open_app_db(&app)?, teacher.as_deref(), class_name.as_deref(), limit.unwrap_or(50))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(day: &str, period: u32, teacher: &str, subject: &str, class_name: &str) -> Slot {
+        Slot {
+            day: day.to_string(),
+            period,
+            teacher: teacher.to_string(),
+            subject: subject.to_string(),
+            class_name: Some(class_name.to_string()),
+        }
+    }
+
+    fn snap(days: &[&str], slots: Vec<Slot>) -> Snapshot {
+        Snapshot { days: days.iter().map(|d| d.to_string()).collect(), slots }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, TS_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn diff_reports_teacher_and_class_changes() {
+        let old = snap(&["wed"], vec![slot("wed", 3, "김교사", "국어", "2-3"), slot("wed", 4, "박교사", "수학", "2-1")]);
+        let new = snap(&["wed"], vec![slot("wed", 3, "박교사", "수학", "2-3"), slot("wed", 4, "박교사", "수학", "2-1")]);
+        let changes = diff(&old, &new);
+        let lines: Vec<_> = changes.iter().map(|c| (c.scope, c.target.as_str(), c.summary.as_str())).collect();
+        assert_eq!(
+            lines,
+            vec![
+                (ChangeScope::Teacher, "김교사", "수 3교시: 국어 → 빈 시간 (2-3)"),
+                (ChangeScope::Teacher, "박교사", "수 3교시: 빈 시간 → 수학 (2-3)"),
+                (ChangeScope::Class, "2-3", "수 3교시: 국어 김교사 → 수학 박교사"),
+            ]
+        );
+
+        // 같은 교사가 다른 과목으로 바뀐 경우.
+        let new = snap(&["wed"], vec![slot("wed", 3, "김교사", "수학", "2-3"), slot("wed", 4, "박교사", "수학", "2-1")]);
+        assert_eq!(diff(&old, &new)[0].summary, "수 3교시: 국어 → 수학 (2-3)");
+    }
+
+    #[test]
+    fn diff_ignores_days_outside_both_windows() {
+        let old = snap(&["2026-03-16", "2026-03-17"], vec![slot("2026-03-16", 1, "김교사", "국어", "1-1")]);
+        let new = snap(&["2026-03-17", "2026-03-18"], vec![slot("2026-03-18", 2, "김교사", "국어", "1-2")]);
+        assert!(diff(&old, &new).is_empty());
+
+        let new = snap(&["2026-03-17"], vec![slot("2026-03-17", 2, "김교사", "국어", "1-2")]);
+        assert_eq!(diff(&old, &new)[0].summary, "3/17(화) 2교시: 빈 시간 → 국어 (1-2)");
+    }
+
+    #[test]
+    fn record_keeps_history_only_when_changed() {
+        let conn = Connection::open_in_memory().unwrap();
+        let first = snap(&["mon"], vec![slot("mon", 1, "김교사", "국어", "1-1")]);
+        assert_eq!(record(&conn, TimetableSource::Comcigan, &first, at("2026-03-02T08:00:00")).unwrap(), Some(vec![]));
+        assert_eq!(record(&conn, TimetableSource::Comcigan, &first, at("2026-03-02T08:10:00")).unwrap(), None);
+
+        let second = snap(&["mon"], vec![slot("mon", 1, "김교사", "국어", "1-2")]);
+        let changes = record(&conn, TimetableSource::Comcigan, &second, at("2026-03-02T08:20:00")).unwrap().unwrap();
+        assert_eq!(changes[0].summary, "월 1교시: 국어 1-1 → 국어 1-2");
+        // 다른 출처의 첫 스냅샷은 비교하지 않는다.
+        assert_eq!(record(&conn, TimetableSource::Appin, &second, at("2026-03-02T08:20:00")).unwrap(), Some(vec![]));
+
+        let mine = list_changes(&conn, Some("김교사"), None, 10).unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].detected_at, "2026-03-02T08:20:00");
+        let class = list_changes(&conn, None, Some("1-1"), 10).unwrap();
+        assert_eq!(class[0].change.summary, "월 1교시: 국어 → 빈 시간 (김교사)");
+        assert_eq!(list_changes(&conn, None, None, 10).unwrap().len(), 3);
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM tbl_timetable_snapshots", [], |r| r.get(0)).unwrap();
+        assert_eq!(count, 3);
+    }
+}
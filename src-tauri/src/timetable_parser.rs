@@ -52,7 +52,12 @@ fn decode_cp949(bytes: &[u8]) -> String {
 
 pub fn parse_timetable() -> Result<TimetableData, String> {
     let gwa_path = get_latest_gwa_file().ok_or("Gwa file not found")?;
-    let file = fs::File::open(&gwa_path).map_err(|e| e.to_string())?;
+    parse_timetable_file(&gwa_path)
+}
+
+/// .Gwa 파일 하나를 읽는다.
+pub fn parse_timetable_file(gwa_path: &Path) -> Result<TimetableData, String> {
+    let file = fs::File::open(gwa_path).map_err(|e| e.to_string())?;
     let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
 
     let mut teachers = Vec::new();
@@ -17,11 +17,7 @@ use window_vibrancy::apply_acrylic;
 use window_vibrancy::{apply_vibrancy, NSVisualEffectMaterial};
 #[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
 use window_vibrancy::apply_blur;
-use chrono::{Local, NaiveTime};
-use winreg::enums::*;
-use winreg::RegKey;
-
-const REG_BASE: &str = r"Software\\HyperCool";
+use chrono::{Local, NaiveDateTime, NaiveTime};
 
 /// Brotli로 압축된 데이터를 압축 해제
 pub fn decompress_brotli(compressed_data: &[u8]) -> Result<String, std::io::Error> {
@@ -134,7 +130,7 @@ pub fn apply_rounded_corners<R: Runtime>(window: &tauri::WebviewWindow<R>) {
 }
 
 /// HHMM 형식 문자열을 NaiveTime으로 변환하는 헬퍼 함수
-fn parse_hhmm(hhmm: &str) -> Option<NaiveTime> {
+pub(crate) fn parse_hhmm(hhmm: &str) -> Option<NaiveTime> {
     if hhmm.len() != 4 {
         return None;
     }
@@ -154,64 +150,28 @@ fn parse_hhmm(hhmm: &str) -> Option<NaiveTime> {
 
 /// 현재 시간이 수업 시간인지 확인하는 함수
 pub fn is_class_time() -> bool {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    let reg_base = REG_BASE;
-
-    let class_times_json = match hkcu.open_subkey(reg_base) {
-        Ok(subkey) => match subkey.get_value::<String, _>("ClassTimes") {
-            Ok(v) if !v.is_empty() => Some(v),
-            _ => None,
-        },
-        _ => None,
-    };
-
-    // 수업 시간이 설정되지 않았으면 false 반환 (항상 표시)
-    let class_times: Vec<String> = match class_times_json {
-        Some(json) => match serde_json::from_str::<Vec<String>>(&json) {
-            Ok(times) if !times.is_empty() => times,
-            _ => return false,
-        },
-        None => return false,
-    };
-
-    let now = Local::now().time();
-
-    // 각 수업 시간대를 체크
-    for time_range in class_times {
-        // HHMM-HHMM 형식 파싱 (예: "0830-0920")
-        let parts: Vec<&str> = time_range.split('-').collect();
-        if parts.len() != 2 {
-            continue;
-        }
+    is_class_time_at(Local::now().naive_local())
+}
 
-        let start_str = parts[0].trim();
-        let end_str = parts[1].trim();
-
-        // HHMM 형식을 NaiveTime으로 변환
-        let start_time = match parse_hhmm(start_str) {
-            Some(t) => t,
-            None => continue,
-        };
-
-        let end_time = match parse_hhmm(end_str) {
-            Some(t) => t,
-            None => continue,
-        };
-
-        // 현재 시간이 이 시간대 내에 있는지 확인
-        let in_range = if start_time <= end_time {
-            now >= start_time && now <= end_time
-        } else {
-            // 자정을 넘어가는 경우
-            now >= start_time || now <= end_time
-        };
-
-        if in_range {
-            return true;
-        }
-    }
+/// 주어진 시각이 그날 종 시간표(`bell_schedule`)의 교시 안인지 확인한다.
+/// 알림의 방해 금지 판정처럼 "지금"이 아닌 시각을 볼 때 쓴다.
+/// 종 시간표를 설정하지 않았으면 수업 시간으로 보지 않는다.
+pub fn is_class_time_at(now: NaiveDateTime) -> bool {
+    class_time_in(crate::bell_schedule::load_configured().as_ref(), now)
+}
 
-    false
+fn class_time_in(schedule: Option<&crate::bell_schedule::BellSchedule>, now: NaiveDateTime) -> bool {
+    schedule.is_some_and(|s| s.profile_for(now.date()).is_class_time(now.time()))
+}
+
+/// `now` 가 [start, end] 안인지. start > end 면 자정을 넘어가는 구간으로 본다.
+pub fn in_time_range(start: NaiveTime, end: NaiveTime, now: NaiveTime) -> bool {
+    if start <= end {
+        now >= start && now <= end
+    } else {
+        // 자정을 넘어가는 경우
+        now >= start || now <= end
+    }
 }
 
 #[cfg(test)]
@@ -232,6 +192,28 @@ mod tests {
         assert_eq!(parse_hhmm("abcd"), None);
     }
 
+    #[test]
+    fn test_in_time_range() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert!(in_time_range(t(8, 30), t(9, 20), t(9, 0)));
+        assert!(!in_time_range(t(8, 30), t(9, 20), t(9, 21)));
+        // 자정을 넘어가는 구간
+        assert!(in_time_range(t(22, 0), t(7, 0), t(23, 30)));
+        assert!(in_time_range(t(22, 0), t(7, 0), t(6, 59)));
+        assert!(!in_time_range(t(22, 0), t(7, 0), t(12, 0)));
+    }
+
+    #[test]
+    fn test_class_time_requires_configured_schedule() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        // 종 시간표도 예전 ClassTimes 도 없으면 기본 프로필 시각이라도 수업 시간이 아니다.
+        assert!(!class_time_in(None, at("2026-03-02 09:00")));
+
+        let schedule = crate::bell_schedule::BellSchedule::default();
+        assert!(class_time_in(Some(&schedule), at("2026-03-02 09:00")));
+        assert!(!class_time_in(Some(&schedule), at("2026-03-02 09:25")));
+    }
+
     #[test]
     fn test_parse_file_paths() {
         // Format: ...|...|...|...|filename|...|...|filename|...
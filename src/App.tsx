@@ -33,7 +33,6 @@ function App() {
   // Custom Hooks
   const { 
     udbPath, setUdbPath, 
-    classTimes, setClassTimes, 
     uiScale, setUiScale, 
     skippedUpdateVersion, setSkippedUpdateVersion,
     sidebarCollapsed, setSidebarCollapsed,
@@ -317,11 +316,6 @@ function App() {
             setUdbPath={setUdbPath}
             pickUdb={openUdbPicker}
             saveToRegistry={saveToRegistry}
-            classTimes={classTimes}
-            setClassTimes={(times) => {
-              setClassTimes(times);
-              saveToRegistry('ClassTimes', JSON.stringify(times));
-            }}
             uiScale={uiScale}
             setUiScale={(scale) => {
               setUiScale(scale);
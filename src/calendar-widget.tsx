@@ -31,8 +31,19 @@ interface TodoItem {
   updatedAt?: string;
   color?: string | null;
   isDeleted?: boolean;
+  referenceId?: string | null;
+  priority?: ManualTodo['priority'];
+  itemKind?: ManualTodo['itemKind'];
+  hasAttachment?: boolean;
 }
 
+const ITEM_KIND_LABELS: Record<string, string> = {
+  event: '행사',
+  deadline: '마감',
+  task: '할 일',
+  notice: '공지',
+};
+
 interface CalendarWidgetProps {
   isPinned?: boolean;
   onPinnedChange?: (pinned: boolean) => void;
@@ -117,7 +128,11 @@ function CalendarWidget({ isPinned = false, onPinnedChange }: CalendarWidgetProp
         isCompleted: completedTodos.has(t.id),
         updatedAt: t.updatedAt,
         color: t.color ?? null,
-        isDeleted: t.isDeleted
+        isDeleted: t.isDeleted,
+        referenceId: t.referenceId ?? null,
+        priority: t.priority,
+        itemKind: t.itemKind,
+        hasAttachment: t.hasAttachment
       }))
     ].filter(t => !t.isDeleted);
   }, [keptMessages, manualTodos, deadlines, calendarTitles, completedTodos]);
@@ -126,6 +141,29 @@ function CalendarWidget({ isPinned = false, onPinnedChange }: CalendarWidgetProp
 
   const lastLoadTimeRef = useRef(0);
 
+  // 첨부 배지 툴팁: 원본 메시지의 첨부 카탈로그를 처음 마우스를 올릴 때 한 번만 조회
+  const [attachmentNames, setAttachmentNames] = useState<Record<string, string[]>>({});
+  const loadAttachmentNames = useCallback(async (todo: TodoItem) => {
+    const ref = todo.referenceId;
+    if (!todo.hasAttachment || !ref || isNaN(Number(ref)) || attachmentNames[ref]) return;
+    try {
+      const links = await invoke<{ file_name: string }[]>('get_message_attachments', { messageId: Number(ref) });
+      setAttachmentNames(prev => ({ ...prev, [ref]: links.map(l => l.file_name) }));
+    } catch (err) {
+      console.error('Failed to load attachments:', err);
+    }
+  }, [attachmentNames]);
+
+  const todoTooltip = (todo: TodoItem): string | undefined => {
+    const parts: string[] = [];
+    if (todo.itemKind) parts.push(`[${ITEM_KIND_LABELS[todo.itemKind] ?? todo.itemKind}]`);
+    if (todo.priority === 'high') parts.push('긴급');
+    const files = todo.referenceId ? attachmentNames[todo.referenceId] : undefined;
+    if (files && files.length > 0) parts.push(`첨부: ${files.join(', ')}`);
+    else if (todo.hasAttachment) parts.push('첨부 있음');
+    return parts.length > 0 ? parts.join(' · ') : undefined;
+  };
+
   const loadTodos = useCallback(async () => {
     const now = Date.now();
     if (now - lastLoadTimeRef.current < 1000) {
@@ -162,9 +200,13 @@ function CalendarWidget({ isPinned = false, onPinnedChange }: CalendarWidgetProp
             updatedAt: item.updatedAt,
             calendarTitle: item.title,
             color: item.color ?? null,
-            isDeleted: item.isDeleted
+            isDeleted: item.isDeleted,
+            referenceId: item.referenceId ?? null,
+            priority: item.priority ?? undefined,
+            itemKind: item.itemKind ?? undefined,
+            hasAttachment: item.hasAttachment ?? undefined
           });
-        } else if (item.type === 'period_schedule' || item.type === 'desktopcal_event') {
+        } else if (item.type === 'period_schedule' || item.type === 'desktopcal_event' || item.type === 'school_schedule') {
           newPeriodSchedules.push({
             id: item.id,
             content: item.content || '',
@@ -501,7 +543,11 @@ function CalendarWidget({ isPinned = false, onPinnedChange }: CalendarWidgetProp
                               setContextMenu({ x: e.clientX, y: e.clientY, todo });
                             }}
                             data-todo-id={todo.id} // 마우스 드래그를 위해 data-todo-id 추가
+                            title={todoTooltip(todo)}
+                            onMouseEnter={() => loadAttachmentNames(todo)}
                           >
+                            {todo.priority === 'high' && <span className="calendar-todo-badge calendar-todo-badge-urgent">!</span>}
+                            {todo.hasAttachment && <span className="calendar-todo-badge">📎</span>}
                             {title}
                           </div>
                           {isDragOver && dragOverPosition === 'below' && (
@@ -0,0 +1,336 @@
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+
+interface BellSlot {
+  label: string;
+  start: string;
+  end: string;
+  period: number | null;
+}
+
+interface BellProfile {
+  id: string;
+  name: string;
+  slots: BellSlot[];
+}
+
+interface BellRule {
+  profile: string;
+  from: string | null;
+  to: string | null;
+  weekdays: number[];
+  note: string;
+}
+
+interface BellOverride {
+  date: string;
+  profile: string;
+  note: string;
+}
+
+interface BellSchedule {
+  profiles: BellProfile[];
+  defaultProfile: string;
+  calendar: BellRule[];
+  overrides: BellOverride[];
+}
+
+const WEEKDAYS = ['월', '화', '수', '목', '금', '토', '일'];
+
+const RemoveIcon = () => (
+  <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
+    <path d="M18 6L6 18M6 6l12 12"></path>
+  </svg>
+);
+
+const AddIcon = () => (
+  <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
+    <line x1="12" y1="5" x2="12" y2="19"></line>
+    <line x1="5" y1="12" x2="19" y2="12"></line>
+  </svg>
+);
+
+// 종 시간표 설정: 프로필(평소·단축수업·시험 …)별 교시 시각, 기간·요일 규칙, 날짜별 지정.
+export const BellScheduleSettings: React.FC = () => {
+  const [schedule, setSchedule] = useState<BellSchedule | null>(null);
+  const [selectedId, setSelectedId] = useState('');
+  const [message, setMessage] = useState<{ text: string; error: boolean } | null>(null);
+
+  useEffect(() => {
+    invoke<BellSchedule>('get_bell_schedule')
+      .then(s => { setSchedule(s); setSelectedId(s.defaultProfile); })
+      .catch(e => setMessage({ text: `종 시간표를 불러오지 못했습니다: ${e}`, error: true }));
+  }, []);
+
+  if (!schedule) {
+    return message ? <div className="field-description">{message.text}</div> : null;
+  }
+
+  const profile = schedule.profiles.find(p => p.id === selectedId) ?? schedule.profiles[0];
+
+  const update = (next: Partial<BellSchedule>) => {
+    setSchedule({ ...schedule, ...next });
+    setMessage(null);
+  };
+
+  const updateProfile = (next: BellProfile) =>
+    update({ profiles: schedule.profiles.map(p => p.id === next.id ? next : p) });
+
+  const updateSlot = (index: number, next: Partial<BellSlot>) =>
+    updateProfile({ ...profile, slots: profile.slots.map((s, i) => i === index ? { ...s, ...next } : s) });
+
+  const addSlot = () => {
+    const lastPeriod = Math.max(0, ...profile.slots.map(s => s.period ?? 0));
+    const last = profile.slots[profile.slots.length - 1];
+    const start = last?.end ?? '09:00';
+    updateProfile({
+      ...profile,
+      slots: [...profile.slots, { label: `${lastPeriod + 1}교시`, start, end: start, period: lastPeriod + 1 }],
+    });
+  };
+
+  const addProfile = () => {
+    const id = `custom-${Date.now().toString(36)}`;
+    update({ profiles: [...schedule.profiles, { id, name: '새 프로필', slots: profile.slots.map(s => ({ ...s })) }] });
+    setSelectedId(id);
+  };
+
+  const removeProfile = () => {
+    const rest = schedule.profiles.filter(p => p.id !== profile.id);
+    update({
+      profiles: rest,
+      calendar: schedule.calendar.filter(r => r.profile !== profile.id),
+      overrides: schedule.overrides.filter(o => o.profile !== profile.id),
+    });
+    setSelectedId(schedule.defaultProfile);
+  };
+
+  const updateRule = (index: number, next: Partial<BellRule>) =>
+    update({ calendar: schedule.calendar.map((r, i) => i === index ? { ...r, ...next } : r) });
+
+  const updateOverride = (index: number, next: Partial<BellOverride>) =>
+    update({ overrides: schedule.overrides.map((o, i) => i === index ? { ...o, ...next } : o) });
+
+  const save = async () => {
+    try {
+      await invoke('set_bell_schedule', { schedule });
+      setMessage({ text: '저장했습니다.', error: false });
+    } catch (e) {
+      setMessage({ text: String(e), error: true });
+    }
+  };
+
+  const profileSelect = (value: string, onChange: (id: string) => void) => (
+    <select value={value} onChange={(e) => onChange(e.target.value)}>
+      {schedule.profiles.map(p => <option key={p.id} value={p.id}>{p.name}</option>)}
+    </select>
+  );
+
+  return (
+    <>
+      <div className="row" style={{ flexWrap: 'wrap', gap: '6px' }}>
+        {schedule.profiles.map(p => (
+          <button
+            key={p.id}
+            className={`bell-chip${p.id === profile.id ? ' active' : ''}`}
+            onClick={() => setSelectedId(p.id)}
+          >
+            {p.name}{p.id === schedule.defaultProfile ? ' (기본)' : ''}
+          </button>
+        ))}
+        <button onClick={addProfile} className="add-time-btn"><AddIcon />프로필 추가</button>
+      </div>
+
+      <div className="row" style={{ marginTop: '12px', gap: '8px' }}>
+        <input
+          type="text"
+          value={profile.name}
+          onChange={(e) => updateProfile({ ...profile, name: e.target.value })}
+          placeholder="프로필 이름"
+          style={{ flex: 1 }}
+        />
+        <button
+          onClick={() => update({ defaultProfile: profile.id })}
+          disabled={profile.id === schedule.defaultProfile}
+        >
+          기본으로
+        </button>
+        <button
+          onClick={removeProfile}
+          disabled={profile.id === schedule.defaultProfile || schedule.profiles.length <= 1}
+        >
+          삭제
+        </button>
+      </div>
+
+      <div className="class-times-container">
+        <div className="class-time-header">
+          <span className="col-period">이름</span>
+          <span className="col-period">교시</span>
+          <span className="col-time">시작 시간</span>
+          <span className="col-sep"></span>
+          <span className="col-time">종료 시간</span>
+          <span className="col-action"></span>
+        </div>
+        <div className="class-times-list">
+          {profile.slots.map((slot, index) => (
+            <div key={index} className="class-time-row">
+              <input
+                type="text"
+                className="time-input"
+                style={{ flex: '0 0 70px', marginRight: '8px' }}
+                value={slot.label}
+                onChange={(e) => updateSlot(index, { label: e.target.value })}
+              />
+              <input
+                type="number"
+                min={1}
+                className="time-input"
+                style={{ flex: '0 0 62px', marginRight: '8px' }}
+                value={slot.period ?? ''}
+                placeholder="-"
+                title="비우면 점심·쉬는 시간처럼 교시가 아닌 칸"
+                onChange={(e) => updateSlot(index, { period: e.target.value ? parseInt(e.target.value, 10) : null })}
+              />
+              <input
+                type="time"
+                className="time-input"
+                value={slot.start}
+                onChange={(e) => updateSlot(index, { start: e.target.value })}
+              />
+              <span className="time-sep">~</span>
+              <input
+                type="time"
+                className="time-input"
+                value={slot.end}
+                onChange={(e) => updateSlot(index, { end: e.target.value })}
+              />
+              <button
+                onClick={() => updateProfile({ ...profile, slots: profile.slots.filter((_, i) => i !== index) })}
+                className="icon-btn remove-btn"
+                title="삭제"
+              >
+                <RemoveIcon />
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+      <div className="row" style={{ marginTop: '12px' }}>
+        <button onClick={addSlot} className="add-time-btn"><AddIcon />칸 추가</button>
+      </div>
+
+      <div className="field-description" style={{ marginTop: '16px' }}>
+        기간·요일 규칙 — 위에 있는 규칙이 우선합니다.
+      </div>
+      <div className="class-times-container">
+        <div className="class-times-list">
+          {schedule.calendar.map((rule, index) => (
+            <div key={index} className="class-time-row" style={{ flexWrap: 'wrap', gap: '6px' }}>
+              {profileSelect(rule.profile, (id) => updateRule(index, { profile: id }))}
+              <input
+                type="date"
+                className="time-input"
+                value={rule.from ?? ''}
+                onChange={(e) => updateRule(index, { from: e.target.value || null })}
+              />
+              <span className="time-sep">~</span>
+              <input
+                type="date"
+                className="time-input"
+                value={rule.to ?? ''}
+                onChange={(e) => updateRule(index, { to: e.target.value || null })}
+              />
+              <div style={{ display: 'flex', gap: '2px' }}>
+                {WEEKDAYS.map((label, i) => {
+                  const day = i + 1;
+                  const on = rule.weekdays.includes(day);
+                  return (
+                    <button
+                      key={day}
+                      className={`bell-chip${on ? ' active' : ''}`}
+                      onClick={() => updateRule(index, {
+                        weekdays: on ? rule.weekdays.filter(w => w !== day) : [...rule.weekdays, day].sort((a, b) => a - b),
+                      })}
+                    >
+                      {label}
+                    </button>
+                  );
+                })}
+              </div>
+              <input
+                type="text"
+                value={rule.note}
+                placeholder="메모 (예: 중간고사)"
+                onChange={(e) => updateRule(index, { note: e.target.value })}
+                style={{ flex: 1 }}
+              />
+              <button
+                onClick={() => update({ calendar: schedule.calendar.filter((_, i) => i !== index) })}
+                className="icon-btn remove-btn"
+                title="삭제"
+              >
+                <RemoveIcon />
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+      <div className="row" style={{ marginTop: '12px' }}>
+        <button
+          onClick={() => update({ calendar: [...schedule.calendar, { profile: profile.id, from: null, to: null, weekdays: [], note: '' }] })}
+          className="add-time-btn"
+        >
+          <AddIcon />규칙 추가
+        </button>
+      </div>
+
+      <div className="field-description" style={{ marginTop: '16px' }}>
+        날짜별 지정 — 규칙보다 우선합니다.
+      </div>
+      <div className="class-times-container">
+        <div className="class-times-list">
+          {schedule.overrides.map((o, index) => (
+            <div key={index} className="class-time-row" style={{ gap: '6px' }}>
+              <input
+                type="date"
+                className="time-input"
+                value={o.date}
+                onChange={(e) => updateOverride(index, { date: e.target.value })}
+              />
+              {profileSelect(o.profile, (id) => updateOverride(index, { profile: id }))}
+              <input
+                type="text"
+                value={o.note}
+                placeholder="메모 (예: 개교기념일 행사)"
+                onChange={(e) => updateOverride(index, { note: e.target.value })}
+                style={{ flex: 1 }}
+              />
+              <button
+                onClick={() => update({ overrides: schedule.overrides.filter((_, i) => i !== index) })}
+                className="icon-btn remove-btn"
+                title="삭제"
+              >
+                <RemoveIcon />
+              </button>
+            </div>
+          ))}
+        </div>
+      </div>
+      <div className="row" style={{ marginTop: '12px' }}>
+        <button
+          onClick={() => update({ overrides: [...schedule.overrides, { date: new Date().toLocaleDateString('sv-SE'), profile: profile.id, note: '' }] })}
+          className="add-time-btn"
+        >
+          <AddIcon />날짜 추가
+        </button>
+        <button onClick={save}>저장</button>
+      </div>
+      {message && (
+        <div className="field-description" style={message.error ? { color: 'var(--danger)' } : undefined}>
+          {message.text}
+        </div>
+      )}
+    </>
+  );
+};
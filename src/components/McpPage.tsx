@@ -21,8 +21,19 @@ interface ClaudeConfigResult {
   already_configured: boolean;
 }
 
+type BriefingBackendKind = 'claude_cli' | 'openai_compat' | 'rules';
+
+interface BriefingBackendSettings {
+  kind: BriefingBackendKind;
+  endpoint: string;
+  model: string;
+  hasApiKey: boolean;
+}
+
 interface BriefingStatus {
   enabled: boolean;
+  backend: BriefingBackendKind;
+  backend_error: string | null;
   claude_installed: boolean;
   claude_path: string | null;
   authed: boolean;
@@ -31,6 +42,21 @@ interface BriefingStatus {
   last_new_count: number;
   last_error: string | null;
   last_seen_id: number;
+  budget: BriefingBudgetStatus | null;
+}
+
+interface BriefingUsageTotals {
+  runs: number;
+  inputTokens: number;
+  outputTokens: number;
+  costUsd: number;
+}
+
+interface BriefingBudgetStatus {
+  today: BriefingUsageTotals;
+  month: BriefingUsageTotals;
+  budget: { dailyUsd: number | null; monthlyUsd: number | null };
+  paused: string | null;
 }
 
 interface BriefingRunResult {
@@ -41,7 +67,21 @@ interface BriefingRunResult {
   reason: string | null;
 }
 
+interface ScheduleRevision {
+  id: number;
+  schedule_id: string;
+  title: string;
+  old_message_id: number | null;
+  new_message_id: number | null;
+  old_start: string | null;
+  new_start: string | null;
+  status: 'applied' | 'pending' | 'dismissed';
+  created_at: string;
+  resolved_at: string | null;
+}
+
 interface BriefingDebugReport {
+  backend: BriefingBackendKind;
   claude_installed: boolean;
   claude_path: string | null;
   authed: boolean;
@@ -84,6 +124,13 @@ function describeRun(r: BriefingRunResult): string {
   return `완료 · 신규 ${r.new_count}건${r.skipped ? ` (건너뜀 ${r.skipped})` : ''}`;
 }
 
+// 정정 기록의 시작 값: "2026-07-15T14:20:00+09:00" → "07/15 14:20", 종일이면 "07/15".
+function formatRevisionStart(start: string | null): string {
+  if (!start) return '—';
+  const date = start.slice(5, 10).replace('-', '/');
+  return start.length > 10 ? `${date} ${start.slice(11, 16)}` : date;
+}
+
 const CONFIG_JSON = `{
   "mcpServers": {
     "hypercool": {
@@ -170,6 +217,12 @@ export const McpPage = () => {
   const [debugRunning, setDebugRunning] = useState(false);
   const [debugReport, setDebugReport] = useState<BriefingDebugReport | null>(null);
   const [debugError, setDebugError] = useState<string | null>(null);
+  const [backendSettings, setBackendSettings] = useState<BriefingBackendSettings | null>(null);
+  const [backendSaving, setBackendSaving] = useState(false);
+  const [backendSaveError, setBackendSaveError] = useState<string | null>(null);
+  // 새로 입력한 API 키. 비워 두면 저장된 키를 그대로 쓴다.
+  const [backendApiKey, setBackendApiKey] = useState('');
+  const [pendingRevisions, setPendingRevisions] = useState<ScheduleRevision[]>([]);
 
   const load = useCallback(async () => {
     try {
@@ -191,18 +244,60 @@ export const McpPage = () => {
   useEffect(() => {
     load();
     invoke<boolean>('check_node_installed').then(setNodeInstalled).catch(() => setNodeInstalled(false));
+    invoke<BriefingBackendSettings>('get_briefing_backend_settings').then(setBackendSettings).catch(() => {});
   }, [load]);
 
+  // 백엔드가 지금 실행 가능한지(claude CLI 면 설치 여부, HTTP 면 엔드포인트 설정 여부).
+  const backendReady = !!briefing && !briefing.backend_error;
+
+  // apiKey: undefined 면 저장된 키 유지, '' 면 삭제.
+  const saveBackendSettings = async (apiKey: string | undefined = backendApiKey || undefined) => {
+    if (!backendSettings || backendSaving) return;
+    setBackendSaving(true);
+    setBackendSaveError(null);
+    try {
+      const { kind, endpoint, model } = backendSettings;
+      await invoke('set_briefing_backend_settings', { kind, endpoint, model, apiKey });
+      setBackendApiKey('');
+      setBackendSettings(await invoke<BriefingBackendSettings>('get_briefing_backend_settings'));
+      const s = await invoke<BriefingStatus>('get_briefing_agent_status');
+      setBriefing(s);
+    } catch (e: unknown) {
+      setBackendSaveError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setBackendSaving(false);
+    }
+  };
+
+  // 정정 메시지로 바뀐 일정 중 사용자가 고친 것(자동 반영 보류) — 검토 대기 목록.
+  const loadRevisions = useCallback(() => {
+    invoke<ScheduleRevision[]>('get_schedule_revisions', { pendingOnly: true })
+      .then(setPendingRevisions)
+      .catch(() => {});
+  }, []);
+
   // 브리핑 에이전트 실행 상태 갱신(자동 실행/완료 시 백엔드가 emit).
   useEffect(() => {
+    loadRevisions();
     const unlisten = listen('briefing-status', () => {
       invoke<BriefingStatus>('get_briefing_agent_status').then(setBriefing).catch(() => {});
+      loadRevisions();
     });
     return () => { void unlisten.then(u => u()); };
-  }, []);
+  }, [loadRevisions]);
+
+  const resolveRevision = async (id: number, apply: boolean) => {
+    try {
+      await invoke('resolve_schedule_revision', { id, apply });
+    } catch (e) {
+      console.error('일정 변경 처리 실패:', e);
+    } finally {
+      loadRevisions();
+    }
+  };
 
   const toggleBriefing = async () => {
-    if (!briefing || briefingToggling || !briefing.claude_installed) return;
+    if (!briefing || briefingToggling || !backendReady) return;
     setBriefingToggling(true);
     try {
       await invoke('set_briefing_agent_enabled', { enabled: !briefing.enabled });
@@ -216,7 +311,7 @@ export const McpPage = () => {
   };
 
   const runBriefingNow = async () => {
-    if (briefingRunning || debugRunning || !briefing?.claude_installed) return;
+    if (briefingRunning || debugRunning || !backendReady) return;
     setBriefingRunning(true);
     setRunResult(null);
     try {
@@ -233,7 +328,7 @@ export const McpPage = () => {
 
   // 디버그: 검색 DB 동기화 → 최근 10개 메시지로 강제 1회 실행(진행 위치 미변경) → 상세 리포트.
   const runBriefingDebug = async () => {
-    if (briefingRunning || debugRunning || !briefing?.claude_installed) return;
+    if (briefingRunning || debugRunning || !backendReady) return;
     setDebugRunning(true);
     setDebugReport(null);
     setDebugError(null);
@@ -295,14 +390,7 @@ export const McpPage = () => {
   };
 
   // 카드 1(자동 일정화) 준비 항목.
-  const autoPrereqs: PrereqRow[] = [
-    {
-      key: 'udb',
-      ready: !!coolStatus?.udb_configured,
-      title: '쿨메신저 연결',
-      purpose: coolStatus ? `메시지 ${coolStatus.search_db_count.toLocaleString()}건 색인됨` : '받은 메시지를 읽어옵니다.',
-      fix: <>쿨메신저에 로그인해 메시지를 한 번 확인한 뒤 HyperCool을 재시작하세요. ‘메시지 분류’ 화면의 ‘파일 선택’으로 <code>.udb</code> 파일을 직접 지정할 수도 있습니다.</>,
-    },
+  const claudePrereqs: PrereqRow[] = [
     {
       key: 'claude',
       ready: !!briefing?.claude_installed,
@@ -318,6 +406,26 @@ export const McpPage = () => {
       fix: <>터미널에서 <code>claude</code> 로 로그인하거나 <code>ANTHROPIC_API_KEY</code> 를 설정하세요.</>,
     },
   ];
+  const backendPrereqs: PrereqRow[] = briefing?.backend === 'claude_cli' || !briefing
+    ? claudePrereqs
+    : [{
+        key: 'backend',
+        ready: backendReady,
+        title: briefing.backend === 'rules' ? '규칙 기반 추출' : 'LLM 엔드포인트',
+        purpose: '메시지에서 일정을 뽑아내는 추출 방식입니다.',
+        fix: <>{briefing.backend_error}</>,
+      }];
+
+  const autoPrereqs: PrereqRow[] = [
+    {
+      key: 'udb',
+      ready: !!coolStatus?.udb_configured,
+      title: '쿨메신저 연결',
+      purpose: coolStatus ? `메시지 ${coolStatus.search_db_count.toLocaleString()}건 색인됨` : '받은 메시지를 읽어옵니다.',
+      fix: <>쿨메신저에 로그인해 메시지를 한 번 확인한 뒤 HyperCool을 재시작하세요. ‘메시지 분류’ 화면의 ‘파일 선택’으로 <code>.udb</code> 파일을 직접 지정할 수도 있습니다.</>,
+    },
+    ...backendPrereqs,
+  ];
 
   // 카드 2(외부 AI 질문) 준비 항목.
   const queryPrereqs: PrereqRow[] = [
@@ -337,10 +445,12 @@ export const McpPage = () => {
     },
   ];
 
-  const briefingSub = !briefing?.claude_installed
-    ? 'Claude Code CLI 미설치'
+  const briefingSub = !briefing || briefing.backend_error
+    ? (briefing?.backend === 'claude_cli' ? 'Claude Code CLI 미설치' : '추출 백엔드 설정 필요')
     : briefing.enabled
-      ? (briefing.last_error
+      ? (briefing.budget?.paused
+        ? `예산 초과로 일시 중지 · 이번 달 $${briefing.budget.month.costUsd.toFixed(2)}`
+        : briefing.last_error
           ? `오류: ${briefing.last_error.slice(0, 40)}`
           : briefing.last_run_at
             ? `마지막 실행: ${formatRunTime(briefing.last_run_at)} · 신규 ${briefing.last_new_count}건`
@@ -372,7 +482,7 @@ export const McpPage = () => {
             <button
               className={`mcp-toggle ${briefing?.enabled ? 'mcp-toggle--on' : 'mcp-toggle--off'}`}
               onClick={toggleBriefing}
-              disabled={briefingToggling || !briefing?.claude_installed}
+              disabled={briefingToggling || !backendReady}
             >
               <span className="mcp-toggle-knob" />
             </button>
@@ -385,6 +495,66 @@ export const McpPage = () => {
               쓰기는 내 달력에만 합니다. 새 메시지 감지 후 약 45초 모아 한 번 실행하며, AI가 만든 일정은 보라색으로 표시됩니다.
             </div>
 
+            {backendSettings && (
+              <div className="mcp-backend-box">
+                <label className="mcp-backend-row">
+                  <span>추출 방식</span>
+                  <select
+                    value={backendSettings.kind}
+                    onChange={e => setBackendSettings({ ...backendSettings, kind: e.target.value as BriefingBackendKind })}
+                  >
+                    <option value="claude_cli">Claude Code CLI (첨부까지 읽음)</option>
+                    <option value="openai_compat">OpenAI 호환 서버 (Ollama·llama.cpp 등)</option>
+                    <option value="rules">규칙 기반 (AI 없이 날짜·기한 표현만)</option>
+                  </select>
+                </label>
+                {backendSettings.kind === 'openai_compat' && (
+                  <>
+                    <label className="mcp-backend-row">
+                      <span>엔드포인트</span>
+                      <input
+                        value={backendSettings.endpoint}
+                        placeholder="http://localhost:11434/v1"
+                        onChange={e => setBackendSettings({ ...backendSettings, endpoint: e.target.value })}
+                      />
+                    </label>
+                    <label className="mcp-backend-row">
+                      <span>모델</span>
+                      <input
+                        value={backendSettings.model}
+                        placeholder="llama3.1"
+                        onChange={e => setBackendSettings({ ...backendSettings, model: e.target.value })}
+                      />
+                    </label>
+                    <label className="mcp-backend-row">
+                      <span>API 키</span>
+                      <input
+                        type="password"
+                        value={backendApiKey}
+                        placeholder={backendSettings.hasApiKey ? '저장됨 (바꿀 때만 입력)' : '로컬 서버는 비워 두세요'}
+                        onChange={e => setBackendApiKey(e.target.value)}
+                      />
+                      {backendSettings.hasApiKey && (
+                        <button
+                          className="mcp-run-btn mcp-run-btn--ghost"
+                          onClick={() => saveBackendSettings('')}
+                          disabled={backendSaving}
+                        >
+                          키 삭제
+                        </button>
+                      )}
+                    </label>
+                  </>
+                )}
+                <div className="mcp-run-row">
+                  <button className="mcp-run-btn mcp-run-btn--ghost" onClick={() => saveBackendSettings()} disabled={backendSaving}>
+                    {backendSaving ? '저장 중…' : '추출 방식 저장'}
+                  </button>
+                  {backendSaveError && <span className="mcp-run-result mcp-run-result--error">{backendSaveError}</span>}
+                </div>
+              </div>
+            )}
+
             <ReadyBlock items={autoPrereqs} />
 
             {briefing?.enabled && (
@@ -392,7 +562,7 @@ export const McpPage = () => {
                 <button
                   className="mcp-run-btn"
                   onClick={runBriefingNow}
-                  disabled={briefingRunning || debugRunning || !briefing.claude_installed}
+                  disabled={briefingRunning || debugRunning || !backendReady}
                 >
                   {briefingRunning ? '실행 중…' : '지금 실행'}
                 </button>
@@ -404,8 +574,28 @@ export const McpPage = () => {
               </div>
             )}
 
+            {pendingRevisions.length > 0 && (
+              <div className="mcp-revision-box">
+                <div className="mcp-debug-label">일정 변경 검토 ({pendingRevisions.length})</div>
+                <div className="mcp-debug-hint">
+                  정정 메시지가 왔지만 직접 수정한 일정이라 자동으로 바꾸지 않았습니다.
+                </div>
+                {pendingRevisions.map(r => (
+                  <div key={r.id} className="mcp-revision-row">
+                    <span className="mcp-revision-title">{r.title}</span>
+                    <span className="mcp-revision-dates">
+                      {formatRevisionStart(r.old_start)} → {formatRevisionStart(r.new_start)}
+                      {r.new_message_id != null && ` · 메시지 #${r.new_message_id}`}
+                    </span>
+                    <button className="mcp-run-btn" onClick={() => resolveRevision(r.id, true)}>적용</button>
+                    <button className="mcp-run-btn mcp-run-btn--ghost" onClick={() => resolveRevision(r.id, false)}>무시</button>
+                  </div>
+                ))}
+              </div>
+            )}
+
             {/* 디버그: 최근 10개로 강제 실행(진행 위치 미변경) + 상세 진단 */}
-            {briefing?.claude_installed && (
+            {backendReady && (
               <div className="mcp-debug-box">
                 <div className="mcp-debug-label">테스트 / 진단</div>
                 <div className="mcp-run-row">
@@ -429,8 +619,8 @@ export const McpPage = () => {
                   <div className="mcp-debug-report">
                     <div className="mcp-debug-summary">
                       추출 {debugReport.extracted_count} · 신규 {debugReport.registered_new} · 중복 {debugReport.skipped_dedup} · 제외 {debugReport.skipped_invalid}
-                      {debugReport.ran_claude ? ` · ${(debugReport.duration_ms / 1000).toFixed(1)}초` : ' · claude 미실행'}
-                      {debugReport.claude_is_error ? ' · claude 오류' : ''}
+                      {debugReport.ran_claude ? ` · ${(debugReport.duration_ms / 1000).toFixed(1)}초` : ' · 추출 미실행'}
+                      {debugReport.claude_is_error ? ' · 추출 오류' : ''}
                     </div>
 
                     <div className="mcp-debug-grid">
@@ -454,7 +644,7 @@ export const McpPage = () => {
 
                     {debugReport.raw_result != null && (
                       <details className="mcp-debug-raw">
-                        <summary>claude 응답 원문 (result)</summary>
+                        <summary>{debugReport.backend === 'claude_cli' ? 'claude 응답 원문 (result)' : '추출 응답 원문'}</summary>
                         <pre>{debugReport.raw_result || '(빈 응답)'}</pre>
                       </details>
                     )}
@@ -4,6 +4,7 @@ import { relaunch } from '@tauri-apps/plugin-process';
 import { getVersion } from '@tauri-apps/api/app';
 import { PageHeader } from './PageHeader';
 import { AuthLanding } from './AuthLanding';
+import { BellScheduleSettings } from './BellScheduleSettings';
 import { invoke } from '@tauri-apps/api/core';
 import { open, save } from '@tauri-apps/plugin-dialog';
 import { eventToAccelerator, prettifyAccelerator } from '../utils/hotkey';
@@ -13,8 +14,6 @@ interface SettingsPageProps {
   setUdbPath: (path: string) => void;
   pickUdb: () => Promise<void>;
   saveToRegistry: (key: string, value: string) => Promise<void>;
-  classTimes: string[];
-  setClassTimes: (times: string[]) => void;
   uiScale: number;
   setUiScale: (scale: number) => void;
   onSync: () => Promise<void>;
@@ -25,7 +24,6 @@ interface SettingsPageProps {
 }
 
 const REG_KEY_UDB = 'UdbPath';
-const REG_KEY_CLASS_TIMES = 'ClassTimes';
 const REG_KEY_UI_SCALE = 'UIScale';
 const REG_KEY_AUTO_START = 'AutoStart';
 const REG_KEY_AUTO_START_HIDE_MAIN = 'AutoStartHideMain';
@@ -37,8 +35,6 @@ export const SettingsPage: React.FC<SettingsPageProps> = ({
   setUdbPath,
   pickUdb,
   saveToRegistry,
-  classTimes,
-  setClassTimes,
   uiScale,
   setUiScale,
   onSync,
@@ -130,25 +126,6 @@ export const SettingsPage: React.FC<SettingsPageProps> = ({
     detectDesktopcal();
   }, []);
 
-  const addClassTime = () => {
-    const newTime = '0900-0950';
-    setClassTimes([...classTimes, newTime]);
-  };
-
-  const removeClassTime = (index: number) => {
-    setClassTimes(classTimes.filter((_, i) => i !== index));
-  };
-
-  const updateClassTime = (index: number, value: string) => {
-    const newTimes = [...classTimes];
-    newTimes[index] = value;
-    setClassTimes(newTimes);
-  };
-
-  const saveClassTimes = () => {
-    saveToRegistry(REG_KEY_CLASS_TIMES, JSON.stringify(classTimes));
-  };
-
   // 탁상달력 동기화 핸들러 (양방향)
   const handleDesktopcalSync = async (customPath?: string) => {
     const dbPath = customPath || desktopcalPath;
@@ -470,65 +447,10 @@ export const SettingsPage: React.FC<SettingsPageProps> = ({
       </div>
 
       <div className="field">
-        <label>수업 시간</label>
-        <div className="class-times-container">
-          <div className="class-time-header">
-            <span className="col-period">교시</span>
-            <span className="col-time">시작 시간</span>
-            <span className="col-sep"></span>
-            <span className="col-time">종료 시간</span>
-            <span className="col-action"></span>
-          </div>
-          <div className="class-times-list">
-            {classTimes.map((time, index) => {
-              const [start, end] = time.split('-');
-              const startTime = start ? `${start.substring(0, 2)}:${start.substring(2, 4)}` : '';
-              const endTime = end ? `${end.substring(0, 2)}:${end.substring(2, 4)}` : '';
-
-              return (
-                <div key={index} className="class-time-row">
-                  <span className="period-label">{index + 1}교시</span>
-                  <input
-                    type="time"
-                    className="time-input"
-                    value={startTime}
-                    onChange={(e) => {
-                      const newStart = e.target.value.replace(':', '');
-                      updateClassTime(index, `${newStart}-${end}`);
-                    }}
-                  />
-                  <span className="time-sep">~</span>
-                  <input
-                    type="time"
-                    className="time-input"
-                    value={endTime}
-                    onChange={(e) => {
-                      const newEnd = e.target.value.replace(':', '');
-                      updateClassTime(index, `${start}-${newEnd}`);
-                    }}
-                  />
-                  <button onClick={() => removeClassTime(index)} className="icon-btn remove-btn" title="삭제">
-                    <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
-                      <path d="M18 6L6 18M6 6l12 12"></path>
-                    </svg>
-                  </button>
-                </div>
-              );
-            })}
-          </div>
-        </div>
-        <div className="row" style={{ marginTop: '12px' }}>
-          <button onClick={addClassTime} className="add-time-btn">
-            <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" strokeLinecap="round" strokeLinejoin="round">
-              <line x1="12" y1="5" x2="12" y2="19"></line>
-              <line x1="5" y1="12" x2="19" y2="12"></line>
-            </svg>
-            수업 시간 추가
-          </button>
-          <button onClick={saveClassTimes}>저장</button>
-        </div>
+        <label>종 시간표</label>
+        <BellScheduleSettings />
         <div className="field-description">
-          수업 시간 동안에는 새로운 메시지가 와도 창이 자동으로 표시되지 않습니다.
+          수업 시간 동안에는 새로운 메시지가 와도 창이 자동으로 표시되지 않습니다. 학교 위젯 시간표와 메시지 속 "N교시" 일정 시각도 그날 적용되는 시간표를 따릅니다.
         </div>
       </div>
 
@@ -38,7 +38,7 @@ export function useSchedules() {
         } as ManualTodo));
 
     const periodSchedules = schedules
-        .filter(s => s.type === 'period_schedule' || s.type === 'desktopcal_event')
+        .filter(s => s.type === 'period_schedule' || s.type === 'desktopcal_event' || s.type === 'school_schedule')
         .map(s => ({
             id: s.id,
             content: s.content || '',
@@ -2,23 +2,11 @@ import { useState, useCallback, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 
 const REG_KEY_UDB = 'UdbPath';
-const REG_KEY_CLASS_TIMES = 'ClassTimes';
 const REG_KEY_UI_SCALE = 'UIScale';
 const REG_KEY_SKIPPED_UPDATE_VERSION = 'SkippedUpdateVersion';
 
-const DEFAULT_CLASS_TIMES = [
-    '0830-0920',
-    '0930-1020',
-    '1030-1120',
-    '1130-1220',
-    '1320-1410',
-    '1420-1510',
-    '1520-1610',
-];
-
 export function useSettings() {
     const [udbPath, setUdbPath] = useState<string>('');
-    const [classTimes, setClassTimes] = useState<string[]>(DEFAULT_CLASS_TIMES);
     const [uiScale, setUiScale] = useState<number>(1.0);
     const [skippedUpdateVersion, setSkippedUpdateVersion] = useState<string | null>(null);
     const [sidebarCollapsed, setSidebarCollapsed] = useState<boolean>(false);
@@ -37,25 +25,6 @@ export function useSettings() {
             const savedPath = await invoke<string | null>('get_registry_value', { key: REG_KEY_UDB });
             if (savedPath) setUdbPath(savedPath);
 
-            const savedClassTimes = await invoke<string | null>('get_registry_value', { key: REG_KEY_CLASS_TIMES });
-            if (savedClassTimes) {
-                try {
-                    const parsed = JSON.parse(savedClassTimes);
-                    if (Array.isArray(parsed) && parsed.length > 0) {
-                        setClassTimes(parsed);
-                    } else {
-                        setClassTimes(DEFAULT_CLASS_TIMES);
-                        await saveToRegistry(REG_KEY_CLASS_TIMES, JSON.stringify(DEFAULT_CLASS_TIMES));
-                    }
-                } catch {
-                    setClassTimes(DEFAULT_CLASS_TIMES);
-                    await saveToRegistry(REG_KEY_CLASS_TIMES, JSON.stringify(DEFAULT_CLASS_TIMES));
-                }
-            } else {
-                setClassTimes(DEFAULT_CLASS_TIMES);
-                await saveToRegistry(REG_KEY_CLASS_TIMES, JSON.stringify(DEFAULT_CLASS_TIMES));
-            }
-
             const savedUIScale = await invoke<string | null>('get_registry_value', { key: REG_KEY_UI_SCALE });
             if (savedUIScale) {
                 try {
@@ -86,8 +55,6 @@ export function useSettings() {
     return {
         udbPath,
         setUdbPath,
-        classTimes,
-        setClassTimes,
         uiScale,
         setUiScale,
         skippedUpdateVersion,
@@ -1,12 +1,13 @@
 import React, { useState, useEffect, useMemo } from 'react';
 import ReactDOM from 'react-dom/client';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import './SchoolWidget.css';
 import { ScheduleItem } from './types/schedule';
 
 import {
-  Tab, TimetableData, MealInfo, Latecomer, PointStatus,
-  AppinData, CatTypeId, CAT_TYPES, Shortcut,
+  Tab, TeacherWeek, TimetableChange, TimetableChangeNotice, BellProfile, MealDay, SchoolInfo, Latecomer, PointStatus,
+  CatTypeId, CAT_TYPES, Shortcut,
 } from './school-widget/types';
 import TabBar from './school-widget/TabBar';
 import ResizeHandles from './components/ResizeHandles';
@@ -47,11 +48,14 @@ export default function SchoolWidget() {
   }, [enabledTabs]);
 
   // ── Timetable ──────────────────────────────────────────────────────────────
-  const [timetableData, setTimetableData] = useState<TimetableData | null>(null);
+  const [timetableTeachers, setTimetableTeachers] = useState<string[]>([]);
+  const [teacherWeek, setTeacherWeek] = useState<TeacherWeek | null>(null);
+  const [timetableChanges, setTimetableChanges] = useState<TimetableChange[]>([]);
+  const [timetableRevision, setTimetableRevision] = useState(0);
+  const [bellProfile, setBellProfile] = useState<BellProfile | null>(null);
   const [timetableSource, setTimetableSource] = useState<'comcigan' | 'appin'>(
     () => localStorage.getItem('schoolTimetableSource') as 'comcigan' | 'appin' || 'comcigan'
   );
-  const [appinData, setAppinData] = useState<AppinData | null>(null);
   const [appinWeekOffset, setAppinWeekOffset] = useState(0);
   const [currentNow, setCurrentNow] = useState(() => new Date());
 
@@ -60,6 +64,15 @@ export default function SchoolWidget() {
     return () => clearInterval(interval);
   }, []);
 
+  // 오늘 적용되는 종 시간표 — 날짜가 바뀌면(단축수업·시험 등) 다시 받는다.
+  const todayKey = currentNow.toDateString();
+  useEffect(() => {
+    if (activeTab !== 'timetable') return;
+    invoke<BellProfile>('get_bell_profile_for', { date: null })
+      .then(setBellProfile)
+      .catch(() => setBellProfile(null));
+  }, [activeTab, todayKey]);
+
   useEffect(() => {
     if (appinWeekOffset !== 0) {
       const timer = setTimeout(() => setAppinWeekOffset(0), 10 * 60 * 1000);
@@ -100,76 +113,13 @@ export default function SchoolWidget() {
   });
 
   const filteredTeachers = useMemo(() => {
-    const list = timetableSource === 'appin' && appinData
-      ? appinData.teachers
-      : (timetableData?.teachers ?? []);
-    if (!debouncedTeacherSearch) return list;
-    return list.filter((t: string) => t.toLowerCase().includes(debouncedTeacherSearch.toLowerCase()));
-  }, [timetableData, appinData, timetableSource, debouncedTeacherSearch]);
-
-  const parsedAppinTeachers = useMemo(() => {
-    if (!appinData) return {};
-    const result: Record<string, Record<string, Record<string, { subject: string; className: string }>>> = {};
-    appinData.teachers.forEach((t: string) => { result[t] = {}; });
-    Object.entries(appinData.days).forEach(([dateStr, classMap]) => {
-      Object.entries(classMap).forEach(([className, periodMap]) => {
-        Object.entries(periodMap).forEach(([period, slot]) => {
-          if (slot.teacher !== null && slot.teacher !== undefined && slot.subject !== null && slot.subject !== undefined) {
-            const tName = appinData.teachers[slot.teacher!];
-            if (tName) {
-              if (!result[tName]) result[tName] = {};
-              if (!result[tName][dateStr]) result[tName][dateStr] = {};
-              result[tName][dateStr][period] = { subject: appinData.subjects[slot.subject!], className };
-            }
-          }
-        });
-      });
-    });
-    return result;
-  }, [appinData]);
-
-  const baseAppinTimetable = useMemo(() => {
-    if (!selectedTeacher || !appinData || !parsedAppinTeachers[selectedTeacher]) return null;
-    const dailyData = parsedAppinTeachers[selectedTeacher];
-
-    // Count days the teacher was present per weekday — a slot only becomes
-    // part of the base if it recurs on a majority of those days. Otherwise
-    // one-off 보강/대체 lessons would pollute the base and later empty slots
-    // would be falsely flagged as deletions.
-    const teacherDaysPerWeekday: Record<number, number> = {};
-    Object.keys(dailyData).forEach(dateStr => {
-      const dow = new Date(dateStr).getDay();
-      teacherDaysPerWeekday[dow] = (teacherDaysPerWeekday[dow] || 0) + 1;
-    });
-
-    const base: Record<number, Record<number, { subject: string; className: string } | null>> = {};
-    for (let d = 1; d <= 5; d++) {
-      base[d] = {};
-      const totalForDay = teacherDaysPerWeekday[d] || 0;
-      const threshold = Math.max(2, Math.ceil(totalForDay / 2));
-      for (let p = 1; p <= 7; p++) {
-        const counts: Record<string, { count: number; data: { subject: string; className: string } }> = {};
-        Object.entries(dailyData).forEach(([dateStr, periodMap]) => {
-          const dateObj = new Date(dateStr);
-          if (dateObj.getDay() === d) {
-            const slot = periodMap[p.toString()];
-            if (slot) {
-              const key = `${slot.subject}|${slot.className}`;
-              if (!counts[key]) counts[key] = { count: 0, data: slot };
-              counts[key].count++;
-            }
-          }
-        });
-        let best: { count: number; data: { subject: string; className: string } } | null = null;
-        Object.values(counts).forEach(c => { if (!best || c.count > best.count) best = c as any; });
-        base[d][p] = best && (best as any).count >= threshold ? (best as any).data : null;
-      }
-    }
-    return base;
-  }, [selectedTeacher, appinData, parsedAppinTeachers]);
+    if (!debouncedTeacherSearch) return timetableTeachers;
+    return timetableTeachers.filter((t: string) => t.toLowerCase().includes(debouncedTeacherSearch.toLowerCase()));
+  }, [timetableTeachers, debouncedTeacherSearch]);
 
   // ── Data ───────────────────────────────────────────────────────────────────
-  const [mealInfo, setMealInfo] = useState<MealInfo>({ lunch: 'Loading...', dinner: 'Loading...' });
+  const [mealWeek, setMealWeek] = useState<MealDay[]>([]);
+  const [mealError, setMealError] = useState<string | null>(null);
   const [latecomers, setLatecomers] = useState<Latecomer[]>([]);
   const [points, setPoints] = useState<PointStatus[]>([]);
   const [todos, setTodos] = useState<ScheduleItem[]>([]);
@@ -197,15 +147,14 @@ export default function SchoolWidget() {
 
   const [loadingStates, setLoadingStates] = useState({ todo: false, timetable: false, meal: false, attendance: false, points: false });
   const [errorStates, setErrorStates] = useState({ timetable: false, meal: false, attendance: false, points: false });
-  const [dataLoaded, setDataLoaded] = useState({ todo: false, timetable: false, appin: false, meal: false, attendance: false, points: false });
+  const [dataLoaded, setDataLoaded] = useState({ todo: false, timetable: false, meal: false, attendance: false, points: false });
 
   // ── Settings ───────────────────────────────────────────────────────────────
   const [grade, setGrade] = useState(() => localStorage.getItem('schoolGrade') || '1');
   const [classNum, setClassNum] = useState(() => localStorage.getItem('schoolClass') || '8');
   const [schoolWidgetPinned, setSchoolWidgetPinned] = useState(false);
   const [defaultTeacher, setDefaultTeacher] = useState('');
-  const [regionCode, setRegionCode] = useState(() => localStorage.getItem('schoolRegionCode') || 'C10');
-  const [schoolCode, setSchoolCode] = useState(() => localStorage.getItem('schoolCode') || '7150451');
+  const [school, setSchool] = useState<SchoolInfo | null>(null);
 
   // ── Cat ────────────────────────────────────────────────────────────────────
   const [enabledCats, setEnabledCats] = useState<CatTypeId[]>(() => {
@@ -246,7 +195,7 @@ export default function SchoolWidget() {
 
   useEffect(() => {
     setDataLoaded(prev => ({ ...prev, meal: false }));
-  }, [regionCode, schoolCode]);
+  }, [school?.atptCode, school?.schoolCode]);
 
   useEffect(() => {
     if (selectedTeacher) localStorage.setItem('lastSelectedTeacher', selectedTeacher);
@@ -258,32 +207,41 @@ export default function SchoolWidget() {
         setSchoolWidgetPinned(await invoke<boolean>('get_school_widget_pinned'));
         const savedTeacher = await invoke<string | null>('get_registry_value', { key: 'SchoolDefaultTeacher' });
         if (savedTeacher) setDefaultTeacher(savedTeacher);
-        const savedRegion = await invoke<string | null>('get_registry_value', { key: 'SchoolRegionCode' });
-        if (savedRegion) { setRegionCode(savedRegion); localStorage.setItem('schoolRegionCode', savedRegion); }
-        const savedSchool = await invoke<string | null>('get_registry_value', { key: 'SchoolCode' });
-        if (savedSchool) { setSchoolCode(savedSchool); localStorage.setItem('schoolCode', savedSchool); }
+        setSchool(await invoke<SchoolInfo | null>('get_school_profile'));
       } catch { /* ignore */ }
     };
     loadSettings();
   }, []);
 
   useEffect(() => {
-    if (timetableData?.teachers.length && timetableSource === 'comcigan') {
-      if (defaultTeacher && timetableData.teachers.includes(defaultTeacher)) {
-        setSelectedTeacher(defaultTeacher);
-      } else {
-        const saved = localStorage.getItem('lastSelectedTeacher');
-        setSelectedTeacher(saved && timetableData.teachers.includes(saved) ? saved : timetableData.teachers[0]);
-      }
-    } else if (appinData?.teachers.length && timetableSource === 'appin') {
-      if (defaultTeacher && appinData.teachers.includes(defaultTeacher)) {
-        setSelectedTeacher(defaultTeacher);
-      } else {
-        const saved = localStorage.getItem('lastSelectedTeacher');
-        setSelectedTeacher(saved && appinData.teachers.includes(saved) ? saved : appinData.teachers[0]);
-      }
+    if (!timetableTeachers.length) return;
+    if (defaultTeacher && timetableTeachers.includes(defaultTeacher)) {
+      setSelectedTeacher(defaultTeacher);
+    } else {
+      const saved = localStorage.getItem('lastSelectedTeacher');
+      setSelectedTeacher(saved && timetableTeachers.includes(saved) ? saved : timetableTeachers[0]);
     }
-  }, [timetableData, appinData, defaultTeacher, timetableSource]);
+  }, [timetableTeachers, defaultTeacher]);
+
+  useEffect(() => {
+    if (activeTab !== 'timetable' || !selectedTeacher) return;
+    const m = appinWeekRange.mondayDate;
+    const weekOf = `${m.getFullYear()}-${String(m.getMonth() + 1).padStart(2, '0')}-${String(m.getDate()).padStart(2, '0')}`;
+    let cancelled = false;
+    invoke<TeacherWeek>('get_teacher_week', { source: timetableSource, teacher: selectedTeacher, weekOf })
+      .then(week => { if (!cancelled) { setTeacherWeek(week); setErrorStates(prev => ({ ...prev, timetable: false })); } })
+      .catch(() => { if (!cancelled) { setTeacherWeek(null); setErrorStates(prev => ({ ...prev, timetable: true })); } });
+    return () => { cancelled = true; };
+  }, [activeTab, selectedTeacher, timetableSource, appinWeekRange, timetableRevision]);
+
+  // 보강·교체로 시간표 파일이 바뀌면 백엔드가 알려 준다. 내 수업이 바뀐 경우만 띄운다.
+  useEffect(() => {
+    const unlistenPromise = listen<TimetableChangeNotice>('timetable-changed', (event) => {
+      setTimetableRevision(r => r + 1);
+      if (event.payload.mine.length) setTimetableChanges(event.payload.mine);
+    });
+    return () => { void unlistenPromise.then(unlisten => unlisten()); };
+  }, []);
 
   useEffect(() => {
     const handleClickOutside = (e: MouseEvent) => {
@@ -294,36 +252,16 @@ export default function SchoolWidget() {
   }, []);
 
   // ── Fetch functions ────────────────────────────────────────────────────────
-  const fetchTimetable = async () => {
-    if (timetableSource === 'appin') {
-      if (dataLoaded.appin && appinData) return;
-      setLoadingStates(prev => ({ ...prev, timetable: true }));
-      setErrorStates(prev => ({ ...prev, timetable: false }));
-      try {
-        const data = await invoke<AppinData>('get_appin_timetable_data');
-        setAppinData(data);
-        setDataLoaded(prev => ({ ...prev, appin: true }));
-        if (data.teachers?.length) {
-          const saved = localStorage.getItem('lastSelectedTeacher');
-          setSelectedTeacher(saved && data.teachers.includes(saved) ? saved : data.teachers[0]);
-        }
-      } catch { setErrorStates(prev => ({ ...prev, timetable: true })); }
-      finally { setLoadingStates(prev => ({ ...prev, timetable: false })); }
-    } else {
-      if (dataLoaded.timetable && timetableData) return;
-      setLoadingStates(prev => ({ ...prev, timetable: true }));
-      setErrorStates(prev => ({ ...prev, timetable: false }));
-      try {
-        const data = await invoke<TimetableData>('get_timetable_data');
-        setTimetableData(data);
-        setDataLoaded(prev => ({ ...prev, timetable: true }));
-        if (data.teachers.length) {
-          const saved = localStorage.getItem('lastSelectedTeacher');
-          setSelectedTeacher(saved && data.teachers.includes(saved) ? saved : data.teachers[0]);
-        }
-      } catch { setErrorStates(prev => ({ ...prev, timetable: true })); }
-      finally { setLoadingStates(prev => ({ ...prev, timetable: false })); }
-    }
+  const fetchTimetable = async (src: 'comcigan' | 'appin' = timetableSource, forceRefresh = false) => {
+    if (!forceRefresh && dataLoaded.timetable && timetableTeachers.length > 0) return;
+    setLoadingStates(prev => ({ ...prev, timetable: true }));
+    setErrorStates(prev => ({ ...prev, timetable: false }));
+    try {
+      const teachers = await invoke<string[]>('get_timetable_teachers', { source: src });
+      setTimetableTeachers(teachers);
+      setDataLoaded(prev => ({ ...prev, timetable: true }));
+    } catch { setErrorStates(prev => ({ ...prev, timetable: true })); }
+    finally { setLoadingStates(prev => ({ ...prev, timetable: false })); }
   };
 
   const fetchMeal = async () => {
@@ -332,12 +270,14 @@ export default function SchoolWidget() {
     try {
       const now = new Date();
       const kstDate = new Date(now.getTime() + (now.getTimezoneOffset() + 9 * 60) * 60000);
-      const date = `${kstDate.getFullYear()}${String(kstDate.getMonth() + 1).padStart(2, '0')}${String(kstDate.getDate()).padStart(2, '0')}`;
-      const data = await invoke<MealInfo>('get_meal_data', { date, atptCode: regionCode, schoolCode });
-      setMealInfo(data);
+      const date = `${kstDate.getFullYear()}-${String(kstDate.getMonth() + 1).padStart(2, '0')}-${String(kstDate.getDate()).padStart(2, '0')}`;
+      // 이번 주(월~금)를 한 번에 받는다.
+      setMealWeek(await invoke<MealDay[]>('get_meals', { from: date }));
+      setMealError(null);
       setDataLoaded(prev => ({ ...prev, meal: true }));
-    } catch {
-      setMealInfo({ lunch: '급식 정보를 불러올 수 없습니다', dinner: '석식 정보를 불러올 수 없습니다' });
+    } catch (e) {
+      setMealWeek([]);
+      setMealError(`급식 정보를 불러올 수 없습니다: ${e}`);
       setErrorStates(prev => ({ ...prev, meal: true }));
     } finally { setLoadingStates(prev => ({ ...prev, meal: false })); }
   };
@@ -388,10 +328,7 @@ export default function SchoolWidget() {
     switch (activeTab) {
       case 'todo': if (!dataLoaded.todo) fetchTodos(); break;
       case 'meal': if (!dataLoaded.meal) fetchMeal(); break;
-      case 'timetable':
-        if (timetableSource === 'appin') { if (!dataLoaded.appin) fetchTimetable(); }
-        else { if (!dataLoaded.timetable) fetchTimetable(); }
-        break;
+      case 'timetable': if (!dataLoaded.timetable) fetchTimetable(); break;
       case 'attendance': if (!dataLoaded.attendance) fetchAttendance(); break;
       case 'points': if (!dataLoaded.points) fetchPoints(); break;
     }
@@ -474,11 +411,7 @@ export default function SchoolWidget() {
       if (defaultTeacher) await invoke('set_registry_value', { key: 'SchoolDefaultTeacher', value: defaultTeacher });
       await invoke('set_registry_value', { key: 'SchoolGrade', value: grade });
       await invoke('set_registry_value', { key: 'SchoolClass', value: classNum });
-      await invoke('set_registry_value', { key: 'SchoolRegionCode', value: regionCode });
-      await invoke('set_registry_value', { key: 'SchoolCode', value: schoolCode });
-      localStorage.setItem('schoolRegionCode', regionCode);
-      localStorage.setItem('schoolCode', schoolCode);
-      setDataLoaded(prev => ({ ...prev, meal: false }));
+      await invoke('set_registry_value', { key: 'SchoolTimetableSource', value: timetableSource });
       alert('설정이 저장되었습니다.');
     } catch { alert('설정 저장에 실패했습니다.'); }
   };
@@ -486,15 +419,10 @@ export default function SchoolWidget() {
   // ── Timetable source switch ────────────────────────────────────────────────
   const handleTimetableSourceChange = (src: 'comcigan' | 'appin') => {
     setTimetableSource(src);
-    if (src === 'comcigan' && timetableData?.teachers.length) {
-      const saved = localStorage.getItem('lastSelectedTeacher');
-      setSelectedTeacher(saved && timetableData.teachers.includes(saved) ? saved : timetableData.teachers[0]);
-    } else if (src === 'appin' && appinData?.teachers.length) {
-      const saved = localStorage.getItem('lastSelectedTeacher');
-      setSelectedTeacher(saved && appinData.teachers.includes(saved) ? saved : appinData.teachers[0]);
-    } else {
-      setSelectedTeacher('');
-    }
+    setSelectedTeacher('');
+    setTeacherWeek(null);
+    setTimetableTeachers([]);
+    fetchTimetable(src, true);
   };
 
   // ── Render ─────────────────────────────────────────────────────────────────
@@ -518,24 +446,22 @@ export default function SchoolWidget() {
           />
         )}
         {activeTab === 'meal' && (
-          <MealTab mealInfo={mealInfo} loading={loadingStates.meal} />
+          <MealTab week={mealWeek} error={mealError} loading={loadingStates.meal} />
         )}
         {activeTab === 'timetable' && (
           <TimetableTab
             timetableSource={timetableSource}
-            timetableData={timetableData}
-            appinData={appinData}
+            week={teacherWeek}
+            changes={timetableChanges}
+            onDismissChanges={() => setTimetableChanges([])}
+            bellProfile={bellProfile}
             selectedTeacher={selectedTeacher}
-            parsedAppinTeachers={parsedAppinTeachers}
-            baseAppinTimetable={baseAppinTimetable}
-            eventsByDateClass={appinData?.eventsByDateClass ?? {}}
-            eventsByDateGrade={appinData?.eventsByDateGrade ?? {}}
             appinWeekRange={appinWeekRange}
             onAppinWeekOffsetChange={setAppinWeekOffset}
             currentNow={currentNow}
             loading={loadingStates.timetable}
             error={errorStates.timetable}
-            onRetry={() => { setDataLoaded(prev => ({ ...prev, timetable: false, appin: false })); fetchTimetable(); }}
+            onRetry={() => fetchTimetable(timetableSource, true)}
             teacherSearch={teacherSearch}
             onTeacherSearchChange={handleTeacherSearchChange}
             showTeacherDropdown={showTeacherDropdown}
@@ -591,18 +517,16 @@ export default function SchoolWidget() {
             }}
             catSize={catSize}
             onCatSizeReset={() => { setCatSize(32); localStorage.setItem('schoolCatSize', '32'); }}
-            timetableData={timetableData}
-            appinData={appinData}
+            teachers={timetableTeachers}
             defaultTeacher={defaultTeacher}
             onDefaultTeacherChange={setDefaultTeacher}
             grade={grade}
             onGradeChange={setGrade}
             classNum={classNum}
             onClassNumChange={setClassNum}
-            regionCode={regionCode}
-            onRegionCodeChange={setRegionCode}
-            schoolCode={schoolCode}
-            onSchoolCodeChange={setSchoolCode}
+            school={school}
+            onSchoolChange={setSchool}
+            onMealAllergensChange={() => setDataLoaded(prev => ({ ...prev, meal: false }))}
             onSave={saveSettings}
           />
         )}
@@ -1,29 +1,83 @@
-import { MealInfo } from '../types';
+import { useEffect, useState } from 'react';
+import { MealDay } from '../types';
 
 interface Props {
-  mealInfo: MealInfo;
+  week: MealDay[];
+  error: string | null;
   loading: boolean;
 }
 
-export default function MealTab({ mealInfo, loading }: Props) {
+const WEEKDAYS = ['일', '월', '화', '수', '목', '금', '토'];
+
+const dayLabel = (date: string) => {
+  const d = new Date(`${date}T00:00:00`);
+  return `${WEEKDAYS[d.getDay()]} ${d.getMonth() + 1}/${d.getDate()}`;
+};
+
+export default function MealTab({ week, error, loading }: Props) {
+  const today = new Date().toLocaleDateString('sv-SE');
+  const [selectedDate, setSelectedDate] = useState(today);
+
+  useEffect(() => {
+    if (week.length && !week.some(d => d.date === selectedDate)) {
+      setSelectedDate(week.some(d => d.date === today) ? today : week[0].date);
+    }
+  }, [week]);
+
+  const day = week.find(d => d.date === selectedDate);
+  const flagged = (meal: string, dish: string) => day?.alerts.some(a => a.meal === meal && a.dish === dish) ?? false;
+
   return (
     <div className="meal-section">
       <div className="section-header">
-        <h2>오늘의 급식</h2>
-        <span className="date">{new Date().toLocaleDateString()}</span>
+        <h2>{selectedDate === today ? '오늘의 급식' : '급식'}</h2>
+        <div className="meal-days">
+          {week.map(d => (
+            <button
+              key={d.date}
+              className={`meal-day-btn${d.date === selectedDate ? ' active' : ''}${d.alerts.length ? ' has-alert' : ''}`}
+              onClick={() => setSelectedDate(d.date)}
+            >
+              {dayLabel(d.date)}
+            </button>
+          ))}
+        </div>
       </div>
       {loading ? (
         <div className="loading">로딩 중...</div>
+      ) : error ? (
+        <div className="meal-empty">{error}</div>
+      ) : !day || day.meals.length === 0 ? (
+        <div className="meal-empty">급식이 없습니다.</div>
       ) : (
         <div className="meal-items-container">
-          <div className="meal-item">
-            <div className="meal-type">중식</div>
-            <div className="meal-menu">{mealInfo.lunch}</div>
-          </div>
-          <div className="meal-item">
-            <div className="meal-type">석식</div>
-            <div className="meal-menu">{mealInfo.dinner}</div>
-          </div>
+          {day.meals.map(meal => (
+            <div key={meal.kind} className="meal-item">
+              <div className="meal-type">
+                {meal.label}
+                {meal.calories != null && <span className="meal-calories">{meal.calories} kcal</span>}
+              </div>
+              <div className="meal-menu">
+                {meal.dishes.map((dish, i) => (
+                  <div
+                    key={i}
+                    className={`meal-dish${flagged(meal.kind, dish.name) ? ' allergen-alert' : ''}`}
+                    title={dish.allergens.map(a => a.name).join(', ')}
+                  >
+                    {dish.name}
+                    {dish.allergens.length > 0 && (
+                      <span className="meal-allergens">{dish.allergens.map(a => a.code).join('.')}</span>
+                    )}
+                  </div>
+                ))}
+              </div>
+              {meal.nutrition.length > 0 && (
+                <div className="meal-nutrition">
+                  {meal.nutrition.slice(0, 3).map(n => `${n.name} ${n.amount}${n.unit}`).join(' · ')}
+                </div>
+              )}
+            </div>
+          ))}
         </div>
       )}
     </div>
@@ -1,4 +1,6 @@
-import { CAT_TYPES, CatTypeId, Tab, ALL_TABS, TimetableData, AppinData } from '../types';
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { CAT_TYPES, CatTypeId, Tab, ALL_TABS, SchoolInfo, Allergen } from '../types';
 
 interface Props {
   // 위젯
@@ -15,8 +17,7 @@ interface Props {
   catSize: number;
   onCatSizeReset: () => void;
   // 시간표
-  timetableData: TimetableData | null;
-  appinData: AppinData | null;
+  teachers: string[];
   defaultTeacher: string;
   onDefaultTeacherChange: (v: string) => void;
   // 학년·반
@@ -25,10 +26,10 @@ interface Props {
   classNum: string;
   onClassNumChange: (v: string) => void;
   // 학교 정보
-  regionCode: string;
-  onRegionCodeChange: (v: string) => void;
-  schoolCode: string;
-  onSchoolCodeChange: (v: string) => void;
+  school: SchoolInfo | null;
+  onSchoolChange: (school: SchoolInfo) => void;
+  // 급식 알레르기
+  onMealAllergensChange: () => void;
   // 저장
   onSave: () => void;
 }
@@ -41,14 +42,144 @@ export default function SettingsTab({
   enabledTabs, onEnabledTabsChange,
   enabledCats, onEnabledCatsChange,
   catSize, onCatSizeReset,
-  timetableData, appinData,
+  teachers,
   defaultTeacher, onDefaultTeacherChange,
   grade, onGradeChange,
   classNum, onClassNumChange,
-  regionCode, onRegionCodeChange,
-  schoolCode, onSchoolCodeChange,
+  school, onSchoolChange,
+  onMealAllergensChange,
   onSave,
 }: Props) {
+  const [scheduleImportStatus, setScheduleImportStatus] = useState('');
+  const [schoolQuery, setSchoolQuery] = useState('');
+  const [schoolResults, setSchoolResults] = useState<SchoolInfo[]>([]);
+  const [schoolSearchStatus, setSchoolSearchStatus] = useState('');
+
+  const [allergens, setAllergens] = useState<Allergen[]>([]);
+  const [myAllergens, setMyAllergens] = useState<number[]>([]);
+
+  const [intranetUrl, setIntranetUrl] = useState('');
+  const [intranetUser, setIntranetUser] = useState('');
+  const [intranetPassword, setIntranetPassword] = useState('');
+  const [intranetHasPassword, setIntranetHasPassword] = useState(false);
+  const [intranetStatus, setIntranetStatus] = useState('');
+
+  const [appinPath, setAppinPath] = useState('');
+  const [appinStartDate, setAppinStartDate] = useState('');
+  const [appinResolved, setAppinResolved] = useState('');
+  const [appinStatus, setAppinStatus] = useState('');
+
+  const loadAppinSettings = () => {
+    invoke<{ path: string | null; startDate: string | null; resolvedPath: string | null; resolvedStartDate: string | null }>('get_appin_settings')
+      .then(s => {
+        setAppinPath(s.path ?? '');
+        setAppinStartDate(s.startDate ?? '');
+        setAppinResolved(s.resolvedPath
+          ? `${s.resolvedPath} · 학년도 시작 ${s.resolvedStartDate ?? '알 수 없음'}`
+          : '압핀 시간표 파일을 찾지 못했습니다.');
+      })
+      .catch(e => setAppinStatus(String(e)));
+  };
+
+  useEffect(() => {
+    if (timetableSource === 'appin') loadAppinSettings();
+  }, [timetableSource]);
+
+  // 비워 두면 파일 위치와 학년도 시작일을 자동으로 찾는다.
+  const handleAppinSave = async () => {
+    try {
+      await invoke('set_appin_settings', { path: appinPath || null, startDate: appinStartDate || null });
+      setAppinStatus('저장됨');
+      loadAppinSettings();
+    } catch (e) {
+      setAppinStatus(String(e));
+    }
+  };
+
+  useEffect(() => {
+    invoke<{ baseUrl: string; username: string; hasPassword: boolean } | null>('get_intranet_settings')
+      .then(s => {
+        if (!s) return;
+        setIntranetUrl(s.baseUrl);
+        setIntranetUser(s.username);
+        setIntranetHasPassword(s.hasPassword);
+      })
+      .catch(e => setIntranetStatus(String(e)));
+  }, []);
+
+  // 저장한 뒤 바로 로그인해 본다. 비밀번호를 비워 두면 저장된 비밀번호를 그대로 쓴다.
+  const handleIntranetSave = async () => {
+    setIntranetStatus('확인 중…');
+    try {
+      await invoke('set_intranet_settings', {
+        baseUrl: intranetUrl,
+        username: intranetUser,
+        password: intranetPassword || null,
+      });
+      setIntranetPassword('');
+      setIntranetHasPassword(true);
+      await invoke('test_intranet_login');
+      setIntranetStatus('로그인 확인됨');
+    } catch (e) {
+      setIntranetStatus(String(e));
+    }
+  };
+
+  useEffect(() => {
+    invoke<Allergen[]>('list_allergens').then(setAllergens).catch(console.error);
+    invoke<number[]>('get_meal_allergens').then(setMyAllergens).catch(console.error);
+  }, []);
+
+  const handleAllergenToggle = async (code: number) => {
+    const next = myAllergens.includes(code)
+      ? myAllergens.filter(c => c !== code)
+      : [...myAllergens, code].sort((a, b) => a - b);
+    try {
+      await invoke('set_meal_allergens', { codes: next });
+      setMyAllergens(next);
+      onMealAllergensChange();
+    } catch (e) { console.error(e); }
+  };
+
+  const handleSchoolSearch = async () => {
+    setSchoolSearchStatus('검색 중…');
+    try {
+      const found = await invoke<SchoolInfo[]>('search_schools', { name: schoolQuery });
+      setSchoolResults(found);
+      setSchoolSearchStatus(found.length ? '' : '검색 결과가 없습니다.');
+    } catch (e) {
+      setSchoolResults([]);
+      setSchoolSearchStatus(String(e));
+    }
+  };
+
+  const handleSchoolSelect = async (picked: SchoolInfo) => {
+    try {
+      await invoke('set_school_profile', { school: picked });
+      onSchoolChange(picked);
+      setSchoolResults([]);
+      setSchoolQuery('');
+      setSchoolSearchStatus('');
+    } catch (e) {
+      setSchoolSearchStatus(String(e));
+    }
+  };
+
+  // 올해 학년도(3월 ~ 이듬해 2월) 학사일정을 달력으로 가져온다.
+  const handleImportSchoolSchedule = async () => {
+    const now = new Date();
+    const year = now.getMonth() >= 2 ? now.getFullYear() : now.getFullYear() - 1;
+    const to = new Date(year + 1, 2, 0).toLocaleDateString('sv-SE');
+    setScheduleImportStatus('가져오는 중…');
+    try {
+      const r = await invoke<{ imported: number; updated: number; unchanged: number; removed: number }>(
+        'import_neis_school_schedule', { from: `${year}-03-01`, to }
+      );
+      setScheduleImportStatus(`새로 ${r.imported} · 변경 ${r.updated} · 삭제 ${r.removed}`);
+    } catch (e) {
+      setScheduleImportStatus(String(e));
+    }
+  };
 
   const handleTimetableSourceChange = (src: 'comcigan' | 'appin') => {
     onTimetableSourceChange(src);
@@ -64,10 +195,6 @@ export default function SettingsTab({
     localStorage.setItem('schoolEnabledTabs', JSON.stringify(next));
   };
 
-  const teachers = timetableSource === 'appin'
-    ? (appinData?.teachers || [])
-    : (timetableData?.teachers || []);
-
   return (
     <div className="settings-section">
 
@@ -182,6 +309,31 @@ export default function SettingsTab({
             ))}
           </select>
         </div>
+        {timetableSource === 'appin' && (
+          <>
+            <div className="settings-row">
+              <span className="settings-label">압핀 파일</span>
+              <input
+                type="text"
+                value={appinPath}
+                onChange={(e) => setAppinPath(e.target.value)}
+                placeholder="자동으로 찾기"
+                className="settings-text-input"
+              />
+            </div>
+            <div className="settings-row">
+              <span className="settings-label">학년도 시작</span>
+              <input
+                type="date"
+                value={appinStartDate}
+                onChange={(e) => setAppinStartDate(e.target.value)}
+                className="settings-text-input"
+              />
+              <button onClick={handleAppinSave} className="settings-action-btn">저장</button>
+            </div>
+            <div className="settings-hint">{appinStatus || appinResolved}</div>
+          </>
+        )}
       </div>
 
       {/* 학년·반 */}
@@ -213,27 +365,97 @@ export default function SettingsTab({
       <div className="settings-card">
         <div className="settings-card-title">학교 정보</div>
         <div className="settings-row">
-          <span className="settings-label">지역 코드</span>
+          <span className="settings-label">학교</span>
+          <span className="settings-value-text">{school ? (school.name || school.schoolCode) : '선택 안 됨'}</span>
+        </div>
+        {school && (
+          <div className="settings-hint">
+            {[school.atptName, school.address, `${school.atptCode} · ${school.schoolCode}`].filter(Boolean).join(' · ')}
+          </div>
+        )}
+        <div className="settings-row">
           <input
             type="text"
-            value={regionCode}
-            onChange={(e) => onRegionCodeChange(e.target.value)}
-            placeholder="예: C10"
+            value={schoolQuery}
+            onChange={(e) => setSchoolQuery(e.target.value)}
+            onKeyDown={(e) => { if (e.key === 'Enter') handleSchoolSearch(); }}
+            placeholder="학교 이름으로 검색"
+            className="settings-text-input"
+          />
+          <button onClick={handleSchoolSearch} className="settings-action-btn">검색</button>
+        </div>
+        {schoolResults.map(s => (
+          <button
+            key={`${s.atptCode}-${s.schoolCode}`}
+            onClick={() => handleSchoolSelect(s)}
+            className="settings-school-result"
+          >
+            <span>{s.name}</span>
+            <span className="settings-hint">{s.atptName}{s.address ? ` · ${s.address}` : ''}</span>
+          </button>
+        ))}
+        {schoolSearchStatus && <div className="settings-hint">{schoolSearchStatus}</div>}
+        <div className="settings-row">
+          <span className="settings-label">학사일정</span>
+          <button onClick={handleImportSchoolSchedule} className="settings-action-btn">달력으로 가져오기</button>
+        </div>
+        {scheduleImportStatus && <div className="settings-hint">{scheduleImportStatus}</div>}
+      </div>
+
+      {/* 내부망 */}
+      <div className="settings-card">
+        <div className="settings-card-title">내부망 (출결·상벌점)</div>
+        <div className="settings-row">
+          <span className="settings-label">주소</span>
+          <input
+            type="text"
+            value={intranetUrl}
+            onChange={(e) => setIntranetUrl(e.target.value)}
+            placeholder="http://…"
             className="settings-text-input"
           />
         </div>
-        <div className="settings-hint">서울 C10 · 경기 J10 · 부산 B10</div>
         <div className="settings-row">
-          <span className="settings-label">학교 코드</span>
+          <span className="settings-label">아이디</span>
           <input
             type="text"
-            value={schoolCode}
-            onChange={(e) => onSchoolCodeChange(e.target.value)}
-            placeholder="예: 7150451"
+            value={intranetUser}
+            onChange={(e) => setIntranetUser(e.target.value)}
             className="settings-text-input"
           />
         </div>
-        <div className="settings-hint">NEIS 학교 정보 검색에서 확인 가능</div>
+        <div className="settings-row">
+          <span className="settings-label">비밀번호</span>
+          <input
+            type="password"
+            value={intranetPassword}
+            onChange={(e) => setIntranetPassword(e.target.value)}
+            placeholder={intranetHasPassword ? '저장됨' : ''}
+            className="settings-text-input"
+          />
+        </div>
+        <div className="settings-row">
+          <span className="settings-hint" style={{ padding: 0 }}>계정은 이 PC 사용자 전용으로 암호화해 저장합니다.</span>
+          <button onClick={handleIntranetSave} className="settings-action-btn">저장·확인</button>
+        </div>
+        {intranetStatus && <div className="settings-hint">{intranetStatus}</div>}
+      </div>
+
+      {/* 급식 알레르기 */}
+      <div className="settings-card">
+        <div className="settings-card-title">급식 알레르기</div>
+        <div className="settings-allergens">
+          {allergens.map(a => (
+            <button
+              key={a.code}
+              onClick={() => handleAllergenToggle(a.code)}
+              className={`settings-allergen${myAllergens.includes(a.code) ? ' active' : ''}`}
+            >
+              {a.code}. {a.name}
+            </button>
+          ))}
+        </div>
+        <div className="settings-hint">고른 알레르기가 든 메뉴를 급식 탭과 아침 브리핑에서 알려 줍니다.</div>
       </div>
 
       <button onClick={onSave} className="save-btn">저장</button>
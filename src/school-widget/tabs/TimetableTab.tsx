@@ -1,5 +1,7 @@
-import React from 'react';
-import { TimetableData, AppinData, PERIOD_START_TIMES, PERIOD_TIMES } from '../types';
+import React, { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import { TeacherWeek, TimetableChange, BellProfile, BellSlot } from '../types';
 
 function getSubjectColor(subjectName: string): string {
   if (!subjectName) return '';
@@ -19,13 +21,11 @@ interface WeekRange {
 
 interface Props {
   timetableSource: 'comcigan' | 'appin';
-  timetableData: TimetableData | null;
-  appinData: AppinData | null;
+  week: TeacherWeek | null;
+  changes: TimetableChange[];
+  onDismissChanges: () => void;
+  bellProfile: BellProfile | null;
   selectedTeacher: string;
-  parsedAppinTeachers: Record<string, Record<string, Record<string, { subject: string; className: string }>>>;
-  baseAppinTimetable: Record<number, Record<number, { subject: string; className: string } | null>> | null;
-  eventsByDateClass: Record<string, Record<string, string>>;
-  eventsByDateGrade: Record<string, (string | null)[]>;
   appinWeekRange: WeekRange;
   onAppinWeekOffsetChange: (fn: (o: number) => number) => void;
   currentNow: Date;
@@ -49,9 +49,7 @@ interface Props {
 }
 
 export default function TimetableTab({
-  timetableSource, timetableData, appinData, selectedTeacher,
-  parsedAppinTeachers, baseAppinTimetable,
-  eventsByDateClass, eventsByDateGrade,
+  timetableSource, week, changes, onDismissChanges, bellProfile, selectedTeacher,
   appinWeekRange,
   onAppinWeekOffsetChange, currentNow, loading, error, onRetry,
   teacherSearch, onTeacherSearchChange, showTeacherDropdown, onShowTeacherDropdown,
@@ -59,20 +57,6 @@ export default function TimetableTab({
   defaultTeacher, favoriteTeachers, onToggleFavorite, onWheelScroll,
 }: Props) {
 
-  const lookupEventLabel = (dateStr: string, className: string | null): string | null => {
-    const byClass = eventsByDateClass[dateStr];
-    if (className && byClass && byClass[className]) return byClass[className];
-    if (className) {
-      const m = /^(\d+)-/.exec(className);
-      if (m) {
-        const grade = parseInt(m[1], 10);
-        const arr = eventsByDateGrade[dateStr];
-        if (arr && arr[grade - 1]) return arr[grade - 1] as string;
-      }
-    }
-    return null;
-  };
-
   const renderGrid = () => {
     if (loading) return <div className="loading">로딩 중...</div>;
     if (error) return (
@@ -82,72 +66,38 @@ export default function TimetableTab({
       </div>
     );
 
-    let schedule: any[][][] = [];
-    if (timetableSource === 'appin') {
-      if (!appinData || !selectedTeacher || !parsedAppinTeachers[selectedTeacher] || !baseAppinTimetable)
-        return <div className="error-message">No Data</div>;
-
-      const teacherData = parsedAppinTeachers[selectedTeacher];
-      const { mondayDate } = appinWeekRange;
-
-      schedule = Array(8).fill(null).map(() => Array(5).fill(null)) as any;
-      for (let dIdx = 0; dIdx < 5; dIdx++) {
-        const targetDate = new Date(mondayDate);
-        targetDate.setDate(mondayDate.getDate() + dIdx);
-        const year = targetDate.getFullYear();
-        const month = String(targetDate.getMonth() + 1).padStart(2, '0');
-        const day = String(targetDate.getDate()).padStart(2, '0');
-        const dateStr = `${year}-${month}-${day}`;
-
-        for (let pIdx = 0; pIdx < 7; pIdx++) {
-          const pStr = (pIdx + 1).toString();
-          const slot = teacherData[dateStr]?.[pStr];
-          const baseSlot = baseAppinTimetable[dIdx + 1]?.[pIdx + 1];
-          const dateHasData = !!teacherData[dateStr];
+    if (!selectedTeacher || !week || week.teacher !== selectedTeacher) return <div className="error-message">No Data</div>;
+    if (week.cells.every(row => row.every(cell => !cell))) return <div className="error-message">No Schedule for {selectedTeacher}</div>;
 
-          // 행사 라벨 우선 — 평소 그 시간에 수업이 있던 셀에만 표기
-          const eventLabel = baseSlot ? lookupEventLabel(dateStr, baseSlot.className) : null;
+    // 바뀐 수업·빠진 수업·행사 판정은 백엔드(get_teacher_week)가 출처와 무관하게 해 준다.
+    const schedule = week.cells.map(row => row.map(cell =>
+      cell ? [cell.subject, cell.className ?? '', cell.changed, cell.event] : null
+    ));
 
-          if (eventLabel) {
-            schedule[pIdx][dIdx] = [eventLabel, '', true, true];
-          } else if (slot) {
-            const isDiff = !baseSlot || baseSlot.subject !== slot.subject || baseSlot.className !== slot.className;
-            schedule[pIdx][dIdx] = [slot.subject, slot.className, isDiff, false];
-          } else if (baseSlot && dateHasData) {
-            schedule[pIdx][dIdx] = ['', '', true, false];
-          } else if (baseSlot) {
-            schedule[pIdx][dIdx] = [baseSlot.subject, baseSlot.className, false, false];
-          } else {
-            schedule[pIdx][dIdx] = null as any;
-          }
-        }
-      }
-    } else {
-      if (!timetableData || !selectedTeacher) return <div className="error-message">No Data</div>;
-      const s = timetableData.timetables[selectedTeacher];
-      if (!s) return <div className="error-message">No Schedule for {selectedTeacher}</div>;
-      schedule = s as any;
-    }
-
-    const days = timetableSource === 'appin' ? appinWeekRange.days : ['월', '화', '수', '목', '금'];
-    const periods = [1, 2, 3, 4, 5, 6, 7];
+    const days = week.dated ? appinWeekRange.days : ['월', '화', '수', '목', '금'];
+    // 행은 오늘 종 시간표(bell_schedule)의 칸 순서. 종 시간표를 못 불러오면 1~7교시만 시각 없이.
+    const slots: BellSlot[] = bellProfile?.slots
+      ?? [1, 2, 3, 4, 5, 6, 7].map(p => ({ label: `${p}교시`, start: '', end: '', period: p }));
+    const toMinutes = (hm: string) => {
+      const [h, m] = hm.split(':').map(Number);
+      return Number.isFinite(h) && Number.isFinite(m) ? h * 60 + m : null;
+    };
 
     const now = currentNow;
     const currentDay = now.getDay();
     const isWeekday = currentDay >= 1 && currentDay <= 5;
     const currentTime = now.getHours() * 60 + now.getMinutes();
 
-    // 각 행(교시·점심)은 "이 교시 시작 ~ 다음 행 시작" 구간을 나타낸다.
+    // 각 행(교시·점심)은 "이 칸 시작 ~ 다음 칸 시작" 구간을 나타낸다.
     // 쉬는 시간을 앞 교시 셀에 포함시켜, 시간이 흘러도 인디케이터가 뒤로 튀지 않고
     // 위→아래로 연속 이동하며 다음 교시 시작 순간 다음 셀로 넘어간다.
-    // 마지막 교시는 다음 행이 없으므로 자기 종료 시각까지만 채운다.
-    // 교시 시각은 types.ts 의 PERIOD_TIMES 를 공유(중복 정의 방지).
+    // 마지막 칸은 다음 행이 없으므로 자기 종료 시각까지만 채운다.
     const getCurrentTimeY = () => {
       if (currentDay < 1 || currentDay > 5) return null;
-      for (let i = 0; i < PERIOD_TIMES.length; i++) {
-        const spanStart = PERIOD_TIMES[i].start;
-        const spanEnd =
-          i + 1 < PERIOD_TIMES.length ? PERIOD_TIMES[i + 1].start : PERIOD_TIMES[i].end;
+      for (let i = 0; i < slots.length; i++) {
+        const spanStart = toMinutes(slots[i].start);
+        const spanEnd = toMinutes(i + 1 < slots.length ? slots[i + 1].start : slots[i].end);
+        if (spanStart === null || spanEnd === null || spanEnd <= spanStart) continue;
         if (currentTime >= spanStart && currentTime < spanEnd) {
           return { rowIndex: i, progress: (currentTime - spanStart) / (spanEnd - spanStart) };
         }
@@ -185,63 +135,47 @@ export default function TimetableTab({
     };
 
     return (
-      <div className="timetable-grid">
+      <div className="timetable-grid" style={{ gridTemplateRows: `auto repeat(${slots.length}, 1fr)` }}>
         <div className="timetable-cell header-empty" style={{ background: 'transparent' }}></div>
         {days.map((dayLabel, index) => (
           <div key={`header-${index}`} className={`timetable-cell timetable-header${isWeekday && index === currentDay - 1 ? ' is-today' : ''}`}>
             {dayLabel}
           </div>
         ))}
-        {periods.map((p, pIdx) => {
-          if (pIdx === 3) {
+        {slots.map((slot, rowIndex) => {
+          if (slot.period === null) {
             return (
-              <React.Fragment key={`period-group-${p}`}>
-                <div className="timetable-cell period">
-                  <span className="period-label">4</span>
-                  <span className="period-time">11:30</span>
-                </div>
-                {days.map((_, dIdx) => renderLesson(schedule[3]?.[dIdx], `4-${dIdx}`, isWeekday && dIdx === currentDay - 1, 3))}
-
+              <React.Fragment key={`slot-${rowIndex}`}>
                 <div className="timetable-cell period lunch">
-                  <span className="period-label">점심</span>
-                  <span className="period-time">12:20</span>
+                  <span className="period-label">{slot.label}</span>
+                  <span className="period-time">{slot.start}</span>
                 </div>
                 {days.map((_, dIdx) => {
                   const isToday = isWeekday && dIdx === currentDay - 1;
-                  const isCurrentTimeCell = isToday && timeY !== null && timeY.rowIndex === 4;
+                  const isCurrentTimeCell = isToday && timeY !== null && timeY.rowIndex === rowIndex;
                   const timeProgress = isCurrentTimeCell && timeY ? timeY.progress : undefined;
                   return (
                     <div
-                      key={`lunch-${dIdx}`}
+                      key={`slot-${rowIndex}-${dIdx}`}
                       className={`timetable-cell lunch-cell${isToday ? ' is-today' : ''}${isCurrentTimeCell ? ' current-time-cell' : ''}`}
                       style={timeProgress !== undefined ? { '--time-progress': timeProgress } as React.CSSProperties : undefined}
                     >
-                      점심시간
+                      {slot.label === '점심' ? '점심시간' : slot.label}
                     </div>
                   );
                 })}
-
-                <div className="timetable-cell period">
-                  <span className="period-label">5</span>
-                  <span className="period-time">13:20</span>
-                </div>
-                {days.map((_, dIdx) => renderLesson(schedule[4]?.[dIdx], `5-${dIdx}`, isWeekday && dIdx === currentDay - 1, 5))}
               </React.Fragment>
             );
           }
 
-          if (pIdx === 4) return null;
-
-          const scheduleIdx = pIdx;
-          const actualRowIndex = pIdx < 3 ? pIdx : pIdx + 1;
-
+          const p = slot.period;
           return (
-            <React.Fragment key={p}>
+            <React.Fragment key={`slot-${rowIndex}`}>
               <div className="timetable-cell period">
                 <span className="period-label">{p}</span>
-                <span className="period-time">{PERIOD_START_TIMES[p]}</span>
+                <span className="period-time">{slot.start}</span>
               </div>
-              {days.map((_, dIdx) => renderLesson(schedule[scheduleIdx]?.[dIdx], `${p}-${dIdx}`, isWeekday && dIdx === currentDay - 1, actualRowIndex))}
+              {days.map((_, dIdx) => renderLesson(schedule[p - 1]?.[dIdx], `${p}-${dIdx}`, isWeekday && dIdx === currentDay - 1, rowIndex))}
             </React.Fragment>
           );
         })}
@@ -251,11 +185,39 @@ export default function TimetableTab({
 
   const displayTeachers = Array.from(new Set([defaultTeacher, ...favoriteTeachers].filter(Boolean)));
 
+  const [exportStatus, setExportStatus] = useState('');
+
+  // 선택한 선생님 시간표를 파일로 내보낸다. .ics 는 보고 있는 주부터 학년도 끝까지 매주 반복.
+  const handleExport = async (format: 'ics' | 'csv' | 'html') => {
+    if (!selectedTeacher) return;
+    const names = { ics: '캘린더', csv: 'CSV', html: '인쇄용 HTML' };
+    const path = await save({
+      filters: [{ name: names[format], extensions: [format] }],
+      defaultPath: `${selectedTeacher}-시간표.${format}`,
+    });
+    if (!path) return;
+    const m = appinWeekRange.mondayDate;
+    const weekOf = `${m.getFullYear()}-${String(m.getMonth() + 1).padStart(2, '0')}-${String(m.getDate()).padStart(2, '0')}`;
+    try {
+      await invoke('export_timetable', { kind: 'teacher', name: selectedTeacher, format, path, weekOf });
+      setExportStatus('저장됨');
+    } catch (e) {
+      setExportStatus(String(e));
+    }
+  };
+
   return (
     <div className="timetable-section">
+      {changes.length > 0 && (
+        <div className="timetable-change-notice" onClick={onDismissChanges} title="눌러서 닫기">
+          <strong>시간표가 바뀌었습니다</strong>
+          {changes.slice(0, 4).map((c, i) => <span key={i}>{c.summary}</span>)}
+          {changes.length > 4 && <span>외 {changes.length - 4}건</span>}
+        </div>
+      )}
       {renderGrid()}
 
-      {timetableSource === 'appin' && (
+      {(week ? week.dated : timetableSource === 'appin') && (
         <>
           <div className="timetable-top-hover-area"></div>
           <div className="timetable-top-hover">
@@ -333,6 +295,15 @@ export default function TimetableTab({
             </div>
           )}
         </div>
+        {selectedTeacher && (
+          <div className="timetable-export" title={exportStatus}>
+            <span>내보내기</span>
+            <button className="refresh-btn-small" onClick={() => handleExport('ics')}>캘린더</button>
+            <button className="refresh-btn-small" onClick={() => handleExport('csv')}>CSV</button>
+            <button className="refresh-btn-small" onClick={() => handleExport('html')}>인쇄</button>
+            {exportStatus && <span className="timetable-export-status">{exportStatus}</span>}
+          </div>
+        )}
       </div>
     </div>
   );
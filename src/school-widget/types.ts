@@ -1,12 +1,87 @@
-export interface TimetableData {
-  teachers: string[];
-  subjects: string[];
-  timetables: Record<string, string[][][]>;
+export interface WeekCell {
+  subject: string;
+  className: string | null;
+  changed: boolean;
+  event: boolean;
 }
 
-export interface MealInfo {
-  lunch: string;
-  dinner: string;
+// get_teacher_week 결과 — cells[교시 - 1][요일]
+export interface TeacherWeek {
+  source: 'comcigan' | 'appin';
+  teacher: string;
+  dated: boolean;
+  dates: string[];
+  cells: (WeekCell | null)[][];
+}
+
+// timetable-changed 이벤트 — 시간표 스냅샷이 바뀌었을 때
+export interface TimetableChange {
+  scope: 'teacher' | 'class';
+  target: string;
+  day: string;
+  period: number;
+  before: string | null;
+  after: string | null;
+  summary: string;
+}
+
+export interface TimetableChangeNotice {
+  source: 'comcigan' | 'appin';
+  teacher: string | null;
+  mine: TimetableChange[];
+  total: number;
+}
+
+// get_bell_profile_for 결과 — 그날 적용되는 종 시간표
+export interface BellSlot {
+  label: string;
+  start: string;
+  end: string;
+  period: number | null;
+}
+
+export interface BellProfile {
+  id: string;
+  name: string;
+  slots: BellSlot[];
+}
+
+export interface SchoolInfo {
+  atptCode: string;
+  atptName: string;
+  schoolCode: string;
+  name: string;
+  kind: string;
+  level: 'els' | 'mis' | 'his' | null;
+  address: string | null;
+}
+
+export interface Allergen {
+  code: number;
+  name: string;
+}
+
+export interface Dish {
+  name: string;
+  allergens: Allergen[];
+}
+
+export type MealKind = 'breakfast' | 'lunch' | 'dinner';
+
+export interface Meal {
+  kind: MealKind;
+  label: string;
+  dishes: Dish[];
+  calories: number | null;
+  nutrition: { name: string; unit: string; amount: number }[];
+  origin: { item: string; origin: string }[];
+}
+
+// 하루 급식. alerts 는 등록한 알레르기가 든 요리.
+export interface MealDay {
+  date: string;
+  meals: Meal[];
+  alerts: { meal: MealKind; dish: string; allergens: Allergen[] }[];
 }
 
 export interface Latecomer {
@@ -23,22 +98,6 @@ export interface PointStatus {
   total: number;
 }
 
-export interface AppinTimetableSlot {
-  subject: number | null;
-  teacher: number | null;
-  classroom?: string;
-}
-
-export interface AppinData {
-  teachers: string[];
-  subjects: string[];
-  classes: string[];
-  events: string[];
-  days: Record<string, Record<string, Record<string, AppinTimetableSlot>>>;
-  eventsByDateClass: Record<string, Record<string, string>>;
-  eventsByDateGrade: Record<string, (string | null)[]>;
-}
-
 export type Tab = 'todo' | 'meal' | 'timetable' | 'attendance' | 'points' | 'shortcut' | 'settings' | 'stock';
 
 export interface Shortcut {
@@ -119,22 +178,6 @@ export const CAT_CONFIG = {
   IDLE_MAX: 4000,
 };
 
-export const PERIOD_START_TIMES: Record<number, string> = {
-  1: '08:30', 2: '09:30', 3: '10:30', 4: '11:30',
-  5: '13:20', 6: '14:20', 7: '15:20',
-};
-
-export const PERIOD_TIMES = [
-  { start: 8 * 60 + 30, end: 9 * 60 + 20 },
-  { start: 9 * 60 + 30, end: 10 * 60 + 20 },
-  { start: 10 * 60 + 30, end: 11 * 60 + 20 },
-  { start: 11 * 60 + 30, end: 12 * 60 + 20 },
-  { start: 12 * 60 + 20, end: 13 * 60 + 20 }, // 점심시간
-  { start: 13 * 60 + 20, end: 14 * 60 + 10 },
-  { start: 14 * 60 + 20, end: 15 * 60 + 10 },
-  { start: 15 * 60 + 20, end: 16 * 60 + 10 },
-];
-
 export const ALL_TABS: { id: Tab; label: string }[] = [
   { id: 'todo', label: '할 일' },
   { id: 'meal', label: '급식' },
@@ -1,15 +1,16 @@
 import { invoke } from '@tauri-apps/api/core';
-import { ScheduleItem } from '../types/schedule';
+import { ScheduleFilter, ScheduleItem } from '../types/schedule';
 
 export const ScheduleService = {
     /**
      * Get schedules within a date range.
      */
-    async getSchedules(range: { start: Date; end: Date }, includeDeleted: boolean = false): Promise<ScheduleItem[]> {
+    async getSchedules(range: { start: Date; end: Date }, includeDeleted: boolean = false, filter?: ScheduleFilter): Promise<ScheduleItem[]> {
         return invoke('get_schedules', {
             start: range.start.toISOString(),
             end: range.end.toISOString(),
-            includeDeleted
+            includeDeleted,
+            filter: filter ?? null
         });
     },
 
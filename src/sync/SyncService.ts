@@ -15,6 +15,13 @@ function toIsoZ(s: string | null | undefined): string {
     return isNaN(d.getTime()) ? s : d.toISOString();
 }
 
+// 나이스 학사일정(school_schedule)은 기기마다 나이스에서 다시 가져오는 읽기 전용 항목이라
+// 동기화하지 않는다. 외부 리더가 아는 타입으로 바꿔 올리면 읽기 전용 표시가 사라지고,
+// 되받을 때 편집 가능한 일정으로 덮어써진다.
+function isLocalOnly(item: ScheduleItem): boolean {
+    return item.type === 'school_schedule';
+}
+
 // Firestore 로 내보낼 때만 적용하는 정규화.
 //  1) 타입: 외부 리더는 manual_todo / period_schedule / message_task 만 인식한다.
 //     탁상달력에서 가져온 desktopcal_memo · desktopcal_event 를 리더가 아는 타입으로 매핑.
@@ -50,7 +57,8 @@ export const SyncService = {
         // Or better: Fetch all from local DB.
         const start = new Date('2000-01-01');
         const end = new Date('2100-12-31');
-        const localItems = await ScheduleService.getSchedules({ start, end }, true);
+        const allLocalItems = await ScheduleService.getSchedules({ start, end }, true);
+        const localItems = allLocalItems.filter(i => !isLocalOnly(i));
 
         // 2. Get Remote Data
         const eventsRef = collection(db, "users", user.uid, COLLECTION_EVENTS);
@@ -88,6 +96,30 @@ export const SyncService = {
             }
         }
 
+        // ── 학사일정 원격 사본 정리(일회성) ─────────────────────────────────
+        // 예전에는 학사일정을 period_schedule 로 바꿔 올렸다. 남은 사본을 지워
+        // 다른 기기가 편집 가능한 일정으로 받아 가지 않게 한다.
+        {
+            const { invoke } = await import('@tauri-apps/api/core');
+            const CLEANUP_KEY = 'SchoolScheduleRemoteCleanupV1Done';
+            const done = await invoke<string | null>('get_registry_value', { key: CLEANUP_KEY });
+            if (done !== 'true') {
+                let delBatch = writeBatch(db);
+                let pending = 0;
+                for (const item of allLocalItems.filter(isLocalOnly)) {
+                    delBatch.delete(doc(eventsRef, item.id));
+                    pending++;
+                    if (pending >= 450) {
+                        await delBatch.commit();
+                        delBatch = writeBatch(db);
+                        pending = 0;
+                    }
+                }
+                if (pending > 0) await delBatch.commit();
+                await invoke('set_registry_value', { key: CLEANUP_KEY, value: 'true' });
+            }
+        }
+
         let remoteItems: ScheduleItem[] = [];
 
         try {
@@ -146,8 +178,11 @@ export const SyncService = {
         console.log(`DEBUG: Processing ${remoteItems.length} remote items`);
         const localMap = new Map(localItems.map(i => [i.id, i]));
         const remoteMap = new Map(remoteItems.map(i => [i.id, i]));
+        const localOnlyIds = new Set(allLocalItems.filter(isLocalOnly).map(i => i.id));
 
         for (const remote of remoteItems) {
+            // 예전에 올라간 학사일정 사본이 로컬 읽기 전용 항목을 덮어쓰지 않게 한다.
+            if (isLocalOnly(remote) || localOnlyIds.has(remote.id)) continue;
             const local = localMap.get(remote.id);
 
             if (!local) {
@@ -31,6 +31,10 @@ export interface ManualTodo {
   calendarTitle?: string; // 달력에 표시될 짧은 제목
   color?: string | null;  // 항목 색상(AI 생성=보라색, 탁상달력 memo=원본 색)
   isDeleted?: boolean;
+  referenceId?: string | null;           // 원본 메시지 id(AI 생성 항목)
+  priority?: 'high' | 'medium' | 'low';  // AI 추출 긴급도
+  itemKind?: 'event' | 'deadline' | 'task' | 'notice';
+  hasAttachment?: boolean;                // 원본 메시지에 첨부가 있음
 }
 
 export interface PeriodSchedule {
@@ -1,4 +1,14 @@
-export type ScheduleType = 'manual_todo' | 'period_schedule' | 'message_task' | 'desktopcal_memo' | 'desktopcal_event';
+export type ScheduleType = 'manual_todo' | 'period_schedule' | 'message_task' | 'desktopcal_memo' | 'desktopcal_event' | 'school_schedule';
+
+export type SchedulePriority = 'high' | 'medium' | 'low';
+export type ScheduleItemKind = 'event' | 'deadline' | 'task' | 'notice';
+
+export interface ScheduleFilter {
+    itemKinds?: ScheduleItemKind[];
+    priorities?: SchedulePriority[];
+    hasAttachment?: boolean;
+    visibleOn?: string;      // YYYY-MM-DD; excludes items whose visibleUntil is earlier
+}
 
 export interface ScheduleItem {
     id: string;              // UUID for local generation
@@ -16,6 +26,12 @@ export interface ScheduleItem {
     color?: string;
     isCompleted: boolean;
 
+    // AI extraction metadata (set only for AI-registered items)
+    priority?: SchedulePriority;
+    itemKind?: ScheduleItemKind;
+    visibleUntil?: string;   // YYYY-MM-DD, hide after this date
+    hasAttachment?: boolean; // source message has attachments (see get_message_attachments)
+
     // Audit
     createdAt: string;
     updatedAt: string;